@@ -251,16 +251,25 @@ fn main() -> Result<()> {
             })
         },
         Some(Subcommand::Benchmark(cmd)) => {
+            use frame_benchmarking_cli::BenchmarkCmd;
+
             let runner = cli.create_runner(cmd)?;
 
-            runner.sync_run(|config| {
-                if cfg!(feature = "runtime-benchmarks") {
-                    cmd.run::<Block, ()>(config)
-                } else {
-                    Err("Runtime benchmarking wasn't enabled when building the node. \
-                    You can enable it with `--features runtime-benchmarks`.".into())
-                }
-            })
+            match cmd {
+                BenchmarkCmd::Pallet(cmd) => {
+                    if cfg!(feature = "runtime-benchmarks") {
+                        runner.sync_run(|config| {
+                            cmd.run_with_spec::<sp_runtime::traits::HashingFor<Block>, ()>(Some(
+                                config.chain_spec,
+                            ))
+                        })
+                    } else {
+                        Err("Runtime benchmarking wasn't enabled when building the node. \
+                        You can enable it with `--features runtime-benchmarks`.".into())
+                    }
+                },
+                _ => Err("Benchmarking sub-command unsupported".into()),
+            }
         },
         None => {
             let runner = cli.create_runner(&cli.run.normalize())?;
@@ -0,0 +1,374 @@
+//! Benchmarking setup for pallet-access-control
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_std::prelude::*;
+use xcm::latest::{MaybeErrorCode, Response};
+
+/// Store a pending access request from a fresh requester to the whitelisted patient
+fn insert_request<T: Config>(index: u32, consent_verified: bool) -> (T::AccountId, H256) {
+    let patient: T::AccountId = whitelisted_caller();
+    let requester: T::AccountId = account("requester", index, 0);
+    let request_id = H256::from_low_u64_be(index as u64 + 1);
+    let record_id = T::BenchmarkHelper::create_record(&patient);
+
+    AccessRequests::<T>::insert(
+        request_id,
+        AccessRequest {
+            request_id,
+            record_id,
+            version: Some(1),
+            requester,
+            patient: patient.clone(),
+            consent_id: Some(H256::repeat_byte(3)),
+            status: AccessStatus::Pending,
+            requested_at: 0,
+            responded_at: None,
+            consent_verified,
+        },
+    );
+    PendingRequests::<T>::mutate(&patient, |pending| {
+        let _ = pending.try_push(request_id);
+    });
+
+    (patient, request_id)
+}
+
+/// Fill the whitelisted patient's pending list with `count` unrelated requests
+fn fill_pending<T: Config>(count: u32) {
+    let patient: T::AccountId = whitelisted_caller();
+    let pending: Vec<H256> =
+        (0..count as u64).map(|i| H256::from_low_u64_be(u64::MAX - i)).collect();
+    PendingRequests::<T>::insert(&patient, BoundedVec::truncate_from(pending));
+}
+
+/// Pending requests that leave room for `extra` more entries
+fn pending_headroom<T: Config>(extra: u32) -> u32 {
+    T::MaxPendingRequests::get().saturating_sub(extra)
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn request_access() {
+        T::BenchmarkHelper::open_consent_channel();
+        let requester: T::AccountId = account("researcher", 0, 0);
+        let patient: T::AccountId = whitelisted_caller();
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(requester), record_id, Some(1), patient, H256::repeat_byte(3));
+
+        assert_eq!(ConsentQueries::<T>::iter().count(), 1);
+    }
+
+    #[benchmark]
+    fn grant_access() {
+        fill_pending::<T>(pending_headroom::<T>(1));
+        let (patient, request_id) = insert_request::<T>(0, true);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), request_id, u64::MAX);
+
+        assert_eq!(AccessRequests::<T>::get(request_id).unwrap().status, AccessStatus::Granted);
+    }
+
+    #[benchmark]
+    fn deny_access() {
+        fill_pending::<T>(pending_headroom::<T>(1));
+        let (patient, request_id) = insert_request::<T>(0, false);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), request_id);
+
+        assert_eq!(AccessRequests::<T>::get(request_id).unwrap().status, AccessStatus::Denied);
+    }
+
+    #[benchmark]
+    fn revoke_access() {
+        let patient: T::AccountId = account("patient", 0, 0);
+        let delegate: T::AccountId = whitelisted_caller();
+        let requester: T::AccountId = account("requester", 0, 0);
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+        AccessGrants::<T>::insert(record_id, &requester, u64::MAX);
+
+        // A delegate scoped to the record's category, which needs both record lookups
+        let category = T::Records::record_category(&record_id).expect("record was just created");
+        Delegates::<T>::insert(
+            &patient,
+            &delegate,
+            Delegation {
+                categories: BoundedVec::truncate_from(vec![category]),
+                expires_at: Some(u64::MAX),
+                created_at: 0,
+            },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(delegate), record_id, requester.clone());
+
+        assert!(AccessGrants::<T>::get(record_id, &requester).is_none());
+    }
+
+    #[benchmark]
+    fn consent_response() {
+        T::BenchmarkHelper::open_consent_channel();
+        let requester: T::AccountId = account("researcher", 0, 0);
+        let patient: T::AccountId = whitelisted_caller();
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+        fill_pending::<T>(pending_headroom::<T>(1));
+        Pallet::<T>::request_access(
+            RawOrigin::Signed(requester).into(),
+            record_id,
+            Some(1),
+            patient,
+            H256::repeat_byte(3),
+        )
+        .expect("consent query must be sent");
+        let (query_id, request_id) =
+            ConsentQueries::<T>::iter().next().expect("consent query was just stored");
+
+        // A valid consent, which queues the request or creates the grant when auto-granting
+        #[extrinsic_call]
+        _(
+            T::BenchmarkHelper::consent_response_origin() as <T as frame_system::Config>::RuntimeOrigin,
+            query_id,
+            Response::DispatchResult(MaybeErrorCode::Success),
+        );
+
+        assert!(AccessRequests::<T>::get(request_id).unwrap().consent_verified);
+    }
+
+    #[benchmark]
+    fn add_delegate() {
+        let patient: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(patient.clone()),
+            delegate.clone(),
+            BoundedVec::new(),
+            Some(u64::MAX),
+        );
+
+        assert!(Delegates::<T>::contains_key(&patient, &delegate));
+    }
+
+    #[benchmark]
+    fn remove_delegate() {
+        let patient: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        Delegates::<T>::insert(
+            &patient,
+            &delegate,
+            Delegation { categories: BoundedVec::new(), expires_at: None, created_at: 0 },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient.clone()), delegate.clone());
+
+        assert!(!Delegates::<T>::contains_key(&patient, &delegate));
+    }
+
+    #[benchmark]
+    fn bulk_grant_access(n: Linear<1, { T::MaxBulkRequests::get() }>) {
+        fill_pending::<T>(pending_headroom::<T>(n));
+        let patient: T::AccountId = whitelisted_caller();
+        let request_ids: Vec<H256> = (0..n).map(|i| insert_request::<T>(i, true).1).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient.clone()), BoundedVec::truncate_from(request_ids), u64::MAX);
+
+        assert_eq!(PendingRequests::<T>::get(&patient).len() as u32, pending_headroom::<T>(n));
+    }
+
+    #[benchmark]
+    fn bulk_deny_access(n: Linear<1, { T::MaxBulkRequests::get() }>) {
+        fill_pending::<T>(pending_headroom::<T>(n));
+        let patient: T::AccountId = whitelisted_caller();
+        let request_ids: Vec<H256> = (0..n).map(|i| insert_request::<T>(i, false).1).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient.clone()), BoundedVec::truncate_from(request_ids));
+
+        assert_eq!(PendingRequests::<T>::get(&patient).len() as u32, pending_headroom::<T>(n));
+    }
+
+    #[benchmark]
+    fn expire_request() {
+        fill_pending::<T>(pending_headroom::<T>(1));
+        let (_, request_id) = insert_request::<T>(0, false);
+
+        #[block]
+        {
+            Pallet::<T>::expire_request(request_id);
+        }
+
+        assert_eq!(AccessRequests::<T>::get(request_id).unwrap().status, AccessStatus::Expired);
+    }
+
+    #[benchmark]
+    fn set_clinician() -> Result<(), BenchmarkError> {
+        let origin =
+            T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let clinician: T::AccountId = account("clinician", 0, 0);
+
+        #[extrinsic_call]
+        _(
+            origin as <T as frame_system::Config>::RuntimeOrigin,
+            clinician.clone(),
+            Some(u64::MAX),
+        );
+
+        assert_eq!(Clinicians::<T>::get(&clinician), Some(u64::MAX));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_auditor() -> Result<(), BenchmarkError> {
+        let origin =
+            T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let auditor: T::AccountId = account("auditor", 0, 0);
+
+        #[extrinsic_call]
+        _(origin as <T as frame_system::Config>::RuntimeOrigin, auditor.clone(), true);
+
+        assert!(Auditors::<T>::contains_key(&auditor));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn break_glass() {
+        let patient: T::AccountId = account("patient", 0, 0);
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+        let clinician: T::AccountId = whitelisted_caller();
+        Clinicians::<T>::insert(&clinician, u64::MAX);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(clinician.clone()), record_id, H256::repeat_byte(9));
+
+        assert!(EmergencyGrants::<T>::contains_key(record_id, &clinician));
+    }
+
+    #[benchmark]
+    fn review_break_glass() {
+        let patient: T::AccountId = account("patient", 0, 0);
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+        let clinician: T::AccountId = account("clinician", 0, 0);
+        Clinicians::<T>::insert(&clinician, u64::MAX);
+        Pallet::<T>::break_glass(
+            RawOrigin::Signed(clinician.clone()).into(),
+            record_id,
+            H256::repeat_byte(9),
+        )
+        .expect("break-glass access must succeed");
+        let access_id = PendingReviews::<T>::iter_keys().next().expect("access was just taken");
+        let auditor: T::AccountId = whitelisted_caller();
+        Auditors::<T>::insert(&auditor, ());
+
+        // Flagging misuse also revokes the emergency grant
+        #[extrinsic_call]
+        _(RawOrigin::Signed(auditor), access_id, true);
+
+        assert!(!EmergencyGrants::<T>::contains_key(record_id, &clinician));
+    }
+
+    #[benchmark]
+    fn set_guardianship() -> Result<(), BenchmarkError> {
+        let origin =
+            T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let ward: T::AccountId = account("ward", 0, 0);
+        let guardian: T::AccountId = account("guardian", 0, 0);
+
+        #[extrinsic_call]
+        _(
+            origin as <T as frame_system::Config>::RuntimeOrigin,
+            ward.clone(),
+            guardian.clone(),
+            Some(u64::MAX),
+        );
+
+        assert_eq!(Guardians::<T>::get(&ward, &guardian), Some(u64::MAX));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn act_for_ward() {
+        let ward: T::AccountId = account("ward", 0, 0);
+        let guardian: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("delegate", 0, 0);
+        Guardians::<T>::insert(&ward, &guardian, u64::MAX);
+        let call: <T as Config>::RuntimeCall = Call::<T>::add_delegate {
+            delegate: delegate.clone(),
+            categories: BoundedVec::new(),
+            expires_at: None,
+        }
+        .into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(guardian), ward.clone(), Box::new(call.into()));
+
+        assert!(Delegates::<T>::contains_key(&ward, &delegate));
+    }
+
+    #[benchmark]
+    fn pin_grant_version() {
+        let patient: T::AccountId = whitelisted_caller();
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+        let requester: T::AccountId = account("requester", 0, 0);
+        AccessGrants::<T>::insert(record_id, &requester, u64::MAX);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), record_id, requester.clone(), Some(1));
+
+        assert_eq!(GrantVersions::<T>::get(record_id, &requester), Some(1));
+    }
+
+    #[benchmark]
+    fn migrate_account() -> Result<(), BenchmarkError> {
+        let origin =
+            T::RegistryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let (old, _) = insert_request::<T>(0, true);
+        for i in 1..T::MaxPendingRequests::get() {
+            insert_request::<T>(i, true);
+        }
+        let new: T::AccountId = account("new", 0, 0);
+
+        #[extrinsic_call]
+        _(origin as <T as frame_system::Config>::RuntimeOrigin, old.clone(), new.clone());
+
+        assert!(PendingRequests::<T>::get(&old).is_empty());
+        assert_eq!(PendingRequests::<T>::get(&new).len() as u32, T::MaxPendingRequests::get());
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn migrate_grant() {
+        let patient: T::AccountId = whitelisted_caller();
+        let record_id = T::BenchmarkHelper::create_record(&patient);
+        let old: T::AccountId = account("requester", 0, 0);
+        let new: T::AccountId = account("new", 0, 0);
+        AccessGrants::<T>::insert(record_id, &old, u64::MAX);
+        GrantVersions::<T>::insert(record_id, &old, 1);
+        AccountMigrations::<T>::insert(&old, &new);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(new.clone()), old, record_id);
+
+        assert_eq!(AccessGrants::<T>::get(record_id, &new), Some(u64::MAX));
+        assert_eq!(GrantVersions::<T>::get(record_id, &new), Some(1));
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -9,6 +9,75 @@
 //! - Permission caching for performance
 //! - Access denial logging
 //!
+//! ## Consent verification flow
+//!
+//! 1. `request_access` stores a `Pending` request and sends an XCM `Transact` of
+//!    `pallet_consent_manager::verify_consent` to the IdentityConsent chain, with a
+//!    `ReportTransactStatus` appendix pointing at a `pallet_xcm` notify query.
+//!    The query carries the requested record's category, upload time and version, which the
+//!    consent's scope is checked against.
+//! 2. The IdentityConsent chain dispatches the call and reports the dispatch result back.
+//! 3. `pallet_xcm` routes the `QueryResponse` to `consent_response`, which moves the request
+//!    to `Granted` (or leaves it `Pending` for patient approval, see `AutoGrantOnConsent`) when
+//!    the consent is valid, and to `Denied` otherwise.
+//!
+//! ## Patient approval
+//!
+//! Once the IdentityConsent chain has verified its consent, a `Pending` request is listed in
+//! `PendingRequests` under its patient until it is decided, so that requests without a consent
+//! cannot fill the patient's queue. A request verified while the queue is full is denied. The
+//! patient, or a delegate added with `add_delegate` (e.g. a guardian or a care team), grants or
+//! denies requests one at a time or in bulk. A delegation can be limited to record categories
+//! (looked up through `Config::Records`) and to an expiry time. Requests left undecided for
+//! `PendingRequestTimeout` blocks move to `Expired` in `on_initialize`, at most
+//! `MaxExpiriesPerBlock` per block; the rest expire on the following blocks.
+//!
+//! A grant can be revoked by the record's patient, one of their delegates, or the grantee
+//! itself. Record ownership is looked up through `Config::Records`.
+//!
+//! ## Record versions
+//!
+//! Health records are versioned, and a grant follows the latest version of its record. A
+//! request may name a version instead, which is checked against consents pinned to a version
+//! (see `pallet_consent_manager::ConsentScope`), and the grant it leads to is pinned to that
+//! version. The patient or one of their delegates can also pin a grant with
+//! `pin_grant_version` (e.g. the version a study was approved on); a pinned grant gives access
+//! to that version only. Versions are looked up through `Config::Records`.
+//!
+//! ## Emergency access
+//!
+//! A clinician treating a patient who cannot approve a request (e.g. unconscious) may
+//! `break_glass`: access to the record is granted at once for `BreakGlassDuration`, without a
+//! consent query or the patient's approval. Emergency grants are kept in `EmergencyGrants`,
+//! apart from the grants approved by patients, and cover every version of the record. The call
+//! must carry the hash of a justification kept off-chain, and the access is logged in
+//! `BreakGlassAccesses`. The emitted event names the patient, so the patient and their delegates
+//! are notified.
+//!
+//! Every break-glass access waits in `PendingReviews` until an auditor signs it off with
+//! `review_break_glass`, or flags it as misuse, which also revokes the clinician's emergency
+//! access to the record; a grant the patient approved is kept. The review queue is unbounded so
+//! that it can never block emergency care.
+//!
+//! Clinicians and auditors are mirrored from the credentials issued and revoked on the
+//! IdentityConsent chain by `RegistryOrigin`, with `set_clinician` and `set_auditor`.
+//!
+//! ## Guardianship
+//!
+//! Guardianships registered on the IdentityConsent chain (e.g. a parent acting for a minor) are
+//! mirrored by `RegistryOrigin` with `set_guardianship`, until the ward's handover date. A
+//! guardian acts for their ward with `act_for_ward`, which dispatches a call with the ward's
+//! signed origin. Only the patient actions admitted by `WardCalls` (e.g. grants, delegates and
+//! record uploads) can be dispatched; the runtime keeps the ward's clinician and auditor
+//! rights, account migration and nested `act_for_ward` calls out of it.
+//!
+//! ## Identity recovery
+//!
+//! When an identity is recovered to a new account on the IdentityConsent chain, `RegistryOrigin`
+//! calls `migrate_account`, which moves the patient's pending requests to the new account.
+//! Grants held by the lost account are keyed by record, so the new account claims each of them
+//! with `migrate_grant`. Delegations are not carried over and must be added again.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "HealthData Chain - Access Control"
 
@@ -22,14 +91,32 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Time};
+    use frame_support::{
+        dispatch::GetDispatchInfo,
+        pallet_prelude::*,
+        traits::{Contains, Time},
+    };
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{Dispatchable, One};
     use sp_std::prelude::*;
     use sp_core::H256;
+    use xcm::latest::{prelude::*, MaybeErrorCode, QueryId, QueryResponseInfo};
+    use crate::weights::WeightInfo;
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Access request status
@@ -41,7 +128,7 @@ pub mod pallet {
         Granted,
         /// Access denied
         Denied,
-        /// Consent expired
+        /// Request expired without a decision
         Expired,
     }
 
@@ -53,6 +140,8 @@ pub mod pallet {
         pub request_id: H256,
         /// Record ID being accessed
         pub record_id: H256,
+        /// Record version requested (`None` = follow the latest version)
+        pub version: Option<u32>,
         /// Requester account
         pub requester: T::AccountId,
         /// Patient (record owner)
@@ -65,15 +154,219 @@ pub mod pallet {
         pub requested_at: u64,
         /// Response timestamp
         pub responded_at: Option<u64>,
+        /// Whether the IdentityConsent chain confirmed the consent
+        pub consent_verified: bool,
+    }
+
+    /// Approval rights delegated by a patient to a guardian or care-team account
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Delegation<T: Config> {
+        /// Record categories the delegate may decide on (all categories when empty)
+        pub categories: BoundedVec<T::RecordCategory, ConstU32<16>>,
+        /// Delegation expiry timestamp (`None` = until removed)
+        pub expires_at: Option<u64>,
+        /// Created timestamp
+        pub created_at: u64,
+    }
+
+    /// Auditor review of a break-glass access
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum BreakGlassReview {
+        /// Awaiting an auditor
+        Pending,
+        /// Signed off by an auditor
+        Approved,
+        /// Flagged as misuse by an auditor
+        Flagged,
+    }
+
+    /// Emergency access taken by a clinician without the patient's approval
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct BreakGlassAccess<T: Config> {
+        /// Record ID accessed
+        pub record_id: H256,
+        /// Clinician who broke the glass
+        pub clinician: T::AccountId,
+        /// Patient (record owner)
+        pub patient: T::AccountId,
+        /// Hash of the justification kept off-chain
+        pub justification_hash: H256,
+        /// Access timestamp
+        pub granted_at: u64,
+        /// Timestamp at which the emergency grant lapses
+        pub expires_at: u64,
+        /// Review status
+        pub review: BreakGlassReview,
+        /// Auditor who reviewed the access
+        pub reviewer: Option<T::AccountId>,
+        /// Review timestamp
+        pub reviewed_at: Option<u64>,
+    }
+
+    /// Read access to health record metadata held by another pallet
+    pub trait RecordInspect<AccountId, Category> {
+        /// Patient owning a record, `None` if the record does not exist
+        fn record_owner(record_id: &H256) -> Option<AccountId>;
+
+        /// Category of a record, `None` if the record does not exist
+        fn record_category(record_id: &H256) -> Option<Category>;
+
+        /// Upload timestamp of a record, `None` if the record does not exist
+        fn record_created_at(record_id: &H256) -> Option<u64>;
+
+        /// Latest version of a record, `None` if the record does not exist
+        fn record_version(record_id: &H256) -> Option<u32>;
+    }
+
+    impl<AccountId, Category> RecordInspect<AccountId, Category> for () {
+        fn record_owner(_record_id: &H256) -> Option<AccountId> {
+            None
+        }
+
+        fn record_category(_record_id: &H256) -> Option<Category> {
+            None
+        }
+
+        fn record_created_at(_record_id: &H256) -> Option<u64> {
+            None
+        }
+
+        fn record_version(_record_id: &H256) -> Option<u32> {
+            None
+        }
+    }
+
+    /// Requested record sent with a consent query, mirroring
+    /// `pallet_consent_manager::RecordContext`
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct ConsentRecord<Category> {
+        pub record_id: H256,
+        pub category: Category,
+        pub created_at: u64,
+        pub de_identified: bool,
+        pub version: Option<u32>,
+    }
+
+    /// Encoding of the consent-manager calls on the IdentityConsent chain
+    ///
+    /// Only the calls dispatched by this pallet are mirrored; the variant index must match
+    /// the call index in `pallet_consent_manager`.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub enum ConsentManagerCall<AccountId, Category> {
+        /// `pallet_consent_manager::verify_consent`
+        #[codec(index = 5)]
+        VerifyConsent {
+            consent_id: H256,
+            data_owner: AccountId,
+            accessor: AccountId,
+            record: Option<ConsentRecord<Category>>,
+        },
+    }
+
+    /// Runtime-specific setup needed to benchmark the XCM consent flow
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper<RuntimeOrigin, AccountId> {
+        /// Make the IdentityConsent chain reachable through `XcmSender`
+        fn open_consent_channel();
+
+        /// Origin of a query response sent by the IdentityConsent chain
+        fn consent_response_origin() -> RuntimeOrigin;
+
+        /// Store a health record owned by `owner`, returning its ID
+        fn create_record(owner: &AccountId) -> H256;
     }
 
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + pallet_xcm::Config {
         /// The overarching event type
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+        /// The overarching call type, used to register the consent response callback
+        type RuntimeCall: From<Call<Self>> + IsType<<Self as pallet_xcm::Config>::RuntimeCall>;
+
         /// Time provider for timestamps
         type TimeProvider: Time;
+
+        /// XCM transport used to reach the IdentityConsent chain
+        type XcmSender: SendXcm;
+
+        /// Location of the IdentityConsent chain, relative to this chain
+        #[pallet::constant]
+        type ConsentChainLocation: Get<Location>;
+
+        /// Index of `pallet_consent_manager` in the IdentityConsent runtime
+        #[pallet::constant]
+        type ConsentManagerPalletIndex: Get<u8>;
+
+        /// Weight limit for the remote `verify_consent` call
+        #[pallet::constant]
+        type ConsentQueryWeight: Get<Weight>;
+
+        /// Number of blocks after which an unanswered consent query is dropped by `pallet_xcm`
+        #[pallet::constant]
+        type ConsentQueryTimeout: Get<BlockNumberFor<Self>>;
+
+        /// Origin of XCM query responses (`pallet_xcm::EnsureResponse`)
+        type ConsentResponseOrigin: EnsureOrigin<
+            <Self as frame_system::Config>::RuntimeOrigin,
+            Success = Location,
+        >;
+
+        /// Grant access as soon as the consent is verified, without waiting for the patient
+        #[pallet::constant]
+        type AutoGrantOnConsent: Get<bool>;
+
+        /// Duration (milliseconds) of grants created from a verified consent
+        #[pallet::constant]
+        type DefaultGrantDuration: Get<u64>;
+
+        /// Category of a health record, used to scope delegations
+        ///
+        /// Sent with consent queries, so it must encode like
+        /// `pallet_consent_manager::RecordCategory`.
+        type RecordCategory: Parameter + MaxEncodedLen;
+
+        /// Lookup of health record ownership and metadata
+        type Records: RecordInspect<Self::AccountId, Self::RecordCategory>;
+
+        /// Maximum pending requests per patient
+        #[pallet::constant]
+        type MaxPendingRequests: Get<u32>;
+
+        /// Number of blocks after which an undecided request expires
+        #[pallet::constant]
+        type PendingRequestTimeout: Get<BlockNumberFor<Self>>;
+
+        /// Maximum requests decided in one bulk call
+        #[pallet::constant]
+        type MaxBulkRequests: Get<u32>;
+
+        /// Maximum requests expired, or blocks of `RequestExpiries` stepped over, per block
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+
+        /// Origin mirroring clinician credentials, auditor roles, guardianships and identity
+        /// recoveries (the IdentityConsent chain)
+        type RegistryOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+        /// Duration (milliseconds) of break-glass grants
+        #[pallet::constant]
+        type BreakGlassDuration: Get<u64>;
+
+        /// Calls a guardian may dispatch on behalf of their ward
+        type WardCalls: Contains<<Self as pallet_xcm::Config>::RuntimeCall>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+
+        /// Benchmark setup for the consent query transport
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<
+            <Self as frame_system::Config>::RuntimeOrigin,
+            Self::AccountId,
+        >;
     }
 
     /// Storage for access requests by request_id
@@ -93,11 +386,120 @@ pub mod pallet {
         u64, // expires_at
     >;
 
+    /// Record versions grants are pinned to (record_id -> requester -> version)
+    #[pallet::storage]
+    #[pallet::getter(fn grant_versions)]
+    pub type GrantVersions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256, // record_id
+        Blake2_128Concat,
+        T::AccountId, // requester
+        u32, // version
+    >;
+
+    /// Break-glass grants, apart from `AccessGrants` (record_id -> clinician -> expires_at)
+    #[pallet::storage]
+    #[pallet::getter(fn emergency_grants)]
+    pub type EmergencyGrants<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256, // record_id
+        Blake2_128Concat,
+        T::AccountId, // clinician
+        u64, // expires_at
+    >;
+
     /// Request counter
     #[pallet::storage]
     #[pallet::getter(fn request_count)]
     pub type RequestCount<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+    /// Outstanding consent queries (XCM query_id -> request_id)
+    #[pallet::storage]
+    #[pallet::getter(fn consent_queries)]
+    pub type ConsentQueries<T: Config> = StorageMap<_, Blake2_128Concat, QueryId, H256>;
+
+    /// Requests awaiting a decision, by patient
+    #[pallet::storage]
+    #[pallet::getter(fn pending_requests)]
+    pub type PendingRequests<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // patient
+        BoundedVec<H256, T::MaxPendingRequests>,
+        ValueQuery,
+    >;
+
+    /// Approval delegations (patient -> delegate -> delegation)
+    #[pallet::storage]
+    #[pallet::getter(fn delegates)]
+    pub type Delegates<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // patient
+        Blake2_128Concat,
+        T::AccountId, // delegate
+        Delegation<T>,
+    >;
+
+    /// Pending requests by the block in which they expire
+    #[pallet::storage]
+    #[pallet::getter(fn request_expiries)]
+    pub type RequestExpiries<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        H256, // request_id
+        (),
+        OptionQuery,
+    >;
+
+    /// Next block of `RequestExpiries` to process
+    #[pallet::storage]
+    #[pallet::getter(fn next_expiry_block)]
+    pub type NextExpiryBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+    /// Clinicians allowed to break the glass (account -> credential valid until)
+    #[pallet::storage]
+    #[pallet::getter(fn clinicians)]
+    pub type Clinicians<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64>;
+
+    /// Auditors reviewing break-glass accesses
+    #[pallet::storage]
+    #[pallet::getter(fn auditors)]
+    pub type Auditors<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    /// Log of break-glass accesses by access ID
+    #[pallet::storage]
+    #[pallet::getter(fn break_glass_accesses)]
+    pub type BreakGlassAccesses<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256, BreakGlassAccess<T>>;
+
+    /// Break-glass accesses awaiting an auditor's review
+    #[pallet::storage]
+    #[pallet::getter(fn pending_reviews)]
+    pub type PendingReviews<T: Config> = StorageMap<_, Blake2_128Concat, H256, ()>;
+
+    /// Legal guardianships (ward -> guardian -> valid until)
+    #[pallet::storage]
+    #[pallet::getter(fn guardians)]
+    pub type Guardians<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // ward
+        Blake2_128Concat,
+        T::AccountId, // guardian
+        u64,
+    >;
+
+    /// Recovered accounts (old account -> new account)
+    #[pallet::storage]
+    #[pallet::getter(fn account_migrations)]
+    pub type AccountMigrations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -124,6 +526,90 @@ pub mod pallet {
             record_id: H256,
             requester: T::AccountId,
         },
+        /// Consent query sent to the IdentityConsent chain [request_id, query_id]
+        ConsentQuerySent {
+            request_id: H256,
+            query_id: QueryId,
+        },
+        /// Consent query answered [request_id, valid]
+        ConsentVerified {
+            request_id: H256,
+            valid: bool,
+        },
+        /// Request expired without a decision [request_id]
+        RequestExpired { request_id: H256 },
+        /// Delegate added or updated [patient, delegate]
+        DelegateAdded {
+            patient: T::AccountId,
+            delegate: T::AccountId,
+        },
+        /// Delegate removed [patient, delegate]
+        DelegateRemoved {
+            patient: T::AccountId,
+            delegate: T::AccountId,
+        },
+        /// Clinician credential mirrored [account, valid_until]
+        ClinicianSet {
+            account: T::AccountId,
+            valid_until: u64,
+        },
+        /// Clinician credential removed [account]
+        ClinicianRemoved { account: T::AccountId },
+        /// Auditor added [account]
+        AuditorAdded { account: T::AccountId },
+        /// Auditor removed [account]
+        AuditorRemoved { account: T::AccountId },
+        /// Emergency access taken without the patient's approval
+        /// [access_id, record_id, clinician, patient, justification_hash, expires_at]
+        BreakGlassAccessed {
+            access_id: H256,
+            record_id: H256,
+            clinician: T::AccountId,
+            patient: T::AccountId,
+            justification_hash: H256,
+            expires_at: u64,
+        },
+        /// Break-glass access reviewed [access_id, auditor, misuse]
+        BreakGlassReviewed {
+            access_id: H256,
+            auditor: T::AccountId,
+            misuse: bool,
+        },
+        /// Guardianship mirrored [ward, guardian, valid_until]
+        GuardianshipSet {
+            ward: T::AccountId,
+            guardian: T::AccountId,
+            valid_until: u64,
+        },
+        /// Guardianship removed [ward, guardian]
+        GuardianshipRemoved {
+            ward: T::AccountId,
+            guardian: T::AccountId,
+        },
+        /// Call dispatched by a guardian on behalf of their ward [ward, guardian]
+        ActedForWard {
+            ward: T::AccountId,
+            guardian: T::AccountId,
+        },
+        /// Grant pinned to a record version, or following the latest one when `None`
+        /// [record_id, requester, version]
+        GrantVersionPinned {
+            record_id: H256,
+            requester: T::AccountId,
+            version: Option<u32>,
+        },
+        /// Pending requests moved to a recovered identity's new account [old, new, requests]
+        AccountMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+            requests: u32,
+        },
+        /// Grant of a recovered account moved to its new account [record_id, old, new]
+        GrantMigrated {
+            record_id: H256,
+            old: T::AccountId,
+            new: T::AccountId,
+        },
     }
 
     #[pallet::error]
@@ -136,21 +622,87 @@ pub mod pallet {
         AccessAlreadyGranted,
         /// No active consent found
         NoActiveConsent,
+        /// Request is not pending
+        RequestNotPending,
+        /// Consent has not been verified by the IdentityConsent chain
+        ConsentNotVerified,
+        /// Consent query could not be sent
+        ConsentQueryFailed,
+        /// Response does not match an outstanding consent query
+        UnknownConsentQuery,
+        /// Response came from an unexpected location
+        InvalidResponder,
+        /// Patient has too many pending requests
+        TooManyPendingRequests,
+        /// Patient cannot delegate to themselves
+        CannotDelegateToSelf,
+        /// Delegation expiry is in the past
+        InvalidExpiry,
+        /// Delegate not found
+        DelegateNotFound,
+        /// Access grant not found
+        GrantNotFound,
+        /// Record not found
+        RecordNotFound,
+        /// Caller does not hold a valid clinician credential
+        NotClinician,
+        /// Caller is not an auditor
+        NotAuditor,
+        /// Break-glass access needs a justification hash
+        MissingJustification,
+        /// Break-glass access not found
+        BreakGlassNotFound,
+        /// Break-glass access already reviewed
+        AlreadyReviewed,
+        /// Caller is not a legal guardian of the ward
+        NotLegalGuardian,
+        /// Call cannot be dispatched on behalf of a ward
+        CallNotAllowed,
+        /// Record version not found
+        VersionNotFound,
+        /// Patient does not own the record
+        NotRecordOwner,
+        /// Accounts of a migration must differ
+        InvalidMigration,
+        /// Account has not been recovered to the caller
+        NotMigrated,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            Self::process_expiries(n)
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Request access to a health record
+        ///
+        /// `patient` must own the record. The request stays `Pending` until the IdentityConsent
+        /// chain answers the consent query, and is queued for the patient's decision once its
+        /// consent is verified. A request for a given `version` leads to a grant pinned to it.
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_access())]
         pub fn request_access(
             origin: OriginFor<T>,
             record_id: H256,
+            version: Option<u32>,
             patient: T::AccountId,
             consent_id: H256,
         ) -> DispatchResult {
             let requester = ensure_signed(origin)?;
 
+            // The consent is checked against `patient`, so it must be the record's owner
+            let owner = T::Records::record_owner(&record_id).ok_or(Error::<T>::RecordNotFound)?;
+            ensure!(owner == patient, Error::<T>::NotRecordOwner);
+
+            if let Some(version) = version {
+                let latest =
+                    T::Records::record_version(&record_id).ok_or(Error::<T>::RecordNotFound)?;
+                ensure!(version > 0 && version <= latest, Error::<T>::VersionNotFound);
+            }
+
             let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
 
             // Generate request ID
@@ -161,28 +713,41 @@ pub mod pallet {
             let request = AccessRequest {
                 request_id,
                 record_id,
+                version,
                 requester: requester.clone(),
-                patient,
+                patient: patient.clone(),
                 consent_id: Some(consent_id),
                 status: AccessStatus::Pending,
                 requested_at: now,
                 responded_at: None,
+                consent_verified: false,
             };
 
+            // The request is queued for the patient's decision once its consent is verified
+            let expires_in = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::PendingRequestTimeout::get());
+            Self::schedule_expiry(expires_in, request_id);
+
+            let query_id = Self::send_consent_query(&request)?;
+
             AccessRequests::<T>::insert(request_id, request);
+            ConsentQueries::<T>::insert(query_id, request_id);
 
             Self::deposit_event(Event::AccessRequested {
                 request_id,
                 record_id,
                 requester,
             });
+            Self::deposit_event(Event::ConsentQuerySent { request_id, query_id });
 
             Ok(())
         }
 
-        /// Grant access to a record (called after consent verification)
+        /// Grant access to a record (patient approval after consent verification)
+        ///
+        /// Callable by the patient or one of their delegates.
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::grant_access())]
         pub fn grant_access(
             origin: OriginFor<T>,
             request_id: H256,
@@ -190,18 +755,576 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
+            Self::do_grant_access(&who, request_id, expires_at)
+        }
+
+        /// Deny access request
+        ///
+        /// Callable by the patient or one of their delegates.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::deny_access())]
+        pub fn deny_access(origin: OriginFor<T>, request_id: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::do_deny_access(&who, request_id)
+        }
+
+        /// Revoke access to a record
+        ///
+        /// Callable by the record's patient, one of their delegates, or the grantee giving up
+        /// its own access.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_access())]
+        pub fn revoke_access(
+            origin: OriginFor<T>,
+            record_id: H256,
+            requester: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                AccessGrants::<T>::contains_key(&record_id, &requester),
+                Error::<T>::GrantNotFound
+            );
+
+            if who != requester {
+                let owner = T::Records::record_owner(&record_id).ok_or(Error::<T>::NotAuthorized)?;
+                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(
+                    who == owner || Self::is_delegate(&owner, &who, &record_id, now),
+                    Error::<T>::NotAuthorized
+                );
+            }
+
+            // Remove access grant
+            AccessGrants::<T>::remove(&record_id, &requester);
+            GrantVersions::<T>::remove(&record_id, &requester);
+
+            Self::deposit_event(Event::AccessRevoked { record_id, requester });
+
+            Ok(())
+        }
+
+        /// Handle the IdentityConsent chain's answer to a consent query
+        ///
+        /// Dispatched by `pallet_xcm` when the `QueryResponse` for `query_id` arrives.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::consent_response())]
+        pub fn consent_response(
+            origin: OriginFor<T>,
+            query_id: QueryId,
+            response: Response,
+        ) -> DispatchResult {
+            let responder = T::ConsentResponseOrigin::ensure_origin(origin)?;
+            ensure!(
+                responder == T::ConsentChainLocation::get(),
+                Error::<T>::InvalidResponder
+            );
+
+            let request_id =
+                ConsentQueries::<T>::take(query_id).ok_or(Error::<T>::UnknownConsentQuery)?;
+
+            let consent_valid =
+                matches!(response, Response::DispatchResult(MaybeErrorCode::Success));
+
+            let mut valid = consent_valid;
             AccessRequests::<T>::try_mutate(request_id, |maybe_request| -> DispatchResult {
                 let request = maybe_request.as_mut().ok_or(Error::<T>::RequestNotFound)?;
 
-                // Only patient can grant access
-                ensure!(request.patient == who, Error::<T>::NotAuthorized);
+                // The patient may already have denied the request
+                if request.status != AccessStatus::Pending {
+                    return Ok(());
+                }
+
+                // The record may have changed hands since the request was made
+                valid = consent_valid && Self::owns_record(&request.patient, &request.record_id);
+
+                let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                request.consent_verified = valid;
+
+                // A verified request waits in the patient's queue unless it is granted at once,
+                // and is denied if the queue is full
+                let denied = !valid
+                    || (!T::AutoGrantOnConsent::get()
+                        && PendingRequests::<T>::mutate(&request.patient, |pending| {
+                            pending.try_push(request_id).is_err()
+                        }));
+
+                if denied {
+                    request.status = AccessStatus::Denied;
+                    request.responded_at = Some(now);
+
+                    Self::deposit_event(Event::AccessDenied {
+                        request_id,
+                        record_id: request.record_id,
+                        requester: request.requester.clone(),
+                    });
+                } else if T::AutoGrantOnConsent::get() {
+                    request.status = AccessStatus::Granted;
+                    request.responded_at = Some(now);
+
+                    let expires_at = now.saturating_add(T::DefaultGrantDuration::get());
+                    Self::store_grant(request, expires_at);
+
+                    Self::deposit_event(Event::AccessGranted {
+                        request_id,
+                        record_id: request.record_id,
+                        requester: request.requester.clone(),
+                    });
+                }
+
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ConsentVerified { request_id, valid });
+
+            Ok(())
+        }
+
+        /// Let `delegate` grant and deny access requests on the caller's behalf
+        ///
+        /// - `categories`: Record categories covered by the delegation (all when empty)
+        /// - `expires_at`: Timestamp after which the delegation lapses (`None` = until removed)
+        ///
+        /// Replaces any existing delegation to the same account.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_delegate())]
+        pub fn add_delegate(
+            origin: OriginFor<T>,
+            delegate: T::AccountId,
+            categories: BoundedVec<T::RecordCategory, ConstU32<16>>,
+            expires_at: Option<u64>,
+        ) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(patient != delegate, Error::<T>::CannotDelegateToSelf);
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(
+                expires_at.map_or(true, |expires_at| expires_at > now),
+                Error::<T>::InvalidExpiry
+            );
+
+            Delegates::<T>::insert(
+                &patient,
+                &delegate,
+                Delegation { categories, expires_at, created_at: now },
+            );
+
+            Self::deposit_event(Event::DelegateAdded { patient, delegate });
+
+            Ok(())
+        }
+
+        /// Remove a delegate
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_delegate())]
+        pub fn remove_delegate(origin: OriginFor<T>, delegate: T::AccountId) -> DispatchResult {
+            let patient = ensure_signed(origin)?;
+
+            ensure!(
+                Delegates::<T>::contains_key(&patient, &delegate),
+                Error::<T>::DelegateNotFound
+            );
+            Delegates::<T>::remove(&patient, &delegate);
+
+            Self::deposit_event(Event::DelegateRemoved { patient, delegate });
+
+            Ok(())
+        }
+
+        /// Grant several access requests at once
+        ///
+        /// Fails as a whole if any of the requests cannot be granted.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::bulk_grant_access(request_ids.len() as u32))]
+        pub fn bulk_grant_access(
+            origin: OriginFor<T>,
+            request_ids: BoundedVec<H256, T::MaxBulkRequests>,
+            expires_at: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            for request_id in request_ids {
+                Self::do_grant_access(&who, request_id, expires_at)?;
+            }
+
+            Ok(())
+        }
+
+        /// Deny several access requests at once
+        ///
+        /// Fails as a whole if any of the requests cannot be denied.
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::bulk_deny_access(request_ids.len() as u32))]
+        pub fn bulk_deny_access(
+            origin: OriginFor<T>,
+            request_ids: BoundedVec<H256, T::MaxBulkRequests>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            for request_id in request_ids {
+                Self::do_deny_access(&who, request_id)?;
+            }
+
+            Ok(())
+        }
+
+        /// Mirror an account's clinician credential (`None` removes it)
+        ///
+        /// - `valid_until`: Timestamp at which the credential lapses
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_clinician())]
+        pub fn set_clinician(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            valid_until: Option<u64>,
+        ) -> DispatchResult {
+            T::RegistryOrigin::ensure_origin(origin)?;
+
+            match valid_until {
+                Some(valid_until) => {
+                    Clinicians::<T>::insert(&account, valid_until);
+                    Self::deposit_event(Event::ClinicianSet { account, valid_until });
+                },
+                None => {
+                    Clinicians::<T>::remove(&account);
+                    Self::deposit_event(Event::ClinicianRemoved { account });
+                },
+            }
+
+            Ok(())
+        }
+
+        /// Mirror an account's auditor credential
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_auditor())]
+        pub fn set_auditor(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            is_auditor: bool,
+        ) -> DispatchResult {
+            T::RegistryOrigin::ensure_origin(origin)?;
+
+            if is_auditor {
+                Auditors::<T>::insert(&account, ());
+                Self::deposit_event(Event::AuditorAdded { account });
+            } else {
+                Auditors::<T>::remove(&account);
+                Self::deposit_event(Event::AuditorRemoved { account });
+            }
+
+            Ok(())
+        }
+
+        /// Take emergency access to a record without the patient's approval
+        ///
+        /// Access is granted at once for `BreakGlassDuration` and queued for an auditor's
+        /// review. Only callable by a clinician with a valid credential.
+        ///
+        /// - `justification_hash`: Hash of the justification kept off-chain
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::break_glass())]
+        pub fn break_glass(
+            origin: OriginFor<T>,
+            record_id: H256,
+            justification_hash: H256,
+        ) -> DispatchResult {
+            let clinician = ensure_signed(origin)?;
+
+            ensure!(justification_hash != H256::zero(), Error::<T>::MissingJustification);
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(Self::is_clinician(&clinician, now), Error::<T>::NotClinician);
+
+            let patient = T::Records::record_owner(&record_id).ok_or(Error::<T>::RecordNotFound)?;
+
+            // Never shorten a longer emergency grant the clinician already holds
+            let expires_at = now.saturating_add(T::BreakGlassDuration::get());
+            EmergencyGrants::<T>::mutate(&record_id, &clinician, |grant| {
+                *grant = Some(grant.map_or(expires_at, |current| current.max(expires_at)));
+            });
+
+            let count = RequestCount::<T>::get();
+            let access_id = Self::generate_request_id(&clinician, count);
+            RequestCount::<T>::put(count.saturating_add(1));
+
+            BreakGlassAccesses::<T>::insert(
+                access_id,
+                BreakGlassAccess {
+                    record_id,
+                    clinician: clinician.clone(),
+                    patient: patient.clone(),
+                    justification_hash,
+                    granted_at: now,
+                    expires_at,
+                    review: BreakGlassReview::Pending,
+                    reviewer: None,
+                    reviewed_at: None,
+                },
+            );
+            PendingReviews::<T>::insert(access_id, ());
+
+            Self::deposit_event(Event::BreakGlassAccessed {
+                access_id,
+                record_id,
+                clinician,
+                patient,
+                justification_hash,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Sign off a break-glass access, or flag it as misuse
+        ///
+        /// Flagging revokes the clinician's emergency access to the record, but not a grant the
+        /// patient approved. Only callable by an auditor other than the clinician.
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::review_break_glass())]
+        pub fn review_break_glass(
+            origin: OriginFor<T>,
+            access_id: H256,
+            misuse: bool,
+        ) -> DispatchResult {
+            let auditor = ensure_signed(origin)?;
+
+            ensure!(Auditors::<T>::contains_key(&auditor), Error::<T>::NotAuditor);
+
+            BreakGlassAccesses::<T>::try_mutate(access_id, |maybe_access| -> DispatchResult {
+                let access = maybe_access.as_mut().ok_or(Error::<T>::BreakGlassNotFound)?;
+
+                ensure!(access.review == BreakGlassReview::Pending, Error::<T>::AlreadyReviewed);
+                ensure!(access.clinician != auditor, Error::<T>::NotAuthorized);
+
+                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                access.review =
+                    if misuse { BreakGlassReview::Flagged } else { BreakGlassReview::Approved };
+                access.reviewer = Some(auditor.clone());
+                access.reviewed_at = Some(now);
+
+                // Misuse cuts the clinician's emergency access short
+                if misuse {
+                    let record_id = access.record_id;
+                    if EmergencyGrants::<T>::take(&record_id, &access.clinician).is_some() {
+                        Self::deposit_event(Event::AccessRevoked {
+                            record_id,
+                            requester: access.clinician.clone(),
+                        });
+                    }
+                }
+
+                Ok(())
+            })?;
+
+            PendingReviews::<T>::remove(access_id);
+
+            Self::deposit_event(Event::BreakGlassReviewed { access_id, auditor, misuse });
+
+            Ok(())
+        }
+
+        /// Mirror a guardianship (`None` removes it)
+        ///
+        /// - `valid_until`: Timestamp at which the ward takes over
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_guardianship())]
+        pub fn set_guardianship(
+            origin: OriginFor<T>,
+            ward: T::AccountId,
+            guardian: T::AccountId,
+            valid_until: Option<u64>,
+        ) -> DispatchResult {
+            T::RegistryOrigin::ensure_origin(origin)?;
+
+            match valid_until {
+                Some(valid_until) => {
+                    Guardians::<T>::insert(&ward, &guardian, valid_until);
+                    Self::deposit_event(Event::GuardianshipSet { ward, guardian, valid_until });
+                },
+                None => {
+                    Guardians::<T>::remove(&ward, &guardian);
+                    Self::deposit_event(Event::GuardianshipRemoved { ward, guardian });
+                },
+            }
+
+            Ok(())
+        }
+
+        /// Dispatch a call on behalf of a ward, as their legal guardian
+        ///
+        /// The call runs with the ward's signed origin and must be admitted by `WardCalls`.
+        #[pallet::call_index(14)]
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            <T as Config>::WeightInfo::act_for_ward().saturating_add(dispatch_info.weight)
+        })]
+        pub fn act_for_ward(
+            origin: OriginFor<T>,
+            ward: T::AccountId,
+            call: Box<<T as pallet_xcm::Config>::RuntimeCall>,
+        ) -> DispatchResult {
+            let guardian = ensure_signed(origin)?;
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(Self::is_legal_guardian(&ward, &guardian, now), Error::<T>::NotLegalGuardian);
+            ensure!(T::WardCalls::contains(&call), Error::<T>::CallNotAllowed);
+
+            let ward_origin: <T as frame_system::Config>::RuntimeOrigin =
+                frame_system::RawOrigin::Signed(ward.clone()).into();
+            call.dispatch(ward_origin.into()).map_err(|e| e.error)?;
+
+            Self::deposit_event(Event::ActedForWard { ward, guardian });
+
+            Ok(())
+        }
+
+        /// Pin a grant to a record version (`None` follows the latest version again)
+        ///
+        /// Callable by the record's patient or one of their delegates.
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::pin_grant_version())]
+        pub fn pin_grant_version(
+            origin: OriginFor<T>,
+            record_id: H256,
+            requester: T::AccountId,
+            version: Option<u32>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                AccessGrants::<T>::contains_key(&record_id, &requester),
+                Error::<T>::GrantNotFound
+            );
+
+            let owner = T::Records::record_owner(&record_id).ok_or(Error::<T>::NotAuthorized)?;
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(
+                who == owner || Self::is_delegate(&owner, &who, &record_id, now),
+                Error::<T>::NotAuthorized
+            );
+
+            match version {
+                Some(version) => {
+                    let latest =
+                        T::Records::record_version(&record_id).ok_or(Error::<T>::RecordNotFound)?;
+                    ensure!(version > 0 && version <= latest, Error::<T>::VersionNotFound);
+                    GrantVersions::<T>::insert(&record_id, &requester, version);
+                },
+                None => GrantVersions::<T>::remove(&record_id, &requester),
+            }
+
+            Self::deposit_event(Event::GrantVersionPinned { record_id, requester, version });
+
+            Ok(())
+        }
+
+        /// Move the pending requests of a recovered identity to its new account
+        ///
+        /// Also lets the new account claim the old account's grants with `migrate_grant`.
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::migrate_account())]
+        pub fn migrate_account(
+            origin: OriginFor<T>,
+            old: T::AccountId,
+            new: T::AccountId,
+        ) -> DispatchResult {
+            T::RegistryOrigin::ensure_origin(origin)?;
+
+            ensure!(old != new, Error::<T>::InvalidMigration);
+
+            let requests = PendingRequests::<T>::take(&old);
+            PendingRequests::<T>::try_mutate(&new, |pending| -> DispatchResult {
+                for request_id in requests.iter() {
+                    pending.try_push(*request_id).map_err(|_| Error::<T>::TooManyPendingRequests)?;
+                }
+                Ok(())
+            })?;
+            for request_id in requests.iter() {
+                AccessRequests::<T>::mutate(request_id, |maybe_request| {
+                    if let Some(request) = maybe_request {
+                        request.patient = new.clone();
+                    }
+                });
+            }
+
+            AccountMigrations::<T>::insert(&old, &new);
+
+            Self::deposit_event(Event::AccountMigrated {
+                old,
+                new,
+                requests: requests.len() as u32,
+            });
+
+            Ok(())
+        }
+
+        /// Move a grant held by a recovered account to its new account
+        ///
+        /// Callable by the account the identity was recovered to.
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::migrate_grant())]
+        pub fn migrate_grant(
+            origin: OriginFor<T>,
+            old: T::AccountId,
+            record_id: H256,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                AccountMigrations::<T>::get(&old).as_ref() == Some(&who),
+                Error::<T>::NotMigrated
+            );
+
+            let expires_at =
+                AccessGrants::<T>::take(&record_id, &old).ok_or(Error::<T>::GrantNotFound)?;
+            AccessGrants::<T>::insert(&record_id, &who, expires_at);
+            if let Some(version) = GrantVersions::<T>::take(&record_id, &old) {
+                GrantVersions::<T>::insert(&record_id, &who, version);
+            }
+
+            Self::deposit_event(Event::GrantMigrated { record_id, old, new: who });
+
+            Ok(())
+        }
+    }
+
+    // Helper functions
+    impl<T: Config> Pallet<T> {
+        /// Generate unique request ID
+        fn generate_request_id(requester: &T::AccountId, nonce: u64) -> H256 {
+            use sp_runtime::traits::Hash;
+            let mut data = requester.encode();
+            data.extend_from_slice(&nonce.encode());
+            T::Hashing::hash(&data)
+        }
+
+        /// Grant a pending, consent-verified request on behalf of `who`
+        fn do_grant_access(
+            who: &T::AccountId,
+            request_id: H256,
+            expires_at: u64,
+        ) -> DispatchResult {
+            AccessRequests::<T>::try_mutate(request_id, |maybe_request| -> DispatchResult {
+                let request = maybe_request.as_mut().ok_or(Error::<T>::RequestNotFound)?;
 
                 let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(Self::can_decide(who, request, now), Error::<T>::NotAuthorized);
+                ensure!(request.status == AccessStatus::Pending, Error::<T>::RequestNotPending);
+
+                // The consent must have been confirmed by the IdentityConsent chain
+                ensure!(request.consent_verified, Error::<T>::ConsentNotVerified);
+                ensure!(
+                    Self::owns_record(&request.patient, &request.record_id),
+                    Error::<T>::NotRecordOwner
+                );
+
                 request.status = AccessStatus::Granted;
                 request.responded_at = Some(now);
+                Self::remove_pending(&request.patient, &request_id);
 
-                // Store access grant
-                AccessGrants::<T>::insert(&request.record_id, &request.requester, expires_at);
+                Self::store_grant(request, expires_at);
 
                 Self::deposit_event(Event::AccessGranted {
                     request_id,
@@ -213,20 +1336,18 @@ pub mod pallet {
             })
         }
 
-        /// Deny access request
-        #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
-        pub fn deny_access(origin: OriginFor<T>, request_id: H256) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
+        /// Deny a pending request on behalf of `who`
+        fn do_deny_access(who: &T::AccountId, request_id: H256) -> DispatchResult {
             AccessRequests::<T>::try_mutate(request_id, |maybe_request| -> DispatchResult {
                 let request = maybe_request.as_mut().ok_or(Error::<T>::RequestNotFound)?;
 
-                ensure!(request.patient == who, Error::<T>::NotAuthorized);
-
                 let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(Self::can_decide(who, request, now), Error::<T>::NotAuthorized);
+                ensure!(request.status == AccessStatus::Pending, Error::<T>::RequestNotPending);
+
                 request.status = AccessStatus::Denied;
                 request.responded_at = Some(now);
+                Self::remove_pending(&request.patient, &request_id);
 
                 Self::deposit_event(Event::AccessDenied {
                     request_id,
@@ -238,42 +1359,230 @@ pub mod pallet {
             })
         }
 
-        /// Revoke access to a record
-        #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
-        pub fn revoke_access(
-            origin: OriginFor<T>,
-            record_id: H256,
-            requester: T::AccountId,
-        ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+        /// Whether `patient` owns `record_id`
+        fn owns_record(patient: &T::AccountId, record_id: &H256) -> bool {
+            T::Records::record_owner(record_id).as_ref() == Some(patient)
+        }
 
-            // Remove access grant
-            AccessGrants::<T>::remove(&record_id, &requester);
+        /// Whether `who` may decide on a request: its patient or a delegate covering the record
+        fn can_decide(who: &T::AccountId, request: &AccessRequest<T>, now: u64) -> bool {
+            *who == request.patient
+                || Self::is_delegate(&request.patient, who, &request.record_id, now)
+        }
 
-            Self::deposit_event(Event::AccessRevoked { record_id, requester });
+        /// Check if `delegate` may act for `patient` on `record_id`
+        pub fn is_delegate(
+            patient: &T::AccountId,
+            delegate: &T::AccountId,
+            record_id: &H256,
+            now: u64,
+        ) -> bool {
+            let Some(delegation) = Delegates::<T>::get(patient, delegate) else {
+                return false;
+            };
 
-            Ok(())
+            if delegation.expires_at.map_or(false, |expires_at| now >= expires_at) {
+                return false;
+            }
+
+            delegation.categories.is_empty()
+                || T::Records::record_category(record_id)
+                    .map_or(false, |category| delegation.categories.contains(&category))
         }
-    }
 
-    // Helper functions
-    impl<T: Config> Pallet<T> {
-        /// Generate unique request ID
-        fn generate_request_id(requester: &T::AccountId, nonce: u64) -> H256 {
-            use sp_runtime::traits::Hash;
-            let mut data = requester.encode();
-            data.extend_from_slice(&nonce.encode());
-            T::Hashing::hash(&data)
+        /// Drop a request from its patient's pending list
+        fn remove_pending(patient: &T::AccountId, request_id: &H256) {
+            PendingRequests::<T>::mutate(patient, |pending| pending.retain(|id| id != request_id));
         }
 
-        /// Check if access is granted
-        pub fn has_access(record_id: &H256, requester: &T::AccountId, now: u64) -> bool {
-            if let Some(expires_at) = AccessGrants::<T>::get(record_id, requester) {
-                expires_at > now
-            } else {
-                false
+        /// Schedule a request to expire in block `expires_in`
+        fn schedule_expiry(expires_in: BlockNumberFor<T>, request_id: H256) {
+            RequestExpiries::<T>::insert(expires_in, request_id, ());
+            NextExpiryBlock::<T>::mutate(|next| {
+                if next.map_or(true, |next| expires_in < next) {
+                    *next = Some(expires_in);
+                }
+            });
+        }
+
+        /// Expire the requests scheduled up to block `n`, bounded by `MaxExpiriesPerBlock`
+        fn process_expiries(n: BlockNumberFor<T>) -> Weight {
+            let db_weight = <T as frame_system::Config>::DbWeight::get();
+            let step_weight = db_weight.reads(1);
+            let expiry_weight =
+                <T as Config>::WeightInfo::expire_request().saturating_add(db_weight.writes(1));
+
+            let Some(mut block) = NextExpiryBlock::<T>::get() else {
+                return db_weight.reads(1);
+            };
+
+            let mut used = db_weight.reads_writes(1, 1);
+            let mut budget = T::MaxExpiriesPerBlock::get();
+
+            while block <= n && budget > 0 {
+                used = used.saturating_add(step_weight);
+
+                let due: Vec<H256> =
+                    RequestExpiries::<T>::iter_key_prefix(block).take(budget as usize).collect();
+                if due.is_empty() {
+                    budget -= 1;
+                    block = block.saturating_add(One::one());
+                    continue;
+                }
+
+                for request_id in due {
+                    used = used.saturating_add(expiry_weight);
+                    budget -= 1;
+
+                    RequestExpiries::<T>::remove(block, request_id);
+                    Self::expire_request(request_id);
+                }
             }
+
+            NextExpiryBlock::<T>::put(block);
+
+            used
+        }
+
+        /// Expire a request that is still pending
+        pub(crate) fn expire_request(request_id: H256) {
+            AccessRequests::<T>::mutate(request_id, |maybe_request| {
+                let Some(request) = maybe_request else { return };
+
+                // Already decided
+                if request.status != AccessStatus::Pending {
+                    return;
+                }
+
+                request.status = AccessStatus::Expired;
+                request.responded_at = Some(T::TimeProvider::now().try_into().ok().unwrap_or(0));
+                Self::remove_pending(&request.patient, &request_id);
+
+                Self::deposit_event(Event::RequestExpired { request_id });
+            });
+        }
+
+        /// Send the consent verification query for a request to the IdentityConsent chain
+        fn send_consent_query(request: &AccessRequest<T>) -> Result<QueryId, DispatchError> {
+            let consent_id = request.consent_id.ok_or(Error::<T>::NoActiveConsent)?;
+            let dest = T::ConsentChainLocation::get();
+
+            // Register the callback before building the message so the query id is known
+            let notify_call = Call::<T>::consent_response {
+                query_id: 0,
+                response: Response::Null,
+            };
+            let max_weight = notify_call.get_dispatch_info().weight;
+            let notify: <T as Config>::RuntimeCall = notify_call.into();
+            let timeout = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::ConsentQueryTimeout::get());
+            let query_id = pallet_xcm::Pallet::<T>::new_notify_query(
+                dest.clone(),
+                notify.into(),
+                timeout,
+                Here,
+            );
+
+            // Where the IdentityConsent chain must send the response
+            let destination = <T as pallet_xcm::Config>::UniversalLocation::get()
+                .invert_target(&dest)
+                .map_err(|()| Error::<T>::ConsentQueryFailed)?;
+
+            let call = ConsentManagerCall::VerifyConsent {
+                consent_id,
+                data_owner: request.patient.clone(),
+                accessor: request.requester.clone(),
+                record: Self::consent_record(&request.record_id, request.version),
+            };
+            let encoded_call = (T::ConsentManagerPalletIndex::get(), call).encode();
+
+            let message = Xcm(vec![
+                UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+                SetAppendix(Xcm(vec![ReportTransactStatus(QueryResponseInfo {
+                    destination,
+                    query_id,
+                    max_weight,
+                })])),
+                Transact {
+                    origin_kind: OriginKind::Xcm,
+                    require_weight_at_most: T::ConsentQueryWeight::get(),
+                    call: encoded_call.into(),
+                },
+            ]);
+
+            send_xcm::<T::XcmSender>(dest, message)
+                .map_err(|_| Error::<T>::ConsentQueryFailed)?;
+
+            Ok(query_id)
+        }
+
+        /// Describe a record for the consent scope check, `None` if it is unknown
+        ///
+        /// Access requests are for the full record, so it is never reported as de-identified.
+        fn consent_record(
+            record_id: &H256,
+            version: Option<u32>,
+        ) -> Option<ConsentRecord<T::RecordCategory>> {
+            Some(ConsentRecord {
+                record_id: *record_id,
+                category: T::Records::record_category(record_id)?,
+                created_at: T::Records::record_created_at(record_id)?,
+                de_identified: false,
+                version,
+            })
+        }
+
+        /// Store the grant of an approved request, pinned to the version it requested if any
+        fn store_grant(request: &AccessRequest<T>, expires_at: u64) {
+            AccessGrants::<T>::insert(&request.record_id, &request.requester, expires_at);
+            match request.version {
+                Some(version) => {
+                    GrantVersions::<T>::insert(&request.record_id, &request.requester, version)
+                },
+                None => GrantVersions::<T>::remove(&request.record_id, &request.requester),
+            }
+        }
+
+        /// Check if `who` holds a clinician credential valid at `now`
+        pub fn is_clinician(who: &T::AccountId, now: u64) -> bool {
+            Clinicians::<T>::get(who).map_or(false, |valid_until| valid_until > now)
+        }
+
+        /// Check if `guardian` may act for `ward` at `now`
+        pub fn is_legal_guardian(ward: &T::AccountId, guardian: &T::AccountId, now: u64) -> bool {
+            Guardians::<T>::get(ward, guardian).map_or(false, |valid_until| now < valid_until)
+        }
+
+        /// Check if access is granted, by the patient or in an emergency
+        pub fn has_access(record_id: &H256, requester: &T::AccountId, now: u64) -> bool {
+            Self::has_granted_access(record_id, requester, now)
+                || Self::has_emergency_access(record_id, requester, now)
+        }
+
+        /// Check if access is granted to `version` of a record, i.e. an emergency grant or a
+        /// grant not pinned to another version
+        pub fn has_version_access(
+            record_id: &H256,
+            requester: &T::AccountId,
+            version: u32,
+            now: u64,
+        ) -> bool {
+            Self::has_emergency_access(record_id, requester, now)
+                || (Self::has_granted_access(record_id, requester, now)
+                    && GrantVersions::<T>::get(record_id, requester)
+                        .map_or(true, |pinned| pinned == version))
+        }
+
+        /// Check if the patient's grant is in force
+        fn has_granted_access(record_id: &H256, requester: &T::AccountId, now: u64) -> bool {
+            AccessGrants::<T>::get(record_id, requester)
+                .map_or(false, |expires_at| expires_at > now)
+        }
+
+        /// Check if a break-glass grant is in force
+        fn has_emergency_access(record_id: &H256, requester: &T::AccountId, now: u64) -> bool {
+            EmergencyGrants::<T>::get(record_id, requester)
+                .map_or(false, |expires_at| expires_at > now)
         }
     }
 }
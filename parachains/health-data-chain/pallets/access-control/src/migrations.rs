@@ -0,0 +1,61 @@
+//! Storage migrations for the access-control pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_core::H256;
+
+/// Version 1 records whether the IdentityConsent chain confirmed the consent of each request
+pub mod v1 {
+    use super::*;
+
+    /// Access request of version 0, before consents were verified over XCM
+    #[derive(Encode, Decode)]
+    pub struct OldAccessRequest<T: Config> {
+        pub request_id: H256,
+        pub record_id: H256,
+        pub requester: T::AccountId,
+        pub patient: T::AccountId,
+        pub consent_id: Option<H256>,
+        pub status: AccessStatus,
+        pub requested_at: u64,
+        pub responded_at: Option<u64>,
+    }
+
+    /// Mark every existing request as unverified, since no consent query was ever answered
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+
+            AccessRequests::<T>::translate::<OldAccessRequest<T>, _>(|_, old| {
+                translated += 1;
+                Some(AccessRequest {
+                    request_id: old.request_id,
+                    record_id: old.record_id,
+                    version: None,
+                    requester: old.requester,
+                    patient: old.patient,
+                    consent_id: old.consent_id,
+                    status: old.status,
+                    requested_at: old.requested_at,
+                    responded_at: old.responded_at,
+                    consent_verified: false,
+                })
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Migrate `AccessRequests` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,380 @@
+//! Mock runtime for access-control pallet tests
+
+use crate as pallet_access_control;
+use codec::Encode;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64, Contains, Everything, Nothing},
+    weights::Weight,
+};
+use frame_system::{EnsureNever, EnsureRoot};
+use sp_core::H256;
+use sp_runtime::{traits::IdentityLookup, AccountId32, BuildStorage};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+    AccountId32Aliases, AllowUnpaidExecutionFrom, FixedWeightBounds, FrameTransactionalProcessor,
+    FungibleAdapter, IsConcrete, SignedAccountId32AsNative,
+};
+use xcm_executor::XcmExecutor;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+pub const PATIENT: AccountId = AccountId32::new([1u8; 32]);
+pub const RESEARCHER: AccountId = AccountId32::new([2u8; 32]);
+pub const GUARDIAN: AccountId = AccountId32::new([3u8; 32]);
+pub const STRANGER: AccountId = AccountId32::new([4u8; 32]);
+pub const CLINICIAN: AccountId = AccountId32::new([5u8; 32]);
+pub const AUDITOR: AccountId = AccountId32::new([6u8; 32]);
+
+/// Duration of break-glass grants
+pub const BREAK_GLASS_DURATION: u64 = 3_600_000;
+
+/// Upload timestamp of every record in `MockRecords`
+pub const RECORD_CREATED_AT: u64 = 500;
+
+/// Latest version of every record in `MockRecords`
+pub const RECORD_VERSION: u32 = 2;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        PolkadotXcm: pallet_xcm,
+        AccessControl: pallet_access_control,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type AccountStore = System;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<5>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const RelayLocation: Location = Location::parent();
+    pub RelayNetwork: NetworkId = ByGenesis([0; 32]);
+    pub UniversalLocation: InteriorLocation =
+        [GlobalConsensus(RelayNetwork::get()), Parachain(2001)].into();
+    pub UnitWeightCost: Weight = Weight::from_parts(1_000, 1_000);
+    pub const MaxInstructions: u32 = 100;
+    pub const MaxAssetsIntoHolding: u32 = 64;
+    /// Messages sent through `TestSendXcm`
+    pub static SentXcm: Vec<(Location, Xcm<()>)> = vec![];
+}
+
+/// Records sent messages instead of delivering them
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+    type Ticket = (Location, Xcm<()>);
+
+    fn validate(
+        dest: &mut Option<Location>,
+        msg: &mut Option<Xcm<()>>,
+    ) -> SendResult<Self::Ticket> {
+        let pair = (dest.take().ok_or(SendError::MissingArgument)?, msg.take().unwrap());
+        Ok((pair, Assets::new()))
+    }
+
+    fn deliver(pair: Self::Ticket) -> Result<XcmHash, SendError> {
+        let hash = pair.1.using_encoded(sp_io::hashing::blake2_256);
+        SentXcm::mutate(|sent| sent.push(pair));
+        Ok(hash)
+    }
+}
+
+pub type LocationToAccountId = AccountId32Aliases<RelayNetwork, AccountId>;
+
+pub type LocalAssetTransactor =
+    FungibleAdapter<Balances, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>;
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+    type RuntimeCall = RuntimeCall;
+    type XcmSender = TestSendXcm;
+    type AssetTransactor = LocalAssetTransactor;
+    type OriginConverter = SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>;
+    type IsReserve = ();
+    type IsTeleporter = ();
+    type UniversalLocation = UniversalLocation;
+    type Barrier = AllowUnpaidExecutionFrom<Everything>;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type Trader = ();
+    type ResponseHandler = PolkadotXcm;
+    type AssetTrap = PolkadotXcm;
+    type AssetLocker = ();
+    type AssetExchanger = ();
+    type AssetClaims = PolkadotXcm;
+    type SubscriptionService = PolkadotXcm;
+    type PalletInstancesInfo = AllPalletsWithSystem;
+    type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+    type FeeManager = ();
+    type MessageExporter = ();
+    type UniversalAliases = Nothing;
+    type CallDispatcher = RuntimeCall;
+    type SafeCallFilter = Everything;
+    type Aliasers = Nothing;
+    type TransactionalProcessor = FrameTransactionalProcessor;
+    type HrmpNewChannelOpenRequestHandler = ();
+    type HrmpChannelAcceptedHandler = ();
+    type HrmpChannelClosingHandler = ();
+    type XcmRecorder = PolkadotXcm;
+}
+
+impl pallet_xcm::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type SendXcmOrigin = EnsureNever<Location>;
+    type XcmRouter = TestSendXcm;
+    type ExecuteXcmOrigin = EnsureNever<Location>;
+    type XcmExecuteFilter = Nothing;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type XcmTeleportFilter = Nothing;
+    type XcmReserveTransferFilter = Nothing;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type UniversalLocation = UniversalLocation;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+    type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+    type Currency = Balances;
+    type CurrencyMatcher = ();
+    type TrustedLockers = ();
+    type SovereignAccountOf = LocationToAccountId;
+    type MaxLockers = ConstU32<8>;
+    type MaxRemoteLockConsumers = ConstU32<0>;
+    type RemoteLockConsumerIdentifier = ();
+    type WeightInfo = pallet_xcm::TestWeightInfo;
+    type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+    pub IdentityConsentLocation: Location = Location::new(1, [Parachain(2000)]);
+    pub const ConsentManagerPalletIndex: u8 = 51;
+    pub ConsentQueryWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+    pub const PendingRequestTimeout: u64 = 10;
+    pub static AutoGrant: bool = false;
+    /// Health records known to `TestRecords` (record_id, owner, category)
+    pub static MockRecords: Vec<(H256, AccountId, u8)> = vec![];
+}
+
+/// Record lookup backed by the `MockRecords` list
+pub struct TestRecords;
+impl pallet_access_control::RecordInspect<AccountId, u8> for TestRecords {
+    fn record_owner(record_id: &H256) -> Option<AccountId> {
+        MockRecords::get().into_iter().find(|(id, ..)| id == record_id).map(|(_, owner, _)| owner)
+    }
+
+    fn record_category(record_id: &H256) -> Option<u8> {
+        MockRecords::get()
+            .into_iter()
+            .find(|(id, ..)| id == record_id)
+            .map(|(.., category)| category)
+    }
+
+    fn record_created_at(record_id: &H256) -> Option<u64> {
+        MockRecords::get().iter().any(|(id, ..)| id == record_id).then_some(RECORD_CREATED_AT)
+    }
+
+    fn record_version(record_id: &H256) -> Option<u32> {
+        MockRecords::get().iter().any(|(id, ..)| id == record_id).then_some(RECORD_VERSION)
+    }
+}
+
+/// Calls a guardian may make for their ward: the patient actions of this pallet
+pub struct TestWardCalls;
+impl Contains<RuntimeCall> for TestWardCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        use pallet_access_control::Call;
+        matches!(
+            call,
+            RuntimeCall::AccessControl(
+                Call::grant_access { .. }
+                    | Call::deny_access { .. }
+                    | Call::bulk_grant_access { .. }
+                    | Call::bulk_deny_access { .. }
+                    | Call::revoke_access { .. }
+                    | Call::add_delegate { .. }
+                    | Call::remove_delegate { .. }
+                    | Call::pin_grant_version { .. }
+            )
+        )
+    }
+}
+
+impl pallet_access_control::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type TimeProvider = Timestamp;
+    type XcmSender = TestSendXcm;
+    type ConsentChainLocation = IdentityConsentLocation;
+    type ConsentManagerPalletIndex = ConsentManagerPalletIndex;
+    type ConsentQueryWeight = ConsentQueryWeight;
+    type ConsentQueryTimeout = ConstU64<100>;
+    type ConsentResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+    type AutoGrantOnConsent = AutoGrant;
+    type DefaultGrantDuration = ConstU64<1_000_000>;
+    type RecordCategory = u8;
+    type Records = TestRecords;
+    type MaxPendingRequests = ConstU32<3>;
+    type PendingRequestTimeout = PendingRequestTimeout;
+    type MaxBulkRequests = ConstU32<3>;
+    type MaxExpiriesPerBlock = ConstU32<2>;
+    type RegistryOrigin = EnsureRoot<AccountId>;
+    type BreakGlassDuration = ConstU64<BREAK_GLASS_DURATION>;
+    type WardCalls = TestWardCalls;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestRecords;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_access_control::BenchmarkHelper<RuntimeOrigin, AccountId> for TestRecords {
+    fn open_consent_channel() {}
+
+    fn consent_response_origin() -> RuntimeOrigin {
+        pallet_xcm::Origin::Response(IdentityConsentLocation::get()).into()
+    }
+
+    fn create_record(owner: &AccountId) -> H256 {
+        let record_id = H256::repeat_byte(0xbe);
+        add_record(record_id, owner.clone(), 1);
+        record_id
+    }
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000);
+        SentXcm::set(vec![]);
+        MockRecords::set(vec![]);
+        AutoGrant::set(false);
+    });
+    ext
+}
+
+// Helper function to register a health record owned by `owner`
+pub fn add_record(record_id: H256, owner: AccountId, category: u8) {
+    MockRecords::mutate(|records| records.push((record_id, owner, category)));
+}
+
+// Helper function to request access to `record_id` as the researcher
+pub fn request_access(record_id: H256) -> H256 {
+    let count = AccessControl::request_count();
+    frame_support::assert_ok!(AccessControl::request_access(
+        RuntimeOrigin::signed(RESEARCHER),
+        record_id,
+        None,
+        PATIENT,
+        H256::repeat_byte(7),
+    ));
+
+    assert_eq!(AccessControl::request_count(), count + 1);
+    System::events()
+        .into_iter()
+        .rev()
+        .find_map(|record| match record.event {
+            RuntimeEvent::AccessControl(pallet_access_control::Event::AccessRequested {
+                request_id,
+                ..
+            }) => Some(request_id),
+            _ => None,
+        })
+        .unwrap()
+}
+
+// Helper function to answer the consent query of a request
+pub fn answer_consent(request_id: H256, valid: bool) {
+    let query_id = pallet_access_control::ConsentQueries::<Test>::iter()
+        .find(|(_, id)| *id == request_id)
+        .map(|(query_id, _)| query_id)
+        .unwrap();
+    let response = if valid {
+        Response::DispatchResult(MaybeErrorCode::Success)
+    } else {
+        Response::DispatchResult(MaybeErrorCode::Error(Default::default()))
+    };
+
+    frame_support::assert_ok!(AccessControl::consent_response(
+        pallet_xcm::Origin::Response(IdentityConsentLocation::get()).into(),
+        query_id,
+        response,
+    ));
+}
+
+// Helper function to create a verified request awaiting the patient's decision
+pub fn verified_request(record_id: H256) -> H256 {
+    let request_id = request_access(record_id);
+    answer_consent(request_id, true);
+    request_id
+}
+
+// Helper function to grant `RESEARCHER` access to a record owned by `PATIENT`
+pub fn granted_record(record_id: H256, category: u8) {
+    add_record(record_id, PATIENT, category);
+    let request_id = verified_request(record_id);
+    frame_support::assert_ok!(AccessControl::grant_access(
+        RuntimeOrigin::signed(PATIENT),
+        request_id,
+        u64::MAX,
+    ));
+}
+
+// Helper function to delegate approval rights from `PATIENT` to `GUARDIAN`
+pub fn add_guardian(categories: Vec<u8>, expires_at: Option<u64>) {
+    frame_support::assert_ok!(AccessControl::add_delegate(
+        RuntimeOrigin::signed(PATIENT),
+        GUARDIAN,
+        sp_runtime::BoundedVec::try_from(categories).unwrap(),
+        expires_at,
+    ));
+}
+
+// Helper function to register `CLINICIAN` and `AUDITOR`
+pub fn register_responders() {
+    frame_support::assert_ok!(AccessControl::set_clinician(
+        RuntimeOrigin::root(),
+        CLINICIAN,
+        Some(u64::MAX),
+    ));
+    frame_support::assert_ok!(AccessControl::set_auditor(RuntimeOrigin::root(), AUDITOR, true));
+}
+
+// Helper function to break the glass on `record_id` as `CLINICIAN`
+pub fn break_glass(record_id: H256) -> H256 {
+    frame_support::assert_ok!(AccessControl::break_glass(
+        RuntimeOrigin::signed(CLINICIAN),
+        record_id,
+        H256::repeat_byte(9),
+    ));
+
+    pallet_access_control::PendingReviews::<Test>::iter_keys()
+        .find(|id| AccessControl::break_glass_accesses(id).unwrap().record_id == record_id)
+        .unwrap()
+}
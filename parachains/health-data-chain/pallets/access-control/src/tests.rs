@@ -0,0 +1,930 @@
+//! Tests for access-control pallet
+
+use crate::{
+    migrations::v1::{MigrateToV1, OldAccessRequest},
+    mock::*,
+    AccessRequests, AccessStatus, BreakGlassReview, ConsentManagerCall, ConsentRecord, Error,
+    Event,
+};
+use codec::Decode;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::unhashed,
+    traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+    BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::DispatchError;
+use xcm::latest::Instruction;
+
+#[test]
+fn request_access_sends_consent_query() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+
+        let request_id = request_access(record_id);
+
+        let request = AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.requester, RESEARCHER);
+        assert_eq!(request.patient, PATIENT);
+        assert_eq!(request.status, AccessStatus::Pending);
+        assert!(!request.consent_verified);
+
+        // One verify_consent message went to the IdentityConsent chain
+        let sent = SentXcm::get();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, IdentityConsentLocation::get());
+    });
+}
+
+#[test]
+fn consent_query_describes_requested_record() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 4);
+
+        request_access(record_id);
+
+        let (_, message) = SentXcm::get().pop().unwrap();
+        let encoded = message
+            .0
+            .into_iter()
+            .find_map(|instruction| match instruction {
+                Instruction::Transact { call, .. } => Some(call.into_encoded()),
+                _ => None,
+            })
+            .unwrap();
+        let (pallet_index, call) =
+            <(u8, ConsentManagerCall<AccountId, u8>)>::decode(&mut &encoded[..]).unwrap();
+
+        // The category is checked against the consent scope on the IdentityConsent chain
+        assert_eq!(pallet_index, ConsentManagerPalletIndex::get());
+        assert_eq!(
+            call,
+            ConsentManagerCall::VerifyConsent {
+                consent_id: H256::repeat_byte(7),
+                data_owner: PATIENT,
+                accessor: RESEARCHER,
+                record: Some(ConsentRecord {
+                    record_id,
+                    category: 4,
+                    created_at: RECORD_CREATED_AT,
+                    de_identified: false,
+                    version: None,
+                }),
+            }
+        );
+    });
+}
+
+#[test]
+fn grant_access_after_consent_works() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let request_id = verified_request(record_id);
+
+        assert_ok!(AccessControl::grant_access(
+            RuntimeOrigin::signed(PATIENT),
+            request_id,
+            5_000,
+        ));
+
+        assert_eq!(AccessControl::access_grants(record_id, RESEARCHER), Some(5_000));
+        assert_eq!(
+            AccessControl::access_requests(request_id).unwrap().status,
+            AccessStatus::Granted
+        );
+        assert!(AccessControl::pending_requests(PATIENT).is_empty());
+        assert!(AccessControl::has_access(&record_id, &RESEARCHER, 1_000));
+        assert!(!AccessControl::has_access(&record_id, &RESEARCHER, 5_000));
+    });
+}
+
+#[test]
+fn grant_access_fails_without_verified_consent() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let request_id = request_access(record_id);
+
+        assert_noop!(
+            AccessControl::grant_access(RuntimeOrigin::signed(PATIENT), request_id, 5_000),
+            Error::<Test>::ConsentNotVerified
+        );
+    });
+}
+
+#[test]
+fn only_verified_requests_are_queued_for_the_patient() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+
+        // Requests without a verified consent leave the patient's queue alone
+        let requests: Vec<H256> = (0..5).map(|_| request_access(record_id)).collect();
+        assert!(AccessControl::pending_requests(PATIENT).is_empty());
+
+        answer_consent(requests[0], false);
+        assert!(AccessControl::pending_requests(PATIENT).is_empty());
+
+        for request_id in &requests[1..4] {
+            answer_consent(*request_id, true);
+        }
+        assert_eq!(AccessControl::pending_requests(PATIENT).into_inner(), requests[1..4].to_vec());
+
+        // The mock queues at most three requests, so the next verified one is denied
+        answer_consent(requests[4], true);
+        assert_eq!(
+            AccessControl::access_requests(requests[4]).unwrap().status,
+            AccessStatus::Denied
+        );
+        assert_eq!(AccessControl::pending_requests(PATIENT).len(), 3);
+    });
+}
+
+#[test]
+fn request_expiries_are_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let requests: Vec<H256> = (0..3).map(|_| verified_request(record_id)).collect();
+        let expires_in = 1 + PendingRequestTimeout::get();
+        let expired = || {
+            requests
+                .iter()
+                .filter(|id| {
+                    AccessControl::access_requests(*id).unwrap().status == AccessStatus::Expired
+                })
+                .count()
+        };
+
+        AccessControl::on_initialize(expires_in - 1);
+        assert_eq!(expired(), 0);
+
+        // The mock expires at most two requests per block
+        AccessControl::on_initialize(expires_in);
+        assert_eq!(expired(), 2);
+        assert_eq!(AccessControl::pending_requests(PATIENT).len(), 1);
+
+        AccessControl::on_initialize(expires_in + 1);
+        assert_eq!(expired(), 3);
+        assert!(AccessControl::pending_requests(PATIENT).is_empty());
+        assert_eq!(AccessControl::next_expiry_block(), Some(expires_in + 1));
+    });
+}
+
+#[test]
+fn request_on_another_patients_record_is_rejected() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, STRANGER, 1);
+
+        // A consent from `PATIENT` cannot open a record `PATIENT` does not own
+        assert_noop!(
+            AccessControl::request_access(
+                RuntimeOrigin::signed(RESEARCHER),
+                record_id,
+                None,
+                PATIENT,
+                H256::repeat_byte(7)
+            ),
+            Error::<Test>::NotRecordOwner
+        );
+        assert_noop!(
+            AccessControl::request_access(
+                RuntimeOrigin::signed(RESEARCHER),
+                H256::repeat_byte(2),
+                None,
+                PATIENT,
+                H256::repeat_byte(7)
+            ),
+            Error::<Test>::RecordNotFound
+        );
+    });
+}
+
+#[test]
+fn grant_requires_patient_to_still_own_the_record() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let request_id = verified_request(record_id);
+
+        // The record changes hands before the patient decides
+        MockRecords::mutate(|records| records[0].1 = STRANGER);
+        assert_noop!(
+            AccessControl::grant_access(RuntimeOrigin::signed(PATIENT), request_id, 5_000),
+            Error::<Test>::NotRecordOwner
+        );
+
+        // Nor is a consent auto-granted on it
+        AutoGrant::set(true);
+        MockRecords::mutate(|records| records[0].1 = PATIENT);
+        let request_id = request_access(record_id);
+        MockRecords::mutate(|records| records[0].1 = STRANGER);
+        answer_consent(request_id, true);
+        assert_eq!(
+            AccessControl::access_requests(request_id).unwrap().status,
+            AccessStatus::Denied
+        );
+        assert!(AccessControl::access_grants(record_id, RESEARCHER).is_none());
+    });
+}
+
+#[test]
+fn delegate_cannot_grant_on_record_the_patient_no_longer_owns() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        add_guardian(vec![], None);
+        let request_id = verified_request(record_id);
+
+        MockRecords::mutate(|records| records[0].1 = STRANGER);
+        assert_noop!(
+            AccessControl::grant_access(RuntimeOrigin::signed(GUARDIAN), request_id, 5_000),
+            Error::<Test>::NotRecordOwner
+        );
+        assert_noop!(
+            AccessControl::bulk_grant_access(
+                RuntimeOrigin::signed(GUARDIAN),
+                BoundedVec::try_from(vec![request_id]).unwrap(),
+                5_000,
+            ),
+            Error::<Test>::NotRecordOwner
+        );
+    });
+}
+
+#[test]
+fn invalid_consent_denies_request() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let request_id = request_access(record_id);
+
+        answer_consent(request_id, false);
+
+        assert_eq!(
+            AccessControl::access_requests(request_id).unwrap().status,
+            AccessStatus::Denied
+        );
+        assert!(AccessControl::pending_requests(PATIENT).is_empty());
+    });
+}
+
+#[test]
+fn revoke_access_by_patient_works() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+
+        assert_ok!(AccessControl::revoke_access(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            RESEARCHER,
+        ));
+
+        assert!(AccessControl::access_grants(record_id, RESEARCHER).is_none());
+        System::assert_last_event(
+            Event::AccessRevoked { record_id, requester: RESEARCHER }.into(),
+        );
+    });
+}
+
+#[test]
+fn revoke_access_by_grantee_works() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+
+        // The grantee can give up its own access
+        assert_ok!(AccessControl::revoke_access(
+            RuntimeOrigin::signed(RESEARCHER),
+            record_id,
+            RESEARCHER,
+        ));
+
+        assert!(AccessControl::access_grants(record_id, RESEARCHER).is_none());
+    });
+}
+
+#[test]
+fn revoke_access_by_delegate_works() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+        add_guardian(vec![1], None);
+
+        assert_ok!(AccessControl::revoke_access(
+            RuntimeOrigin::signed(GUARDIAN),
+            record_id,
+            RESEARCHER,
+        ));
+
+        assert!(AccessControl::access_grants(record_id, RESEARCHER).is_none());
+    });
+}
+
+#[test]
+fn revoke_access_fails_for_out_of_scope_delegate() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+
+        // Delegation only covers another category
+        add_guardian(vec![2], None);
+
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::signed(GUARDIAN), record_id, RESEARCHER),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn revoke_access_fails_for_expired_delegate() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+        add_guardian(vec![], Some(2_000));
+
+        Timestamp::set_timestamp(2_000);
+
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::signed(GUARDIAN), record_id, RESEARCHER),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn revoke_access_fails_for_stranger() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::signed(STRANGER), record_id, RESEARCHER),
+            Error::<Test>::NotAuthorized
+        );
+
+        // Another grantee cannot revoke someone else's grant either
+        insert_grant(record_id, STRANGER);
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::signed(STRANGER), record_id, RESEARCHER),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn revoke_access_fails_for_unknown_record_owner() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+
+        // The record is no longer known to the records pallet
+        MockRecords::set(vec![]);
+
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::signed(PATIENT), record_id, RESEARCHER),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn revoke_access_fails_for_unsigned_or_root_origin() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::none(), record_id, RESEARCHER),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::root(), record_id, RESEARCHER),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn revoke_access_fails_without_grant() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+
+        assert_noop!(
+            AccessControl::revoke_access(RuntimeOrigin::signed(PATIENT), record_id, RESEARCHER),
+            Error::<Test>::GrantNotFound
+        );
+    });
+}
+
+#[test]
+fn break_glass_grants_access_at_once_and_queues_review() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        register_responders();
+
+        let access_id = break_glass(record_id);
+
+        // Access is granted without a consent query or the patient's approval
+        let expires_at = 1_000 + BREAK_GLASS_DURATION;
+        assert_eq!(AccessControl::emergency_grants(record_id, CLINICIAN), Some(expires_at));
+        assert!(AccessControl::access_grants(record_id, CLINICIAN).is_none());
+        assert!(AccessControl::has_access(&record_id, &CLINICIAN, 1_000));
+        assert!(SentXcm::get().is_empty());
+
+        let access = AccessControl::break_glass_accesses(access_id).unwrap();
+        assert_eq!(access.patient, PATIENT);
+        assert_eq!(access.justification_hash, H256::repeat_byte(9));
+        assert_eq!(access.review, BreakGlassReview::Pending);
+        assert!(AccessControl::pending_reviews(access_id).is_some());
+        System::assert_last_event(
+            Event::BreakGlassAccessed {
+                access_id,
+                record_id,
+                clinician: CLINICIAN,
+                patient: PATIENT,
+                justification_hash: H256::repeat_byte(9),
+                expires_at,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn break_glass_requires_valid_credential_and_justification() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let justification = H256::repeat_byte(9);
+
+        assert_noop!(
+            AccessControl::break_glass(RuntimeOrigin::signed(CLINICIAN), record_id, justification),
+            Error::<Test>::NotClinician
+        );
+
+        // Only the registry origin mirrors credentials
+        assert_noop!(
+            AccessControl::set_clinician(RuntimeOrigin::signed(CLINICIAN), CLINICIAN, Some(2_000)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(AccessControl::set_clinician(RuntimeOrigin::root(), CLINICIAN, Some(2_000)));
+
+        assert_noop!(
+            AccessControl::break_glass(RuntimeOrigin::signed(CLINICIAN), record_id, H256::zero()),
+            Error::<Test>::MissingJustification
+        );
+        assert_noop!(
+            AccessControl::break_glass(
+                RuntimeOrigin::signed(CLINICIAN),
+                H256::repeat_byte(2),
+                justification
+            ),
+            Error::<Test>::RecordNotFound
+        );
+
+        // The credential lapses
+        Timestamp::set_timestamp(2_000);
+        assert_noop!(
+            AccessControl::break_glass(RuntimeOrigin::signed(CLINICIAN), record_id, justification),
+            Error::<Test>::NotClinician
+        );
+    });
+}
+
+#[test]
+fn break_glass_keeps_patient_grant_through_misuse() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        register_responders();
+        insert_grant(record_id, CLINICIAN);
+        assert_ok!(AccessControl::pin_grant_version(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            CLINICIAN,
+            Some(1),
+        ));
+
+        let access_id = break_glass(record_id);
+
+        // The emergency grant covers every version
+        assert_eq!(AccessControl::access_grants(record_id, CLINICIAN), Some(u64::MAX));
+        assert!(AccessControl::has_version_access(&record_id, &CLINICIAN, 2, 1_000));
+
+        assert_ok!(AccessControl::review_break_glass(
+            RuntimeOrigin::signed(AUDITOR),
+            access_id,
+            true,
+        ));
+
+        // Only the emergency grant is revoked
+        assert!(AccessControl::emergency_grants(record_id, CLINICIAN).is_none());
+        assert_eq!(AccessControl::access_grants(record_id, CLINICIAN), Some(u64::MAX));
+        assert_eq!(AccessControl::grant_versions(record_id, CLINICIAN), Some(1));
+        assert!(AccessControl::has_version_access(&record_id, &CLINICIAN, 1, 1_000));
+        assert!(!AccessControl::has_version_access(&record_id, &CLINICIAN, 2, 1_000));
+    });
+}
+
+#[test]
+fn auditor_approves_break_glass() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        register_responders();
+        let access_id = break_glass(record_id);
+
+        assert_noop!(
+            AccessControl::review_break_glass(RuntimeOrigin::signed(STRANGER), access_id, false),
+            Error::<Test>::NotAuditor
+        );
+
+        assert_ok!(AccessControl::review_break_glass(
+            RuntimeOrigin::signed(AUDITOR),
+            access_id,
+            false,
+        ));
+
+        let access = AccessControl::break_glass_accesses(access_id).unwrap();
+        assert_eq!(access.review, BreakGlassReview::Approved);
+        assert_eq!(access.reviewer, Some(AUDITOR));
+        assert!(AccessControl::pending_reviews(access_id).is_none());
+        assert!(AccessControl::has_access(&record_id, &CLINICIAN, 1_000));
+
+        assert_noop!(
+            AccessControl::review_break_glass(RuntimeOrigin::signed(AUDITOR), access_id, true),
+            Error::<Test>::AlreadyReviewed
+        );
+    });
+}
+
+#[test]
+fn flagging_break_glass_revokes_access() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        register_responders();
+        let access_id = break_glass(record_id);
+
+        // A clinician who is also an auditor cannot review their own access
+        assert_ok!(AccessControl::set_auditor(RuntimeOrigin::root(), CLINICIAN, true));
+        assert_noop!(
+            AccessControl::review_break_glass(RuntimeOrigin::signed(CLINICIAN), access_id, false),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(AccessControl::review_break_glass(
+            RuntimeOrigin::signed(AUDITOR),
+            access_id,
+            true,
+        ));
+
+        assert_eq!(
+            AccessControl::break_glass_accesses(access_id).unwrap().review,
+            BreakGlassReview::Flagged
+        );
+        assert!(!AccessControl::has_access(&record_id, &CLINICIAN, 1_000));
+        System::assert_has_event(
+            Event::AccessRevoked { record_id, requester: CLINICIAN }.into(),
+        );
+        System::assert_last_event(
+            Event::BreakGlassReviewed { access_id, auditor: AUDITOR, misuse: true }.into(),
+        );
+    });
+}
+
+// Helper function to insert a grant directly
+fn insert_grant(record_id: H256, grantee: AccountId) {
+    crate::AccessGrants::<Test>::insert(record_id, grantee, u64::MAX);
+}
+
+#[test]
+fn guardian_acts_for_ward() {
+    new_test_ext().execute_with(|| {
+        let add_delegate = || {
+            Box::new(RuntimeCall::AccessControl(crate::Call::add_delegate {
+                delegate: STRANGER,
+                categories: Default::default(),
+                expires_at: None,
+            }))
+        };
+
+        assert_noop!(
+            AccessControl::act_for_ward(RuntimeOrigin::signed(GUARDIAN), PATIENT, add_delegate()),
+            Error::<Test>::NotLegalGuardian
+        );
+
+        // Mirrored from the IdentityConsent chain until the ward's handover date
+        assert_noop!(
+            AccessControl::set_guardianship(
+                RuntimeOrigin::signed(GUARDIAN),
+                PATIENT,
+                GUARDIAN,
+                Some(2_000)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(AccessControl::set_guardianship(
+            RuntimeOrigin::root(),
+            PATIENT,
+            GUARDIAN,
+            Some(2_000),
+        ));
+
+        assert_ok!(AccessControl::act_for_ward(
+            RuntimeOrigin::signed(GUARDIAN),
+            PATIENT,
+            add_delegate(),
+        ));
+        assert!(AccessControl::delegates(PATIENT, STRANGER).is_some());
+        System::assert_last_event(Event::ActedForWard { ward: PATIENT, guardian: GUARDIAN }.into());
+
+        // Only calls admitted by `WardCalls`
+        let remark = Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] }));
+        assert_noop!(
+            AccessControl::act_for_ward(RuntimeOrigin::signed(GUARDIAN), PATIENT, remark),
+            Error::<Test>::CallNotAllowed
+        );
+
+        // The ward takes over
+        Timestamp::set_timestamp(2_000);
+        assert_noop!(
+            AccessControl::act_for_ward(RuntimeOrigin::signed(GUARDIAN), PATIENT, add_delegate()),
+            Error::<Test>::NotLegalGuardian
+        );
+    });
+}
+
+#[test]
+fn guardian_cannot_use_ward_responder_rights() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        register_responders();
+        assert_ok!(AccessControl::set_guardianship(
+            RuntimeOrigin::root(),
+            CLINICIAN,
+            GUARDIAN,
+            None,
+        ));
+
+        let break_glass = Box::new(RuntimeCall::AccessControl(crate::Call::break_glass {
+            record_id,
+            justification_hash: H256::repeat_byte(9),
+        }));
+        assert_noop!(
+            AccessControl::act_for_ward(RuntimeOrigin::signed(GUARDIAN), CLINICIAN, break_glass),
+            Error::<Test>::CallNotAllowed
+        );
+
+        // Nor can a guardian hide a call in a nested `act_for_ward`
+        let nested = Box::new(RuntimeCall::AccessControl(crate::Call::act_for_ward {
+            ward: CLINICIAN,
+            call: Box::new(RuntimeCall::AccessControl(crate::Call::remove_delegate {
+                delegate: STRANGER,
+            })),
+        }));
+        assert_noop!(
+            AccessControl::act_for_ward(RuntimeOrigin::signed(GUARDIAN), CLINICIAN, nested),
+            Error::<Test>::CallNotAllowed
+        );
+    });
+}
+
+#[test]
+fn request_for_a_version_leads_to_a_pinned_grant() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+
+        assert_noop!(
+            AccessControl::request_access(
+                RuntimeOrigin::signed(RESEARCHER),
+                record_id,
+                Some(RECORD_VERSION + 1),
+                PATIENT,
+                H256::repeat_byte(7)
+            ),
+            Error::<Test>::VersionNotFound
+        );
+
+        assert_ok!(AccessControl::request_access(
+            RuntimeOrigin::signed(RESEARCHER),
+            record_id,
+            Some(1),
+            PATIENT,
+            H256::repeat_byte(7)
+        ));
+        let (_, request_id) = crate::ConsentQueries::<Test>::iter().next().unwrap();
+        assert_eq!(AccessControl::access_requests(request_id).unwrap().version, Some(1));
+
+        answer_consent(request_id, true);
+        assert_ok!(AccessControl::grant_access(RuntimeOrigin::signed(PATIENT), request_id, 5_000));
+
+        assert_eq!(AccessControl::grant_versions(record_id, RESEARCHER), Some(1));
+        assert!(AccessControl::has_version_access(&record_id, &RESEARCHER, 1, 1_000));
+        assert!(!AccessControl::has_version_access(&record_id, &RESEARCHER, 2, 1_000));
+    });
+}
+
+#[test]
+fn pinned_grant_covers_only_its_version() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+        assert!(AccessControl::has_version_access(&record_id, &RESEARCHER, 1, 1_000));
+        assert!(AccessControl::has_version_access(&record_id, &RESEARCHER, 2, 1_000));
+
+        // Only the patient or a delegate pins, to an existing version
+        assert_noop!(
+            AccessControl::pin_grant_version(
+                RuntimeOrigin::signed(RESEARCHER),
+                record_id,
+                RESEARCHER,
+                Some(1)
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            AccessControl::pin_grant_version(
+                RuntimeOrigin::signed(PATIENT),
+                record_id,
+                RESEARCHER,
+                Some(RECORD_VERSION + 1)
+            ),
+            Error::<Test>::VersionNotFound
+        );
+        assert_noop!(
+            AccessControl::pin_grant_version(
+                RuntimeOrigin::signed(PATIENT),
+                record_id,
+                STRANGER,
+                Some(1)
+            ),
+            Error::<Test>::GrantNotFound
+        );
+
+        assert_ok!(AccessControl::pin_grant_version(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            RESEARCHER,
+            Some(1),
+        ));
+        System::assert_last_event(
+            Event::GrantVersionPinned { record_id, requester: RESEARCHER, version: Some(1) }.into(),
+        );
+        assert!(AccessControl::has_version_access(&record_id, &RESEARCHER, 1, 1_000));
+        assert!(!AccessControl::has_version_access(&record_id, &RESEARCHER, 2, 1_000));
+
+        // Following the latest version again
+        assert_ok!(AccessControl::pin_grant_version(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            RESEARCHER,
+            None,
+        ));
+        assert!(AccessControl::has_version_access(&record_id, &RESEARCHER, 2, 1_000));
+
+        // The pin goes with the grant
+        assert_ok!(AccessControl::pin_grant_version(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            RESEARCHER,
+            Some(1),
+        ));
+        assert_ok!(AccessControl::revoke_access(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            RESEARCHER,
+        ));
+        assert!(AccessControl::grant_versions(record_id, RESEARCHER).is_none());
+    });
+}
+
+#[test]
+fn migrate_account_moves_pending_requests() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let request_id = verified_request(record_id);
+
+        assert_noop!(
+            AccessControl::migrate_account(RuntimeOrigin::signed(PATIENT), PATIENT, STRANGER),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AccessControl::migrate_account(RuntimeOrigin::root(), PATIENT, PATIENT),
+            Error::<Test>::InvalidMigration
+        );
+
+        assert_ok!(AccessControl::migrate_account(RuntimeOrigin::root(), PATIENT, STRANGER));
+        assert!(AccessControl::pending_requests(PATIENT).is_empty());
+        assert_eq!(AccessControl::pending_requests(STRANGER).into_inner(), vec![request_id]);
+        assert_eq!(AccessControl::access_requests(request_id).unwrap().patient, STRANGER);
+        System::assert_last_event(
+            Event::AccountMigrated { old: PATIENT, new: STRANGER, requests: 1 }.into(),
+        );
+
+        // Once the record is re-keyed too, the new account decides the request
+        MockRecords::set(vec![(record_id, STRANGER, 1)]);
+        assert_noop!(
+            AccessControl::grant_access(RuntimeOrigin::signed(PATIENT), request_id, u64::MAX),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(AccessControl::grant_access(
+            RuntimeOrigin::signed(STRANGER),
+            request_id,
+            u64::MAX,
+        ));
+        assert!(AccessControl::access_grants(record_id, RESEARCHER).is_some());
+    });
+}
+
+#[test]
+fn recovered_account_claims_its_grants() {
+    new_test_ext().execute_with(|| {
+        let record_id = H256::repeat_byte(1);
+        granted_record(record_id, 1);
+        assert_ok!(AccessControl::pin_grant_version(
+            RuntimeOrigin::signed(PATIENT),
+            record_id,
+            RESEARCHER,
+            Some(1),
+        ));
+
+        assert_noop!(
+            AccessControl::migrate_grant(RuntimeOrigin::signed(STRANGER), RESEARCHER, record_id),
+            Error::<Test>::NotMigrated
+        );
+        assert_ok!(AccessControl::migrate_account(RuntimeOrigin::root(), RESEARCHER, STRANGER));
+
+        // Only the account the identity was recovered to
+        assert_noop!(
+            AccessControl::migrate_grant(RuntimeOrigin::signed(PATIENT), RESEARCHER, record_id),
+            Error::<Test>::NotMigrated
+        );
+        assert_ok!(AccessControl::migrate_grant(
+            RuntimeOrigin::signed(STRANGER),
+            RESEARCHER,
+            record_id,
+        ));
+
+        assert!(AccessControl::access_grants(record_id, RESEARCHER).is_none());
+        assert_eq!(AccessControl::access_grants(record_id, STRANGER), Some(u64::MAX));
+        assert!(AccessControl::grant_versions(record_id, RESEARCHER).is_none());
+        assert_eq!(AccessControl::grant_versions(record_id, STRANGER), Some(1));
+        System::assert_last_event(
+            Event::GrantMigrated { record_id, old: RESEARCHER, new: STRANGER }.into(),
+        );
+
+        assert_noop!(
+            AccessControl::migrate_grant(RuntimeOrigin::signed(STRANGER), RESEARCHER, record_id),
+            Error::<Test>::GrantNotFound
+        );
+    });
+}
+
+#[test]
+fn migration_to_v1_leaves_existing_requests_unverified() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<AccessControl>();
+        let record_id = H256::repeat_byte(1);
+        add_record(record_id, PATIENT, 1);
+        let request_id = H256::repeat_byte(9);
+        let old = OldAccessRequest::<Test> {
+            request_id,
+            record_id,
+            requester: RESEARCHER,
+            patient: PATIENT,
+            consent_id: Some(H256::repeat_byte(7)),
+            status: AccessStatus::Pending,
+            requested_at: 500,
+            responded_at: None,
+        };
+        unhashed::put(&AccessRequests::<Test>::hashed_key_for(request_id), &old);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let request = AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.requester, RESEARCHER);
+        assert_eq!(request.status, AccessStatus::Pending);
+        assert!(!request.consent_verified);
+        assert_eq!(AccessControl::on_chain_storage_version(), 1);
+
+        // No consent query was answered for the request, so it cannot be granted
+        assert_noop!(
+            AccessControl::grant_access(RuntimeOrigin::signed(PATIENT), request_id, u64::MAX),
+            Error::<Test>::ConsentNotVerified
+        );
+    });
+}
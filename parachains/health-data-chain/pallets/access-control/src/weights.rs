@@ -0,0 +1,352 @@
+//! Estimated weights for `pallet_access_control`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/health-data-node benchmark pallet \
+//!     --chain dev --pallet pallet_access_control --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/access-control/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_access_control`.
+pub trait WeightInfo {
+    fn request_access() -> Weight;
+    fn grant_access() -> Weight;
+    fn deny_access() -> Weight;
+    fn revoke_access() -> Weight;
+    fn consent_response() -> Weight;
+    fn add_delegate() -> Weight;
+    fn remove_delegate() -> Weight;
+    fn bulk_grant_access(n: u32) -> Weight;
+    fn bulk_deny_access(n: u32) -> Weight;
+    fn expire_request() -> Weight;
+    fn set_clinician() -> Weight;
+    fn set_auditor() -> Weight;
+    fn break_glass() -> Weight;
+    fn review_break_glass() -> Weight;
+    fn set_guardianship() -> Weight;
+    fn act_for_ward() -> Weight;
+    fn pin_grant_version() -> Weight;
+    fn migrate_account() -> Weight;
+    fn migrate_grant() -> Weight;
+}
+
+/// Estimated weights for `pallet_access_control`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Timestamp::Now`, `HealthRecords::HealthRecords`, `HealthRecords::LatestVersions`,
+    //     `AccessControl::RequestCount`, `AccessControl::NextExpiryBlock`,
+    //     `PolkadotXcm::QueryCounter`, `ParachainSystem::HostConfiguration`,
+    //     `ParachainSystem::RelevantMessagingState`, `XcmpQueue::DeliveryFeeFactor`,
+    //     `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `AccessControl::RequestCount`, `AccessControl::NextExpiryBlock`,
+    //     `PolkadotXcm::QueryCounter`, `XcmpQueue::OutboundXcmpStatus`,
+    //     `XcmpQueue::OutboundXcmpMessages`, `PolkadotXcm::Queries`,
+    //     `AccessControl::RequestExpiries`, `AccessControl::AccessRequests`,
+    //     `AccessControl::ConsentQueries`
+    fn request_access() -> Weight {
+        Weight::from_parts(70_000_000, 34_909)
+            .saturating_add(T::DbWeight::get().reads(10_u64))
+            .saturating_add(T::DbWeight::get().writes(9_u64))
+    }
+
+    // Reads: `AccessControl::AccessRequests`, `Timestamp::Now`, `AccessControl::Delegates`,
+    //     `HealthRecords::HealthRecords`, `AccessControl::PendingRequests`
+    // Writes: `AccessControl::AccessRequests`, `AccessControl::PendingRequests`,
+    //     `AccessControl::AccessGrants`, `AccessControl::GrantVersions`
+    fn grant_access() -> Weight {
+        Weight::from_parts(41_000_000, 35_230)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    // Reads: `AccessControl::AccessRequests`, `Timestamp::Now`, `AccessControl::Delegates`,
+    //     `HealthRecords::HealthRecords`, `AccessControl::PendingRequests`
+    // Writes: `AccessControl::AccessRequests`, `AccessControl::PendingRequests`
+    fn deny_access() -> Weight {
+        Weight::from_parts(35_000_000, 35_230)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `AccessControl::AccessGrants`, `HealthRecords::HealthRecords`, `Timestamp::Now`,
+    //     `AccessControl::Delegates`
+    // Writes: `AccessControl::AccessGrants`, `AccessControl::GrantVersions`
+    fn revoke_access() -> Weight {
+        Weight::from_parts(30_000_000, 4_127)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `AccessControl::ConsentQueries`, `AccessControl::AccessRequests`,
+    //     `HealthRecords::HealthRecords`, `Timestamp::Now`, `AccessControl::PendingRequests`
+    // Writes: `AccessControl::ConsentQueries`, `AccessControl::AccessRequests`,
+    //     `AccessControl::PendingRequests`, `AccessControl::AccessGrants`,
+    //     `AccessControl::GrantVersions`
+    fn consent_response() -> Weight {
+        Weight::from_parts(47_000_000, 35_230)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Timestamp::Now`
+    // Writes: `AccessControl::Delegates`
+    fn add_delegate() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `AccessControl::Delegates`
+    // Writes: `AccessControl::Delegates`
+    fn remove_delegate() -> Weight {
+        Weight::from_parts(17_000_000, 3_636)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `AccessControl::AccessRequests` per `n`, `Timestamp::Now`,
+    //     `AccessControl::Delegates` per `n`, `HealthRecords::HealthRecords` per `n`,
+    //     `AccessControl::PendingRequests`
+    // Writes: `AccessControl::AccessRequests` per `n`, `AccessControl::PendingRequests`,
+    //     `AccessControl::AccessGrants` per `n`, `AccessControl::GrantVersions` per `n`
+    // `n` ranges over `[1, 100]`
+    fn bulk_grant_access(n: u32) -> Weight {
+        Weight::from_parts(9_000_000, 990)
+            .saturating_add(Weight::from_parts(36_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(0, 34_240).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().reads(3_u64.saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64.saturating_mul(n.into())))
+    }
+
+    // Reads: `AccessControl::AccessRequests` per `n`, `Timestamp::Now`,
+    //     `AccessControl::Delegates` per `n`, `HealthRecords::HealthRecords` per `n`,
+    //     `AccessControl::PendingRequests`
+    // Writes: `AccessControl::AccessRequests` per `n`, `AccessControl::PendingRequests`
+    // `n` ranges over `[1, 100]`
+    fn bulk_deny_access(n: u32) -> Weight {
+        Weight::from_parts(9_000_000, 990)
+            .saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(0, 34_240).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().reads(3_u64.saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64.saturating_mul(n.into())))
+    }
+
+    // Reads: `AccessControl::AccessRequests`, `Timestamp::Now`, `AccessControl::PendingRequests`
+    // Writes: `AccessControl::AccessRequests`, `AccessControl::PendingRequests`
+    fn expire_request() -> Weight {
+        Weight::from_parts(29_000_000, 35_230)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Writes: `AccessControl::Clinicians`
+    fn set_clinician() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Writes: `AccessControl::Auditors`
+    fn set_auditor() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `AccessControl::Clinicians`, `HealthRecords::HealthRecords`,
+    //     `AccessControl::EmergencyGrants`, `AccessControl::RequestCount`
+    // Writes: `AccessControl::EmergencyGrants`, `AccessControl::RequestCount`,
+    //     `AccessControl::PendingReviews`, `AccessControl::BreakGlassAccesses`
+    fn break_glass() -> Weight {
+        Weight::from_parts(41_000_000, 4_127)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    // Reads: `AccessControl::Auditors`, `AccessControl::BreakGlassAccesses`, `Timestamp::Now`,
+    //     `AccessControl::EmergencyGrants`
+    // Writes: `AccessControl::BreakGlassAccesses`, `AccessControl::EmergencyGrants`,
+    //     `AccessControl::PendingReviews`
+    fn review_break_glass() -> Weight {
+        Weight::from_parts(36_000_000, 4_360)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Writes: `AccessControl::Guardians`
+    fn set_guardianship() -> Weight {
+        Weight::from_parts(16_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `AccessControl::Guardians`
+    // Writes: `AccessControl::Delegates`
+    fn act_for_ward() -> Weight {
+        Weight::from_parts(36_000_000, 3_612)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `AccessControl::AccessGrants`, `HealthRecords::HealthRecords`, `Timestamp::Now`,
+    //     `HealthRecords::LatestVersions`
+    // Writes: `AccessControl::GrantVersions`
+    fn pin_grant_version() -> Weight {
+        Weight::from_parts(24_000_000, 4_127)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `AccessControl::PendingRequests` x2, `AccessControl::AccessRequests` x100
+    // Writes: `AccessControl::PendingRequests` x2, `AccessControl::AccessRequests` x100,
+    //     `AccessControl::AccountMigrations`
+    fn migrate_account() -> Weight {
+        Weight::from_parts(1_540_000_000, 338_457)
+            .saturating_add(T::DbWeight::get().reads(102_u64))
+            .saturating_add(T::DbWeight::get().writes(103_u64))
+    }
+
+    // Reads: `AccessControl::AccountMigrations`, `AccessControl::AccessGrants`,
+    //     `AccessControl::GrantVersions`
+    // Writes: `AccessControl::AccessGrants` x2, `AccessControl::GrantVersions` x2
+    fn migrate_grant() -> Weight {
+        Weight::from_parts(27_000_000, 3_593)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn request_access() -> Weight {
+        Weight::from_parts(70_000_000, 34_909)
+            .saturating_add(RocksDbWeight::get().reads(10_u64))
+            .saturating_add(RocksDbWeight::get().writes(9_u64))
+    }
+
+    fn grant_access() -> Weight {
+        Weight::from_parts(41_000_000, 35_230)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn deny_access() -> Weight {
+        Weight::from_parts(35_000_000, 35_230)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn revoke_access() -> Weight {
+        Weight::from_parts(30_000_000, 4_127)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn consent_response() -> Weight {
+        Weight::from_parts(47_000_000, 35_230)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn add_delegate() -> Weight {
+        Weight::from_parts(19_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_delegate() -> Weight {
+        Weight::from_parts(17_000_000, 3_636)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn bulk_grant_access(n: u32) -> Weight {
+        Weight::from_parts(9_000_000, 990)
+            .saturating_add(Weight::from_parts(36_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(0, 34_240).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().reads(3_u64.saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64.saturating_mul(n.into())))
+    }
+
+    fn bulk_deny_access(n: u32) -> Weight {
+        Weight::from_parts(9_000_000, 990)
+            .saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(n.into()))
+            .saturating_add(Weight::from_parts(0, 34_240).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().reads(3_u64.saturating_mul(n.into())))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64.saturating_mul(n.into())))
+    }
+
+    fn expire_request() -> Weight {
+        Weight::from_parts(29_000_000, 35_230)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn set_clinician() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_auditor() -> Weight {
+        Weight::from_parts(14_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn break_glass() -> Weight {
+        Weight::from_parts(41_000_000, 4_127)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn review_break_glass() -> Weight {
+        Weight::from_parts(36_000_000, 4_360)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn set_guardianship() -> Weight {
+        Weight::from_parts(16_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn act_for_ward() -> Weight {
+        Weight::from_parts(36_000_000, 3_612)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn pin_grant_version() -> Weight {
+        Weight::from_parts(24_000_000, 4_127)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn migrate_account() -> Weight {
+        Weight::from_parts(1_540_000_000, 338_457)
+            .saturating_add(RocksDbWeight::get().reads(102_u64))
+            .saturating_add(RocksDbWeight::get().writes(103_u64))
+    }
+
+    fn migrate_grant() -> Weight {
+        Weight::from_parts(27_000_000, 3_593)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+}
@@ -0,0 +1,159 @@
+//! Benchmarking setup for pallet-encryption
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Fill the account's key list so that `free` slots remain
+fn fill_account_keys<T: Config>(owner: &T::AccountId, free: u32) {
+    let count = T::MaxKeysPerAccount::get().saturating_sub(free);
+    let existing: Vec<H256> = (0..count as u64).map(H256::from_low_u64_be).collect();
+    AccountKeys::<T>::insert(owner, BoundedVec::truncate_from(existing));
+}
+
+/// Generate a record key for the whitelisted owner and return its ID
+fn generate<T: Config>(record_id: H256) -> (T::AccountId, H256) {
+    let owner: T::AccountId = whitelisted_caller();
+
+    Pallet::<T>::generate_key(
+        RawOrigin::Signed(owner.clone()).into(),
+        EncryptionAlgorithm::ChaCha20Poly1305,
+        KeyPurpose::RecordEncryption,
+        Some(record_id),
+        None,
+    )
+    .expect("key generation must succeed");
+
+    let key_id = RecordKeys::<T>::get(record_id).expect("key was just generated");
+    (owner, key_id)
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn generate_key() {
+        let owner: T::AccountId = whitelisted_caller();
+        fill_account_keys::<T>(&owner, 1);
+        let record_id = H256::repeat_byte(1);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(owner),
+            EncryptionAlgorithm::ChaCha20Poly1305,
+            KeyPurpose::RecordEncryption,
+            Some(record_id),
+            Some(u64::MAX),
+        );
+
+        assert!(RecordKeys::<T>::contains_key(record_id));
+    }
+
+    #[benchmark]
+    fn rotate_key() {
+        let owner: T::AccountId = whitelisted_caller();
+        fill_account_keys::<T>(&owner, 2);
+        let record_id = H256::repeat_byte(1);
+        let (owner, old_key_id) = generate::<T>(record_id);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), record_id, EncryptionAlgorithm::AES256GCM, Some(u64::MAX));
+
+        assert_ne!(RecordKeys::<T>::get(record_id), Some(old_key_id));
+    }
+
+    #[benchmark]
+    fn revoke_key() {
+        let (owner, key_id) = generate::<T>(H256::repeat_byte(1));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), key_id);
+
+        assert!(!EncryptionKeys::<T>::get(key_id).unwrap().active);
+    }
+
+    #[benchmark]
+    fn grant_key_access() {
+        let (owner, key_id) = generate::<T>(H256::repeat_byte(1));
+        let grantee: T::AccountId = account("grantee", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), key_id, grantee.clone(), Some(u64::MAX));
+
+        assert!(KeyAccessGrants::<T>::contains_key(key_id, &grantee));
+    }
+
+    #[benchmark]
+    fn revoke_key_access() {
+        let (owner, key_id) = generate::<T>(H256::repeat_byte(1));
+        let grantee: T::AccountId = account("grantee", 0, 0);
+        Pallet::<T>::grant_key_access(
+            RawOrigin::Signed(owner.clone()).into(),
+            key_id,
+            grantee.clone(),
+            None,
+        )
+        .expect("granting key access must succeed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), key_id, grantee.clone());
+
+        assert!(!KeyAccessGrants::<T>::contains_key(key_id, &grantee));
+    }
+
+    #[benchmark]
+    fn migrate_keys() -> Result<(), BenchmarkError> {
+        let origin =
+            T::MigrationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let (_, key_id) = generate::<T>(H256::repeat_byte(1));
+        let key = EncryptionKeys::<T>::get(key_id).expect("key was just generated");
+        let old: T::AccountId = account("old", 0, 0);
+        let new: T::AccountId = account("new", 0, 0);
+
+        // A full key list
+        let keys: Vec<H256> =
+            (0..T::MaxKeysPerAccount::get() as u64).map(H256::from_low_u64_be).collect();
+        for id in keys.iter() {
+            EncryptionKeys::<T>::insert(
+                id,
+                EncryptionKey { key_id: *id, owner: old.clone(), ..key.clone() },
+            );
+        }
+        AccountKeys::<T>::insert(&old, BoundedVec::truncate_from(keys));
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, old.clone(), new.clone());
+
+        assert_eq!(AccountKeys::<T>::get(&new).len() as u32, T::MaxKeysPerAccount::get());
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn revoke_account_keys() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ErasureOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let (owner, key_id) = generate::<T>(H256::repeat_byte(1));
+        let key = EncryptionKeys::<T>::get(key_id).expect("key was just generated");
+
+        // A full key list, all of which are revoked
+        let keys: Vec<H256> =
+            (0..T::MaxKeysPerAccount::get() as u64).map(H256::from_low_u64_be).collect();
+        for id in keys.iter() {
+            EncryptionKeys::<T>::insert(id, EncryptionKey { key_id: *id, ..key.clone() });
+        }
+        AccountKeys::<T>::insert(&owner, BoundedVec::truncate_from(keys));
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, owner.clone());
+
+        assert!(!EncryptionKeys::<T>::get(H256::zero()).unwrap().active);
+
+        Ok(())
+    }
+}
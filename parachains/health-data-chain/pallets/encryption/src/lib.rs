@@ -9,6 +9,18 @@
 //! - Key access control and sharing
 //! - Key revocation and lifecycle management
 //!
+//! ## Account migration
+//!
+//! When a patient's identity is recovered to a new account on the IdentityConsent chain, that
+//! chain calls `migrate_keys` over XCM (`MigrationOrigin`) to transfer ownership of the patient's
+//! keys to the new account. Key access grants are kept.
+//!
+//! ## Erasure
+//!
+//! When a patient exercises the right to erasure on the IdentityConsent chain, that chain calls
+//! `revoke_account_keys` over XCM (`ErasureOrigin`), which revokes all of the patient's keys.
+//! Access grants on a revoked key no longer give access to it.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "HealthData Chain - Encryption"
 
@@ -22,12 +34,19 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -105,6 +124,15 @@ pub mod pallet {
         /// Maximum key access grants per key
         #[pallet::constant]
         type MaxAccessGrantsPerKey: Get<u32>;
+
+        /// Origin moving an account's keys to a new account (the IdentityConsent chain)
+        type MigrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin revoking an account's keys on erasure (the IdentityConsent chain)
+        type ErasureOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     /// Storage for encryption keys by key_id
@@ -171,6 +199,17 @@ pub mod pallet {
             key_id: H256,
             grantee: T::AccountId,
         },
+        /// Keys moved to a new account [old, new, keys]
+        KeysMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+            keys: u32,
+        },
+        /// All keys of an account revoked on erasure [account, keys]
+        AccountKeysRevoked {
+            account: T::AccountId,
+            keys: u32,
+        },
     }
 
     #[pallet::error]
@@ -191,13 +230,15 @@ pub mod pallet {
         RecordAlreadyHasKey,
         /// No key for record
         NoKeyForRecord,
+        /// Cannot migrate an account to itself
+        InvalidMigration,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Generate a new encryption key
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::generate_key())]
         pub fn generate_key(
             origin: OriginFor<T>,
             algorithm: EncryptionAlgorithm,
@@ -262,7 +303,7 @@ pub mod pallet {
 
         /// Rotate encryption key for a record
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::rotate_key())]
         pub fn rotate_key(
             origin: OriginFor<T>,
             record_id: H256,
@@ -332,7 +373,7 @@ pub mod pallet {
 
         /// Revoke an encryption key
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::revoke_key())]
         pub fn revoke_key(origin: OriginFor<T>, key_id: H256) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -352,7 +393,7 @@ pub mod pallet {
 
         /// Grant access to a key
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::grant_key_access())]
         pub fn grant_key_access(
             origin: OriginFor<T>,
             key_id: H256,
@@ -381,7 +422,7 @@ pub mod pallet {
 
         /// Revoke key access
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::revoke_key_access())]
         pub fn revoke_key_access(
             origin: OriginFor<T>,
             key_id: H256,
@@ -398,6 +439,62 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Move an account's keys to a new account after an identity recovery
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::migrate_keys())]
+        pub fn migrate_keys(
+            origin: OriginFor<T>,
+            old: T::AccountId,
+            new: T::AccountId,
+        ) -> DispatchResult {
+            T::MigrationOrigin::ensure_origin(origin)?;
+
+            ensure!(old != new, Error::<T>::InvalidMigration);
+
+            let keys = AccountKeys::<T>::take(&old);
+            AccountKeys::<T>::try_mutate(&new, |account_keys| -> DispatchResult {
+                for key_id in keys.iter() {
+                    account_keys.try_push(*key_id).map_err(|_| Error::<T>::MaxKeysReached)?;
+                }
+                Ok(())
+            })?;
+
+            for key_id in keys.iter() {
+                EncryptionKeys::<T>::mutate(key_id, |maybe_key| {
+                    if let Some(key) = maybe_key {
+                        key.owner = new.clone();
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::KeysMigrated { old, new, keys: keys.len() as u32 });
+
+            Ok(())
+        }
+
+        /// Revoke all keys of an account after a right-to-erasure request
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::revoke_account_keys())]
+        pub fn revoke_account_keys(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            T::ErasureOrigin::ensure_origin(origin)?;
+
+            let keys = AccountKeys::<T>::get(&account);
+            for key_id in keys.iter() {
+                EncryptionKeys::<T>::mutate(key_id, |maybe_key| {
+                    if let Some(key) = maybe_key {
+                        if key.active {
+                            key.active = false;
+                            Self::deposit_event(Event::KeyRevoked { key_id: *key_id });
+                        }
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::AccountKeysRevoked { account, keys: keys.len() as u32 });
+
+            Ok(())
+        }
     }
 
     // Helper functions
@@ -423,6 +520,11 @@ pub mod pallet {
                     }
                     return true;
                 }
+
+                // Grants on a revoked (not rotated) key give no access
+                if !key.active && !key.rotated {
+                    return false;
+                }
             }
 
             // Check if has access grant
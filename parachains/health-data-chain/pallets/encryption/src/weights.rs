@@ -0,0 +1,140 @@
+//! Estimated weights for `pallet_encryption`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/health-data-node benchmark pallet \
+//!     --chain dev --pallet pallet_encryption --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/encryption/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_encryption`.
+pub trait WeightInfo {
+    fn generate_key() -> Weight;
+    fn rotate_key() -> Weight;
+    fn revoke_key() -> Weight;
+    fn grant_key_access() -> Weight;
+    fn revoke_key_access() -> Weight;
+    fn migrate_keys() -> Weight;
+    fn revoke_account_keys() -> Weight;
+}
+
+/// Estimated weights for `pallet_encryption`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Timestamp::Now`, `Encryption::AccountKeys`, `Encryption::RecordKeys`,
+    //     `Encryption::KeyCount`
+    // Writes: `Encryption::AccountKeys`, `Encryption::RecordKeys`, `Encryption::KeyCount`,
+    //     `Encryption::EncryptionKeys`
+    fn generate_key() -> Weight {
+        Weight::from_parts(34_000_000, 6_795)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `Encryption::RecordKeys`, `Encryption::EncryptionKeys`,
+    //     `Encryption::KeyCount`, `Encryption::AccountKeys`
+    // Writes: `Encryption::RecordKeys`, `Encryption::EncryptionKeys` x2, `Encryption::KeyCount`,
+    //     `Encryption::AccountKeys`
+    fn rotate_key() -> Weight {
+        Weight::from_parts(41_000_000, 6_795)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Encryption::EncryptionKeys`
+    // Writes: `Encryption::EncryptionKeys`
+    fn revoke_key() -> Weight {
+        Weight::from_parts(17_000_000, 3_726)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Encryption::EncryptionKeys`, `Timestamp::Now`
+    // Writes: `Encryption::KeyAccessGrants`
+    fn grant_key_access() -> Weight {
+        Weight::from_parts(20_000_000, 3_726)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Encryption::EncryptionKeys`
+    // Writes: `Encryption::KeyAccessGrants`
+    fn revoke_key_access() -> Weight {
+        Weight::from_parts(18_000_000, 3_726)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Encryption::AccountKeys` x2, `Encryption::EncryptionKeys` x100
+    // Writes: `Encryption::AccountKeys` x2, `Encryption::EncryptionKeys` x100
+    fn migrate_keys() -> Weight {
+        Weight::from_parts(398_000_000, 320_114)
+            .saturating_add(T::DbWeight::get().reads(102_u64))
+            .saturating_add(T::DbWeight::get().writes(102_u64))
+    }
+
+    // Reads: `Encryption::AccountKeys`, `Encryption::EncryptionKeys` x100
+    // Writes: `Encryption::EncryptionKeys` x100
+    fn revoke_account_keys() -> Weight {
+        Weight::from_parts(412_000_000, 320_114)
+            .saturating_add(T::DbWeight::get().reads(101_u64))
+            .saturating_add(T::DbWeight::get().writes(100_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn generate_key() -> Weight {
+        Weight::from_parts(34_000_000, 6_795)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn rotate_key() -> Weight {
+        Weight::from_parts(41_000_000, 6_795)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn revoke_key() -> Weight {
+        Weight::from_parts(17_000_000, 3_726)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn grant_key_access() -> Weight {
+        Weight::from_parts(20_000_000, 3_726)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn revoke_key_access() -> Weight {
+        Weight::from_parts(18_000_000, 3_726)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn migrate_keys() -> Weight {
+        Weight::from_parts(398_000_000, 320_114)
+            .saturating_add(RocksDbWeight::get().reads(102_u64))
+            .saturating_add(RocksDbWeight::get().writes(102_u64))
+    }
+
+    fn revoke_account_keys() -> Weight {
+        Weight::from_parts(412_000_000, 320_114)
+            .saturating_add(RocksDbWeight::get().reads(101_u64))
+            .saturating_add(RocksDbWeight::get().writes(100_u64))
+    }
+}
@@ -0,0 +1,272 @@
+//! Benchmarking setup for pallet-health-records
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::{traits::ConstU32, BoundedVec};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Upload a record for the whitelisted patient and return its ID
+fn upload<T: Config>() -> (T::AccountId, H256) {
+    let patient: T::AccountId = whitelisted_caller();
+
+    Pallet::<T>::upload_record(
+        RawOrigin::Signed(patient.clone()).into(),
+        BoundedVec::truncate_from(vec![b'Q'; 64]),
+        RecordCategory::LabResults,
+        DataFormat::FHIR,
+        BoundedVec::truncate_from(vec![b't'; 128]),
+        1024,
+        Some(H256::repeat_byte(1)),
+    )
+    .expect("record upload must succeed");
+
+    let record_id = *PatientRecords::<T>::get(&patient).last().expect("record was just uploaded");
+    (patient, record_id)
+}
+
+/// Store `n` records for `patient`
+fn store_records<T: Config>(patient: &T::AccountId, n: u32) {
+    let (_, record_id) = upload::<T>();
+    let record = HealthRecords::<T>::get(record_id).expect("record was just uploaded");
+    let ids: Vec<H256> = (0..n as u64).map(H256::from_low_u64_be).collect();
+    for id in ids.iter() {
+        HealthRecords::<T>::insert(
+            id,
+            HealthRecord { record_id: *id, patient: patient.clone(), ..record.clone() },
+        );
+    }
+    PatientRecords::<T>::insert(patient, BoundedVec::truncate_from(ids));
+}
+
+/// Store versions for each of `patient`'s records, as many as one erasure batch removes
+fn store_versions<T: Config>(patient: &T::AccountId) {
+    let per_record = (T::MaxVersionErasureBatch::get() / T::MaxErasureBatch::get()).max(1);
+    let version = RecordVersion {
+        ipfs_hash: BoundedVec::truncate_from(vec![b'Q'; 64]),
+        file_size: 1024,
+        encryption_key_id: Some(H256::repeat_byte(1)),
+        created_at: 0,
+        reason: BoundedVec::truncate_from(vec![b'r'; 256]),
+    };
+    for record_id in PatientRecords::<T>::get(patient).iter() {
+        for number in 1..=per_record {
+            RecordVersions::<T>::insert(record_id, number, version.clone());
+        }
+        LatestVersions::<T>::insert(record_id, per_record);
+    }
+}
+
+/// Fill `page` of the record's access log, leaving room for `room` entries
+fn fill_logs<T: Config>(record_id: H256, page: u32, room: u32, accessor: &T::AccountId) {
+    let purpose: BoundedVec<u8, ConstU32<64>> = BoundedVec::truncate_from(vec![b'p'; 64]);
+    let logs: Vec<AccessLog<T>> = (0..T::AccessLogPageSize::get().saturating_sub(room))
+        .map(|_| AccessLog {
+            record_id,
+            accessor: accessor.clone(),
+            accessed_at: 0,
+            purpose: purpose.clone(),
+        })
+        .collect();
+    AccessLogs::<T>::insert(record_id, page, BoundedVec::truncate_from(logs));
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn upload_record() {
+        let patient: T::AccountId = whitelisted_caller();
+
+        // Fill the patient's record list up to one below its bound
+        let existing: Vec<H256> = (0..9_999u64).map(H256::from_low_u64_be).collect();
+        PatientRecords::<T>::insert(&patient, BoundedVec::truncate_from(existing));
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(patient.clone()),
+            BoundedVec::truncate_from(vec![b'Q'; 64]),
+            RecordCategory::LabResults,
+            DataFormat::FHIR,
+            BoundedVec::truncate_from(vec![b't'; 128]),
+            1024,
+            Some(H256::repeat_byte(1)),
+        );
+
+        assert_eq!(PatientRecords::<T>::get(&patient).len(), 10_000);
+    }
+
+    #[benchmark]
+    fn update_record() {
+        let (patient, record_id) = upload::<T>();
+        let title: BoundedVec<u8, ConstU32<128>> = BoundedVec::truncate_from(vec![b'u'; 128]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), record_id, Some(title.clone()));
+
+        assert_eq!(HealthRecords::<T>::get(record_id).unwrap().title, title);
+    }
+
+    #[benchmark]
+    fn deactivate_record() {
+        let (patient, record_id) = upload::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), record_id);
+
+        assert!(!HealthRecords::<T>::get(record_id).unwrap().active);
+    }
+
+    #[benchmark]
+    fn log_access() {
+        let (_, record_id) = upload::<T>();
+        let accessor: T::AccountId = account("accessor", 0, 0);
+        let purpose: BoundedVec<u8, ConstU32<64>> = BoundedVec::truncate_from(vec![b'p'; 64]);
+        fill_logs::<T>(record_id, 0, 1, &accessor);
+
+        // The accessor is not the patient, so its grant is checked
+        T::BenchmarkHelper::grant_access(&record_id, Some(&H256::repeat_byte(1)), &accessor);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(accessor), record_id, purpose);
+
+        assert_eq!(AccessLogs::<T>::get(record_id, 0).len() as u32, T::AccessLogPageSize::get());
+    }
+
+    #[benchmark]
+    fn read_record() {
+        let (_, record_id) = upload::<T>();
+        let accessor: T::AccountId = account("accessor", 0, 0);
+        fill_logs::<T>(record_id, 0, 1, &accessor);
+        T::BenchmarkHelper::grant_access(&record_id, Some(&H256::repeat_byte(1)), &accessor);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(accessor), record_id);
+
+        assert_eq!(AccessLogs::<T>::get(record_id, 0).len() as u32, T::AccessLogPageSize::get());
+    }
+
+    #[benchmark]
+    fn archive_access_logs() {
+        let (patient, record_id) = upload::<T>();
+        fill_logs::<T>(record_id, 0, 0, &account("accessor", 0, 0));
+        AccessLogIndex::<T>::insert(
+            record_id,
+            LogIndex { first_page: 0, current_page: 1, total: T::AccessLogPageSize::get().into() },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), record_id);
+
+        assert!(ArchivedAccessLogs::<T>::contains_key(record_id, 0));
+    }
+
+    #[benchmark]
+    fn migrate_patient() -> Result<(), BenchmarkError> {
+        let origin =
+            T::MigrationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let old: T::AccountId = account("old", 0, 0);
+        let new: T::AccountId = account("new", 0, 0);
+        store_records::<T>(&old, T::MaxMigrationBatch::get());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, old.clone(), new.clone());
+
+        assert_eq!(PatientRecords::<T>::get(&new).len() as u32, T::MaxMigrationBatch::get());
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn continue_migration() {
+        let old: T::AccountId = account("old", 0, 0);
+        let new: T::AccountId = account("new", 0, 0);
+        store_records::<T>(&old, T::MaxMigrationBatch::get());
+        PatientMigrations::<T>::insert(&old, &new);
+        let caller: T::AccountId = account("caller", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), old.clone());
+
+        assert!(!PatientMigrations::<T>::contains_key(&old));
+    }
+
+    #[benchmark]
+    fn erase_patient() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ErasureOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let patient: T::AccountId = account("patient", 0, 0);
+        store_records::<T>(&patient, T::MaxErasureBatch::get() + 1);
+        store_versions::<T>(&patient);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, patient.clone());
+
+        assert_eq!(
+            PatientErasures::<T>::get(&patient),
+            Some(ErasureProgress { records: T::MaxErasureBatch::get(), versions: 0 })
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn continue_erasure() {
+        let patient: T::AccountId = account("patient", 0, 0);
+        store_records::<T>(&patient, T::MaxErasureBatch::get() + 1);
+        store_versions::<T>(&patient);
+        PatientErasures::<T>::insert(&patient, ErasureProgress { records: 1, versions: 0 });
+        let caller: T::AccountId = account("caller", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), patient.clone());
+
+        assert!(!PatientErasures::<T>::contains_key(&patient));
+    }
+
+    #[benchmark]
+    fn amend_record() {
+        let (patient, record_id) = upload::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(patient),
+            record_id,
+            BoundedVec::truncate_from(vec![b'R'; 64]),
+            2048,
+            Some(H256::repeat_byte(2)),
+            BoundedVec::truncate_from(vec![b'r'; 256]),
+        );
+
+        assert_eq!(LatestVersions::<T>::get(record_id), 2);
+    }
+
+    #[benchmark]
+    fn read_record_version() {
+        let (patient, record_id) = upload::<T>();
+        Pallet::<T>::amend_record(
+            RawOrigin::Signed(patient).into(),
+            record_id,
+            BoundedVec::truncate_from(vec![b'R'; 64]),
+            2048,
+            Some(H256::repeat_byte(2)),
+            BoundedVec::truncate_from(vec![b'r'; 256]),
+        )
+        .expect("record amendment must succeed");
+        let accessor: T::AccountId = account("accessor", 0, 0);
+        fill_logs::<T>(record_id, 0, 1, &accessor);
+
+        // The accessor holds a grant on the first version's key
+        T::BenchmarkHelper::grant_access(&record_id, Some(&H256::repeat_byte(1)), &accessor);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(accessor), record_id, 1);
+
+        assert_eq!(AccessLogs::<T>::get(record_id, 0).len() as u32, T::AccessLogPageSize::get());
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -10,6 +10,53 @@
 //! - Record ownership and access tracking
 //! - Audit trail for all record operations
 //!
+//! ## Access checks
+//!
+//! `log_access` and `read_record` succeed only for the record's patient or an account with a
+//! live grant, as reported by [`Config::AccessCheck`] (an access-control grant on the record or
+//! access to its encryption key). Other attempts are not logged as accesses: they are counted
+//! in `AccessDenials` and reported with an `AccessDenied` event, and the call still succeeds so
+//! that the denial is kept on-chain.
+//!
+//! ## Versions
+//!
+//! Records are versioned: the upload is version 1, and the patient appends a new version with
+//! `amend_record` (e.g. a corrected lab result), giving its IPFS hash, file size, encryption key
+//! and the reason for the amendment. The `HealthRecord` always describes the latest version,
+//! under the same `record_id`, and every version is kept in `RecordVersions` under its
+//! `(record_id, version)` key, so a record can be amended any number of times.
+//! `get_record_versions` reads the history a page at a time.
+//!
+//! `read_record` reads the latest version and `read_record_version` a given one. A grant
+//! follows the latest version unless [`Config::AccessCheck`] reports it pinned to another one
+//! (see `pallet_access_control::pin_grant_version`). An access request may name a version: the
+//! consent scope can pin a record to a version (`ConsentScope::record_versions` on the
+//! IdentityConsent chain), and the grant the request leads to is pinned to it. Listings only
+//! count records and do not name them, so they are not versioned.
+//!
+//! ## Access logs
+//!
+//! Access logs are stored in pages of `AccessLogPageSize` entries, so a record never stops
+//! accepting accesses. The patient may archive the oldest full page with
+//! `archive_access_logs`: the page is removed and only its hash is kept on-chain, against
+//! which an off-chain copy can be checked.
+//!
+//! ## Account migration
+//!
+//! When a patient's identity is recovered to a new account on the IdentityConsent chain, that
+//! chain calls `migrate_patient` over XCM (`MigrationOrigin`) to move the patient's records to
+//! the new account. Records are moved `MaxMigrationBatch` at a time; further batches are moved
+//! by anyone with `continue_migration`. Grants are keyed by record, so they carry over.
+//!
+//! ## Erasure
+//!
+//! When a patient exercises the right to erasure on the IdentityConsent chain, that chain calls
+//! `erase_patient` over XCM (`ErasureOrigin`). The patient's records are deactivated, their
+//! title and IPFS hash wiped and their versions removed, at most `MaxErasureBatch` records and
+//! `MaxVersionErasureBatch` versions at a time; further batches are erased by anyone with
+//! `continue_erasure`. Record IDs, version counts and access
+//! logs are kept for the audit trail.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "HealthData Chain - Health Records"
 
@@ -23,14 +70,27 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
+    use sp_runtime::traits::Hash;
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Health record data format types
@@ -103,6 +163,21 @@ pub mod pallet {
         pub active: bool,
     }
 
+    /// One version of a health record's content
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RecordVersion {
+        /// IPFS content hash
+        pub ipfs_hash: BoundedVec<u8, ConstU32<64>>,
+        /// File size in bytes
+        pub file_size: u64,
+        /// Encryption key ID (reference to encryption pallet)
+        pub encryption_key_id: Option<H256>,
+        /// Timestamp the version was added
+        pub created_at: u64,
+        /// Reason for the amendment (empty for the uploaded version)
+        pub reason: BoundedVec<u8, ConstU32<256>>,
+    }
+
     /// Access log entry for health records
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
@@ -117,6 +192,73 @@ pub mod pallet {
         pub purpose: BoundedVec<u8, ConstU32<64>>,
     }
 
+    /// Record of refused access attempts by one account on one record
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct AccessDenial {
+        /// Number of refused attempts
+        pub attempts: u32,
+        /// First refused attempt timestamp
+        pub first_attempt: u64,
+        /// Last refused attempt timestamp
+        pub last_attempt: u64,
+    }
+
+    /// Position of a record's access log pages
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct LogIndex {
+        /// Oldest page still stored on-chain
+        pub first_page: u32,
+        /// Page new entries are appended to
+        pub current_page: u32,
+        /// Number of entries ever logged
+        pub total: u64,
+    }
+
+    /// Progress of a patient's erasure
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct ErasureProgress {
+        /// Number of the patient's records fully erased
+        pub records: u32,
+        /// Number of versions already removed from the next record
+        pub versions: u32,
+    }
+
+    /// Grants held outside this pallet that allow reading a record
+    pub trait RecordAccess<AccountId> {
+        /// Check if `accessor` holds a live grant on `version` of the record or on the version's
+        /// encryption key
+        fn has_access(
+            record_id: &H256,
+            accessor: &AccountId,
+            version: u32,
+            encryption_key_id: Option<&H256>,
+            now: u64,
+        ) -> bool;
+    }
+
+    impl<AccountId> RecordAccess<AccountId> for () {
+        fn has_access(
+            _record_id: &H256,
+            _accessor: &AccountId,
+            _version: u32,
+            _encryption_key_id: Option<&H256>,
+            _now: u64,
+        ) -> bool {
+            false
+        }
+    }
+
+    /// Runtime-specific setup needed to benchmark access checks
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper<AccountId> {
+        /// Give `accessor` a live grant on a record through `Config::AccessCheck`
+        fn grant_access(record_id: &H256, encryption_key_id: Option<&H256>, accessor: &AccountId);
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The overarching event type
@@ -129,9 +271,37 @@ pub mod pallet {
         #[pallet::constant]
         type MaxRecordsPerPatient: Get<u32>;
 
-        /// Maximum number of access logs per record
+        /// Number of access log entries per page
+        #[pallet::constant]
+        type AccessLogPageSize: Get<u32>;
+
+        /// Grants allowing accounts other than the patient to read a record
+        type AccessCheck: RecordAccess<Self::AccountId>;
+
+        /// Origin moving a patient's records to a new account (the IdentityConsent chain)
+        type MigrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of records moved to a new account per call
+        #[pallet::constant]
+        type MaxMigrationBatch: Get<u32>;
+
+        /// Origin erasing a patient's records (the IdentityConsent chain)
+        type ErasureOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of records erased per call
+        #[pallet::constant]
+        type MaxErasureBatch: Get<u32>;
+
+        /// Maximum number of record versions removed per erasure call
         #[pallet::constant]
-        type MaxAccessLogsPerRecord: Get<u32>;
+        type MaxVersionErasureBatch: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+
+        /// Benchmark setup for access grants
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId>;
     }
 
     /// Storage for health records by record_id
@@ -150,17 +320,78 @@ pub mod pallet {
         ValueQuery,
     >;
 
-    /// Storage for access logs by record_id
+    /// Versions of each record (record_id -> version -> content)
+    #[pallet::storage]
+    #[pallet::getter(fn record_versions)]
+    pub type RecordVersions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256, // record_id
+        Twox64Concat,
+        u32, // version
+        RecordVersion,
+    >;
+
+    /// Latest version number by record_id
+    #[pallet::storage]
+    #[pallet::getter(fn latest_version)]
+    pub type LatestVersions<T: Config> = StorageMap<_, Blake2_128Concat, H256, u32, ValueQuery>;
+
+    /// Storage for access log pages (record_id -> page -> entries)
     #[pallet::storage]
     #[pallet::getter(fn access_logs)]
-    pub type AccessLogs<T: Config> = StorageMap<
+    pub type AccessLogs<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        H256,
-        BoundedVec<AccessLog<T>, ConstU32<10000>>,
+        H256, // record_id
+        Twox64Concat,
+        u32, // page
+        BoundedVec<AccessLog<T>, T::AccessLogPageSize>,
         ValueQuery,
     >;
 
+    /// Access log page positions by record_id
+    #[pallet::storage]
+    #[pallet::getter(fn access_log_index)]
+    pub type AccessLogIndex<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256, LogIndex, ValueQuery>;
+
+    /// Hashes of archived access log pages (record_id -> page -> hash)
+    #[pallet::storage]
+    #[pallet::getter(fn archived_access_logs)]
+    pub type ArchivedAccessLogs<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256, // record_id
+        Twox64Concat,
+        u32, // page
+        T::Hash,
+    >;
+
+    /// Refused access attempts (record_id -> accessor -> denial)
+    #[pallet::storage]
+    #[pallet::getter(fn access_denials)]
+    pub type AccessDenials<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256, // record_id
+        Blake2_128Concat,
+        T::AccountId, // accessor
+        AccessDenial,
+    >;
+
+    /// Patients whose records are being moved to a new account (old account -> new account)
+    #[pallet::storage]
+    #[pallet::getter(fn patient_migrations)]
+    pub type PatientMigrations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+    /// Progress of the patients whose records are being erased
+    #[pallet::storage]
+    #[pallet::getter(fn patient_erasures)]
+    pub type PatientErasures<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ErasureProgress>;
+
     /// Record counter for generating unique IDs
     #[pallet::storage]
     #[pallet::getter(fn record_count)]
@@ -177,6 +408,8 @@ pub mod pallet {
         },
         /// Health record updated [record_id]
         RecordUpdated { record_id: H256 },
+        /// New version of a health record added [record_id, version]
+        RecordAmended { record_id: H256, version: u32 },
         /// Health record deactivated [record_id]
         RecordDeactivated { record_id: H256 },
         /// Health record accessed [record_id, accessor]
@@ -184,6 +417,46 @@ pub mod pallet {
             record_id: H256,
             accessor: T::AccountId,
         },
+        /// Health record read [record_id, accessor]
+        RecordRead {
+            record_id: H256,
+            accessor: T::AccountId,
+        },
+        /// Given version of a health record read [record_id, version, accessor]
+        RecordVersionRead {
+            record_id: H256,
+            version: u32,
+            accessor: T::AccountId,
+        },
+        /// Access attempt refused for lack of ownership or grant [record_id, accessor]
+        AccessDenied {
+            record_id: H256,
+            accessor: T::AccountId,
+        },
+        /// Access log page archived [record_id, page, page_hash]
+        AccessLogArchived {
+            record_id: H256,
+            page: u32,
+            page_hash: T::Hash,
+        },
+        /// Batch of a patient's records moved to a new account [old, new, records]
+        RecordsMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+            records: u32,
+        },
+        /// All of a patient's records moved to a new account [old, new]
+        PatientMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+        },
+        /// Batch of a patient's records wiped and deactivated [patient, records]
+        RecordsErased {
+            patient: T::AccountId,
+            records: u32,
+        },
+        /// All of a patient's records erased [patient]
+        PatientErased { patient: T::AccountId },
     }
 
     #[pallet::error]
@@ -196,12 +469,26 @@ pub mod pallet {
         RecordDeactivated,
         /// Maximum records per patient reached
         MaxRecordsReached,
-        /// Maximum access logs reached
-        MaxAccessLogsReached,
+        /// No full access log page left to archive
+        NothingToArchive,
         /// Invalid IPFS hash
         InvalidIPFSHash,
         /// Invalid title
         InvalidTitle,
+        /// Records of this account are already being migrated
+        MigrationInProgress,
+        /// No migration of this account's records is pending
+        NoMigrationPending,
+        /// Cannot migrate an account to itself
+        InvalidMigration,
+        /// Records of this account are already being erased
+        ErasureInProgress,
+        /// No erasure of this account's records is pending
+        NoErasurePending,
+        /// An amendment needs a reason
+        InvalidReason,
+        /// Record version not found
+        VersionNotFound,
     }
 
     #[pallet::call]
@@ -217,7 +504,7 @@ pub mod pallet {
         /// - `file_size`: File size in bytes
         /// - `encryption_key_id`: Optional encryption key reference
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::upload_record())]
         pub fn upload_record(
             origin: OriginFor<T>,
             ipfs_hash: BoundedVec<u8, ConstU32<64>>,
@@ -240,6 +527,14 @@ pub mod pallet {
             let record_id = Self::generate_record_id(&patient, count);
             RecordCount::<T>::put(count.saturating_add(1));
 
+            let version = RecordVersion {
+                ipfs_hash: ipfs_hash.clone(),
+                file_size,
+                encryption_key_id,
+                created_at: now,
+                reason: BoundedVec::new(),
+            };
+
             let record = HealthRecord {
                 record_id,
                 patient: patient.clone(),
@@ -255,8 +550,10 @@ pub mod pallet {
                 active: true,
             };
 
-            // Store record
+            // Store record as its first version
             HealthRecords::<T>::insert(record_id, record);
+            RecordVersions::<T>::insert(record_id, 1, version);
+            LatestVersions::<T>::insert(record_id, 1);
 
             // Update patient's record list
             PatientRecords::<T>::try_mutate(&patient, |records| -> DispatchResult {
@@ -275,7 +572,7 @@ pub mod pallet {
 
         /// Update record metadata
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_record())]
         pub fn update_record(
             origin: OriginFor<T>,
             record_id: H256,
@@ -303,7 +600,7 @@ pub mod pallet {
 
         /// Deactivate a health record
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::deactivate_record())]
         pub fn deactivate_record(origin: OriginFor<T>, record_id: H256) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -322,8 +619,11 @@ pub mod pallet {
         }
 
         /// Log access to a health record
+        ///
+        /// Requires ownership of the record or a live grant; other attempts are stored as
+        /// denials.
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::log_access())]
         pub fn log_access(
             origin: OriginFor<T>,
             record_id: H256,
@@ -331,13 +631,339 @@ pub mod pallet {
         ) -> DispatchResult {
             let accessor = ensure_signed(origin)?;
 
+            if Self::do_access(&accessor, record_id, None, purpose)? {
+                Self::deposit_event(Event::RecordAccessed { record_id, accessor });
+            }
+
+            Ok(())
+        }
+
+        /// Read a health record, leaving an entry in its audit trail
+        ///
+        /// Requires ownership of the record or a live grant; other attempts are stored as
+        /// denials.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::read_record())]
+        pub fn read_record(origin: OriginFor<T>, record_id: H256) -> DispatchResult {
+            let accessor = ensure_signed(origin)?;
+
+            let purpose = BoundedVec::truncate_from(b"read".to_vec());
+            if Self::do_access(&accessor, record_id, None, purpose)? {
+                Self::deposit_event(Event::RecordRead { record_id, accessor });
+            }
+
+            Ok(())
+        }
+
+        /// Archive the oldest full access log page of a record
+        ///
+        /// The page is removed from storage and its hash kept in `ArchivedAccessLogs`. The page
+        /// currently being written to is never archived.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::archive_access_logs())]
+        pub fn archive_access_logs(origin: OriginFor<T>, record_id: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let record = HealthRecords::<T>::get(record_id).ok_or(Error::<T>::RecordNotFound)?;
+
+            // Only patient can archive
+            ensure!(record.patient == who, Error::<T>::NotAuthorized);
+
+            AccessLogIndex::<T>::try_mutate(record_id, |index| -> DispatchResult {
+                ensure!(index.first_page < index.current_page, Error::<T>::NothingToArchive);
+
+                let page = index.first_page;
+                let page_hash = T::Hashing::hash_of(&AccessLogs::<T>::take(record_id, page));
+                ArchivedAccessLogs::<T>::insert(record_id, page, page_hash);
+                index.first_page = page.saturating_add(1);
+
+                Self::deposit_event(Event::AccessLogArchived { record_id, page, page_hash });
+
+                Ok(())
+            })
+        }
+
+        /// Move a patient's records to a new account after an identity recovery
+        ///
+        /// Moves the first `MaxMigrationBatch` records; the rest are moved with
+        /// `continue_migration`.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::migrate_patient())]
+        pub fn migrate_patient(
+            origin: OriginFor<T>,
+            old: T::AccountId,
+            new: T::AccountId,
+        ) -> DispatchResult {
+            T::MigrationOrigin::ensure_origin(origin)?;
+
+            ensure!(old != new, Error::<T>::InvalidMigration);
+            ensure!(!PatientMigrations::<T>::contains_key(&old), Error::<T>::MigrationInProgress);
+
+            PatientMigrations::<T>::insert(&old, &new);
+            Self::migrate_records(&old, &new)
+        }
+
+        /// Move the next batch of records of a pending migration
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::continue_migration())]
+        pub fn continue_migration(origin: OriginFor<T>, old: T::AccountId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let new = PatientMigrations::<T>::get(&old).ok_or(Error::<T>::NoMigrationPending)?;
+            Self::migrate_records(&old, &new)
+        }
+
+        /// Erase a patient's records after a right-to-erasure request
+        ///
+        /// Erases the first `MaxErasureBatch` records, removing at most `MaxVersionErasureBatch`
+        /// versions; the rest are erased with `continue_erasure`.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::erase_patient())]
+        pub fn erase_patient(origin: OriginFor<T>, patient: T::AccountId) -> DispatchResult {
+            T::ErasureOrigin::ensure_origin(origin)?;
+
+            ensure!(!PatientErasures::<T>::contains_key(&patient), Error::<T>::ErasureInProgress);
+
+            Self::erase_records(&patient, ErasureProgress::default());
+            Ok(())
+        }
+
+        /// Erase the next batch of records of a pending erasure
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::continue_erasure())]
+        pub fn continue_erasure(origin: OriginFor<T>, patient: T::AccountId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let progress =
+                PatientErasures::<T>::get(&patient).ok_or(Error::<T>::NoErasurePending)?;
+            Self::erase_records(&patient, progress);
+            Ok(())
+        }
+
+        /// Add a new version of a health record
+        ///
+        /// Parameters:
+        /// - `origin`: Patient owning the record
+        /// - `record_id`: Record amended
+        /// - `ipfs_hash`: IPFS content hash of the new version
+        /// - `file_size`: File size in bytes
+        /// - `encryption_key_id`: Optional encryption key reference
+        /// - `reason`: Reason for the amendment
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::amend_record())]
+        pub fn amend_record(
+            origin: OriginFor<T>,
+            record_id: H256,
+            ipfs_hash: BoundedVec<u8, ConstU32<64>>,
+            file_size: u64,
+            encryption_key_id: Option<H256>,
+            reason: BoundedVec<u8, ConstU32<256>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!ipfs_hash.is_empty(), Error::<T>::InvalidIPFSHash);
+            ensure!(!reason.is_empty(), Error::<T>::InvalidReason);
+
             HealthRecords::<T>::try_mutate(record_id, |maybe_record| -> DispatchResult {
                 let record = maybe_record.as_mut().ok_or(Error::<T>::RecordNotFound)?;
 
+                // Only patient can amend
+                ensure!(record.patient == who, Error::<T>::NotAuthorized);
                 ensure!(record.active, Error::<T>::RecordDeactivated);
 
+                let version = LatestVersions::<T>::get(record_id).saturating_add(1);
+
                 let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
 
+                RecordVersions::<T>::insert(
+                    record_id,
+                    version,
+                    RecordVersion {
+                        ipfs_hash: ipfs_hash.clone(),
+                        file_size,
+                        encryption_key_id,
+                        created_at: now,
+                        reason,
+                    },
+                );
+                LatestVersions::<T>::insert(record_id, version);
+
+                record.ipfs_hash = ipfs_hash;
+                record.file_size = file_size;
+                record.encryption_key_id = encryption_key_id;
+
+                Self::deposit_event(Event::RecordAmended { record_id, version });
+
+                Ok(())
+            })
+        }
+
+        /// Read a given version of a health record, leaving an entry in its audit trail
+        ///
+        /// Requires ownership of the record or a live grant on that version; other attempts are
+        /// stored as denials.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::read_record_version())]
+        pub fn read_record_version(
+            origin: OriginFor<T>,
+            record_id: H256,
+            version: u32,
+        ) -> DispatchResult {
+            let accessor = ensure_signed(origin)?;
+
+            let purpose = BoundedVec::truncate_from(b"read".to_vec());
+            if Self::do_access(&accessor, record_id, Some(version), purpose)? {
+                Self::deposit_event(Event::RecordVersionRead { record_id, version, accessor });
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Outcome of an access attempt: `Ok(false)` when it was refused and stored as a denial
+    type AccessResult = Result<bool, DispatchError>;
+
+    // Helper functions
+    impl<T: Config> Pallet<T> {
+        /// Move a batch of records from `old` to `new`, completing the migration once none
+        /// are left
+        fn migrate_records(old: &T::AccountId, new: &T::AccountId) -> DispatchResult {
+            let mut remaining = PatientRecords::<T>::get(old).into_inner();
+            let batch = remaining.len().min(T::MaxMigrationBatch::get() as usize);
+            let moved: Vec<H256> = remaining.drain(..batch).collect();
+
+            PatientRecords::<T>::try_mutate(new, |records| -> DispatchResult {
+                for record_id in moved.iter() {
+                    records.try_push(*record_id).map_err(|_| Error::<T>::MaxRecordsReached)?;
+                }
+                Ok(())
+            })?;
+
+            for record_id in moved.iter() {
+                HealthRecords::<T>::mutate(record_id, |maybe_record| {
+                    if let Some(record) = maybe_record {
+                        record.patient = new.clone();
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::RecordsMigrated {
+                old: old.clone(),
+                new: new.clone(),
+                records: moved.len() as u32,
+            });
+
+            if remaining.is_empty() {
+                PatientRecords::<T>::remove(old);
+                PatientMigrations::<T>::remove(old);
+                Self::deposit_event(Event::PatientMigrated { old: old.clone(), new: new.clone() });
+            } else {
+                PatientRecords::<T>::insert(old, BoundedVec::truncate_from(remaining));
+            }
+
+            Ok(())
+        }
+
+        /// Wipe and deactivate the next batch of records and remove their versions, completing
+        /// the erasure once none are left
+        ///
+        /// A record whose versions do not fit in the batch is finished by the next call.
+        fn erase_records(patient: &T::AccountId, mut progress: ErasureProgress) {
+            let records = PatientRecords::<T>::get(patient);
+            let mut erased = 0u32;
+            let mut versions_left = T::MaxVersionErasureBatch::get();
+
+            for record_id in records.iter().skip(progress.records as usize) {
+                if erased >= T::MaxErasureBatch::get() || versions_left == 0 {
+                    break;
+                }
+
+                if progress.versions == 0 {
+                    HealthRecords::<T>::mutate(record_id, |maybe_record| {
+                        if let Some(record) = maybe_record {
+                            record.ipfs_hash = BoundedVec::new();
+                            record.title = BoundedVec::new();
+                            record.active = false;
+                        }
+                    });
+                }
+
+                // Versions are numbered from 1, so the removed ones are counted, not iterated
+                let latest = LatestVersions::<T>::get(record_id);
+                let last = latest.min(progress.versions.saturating_add(versions_left));
+                for version in progress.versions.saturating_add(1)..=last {
+                    RecordVersions::<T>::remove(record_id, version);
+                }
+                versions_left = versions_left.saturating_sub(last - progress.versions);
+
+                if last < latest {
+                    progress.versions = last;
+                    break;
+                }
+                progress.records = progress.records.saturating_add(1);
+                progress.versions = 0;
+                erased = erased.saturating_add(1);
+            }
+
+            Self::deposit_event(Event::RecordsErased { patient: patient.clone(), records: erased });
+
+            if progress.records as usize >= records.len() {
+                PatientErasures::<T>::remove(patient);
+                Self::deposit_event(Event::PatientErased { patient: patient.clone() });
+            } else {
+                PatientErasures::<T>::insert(patient, progress);
+            }
+        }
+
+        /// Log an access by `accessor` to `version` of a record (the latest when `None`), or
+        /// store a denial if it may not access it
+        fn do_access(
+            accessor: &T::AccountId,
+            record_id: H256,
+            version: Option<u32>,
+            purpose: BoundedVec<u8, ConstU32<64>>,
+        ) -> AccessResult {
+            HealthRecords::<T>::try_mutate(record_id, |maybe_record| -> AccessResult {
+                let record = maybe_record.as_mut().ok_or(Error::<T>::RecordNotFound)?;
+
+                ensure!(record.active, Error::<T>::RecordDeactivated);
+
+                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+                let allowed = match version {
+                    Some(version) => {
+                        let content = RecordVersions::<T>::get(record_id, version)
+                            .ok_or(Error::<T>::VersionNotFound)?;
+                        Self::can_access_version(
+                            record,
+                            accessor,
+                            version,
+                            content.encryption_key_id.as_ref(),
+                            now,
+                        )
+                    },
+                    None => Self::can_access(record, accessor, now),
+                };
+
+                if !allowed {
+                    AccessDenials::<T>::mutate(record_id, accessor, |maybe_denial| {
+                        let denial = maybe_denial.get_or_insert(AccessDenial {
+                            attempts: 0,
+                            first_attempt: now,
+                            last_attempt: now,
+                        });
+                        denial.attempts = denial.attempts.saturating_add(1);
+                        denial.last_attempt = now;
+                    });
+
+                    Self::deposit_event(Event::AccessDenied {
+                        record_id,
+                        accessor: accessor.clone(),
+                    });
+
+                    return Ok(false);
+                }
+
                 // Update access stats
                 record.access_count = record.access_count.saturating_add(1);
                 record.last_accessed = Some(now);
@@ -350,23 +976,80 @@ pub mod pallet {
                     purpose,
                 };
 
-                AccessLogs::<T>::try_mutate(record_id, |logs| -> DispatchResult {
-                    logs.try_push(log_entry).map_err(|_| Error::<T>::MaxAccessLogsReached)?;
-                    Ok(())
-                })?;
+                Self::append_log(record_id, log_entry);
 
-                Self::deposit_event(Event::RecordAccessed { record_id, accessor });
-
-                Ok(())
+                Ok(true)
             })
         }
-    }
 
-    // Helper functions
-    impl<T: Config> Pallet<T> {
+        /// Append an entry to the record's access log, starting a new page when it is full
+        fn append_log(record_id: H256, entry: AccessLog<T>) {
+            AccessLogIndex::<T>::mutate(record_id, |index| {
+                let mut page = AccessLogs::<T>::get(record_id, index.current_page);
+                if let Err(entry) = page.try_push(entry) {
+                    index.current_page = index.current_page.saturating_add(1);
+                    page = BoundedVec::truncate_from(vec![entry]);
+                }
+
+                AccessLogs::<T>::insert(record_id, index.current_page, page);
+                index.total = index.total.saturating_add(1);
+            });
+        }
+
+        /// Get one page of a record's access log (empty if archived or not written yet)
+        pub fn access_log_page(record_id: &H256, page: u32) -> Vec<AccessLog<T>> {
+            AccessLogs::<T>::get(record_id, page).into_inner()
+        }
+
+        /// Get up to `count` of the latest access log entries still on-chain, newest first
+        pub fn recent_access_logs(record_id: &H256, count: u32) -> Vec<AccessLog<T>> {
+            let index = AccessLogIndex::<T>::get(record_id);
+            let mut logs = Vec::new();
+
+            for page in (index.first_page..=index.current_page).rev() {
+                for entry in AccessLogs::<T>::get(record_id, page).into_iter().rev() {
+                    if logs.len() as u32 >= count {
+                        return logs;
+                    }
+                    logs.push(entry);
+                }
+            }
+
+            logs
+        }
+
+        /// Check if `accessor` owns the record or holds a live grant on its latest version
+        pub fn can_access(record: &HealthRecord<T>, accessor: &T::AccountId, now: u64) -> bool {
+            Self::can_access_version(
+                record,
+                accessor,
+                LatestVersions::<T>::get(record.record_id),
+                record.encryption_key_id.as_ref(),
+                now,
+            )
+        }
+
+        /// Check if `accessor` owns the record or holds a live grant on `version` of it, whose
+        /// content is encrypted with `encryption_key_id`
+        fn can_access_version(
+            record: &HealthRecord<T>,
+            accessor: &T::AccountId,
+            version: u32,
+            encryption_key_id: Option<&H256>,
+            now: u64,
+        ) -> bool {
+            &record.patient == accessor
+                || T::AccessCheck::has_access(
+                    &record.record_id,
+                    accessor,
+                    version,
+                    encryption_key_id,
+                    now,
+                )
+        }
+
         /// Generate unique record ID
         fn generate_record_id(patient: &T::AccountId, nonce: u64) -> H256 {
-            use sp_runtime::traits::Hash;
             let mut data = patient.encode();
             data.extend_from_slice(&nonce.encode());
             T::Hashing::hash(&data)
@@ -381,6 +1064,22 @@ pub mod pallet {
                 .collect()
         }
 
+        /// Get up to `count` versions of a record from version `from`, oldest first
+        pub fn get_record_versions(
+            record_id: &H256,
+            from: u32,
+            count: u32,
+        ) -> Vec<(u32, RecordVersion)> {
+            let from = from.max(1);
+            let last = LatestVersions::<T>::get(record_id)
+                .min(from.saturating_add(count).saturating_sub(1));
+            (from..=last)
+                .filter_map(|version| {
+                    RecordVersions::<T>::get(record_id, version).map(|content| (version, content))
+                })
+                .collect()
+        }
+
         /// Get active records for a patient
         pub fn get_active_patient_records(patient: &T::AccountId) -> Vec<HealthRecord<T>> {
             Self::get_patient_records(patient)
@@ -0,0 +1,83 @@
+//! Storage migrations for the health-records pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, storage_alias,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Storage layout of version 0
+pub mod v0 {
+    use super::*;
+
+    /// Access logs of version 0, one unpaged list per record
+    #[storage_alias]
+    pub type AccessLogs<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, H256, Vec<AccessLog<T>>, ValueQuery>;
+}
+
+/// Version 1 versions records and stores access logs in pages of `AccessLogPageSize` entries
+pub mod v1 {
+    use super::*;
+
+    /// Store each existing record's content as its version 1, then move each record's access
+    /// log into pages and index them in `AccessLogIndex`
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut records = 0u64;
+            for (record_id, record) in HealthRecords::<T>::iter() {
+                records += 1;
+                RecordVersions::<T>::insert(
+                    record_id,
+                    1,
+                    RecordVersion {
+                        ipfs_hash: record.ipfs_hash,
+                        file_size: record.file_size,
+                        encryption_key_id: record.encryption_key_id,
+                        created_at: record.uploaded_at,
+                        reason: BoundedVec::new(),
+                    },
+                );
+                LatestVersions::<T>::insert(record_id, 1);
+            }
+
+            // The pages share the old logs' storage prefix, so the old logs are taken out first
+            let old_logs: Vec<(H256, Vec<AccessLog<T>>)> = v0::AccessLogs::<T>::drain().collect();
+            let mut reads = records + old_logs.len() as u64;
+            let mut writes = 2 * records + old_logs.len() as u64;
+
+            let page_size = T::AccessLogPageSize::get().max(1) as usize;
+            for (record_id, entries) in old_logs {
+                let mut index = LogIndex { total: entries.len() as u64, ..Default::default() };
+                for (page, chunk) in entries.chunks(page_size).enumerate() {
+                    index.current_page = page as u32;
+                    AccessLogs::<T>::insert(
+                        record_id,
+                        index.current_page,
+                        BoundedVec::truncate_from(chunk.to_vec()),
+                    );
+                    writes += 1;
+                }
+
+                AccessLogIndex::<T>::insert(record_id, index);
+                reads += 1;
+                writes += 1;
+            }
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Migrate `RecordVersions` and `AccessLogs` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,172 @@
+//! Mock runtime for health-records pallet tests
+
+use crate as pallet_health_records;
+use frame_support::{
+    parameter_types,
+    traits::{ConstU16, ConstU32, ConstU64},
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BoundedVec, BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Timestamp: pallet_timestamp,
+        HealthRecords: pallet_health_records,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+    /// Live grants known to `TestAccess` (record_id or key_id, accessor, expires_at)
+    pub static Grants: Vec<(H256, u64, u64)> = vec![];
+    /// Record grants pinned to a version (record_id, accessor, version)
+    pub static Pins: Vec<(H256, u64, u32)> = vec![];
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+/// Access check backed by the `Grants` and `Pins` lists, matching both record and key grants
+pub struct TestAccess;
+impl pallet_health_records::RecordAccess<u64> for TestAccess {
+    fn has_access(
+        record_id: &H256,
+        accessor: &u64,
+        version: u32,
+        encryption_key_id: Option<&H256>,
+        now: u64,
+    ) -> bool {
+        let pinned = Pins::get()
+            .into_iter()
+            .find(|(id, who, _)| id == record_id && who == accessor)
+            .map(|(.., pinned)| pinned);
+        Grants::get().iter().any(|(id, who, expires_at)| {
+            let granted = (id == record_id && pinned.map_or(true, |pinned| pinned == version))
+                || Some(id) == encryption_key_id;
+            granted && who == accessor && *expires_at > now
+        })
+    }
+}
+
+impl pallet_health_records::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxRecordsPerPatient = ConstU32<100>;
+    type AccessLogPageSize = ConstU32<3>;
+    type AccessCheck = TestAccess;
+    type MigrationOrigin = frame_system::EnsureRoot<u64>;
+    type MaxMigrationBatch = ConstU32<2>;
+    type ErasureOrigin = frame_system::EnsureRoot<u64>;
+    type MaxErasureBatch = ConstU32<2>;
+    type MaxVersionErasureBatch = ConstU32<4>;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestAccess;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_health_records::BenchmarkHelper<u64> for TestAccess {
+    fn grant_access(record_id: &H256, encryption_key_id: Option<&H256>, accessor: &u64) {
+        grant(*encryption_key_id.unwrap_or(record_id), *accessor, u64::MAX);
+    }
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_timestamp::GenesisConfig::<Test> { now: 0 }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000);
+        Grants::set(vec![]);
+        Pins::set(vec![]);
+    });
+    ext
+}
+
+// Helper function to upload a lab results record, returning its ID
+pub fn upload(patient: u64, encryption_key_id: Option<H256>) -> H256 {
+    frame_support::assert_ok!(HealthRecords::upload_record(
+        RuntimeOrigin::signed(patient),
+        BoundedVec::try_from(b"QmHash".to_vec()).unwrap(),
+        pallet_health_records::RecordCategory::LabResults,
+        pallet_health_records::DataFormat::FHIR,
+        BoundedVec::try_from(b"Blood panel".to_vec()).unwrap(),
+        1024,
+        encryption_key_id,
+    ));
+
+    *HealthRecords::patient_records(patient).last().unwrap()
+}
+
+// Helper function to grant access on a record or key until `expires_at`
+pub fn grant(id: H256, accessor: u64, expires_at: u64) {
+    Grants::mutate(|grants| grants.push((id, accessor, expires_at)));
+}
+
+// Helper function to pin a record grant to a version
+pub fn pin(record_id: H256, accessor: u64, version: u32) {
+    Pins::mutate(|pins| pins.push((record_id, accessor, version)));
+}
+
+// Helper function to add a version of a record with a new IPFS hash and encryption key
+pub fn amend(patient: u64, record_id: H256, ipfs_hash: &str, encryption_key_id: Option<H256>) {
+    frame_support::assert_ok!(HealthRecords::amend_record(
+        RuntimeOrigin::signed(patient),
+        record_id,
+        BoundedVec::try_from(ipfs_hash.as_bytes().to_vec()).unwrap(),
+        2048,
+        encryption_key_id,
+        BoundedVec::try_from(b"Corrected result".to_vec()).unwrap(),
+    ));
+}
+
+// Helper function to create an access purpose
+pub fn purpose(purpose: &str) -> BoundedVec<u8, ConstU32<64>> {
+    BoundedVec::try_from(purpose.as_bytes().to_vec()).unwrap()
+}
@@ -0,0 +1,536 @@
+//! Tests for health-records pallet
+
+use crate::{
+    migrations::{v0, v1::MigrateToV1},
+    mock::*,
+    AccessDenial, AccessLog, ErasureProgress, Error, Event, LogIndex,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+#[test]
+fn patient_can_log_access() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let record_id = upload(patient, None);
+
+        assert_ok!(HealthRecords::log_access(
+            RuntimeOrigin::signed(patient),
+            record_id,
+            purpose("review"),
+        ));
+
+        let record = HealthRecords::health_records(record_id).unwrap();
+        assert_eq!(record.access_count, 1);
+        assert_eq!(record.last_accessed, Some(1_000));
+
+        let logs = HealthRecords::access_log_page(&record_id, 0);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].accessor, patient);
+        assert_eq!(logs[0].purpose, purpose("review"));
+
+        System::assert_last_event(Event::RecordAccessed { record_id, accessor: patient }.into());
+    });
+}
+
+#[test]
+fn grantee_can_log_access() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let record_id = upload(patient, None);
+        grant(record_id, researcher, 5_000);
+
+        assert_ok!(HealthRecords::log_access(
+            RuntimeOrigin::signed(researcher),
+            record_id,
+            purpose("study"),
+        ));
+
+        assert_eq!(HealthRecords::health_records(record_id).unwrap().access_count, 1);
+        assert_eq!(HealthRecords::access_log_page(&record_id, 0)[0].accessor, researcher);
+        assert!(HealthRecords::access_denials(record_id, researcher).is_none());
+    });
+}
+
+#[test]
+fn key_grantee_can_read_record() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let key_id = H256::repeat_byte(9);
+        let record_id = upload(patient, Some(key_id));
+
+        // Access to the encryption key is enough
+        grant(key_id, researcher, 5_000);
+
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(researcher), record_id));
+
+        let logs = HealthRecords::access_log_page(&record_id, 0);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].purpose, purpose("read"));
+        System::assert_last_event(Event::RecordRead { record_id, accessor: researcher }.into());
+    });
+}
+
+#[test]
+fn stranger_access_is_stored_as_denial() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let stranger = 3u64;
+        let record_id = upload(patient, None);
+
+        assert_ok!(HealthRecords::log_access(
+            RuntimeOrigin::signed(stranger),
+            record_id,
+            purpose("snoop"),
+        ));
+        System::assert_last_event(Event::AccessDenied { record_id, accessor: stranger }.into());
+
+        Timestamp::set_timestamp(2_000);
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(stranger), record_id));
+
+        // Nothing was logged as an access
+        let record = HealthRecords::health_records(record_id).unwrap();
+        assert_eq!(record.access_count, 0);
+        assert_eq!(record.last_accessed, None);
+        assert!(HealthRecords::access_log_page(&record_id, 0).is_empty());
+
+        assert_eq!(
+            HealthRecords::access_denials(record_id, stranger),
+            Some(AccessDenial { attempts: 2, first_attempt: 1_000, last_attempt: 2_000 })
+        );
+    });
+}
+
+#[test]
+fn expired_grant_is_denied() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let record_id = upload(patient, None);
+        grant(record_id, researcher, 1_000);
+
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(researcher), record_id));
+
+        assert!(HealthRecords::access_log_page(&record_id, 0).is_empty());
+        assert_eq!(HealthRecords::access_denials(record_id, researcher).unwrap().attempts, 1);
+    });
+}
+
+#[test]
+fn grant_on_another_record_is_denied() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let record_id = upload(patient, None);
+        let other_record = upload(patient, None);
+        grant(other_record, researcher, 5_000);
+
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(researcher), record_id));
+
+        assert!(HealthRecords::access_log_page(&record_id, 0).is_empty());
+        assert!(HealthRecords::access_denials(record_id, researcher).is_some());
+    });
+}
+
+#[test]
+fn access_fails_for_unknown_or_deactivated_record() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+
+        assert_noop!(
+            HealthRecords::read_record(RuntimeOrigin::signed(patient), H256::zero()),
+            Error::<Test>::RecordNotFound
+        );
+
+        let record_id = upload(patient, None);
+        assert_ok!(HealthRecords::deactivate_record(RuntimeOrigin::signed(patient), record_id));
+
+        assert_noop!(
+            HealthRecords::log_access(RuntimeOrigin::signed(patient), record_id, purpose("x")),
+            Error::<Test>::RecordDeactivated
+        );
+    });
+}
+
+#[test]
+fn access_logs_continue_on_a_new_page() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let record_id = upload(patient, None);
+
+        // Pages hold three entries in the mock
+        for _ in 0..7 {
+            assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(patient), record_id));
+        }
+
+        assert_eq!(
+            HealthRecords::access_log_index(record_id),
+            LogIndex { first_page: 0, current_page: 2, total: 7 }
+        );
+        assert_eq!(HealthRecords::access_log_page(&record_id, 0).len(), 3);
+        assert_eq!(HealthRecords::access_log_page(&record_id, 1).len(), 3);
+        assert_eq!(HealthRecords::access_log_page(&record_id, 2).len(), 1);
+        assert_eq!(HealthRecords::health_records(record_id).unwrap().access_count, 7);
+
+        assert_eq!(HealthRecords::recent_access_logs(&record_id, 5).len(), 5);
+        assert_eq!(HealthRecords::recent_access_logs(&record_id, 50).len(), 7);
+    });
+}
+
+#[test]
+fn recent_access_logs_are_newest_first() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let record_id = upload(patient, None);
+
+        for (i, p) in ["a", "b", "c", "d"].iter().enumerate() {
+            Timestamp::set_timestamp(1_000 + i as u64);
+            assert_ok!(HealthRecords::log_access(
+                RuntimeOrigin::signed(patient),
+                record_id,
+                purpose(p),
+            ));
+        }
+
+        let logs = HealthRecords::recent_access_logs(&record_id, 2);
+        assert_eq!(logs[0].purpose, purpose("d"));
+        assert_eq!(logs[1].purpose, purpose("c"));
+    });
+}
+
+#[test]
+fn archive_access_logs_works() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let record_id = upload(patient, None);
+
+        for _ in 0..4 {
+            assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(patient), record_id));
+        }
+
+        let page = HealthRecords::access_logs(record_id, 0);
+        let page_hash = BlakeTwo256::hash_of(&page);
+
+        assert_ok!(HealthRecords::archive_access_logs(RuntimeOrigin::signed(patient), record_id));
+
+        assert!(HealthRecords::access_log_page(&record_id, 0).is_empty());
+        assert_eq!(HealthRecords::archived_access_logs(record_id, 0), Some(page_hash));
+        assert_eq!(HealthRecords::access_log_index(record_id).first_page, 1);
+        System::assert_last_event(
+            Event::AccessLogArchived { record_id, page: 0, page_hash }.into(),
+        );
+
+        // Archived entries are no longer returned
+        assert_eq!(HealthRecords::recent_access_logs(&record_id, 10).len(), 1);
+    });
+}
+
+#[test]
+fn archive_access_logs_keeps_current_page() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let stranger = 3u64;
+        let record_id = upload(patient, None);
+
+        for _ in 0..3 {
+            assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(patient), record_id));
+        }
+
+        // The only page is still the one being written to
+        assert_noop!(
+            HealthRecords::archive_access_logs(RuntimeOrigin::signed(patient), record_id),
+            Error::<Test>::NothingToArchive
+        );
+
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(patient), record_id));
+        assert_noop!(
+            HealthRecords::archive_access_logs(RuntimeOrigin::signed(stranger), record_id),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn migrate_patient_moves_records_in_batches() {
+    new_test_ext().execute_with(|| {
+        let (patient, new_account) = (1u64, 9u64);
+        let records: Vec<H256> = (0..3).map(|_| upload(patient, None)).collect();
+
+        // Restricted to the migration origin
+        assert_noop!(
+            HealthRecords::migrate_patient(RuntimeOrigin::signed(patient), patient, new_account),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(HealthRecords::migrate_patient(RuntimeOrigin::root(), patient, new_account));
+        System::assert_last_event(
+            Event::RecordsMigrated { old: patient, new: new_account, records: 2 }.into(),
+        );
+        assert_eq!(HealthRecords::patient_records(new_account).to_vec(), records[..2].to_vec());
+        assert_eq!(HealthRecords::patient_migrations(patient), Some(new_account));
+        assert_noop!(
+            HealthRecords::migrate_patient(RuntimeOrigin::root(), patient, new_account),
+            Error::<Test>::MigrationInProgress
+        );
+
+        // Anyone can move the rest
+        assert_ok!(HealthRecords::continue_migration(RuntimeOrigin::signed(3), patient));
+        System::assert_last_event(Event::PatientMigrated { old: patient, new: new_account }.into());
+        assert_eq!(HealthRecords::patient_records(new_account).to_vec(), records);
+        assert!(HealthRecords::patient_records(patient).is_empty());
+        assert_eq!(HealthRecords::patient_migrations(patient), None);
+
+        // The new account owns the records
+        for record_id in records {
+            assert_eq!(HealthRecords::health_records(record_id).unwrap().patient, new_account);
+        }
+        assert_noop!(
+            HealthRecords::continue_migration(RuntimeOrigin::signed(3), patient),
+            Error::<Test>::NoMigrationPending
+        );
+    });
+}
+
+#[test]
+fn erase_patient_wipes_records_in_batches() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let records: Vec<H256> = (0..3).map(|_| upload(patient, None)).collect();
+
+        // Restricted to the erasure origin
+        assert_noop!(
+            HealthRecords::erase_patient(RuntimeOrigin::signed(patient), patient),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(HealthRecords::erase_patient(RuntimeOrigin::root(), patient));
+        System::assert_last_event(Event::RecordsErased { patient, records: 2 }.into());
+        assert_eq!(
+            HealthRecords::patient_erasures(patient),
+            Some(ErasureProgress { records: 2, versions: 0 })
+        );
+        assert!(HealthRecords::health_records(records[2]).unwrap().active);
+        assert_noop!(
+            HealthRecords::erase_patient(RuntimeOrigin::root(), patient),
+            Error::<Test>::ErasureInProgress
+        );
+
+        // Anyone can erase the rest
+        assert_ok!(HealthRecords::continue_erasure(RuntimeOrigin::signed(3), patient));
+        System::assert_last_event(Event::PatientErased { patient }.into());
+        assert_eq!(HealthRecords::patient_erasures(patient), None);
+
+        // Records are kept as deactivated tombstones
+        for record_id in records {
+            let record = HealthRecords::health_records(record_id).unwrap();
+            assert!(!record.active);
+            assert!(record.title.is_empty());
+            assert!(record.ipfs_hash.is_empty());
+            assert!(HealthRecords::get_record_versions(&record_id, 1, 10).is_empty());
+        }
+        assert_noop!(
+            HealthRecords::continue_erasure(RuntimeOrigin::signed(3), patient),
+            Error::<Test>::NoErasurePending
+        );
+    });
+}
+
+#[test]
+fn amend_record_keeps_every_version() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let record_id = upload(patient, Some(H256::repeat_byte(1)));
+        let reason = || BoundedVec::try_from(b"Corrected result".to_vec()).unwrap();
+
+        // Only the patient can amend, with a reason
+        assert_noop!(
+            HealthRecords::amend_record(
+                RuntimeOrigin::signed(2),
+                record_id,
+                BoundedVec::try_from(b"QmFixed".to_vec()).unwrap(),
+                2048,
+                None,
+                reason(),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            HealthRecords::amend_record(
+                RuntimeOrigin::signed(patient),
+                record_id,
+                BoundedVec::try_from(b"QmFixed".to_vec()).unwrap(),
+                2048,
+                None,
+                BoundedVec::new(),
+            ),
+            Error::<Test>::InvalidReason
+        );
+
+        amend(patient, record_id, "QmFixed", Some(H256::repeat_byte(2)));
+        System::assert_last_event(Event::RecordAmended { record_id, version: 2 }.into());
+
+        // The record describes the latest version under the same ID
+        let record = HealthRecords::health_records(record_id).unwrap();
+        assert_eq!(record.ipfs_hash.to_vec(), b"QmFixed".to_vec());
+        assert_eq!(record.file_size, 2048);
+        assert_eq!(record.encryption_key_id, Some(H256::repeat_byte(2)));
+        assert_eq!(HealthRecords::latest_version(record_id), 2);
+
+        let versions = HealthRecords::get_record_versions(&record_id, 1, 10);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].0, 1);
+        assert_eq!(versions[0].1.ipfs_hash.to_vec(), b"QmHash".to_vec());
+        assert_eq!(versions[0].1.encryption_key_id, Some(H256::repeat_byte(1)));
+        assert!(versions[0].1.reason.is_empty());
+        assert_eq!(versions[1].0, 2);
+        assert_eq!(versions[1].1.reason, reason());
+
+        // There is no cap on the number of versions; the history is read a page at a time
+        for _ in 0..20 {
+            amend(patient, record_id, "QmFixedAgain", None);
+        }
+        assert_eq!(HealthRecords::latest_version(record_id), 22);
+        let page: Vec<u32> = HealthRecords::get_record_versions(&record_id, 11, 5)
+            .into_iter()
+            .map(|(version, _)| version)
+            .collect();
+        assert_eq!(page, vec![11, 12, 13, 14, 15]);
+        assert_eq!(HealthRecords::get_record_versions(&record_id, 20, 5).len(), 3);
+    });
+}
+
+#[test]
+fn erasure_removes_long_version_histories_in_batches() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let long = upload(patient, None);
+        let short = upload(patient, None);
+        for _ in 0..5 {
+            amend(patient, long, "QmFixed", None);
+        }
+
+        // Four versions are removed per call in the mock
+        assert_ok!(HealthRecords::erase_patient(RuntimeOrigin::root(), patient));
+        System::assert_last_event(Event::RecordsErased { patient, records: 0 }.into());
+        assert_eq!(
+            HealthRecords::patient_erasures(patient),
+            Some(ErasureProgress { records: 0, versions: 4 })
+        );
+        assert!(!HealthRecords::health_records(long).unwrap().active);
+        assert_eq!(HealthRecords::get_record_versions(&long, 1, 10).len(), 2);
+
+        assert_ok!(HealthRecords::continue_erasure(RuntimeOrigin::signed(3), patient));
+        System::assert_has_event(Event::RecordsErased { patient, records: 2 }.into());
+        System::assert_last_event(Event::PatientErased { patient }.into());
+        assert!(HealthRecords::get_record_versions(&long, 1, 10).is_empty());
+        assert!(HealthRecords::get_record_versions(&short, 1, 10).is_empty());
+        assert_eq!(HealthRecords::latest_version(long), 6);
+    });
+}
+
+#[test]
+fn pinned_grant_reads_only_its_version() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let clinician = 3u64;
+        let record_id = upload(patient, None);
+        amend(patient, record_id, "QmFixed", None);
+        grant(record_id, researcher, 5_000);
+        grant(record_id, clinician, 5_000);
+        pin(record_id, researcher, 1);
+
+        // A pinned grant no longer covers the latest version
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(researcher), record_id));
+        System::assert_last_event(Event::AccessDenied { record_id, accessor: researcher }.into());
+
+        assert_ok!(HealthRecords::read_record_version(
+            RuntimeOrigin::signed(researcher),
+            record_id,
+            1,
+        ));
+        System::assert_last_event(
+            Event::RecordVersionRead { record_id, version: 1, accessor: researcher }.into(),
+        );
+        assert_ok!(HealthRecords::read_record_version(
+            RuntimeOrigin::signed(researcher),
+            record_id,
+            2,
+        ));
+        System::assert_last_event(Event::AccessDenied { record_id, accessor: researcher }.into());
+
+        // A grant following the latest version covers every version
+        assert_ok!(HealthRecords::read_record(RuntimeOrigin::signed(clinician), record_id));
+        assert_ok!(HealthRecords::read_record_version(
+            RuntimeOrigin::signed(clinician),
+            record_id,
+            1,
+        ));
+        assert_eq!(HealthRecords::access_denials(record_id, researcher).unwrap().attempts, 2);
+        assert_eq!(HealthRecords::health_records(record_id).unwrap().access_count, 3);
+
+        assert_noop!(
+            HealthRecords::read_record_version(RuntimeOrigin::signed(patient), record_id, 3),
+            Error::<Test>::VersionNotFound
+        );
+    });
+}
+
+#[test]
+fn migration_to_v1_moves_access_logs_into_pages() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<HealthRecords>();
+        let record_id = H256::repeat_byte(1);
+        let logs: Vec<AccessLog<Test>> = (0..5u64)
+            .map(|i| AccessLog {
+                record_id,
+                accessor: 2,
+                accessed_at: 1000 + i,
+                purpose: purpose("review"),
+            })
+            .collect();
+        v0::AccessLogs::<Test>::insert(record_id, logs.clone());
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(HealthRecords::on_chain_storage_version(), 1);
+        assert_eq!(
+            HealthRecords::access_log_index(record_id),
+            LogIndex { first_page: 0, current_page: 1, total: 5 }
+        );
+        assert_eq!(HealthRecords::access_log_page(&record_id, 0), logs[..3].to_vec());
+        assert_eq!(HealthRecords::access_log_page(&record_id, 1), logs[3..].to_vec());
+
+        // The newest migrated entry is read first
+        assert_eq!(HealthRecords::recent_access_logs(&record_id, 1)[0].accessed_at, 1004);
+    });
+}
+
+#[test]
+fn migration_to_v1_stores_existing_records_as_version_1() {
+    new_test_ext().execute_with(|| {
+        let record_id = upload(1, Some(H256::repeat_byte(1)));
+
+        // Records of version 0 have no versions
+        StorageVersion::new(0).put::<HealthRecords>();
+        crate::RecordVersions::<Test>::remove(record_id, 1);
+        crate::LatestVersions::<Test>::remove(record_id);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let record = HealthRecords::health_records(record_id).unwrap();
+        let version = HealthRecords::record_versions(record_id, 1).unwrap();
+        assert_eq!(HealthRecords::latest_version(record_id), 1);
+        assert_eq!(version.ipfs_hash, record.ipfs_hash);
+        assert_eq!(version.encryption_key_id, Some(H256::repeat_byte(1)));
+        assert_eq!(version.created_at, record.uploaded_at);
+        assert!(version.reason.is_empty());
+    });
+}
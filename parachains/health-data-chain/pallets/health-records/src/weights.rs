@@ -0,0 +1,236 @@
+//! Estimated weights for `pallet_health_records`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/health-data-node benchmark pallet \
+//!     --chain dev --pallet pallet_health_records --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/health-records/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_health_records`.
+pub trait WeightInfo {
+    fn upload_record() -> Weight;
+    fn update_record() -> Weight;
+    fn deactivate_record() -> Weight;
+    fn log_access() -> Weight;
+    fn read_record() -> Weight;
+    fn archive_access_logs() -> Weight;
+    fn migrate_patient() -> Weight;
+    fn continue_migration() -> Weight;
+    fn erase_patient() -> Weight;
+    fn continue_erasure() -> Weight;
+    fn amend_record() -> Weight;
+    fn read_record_version() -> Weight;
+}
+
+/// Estimated weights for `pallet_health_records`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Timestamp::Now`, `HealthRecords::RecordCount`, `HealthRecords::PatientRecords`
+    // Writes: `HealthRecords::RecordCount`, `HealthRecords::PatientRecords`,
+    //     `HealthRecords::HealthRecords`, `HealthRecords::RecordVersions`,
+    //     `HealthRecords::LatestVersions`
+    fn upload_record() -> Weight {
+        Weight::from_parts(104_000_000, 321_529)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`
+    // Writes: `HealthRecords::HealthRecords`
+    fn update_record() -> Weight {
+        Weight::from_parts(18_000_000, 3_839)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`
+    // Writes: `HealthRecords::HealthRecords`
+    fn deactivate_record() -> Weight {
+        Weight::from_parts(17_000_000, 3_839)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`, `HealthRecords::LatestVersions`, `Timestamp::Now`,
+    //     `AccessControl::AccessGrants`, `AccessControl::GrantVersions`,
+    //     `Encryption::EncryptionKeys`, `Encryption::KeyAccessGrants`,
+    //     `HealthRecords::AccessLogIndex`, `HealthRecords::AccessLogs`
+    // Writes: `HealthRecords::HealthRecords`, `HealthRecords::AccessLogIndex`,
+    //     `HealthRecords::AccessLogs`
+    fn log_access() -> Weight {
+        Weight::from_parts(53_000_000, 17_239)
+            .saturating_add(T::DbWeight::get().reads(9_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`, `HealthRecords::LatestVersions`, `Timestamp::Now`,
+    //     `AccessControl::AccessGrants`, `AccessControl::GrantVersions`,
+    //     `Encryption::EncryptionKeys`, `Encryption::KeyAccessGrants`,
+    //     `HealthRecords::AccessLogIndex`, `HealthRecords::AccessLogs`
+    // Writes: `HealthRecords::HealthRecords`, `HealthRecords::AccessLogIndex`,
+    //     `HealthRecords::AccessLogs`
+    fn read_record() -> Weight {
+        Weight::from_parts(51_000_000, 17_239)
+            .saturating_add(T::DbWeight::get().reads(9_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`, `HealthRecords::AccessLogIndex`,
+    //     `HealthRecords::AccessLogs`
+    // Writes: `HealthRecords::AccessLogIndex`, `HealthRecords::AccessLogs`,
+    //     `HealthRecords::ArchivedAccessLogs`
+    fn archive_access_logs() -> Weight {
+        Weight::from_parts(58_000_000, 17_239)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `HealthRecords::PatientMigrations`, `HealthRecords::PatientRecords` x2,
+    //     `HealthRecords::HealthRecords` x100
+    // Writes: `HealthRecords::PatientMigrations`, `HealthRecords::PatientRecords` x2,
+    //     `HealthRecords::HealthRecords` x100
+    fn migrate_patient() -> Weight {
+        Weight::from_parts(412_000_000, 320_114)
+            .saturating_add(T::DbWeight::get().reads(103_u64))
+            .saturating_add(T::DbWeight::get().writes(103_u64))
+    }
+
+    // Reads: `HealthRecords::PatientMigrations`, `HealthRecords::PatientRecords` x2,
+    //     `HealthRecords::HealthRecords` x100
+    // Writes: `HealthRecords::PatientMigrations`, `HealthRecords::PatientRecords` x2,
+    //     `HealthRecords::HealthRecords` x100
+    fn continue_migration() -> Weight {
+        Weight::from_parts(409_000_000, 320_114)
+            .saturating_add(T::DbWeight::get().reads(103_u64))
+            .saturating_add(T::DbWeight::get().writes(103_u64))
+    }
+
+    // Reads: `HealthRecords::PatientErasures`, `HealthRecords::PatientRecords`,
+    //     `HealthRecords::HealthRecords` x100, `HealthRecords::LatestVersions` x100
+    // Writes: `HealthRecords::PatientErasures`, `HealthRecords::HealthRecords` x100,
+    //     `HealthRecords::RecordVersions` x500
+    fn erase_patient() -> Weight {
+        Weight::from_parts(352_000_000, 575_314)
+            .saturating_add(T::DbWeight::get().reads(202_u64))
+            .saturating_add(T::DbWeight::get().writes(601_u64))
+    }
+
+    // Reads: `HealthRecords::PatientErasures`, `HealthRecords::PatientRecords`,
+    //     `HealthRecords::HealthRecords` x100, `HealthRecords::LatestVersions` x100
+    // Writes: `HealthRecords::PatientErasures`, `HealthRecords::HealthRecords` x100,
+    //     `HealthRecords::RecordVersions` x500
+    fn continue_erasure() -> Weight {
+        Weight::from_parts(355_000_000, 575_314)
+            .saturating_add(T::DbWeight::get().reads(202_u64))
+            .saturating_add(T::DbWeight::get().writes(601_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`, `HealthRecords::LatestVersions`, `Timestamp::Now`
+    // Writes: `HealthRecords::HealthRecords`, `HealthRecords::LatestVersions`,
+    //     `HealthRecords::RecordVersions`
+    fn amend_record() -> Weight {
+        Weight::from_parts(34_000_000, 3_839)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `HealthRecords::HealthRecords`, `HealthRecords::RecordVersions`, `Timestamp::Now`,
+    //     `AccessControl::AccessGrants`, `AccessControl::GrantVersions`,
+    //     `Encryption::EncryptionKeys`, `Encryption::KeyAccessGrants`,
+    //     `HealthRecords::AccessLogIndex`, `HealthRecords::AccessLogs`
+    // Writes: `HealthRecords::HealthRecords`, `HealthRecords::AccessLogIndex`,
+    //     `HealthRecords::AccessLogs`
+    fn read_record_version() -> Weight {
+        Weight::from_parts(55_000_000, 17_239)
+            .saturating_add(T::DbWeight::get().reads(9_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn upload_record() -> Weight {
+        Weight::from_parts(104_000_000, 321_529)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn update_record() -> Weight {
+        Weight::from_parts(18_000_000, 3_839)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn deactivate_record() -> Weight {
+        Weight::from_parts(17_000_000, 3_839)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn log_access() -> Weight {
+        Weight::from_parts(53_000_000, 17_239)
+            .saturating_add(RocksDbWeight::get().reads(9_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn read_record() -> Weight {
+        Weight::from_parts(51_000_000, 17_239)
+            .saturating_add(RocksDbWeight::get().reads(9_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn archive_access_logs() -> Weight {
+        Weight::from_parts(58_000_000, 17_239)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn migrate_patient() -> Weight {
+        Weight::from_parts(412_000_000, 320_114)
+            .saturating_add(RocksDbWeight::get().reads(103_u64))
+            .saturating_add(RocksDbWeight::get().writes(103_u64))
+    }
+
+    fn continue_migration() -> Weight {
+        Weight::from_parts(409_000_000, 320_114)
+            .saturating_add(RocksDbWeight::get().reads(103_u64))
+            .saturating_add(RocksDbWeight::get().writes(103_u64))
+    }
+
+    fn erase_patient() -> Weight {
+        Weight::from_parts(352_000_000, 575_314)
+            .saturating_add(RocksDbWeight::get().reads(202_u64))
+            .saturating_add(RocksDbWeight::get().writes(601_u64))
+    }
+
+    fn continue_erasure() -> Weight {
+        Weight::from_parts(355_000_000, 575_314)
+            .saturating_add(RocksDbWeight::get().reads(202_u64))
+            .saturating_add(RocksDbWeight::get().writes(601_u64))
+    }
+
+    fn amend_record() -> Weight {
+        Weight::from_parts(34_000_000, 3_839)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn read_record_version() -> Weight {
+        Weight::from_parts(55_000_000, 17_239)
+            .saturating_add(RocksDbWeight::get().reads(9_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+}
@@ -0,0 +1,138 @@
+//! Benchmarking setup for pallet-ipfs-integration
+
+use super::*;
+
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::ConstU32, BoundedVec};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+/// A full-length content hash derived from `seed`
+fn content_hash(seed: u32) -> BoundedVec<u8, ConstU32<64>> {
+    let mut hash = b"Qm".to_vec();
+    hash.resize(60, b'x');
+    hash.extend_from_slice(&seed.encode());
+    BoundedVec::truncate_from(hash)
+}
+
+/// A node with full-length identifiers derived from `seed`
+fn node(seed: u32) -> IPFSNode {
+    let mut peer_id = b"12D3KooW".to_vec();
+    peer_id.resize(60, b'p');
+    peer_id.extend_from_slice(&seed.encode());
+
+    IPFSNode {
+        multiaddr: BoundedVec::truncate_from(vec![b'm'; 256]),
+        peer_id: BoundedVec::truncate_from(peer_id),
+        active: true,
+    }
+}
+
+/// Fill `owner`'s content list up to its bound, pinning the items from `first` on for a
+/// full erasure batch
+fn fill_content<T: Config>(owner: &T::AccountId, first: u32) {
+    let hashes: Vec<_> = (0..10_000).map(content_hash).collect();
+    for ipfs_hash in hashes.iter().skip(first as usize).take(T::MaxErasureBatch::get() as usize) {
+        IPFSContents::<T>::insert(
+            ipfs_hash,
+            IPFSContent {
+                ipfs_hash: ipfs_hash.clone(),
+                owner: owner.clone(),
+                size: 1024,
+                status: ContentStatus::Pinned,
+                pinned_at: Some(0),
+                unpinned_at: None,
+                pin_count: 1,
+            },
+        );
+    }
+    OwnerContent::<T>::insert(owner, BoundedVec::truncate_from(hashes));
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn pin_content() {
+        let owner: T::AccountId = whitelisted_caller();
+
+        // Fill the owner's content list up to one below its bound
+        let existing: Vec<_> = (1..10_000).map(content_hash).collect();
+        OwnerContent::<T>::insert(&owner, BoundedVec::truncate_from(existing));
+        let ipfs_hash = content_hash(0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), ipfs_hash.clone(), 1024);
+
+        assert!(Pallet::<T>::is_pinned(&ipfs_hash));
+    }
+
+    #[benchmark]
+    fn unpin_content() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ipfs_hash = content_hash(0);
+        Pallet::<T>::pin_content(RawOrigin::Signed(owner.clone()).into(), ipfs_hash.clone(), 1024)
+            .expect("pinning must succeed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ipfs_hash.clone());
+
+        assert!(!Pallet::<T>::is_pinned(&ipfs_hash));
+    }
+
+    #[benchmark]
+    fn add_node() {
+        let nodes: Vec<_> = (1..100).map(node).collect();
+        IPFSNodes::<T>::put(BoundedVec::truncate_from(nodes));
+        let new_node = node(0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, new_node.multiaddr, new_node.peer_id);
+
+        assert_eq!(IPFSNodes::<T>::get().len(), 100);
+    }
+
+    #[benchmark]
+    fn remove_node() {
+        // The removed node is the last one, so the whole list is scanned
+        let nodes: Vec<_> = (0..100).map(node).collect();
+        IPFSNodes::<T>::put(BoundedVec::truncate_from(nodes));
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, node(99).peer_id);
+
+        assert_eq!(IPFSNodes::<T>::get().len(), 99);
+    }
+
+    #[benchmark]
+    fn erase_owner_content() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ErasureOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let owner: T::AccountId = account("owner", 0, 0);
+        fill_content::<T>(&owner, 0);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, owner.clone());
+
+        assert_eq!(OwnerErasures::<T>::get(&owner), Some(T::MaxErasureBatch::get()));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn continue_erasure() {
+        // The last batch is left
+        let owner: T::AccountId = account("owner", 0, 0);
+        let processed = 10_000 - T::MaxErasureBatch::get();
+        fill_content::<T>(&owner, processed);
+        OwnerErasures::<T>::insert(&owner, processed);
+        let caller: T::AccountId = account("caller", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), owner.clone());
+
+        assert!(!OwnerErasures::<T>::contains_key(&owner));
+    }
+}
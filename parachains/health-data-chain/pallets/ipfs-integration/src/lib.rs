@@ -9,6 +9,13 @@
 //! - IPFS node configuration
 //! - Content availability tracking
 //!
+//! ## Erasure
+//!
+//! When a patient exercises the right to erasure on the IdentityConsent chain, that chain calls
+//! `erase_owner_content` over XCM (`ErasureOrigin`) to unpin all of the patient's content,
+//! `MaxErasureBatch` items at a time; further batches are unpinned by anyone with
+//! `continue_erasure`. Each item emits `ContentUnpinned`, on which pinning services drop it.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "HealthData Chain - IPFS Integration"
 
@@ -22,12 +29,19 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -89,6 +103,16 @@ pub mod pallet {
         /// Maximum number of IPFS nodes
         #[pallet::constant]
         type MaxNodes: Get<u32>;
+
+        /// Origin erasing an owner's content (the IdentityConsent chain)
+        type ErasureOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of content items unpinned per erasure call
+        #[pallet::constant]
+        type MaxErasureBatch: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     /// Storage for IPFS content by hash
@@ -117,6 +141,11 @@ pub mod pallet {
     #[pallet::getter(fn ipfs_nodes)]
     pub type IPFSNodes<T: Config> = StorageValue<_, BoundedVec<IPFSNode, ConstU32<100>>, ValueQuery>;
 
+    /// Owners whose content is being erased (owner -> number of items processed so far)
+    #[pallet::storage]
+    #[pallet::getter(fn owner_erasures)]
+    pub type OwnerErasures<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -143,6 +172,13 @@ pub mod pallet {
         NodeRemoved {
             peer_id: BoundedVec<u8, ConstU32<64>>,
         },
+        /// Batch of an owner's content unpinned for erasure [owner, contents]
+        ContentErased {
+            owner: T::AccountId,
+            contents: u32,
+        },
+        /// All of an owner's content unpinned for erasure [owner]
+        OwnerErased { owner: T::AccountId },
     }
 
     #[pallet::error]
@@ -165,6 +201,10 @@ pub mod pallet {
         InvalidMultiaddr,
         /// Invalid peer ID
         InvalidPeerId,
+        /// Content of this owner is already being erased
+        ErasureInProgress,
+        /// No erasure of this owner's content is pending
+        NoErasurePending,
     }
 
     #[pallet::call]
@@ -176,7 +216,7 @@ pub mod pallet {
         /// - `ipfs_hash`: IPFS content hash (CID)
         /// - `size`: Content size in bytes
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::pin_content())]
         pub fn pin_content(
             origin: OriginFor<T>,
             ipfs_hash: BoundedVec<u8, ConstU32<64>>,
@@ -231,7 +271,7 @@ pub mod pallet {
 
         /// Unpin IPFS content
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::unpin_content())]
         pub fn unpin_content(
             origin: OriginFor<T>,
             ipfs_hash: BoundedVec<u8, ConstU32<64>>,
@@ -264,7 +304,7 @@ pub mod pallet {
 
         /// Add IPFS node to network
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::add_node())]
         pub fn add_node(
             origin: OriginFor<T>,
             multiaddr: BoundedVec<u8, ConstU32<256>>,
@@ -293,7 +333,7 @@ pub mod pallet {
 
         /// Remove IPFS node from network
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::remove_node())]
         pub fn remove_node(
             origin: OriginFor<T>,
             peer_id: BoundedVec<u8, ConstU32<64>>,
@@ -310,10 +350,75 @@ pub mod pallet {
                 }
             })
         }
+
+        /// Unpin an owner's content after a right-to-erasure request
+        ///
+        /// Unpins the first `MaxErasureBatch` items; the rest are unpinned with
+        /// `continue_erasure`.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::erase_owner_content())]
+        pub fn erase_owner_content(origin: OriginFor<T>, owner: T::AccountId) -> DispatchResult {
+            T::ErasureOrigin::ensure_origin(origin)?;
+
+            ensure!(!OwnerErasures::<T>::contains_key(&owner), Error::<T>::ErasureInProgress);
+
+            Self::erase_content(&owner, 0);
+            Ok(())
+        }
+
+        /// Unpin the next batch of content of a pending erasure
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::continue_erasure())]
+        pub fn continue_erasure(origin: OriginFor<T>, owner: T::AccountId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let processed = OwnerErasures::<T>::get(&owner).ok_or(Error::<T>::NoErasurePending)?;
+            Self::erase_content(&owner, processed);
+            Ok(())
+        }
     }
 
     // Helper functions
     impl<T: Config> Pallet<T> {
+        /// Unpin the batch of content following the first `processed` items, completing the
+        /// erasure once none are left
+        fn erase_content(owner: &T::AccountId, processed: u32) {
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let hashes = OwnerContent::<T>::get(owner);
+            let batch: Vec<_> = hashes
+                .iter()
+                .skip(processed as usize)
+                .take(T::MaxErasureBatch::get() as usize)
+                .cloned()
+                .collect();
+
+            let mut unpinned = 0u32;
+            for ipfs_hash in batch.iter() {
+                IPFSContents::<T>::mutate(ipfs_hash, |maybe_content| {
+                    let Some(content) = maybe_content else { return };
+                    if content.status != ContentStatus::Unpinned {
+                        content.status = ContentStatus::Unpinned;
+                        content.unpinned_at = Some(now);
+                        unpinned = unpinned.saturating_add(1);
+                        Self::deposit_event(Event::ContentUnpinned {
+                            ipfs_hash: ipfs_hash.clone(),
+                            owner: owner.clone(),
+                        });
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::ContentErased { owner: owner.clone(), contents: unpinned });
+
+            let processed = processed.saturating_add(batch.len() as u32);
+            if processed as usize >= hashes.len() {
+                OwnerErasures::<T>::remove(owner);
+                Self::deposit_event(Event::OwnerErased { owner: owner.clone() });
+            } else {
+                OwnerErasures::<T>::insert(owner, processed);
+            }
+        }
+
         /// Get all content for an owner
         pub fn get_owner_content(owner: &T::AccountId) -> Vec<IPFSContent<T>> {
             let content_hashes = OwnerContent::<T>::get(owner);
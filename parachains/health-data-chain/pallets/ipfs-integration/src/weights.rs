@@ -0,0 +1,123 @@
+//! Estimated weights for `pallet_ipfs_integration`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/health-data-node benchmark pallet \
+//!     --chain dev --pallet pallet_ipfs_integration --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/ipfs-integration/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_ipfs_integration`.
+pub trait WeightInfo {
+    fn pin_content() -> Weight;
+    fn unpin_content() -> Weight;
+    fn add_node() -> Weight;
+    fn remove_node() -> Weight;
+    fn erase_owner_content() -> Weight;
+    fn continue_erasure() -> Weight;
+}
+
+/// Estimated weights for `pallet_ipfs_integration`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Timestamp::Now`, `IPFSIntegration::IPFSContents`, `IPFSIntegration::OwnerContent`
+    // Writes: `IPFSIntegration::IPFSContents`, `IPFSIntegration::OwnerContent`
+    fn pin_content() -> Weight {
+        Weight::from_parts(174_000_000, 650_529)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `IPFSIntegration::IPFSContents`, `Timestamp::Now`
+    // Writes: `IPFSIntegration::IPFSContents`
+    fn unpin_content() -> Weight {
+        Weight::from_parts(19_000_000, 3_692)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IPFSIntegration::IPFSNodes`
+    // Writes: `IPFSIntegration::IPFSNodes`
+    fn add_node() -> Weight {
+        Weight::from_parts(31_000_000, 33_214)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IPFSIntegration::IPFSNodes`
+    // Writes: `IPFSIntegration::IPFSNodes`
+    fn remove_node() -> Weight {
+        Weight::from_parts(34_000_000, 33_214)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `IPFSIntegration::OwnerContent`,
+    //     `IPFSIntegration::IPFSContents` x100, `IPFSIntegration::OwnerErasures`
+    // Writes: `IPFSIntegration::IPFSContents` x100, `IPFSIntegration::OwnerErasures`
+    fn erase_owner_content() -> Weight {
+        Weight::from_parts(1_214_000_000, 1_019_729)
+            .saturating_add(T::DbWeight::get().reads(103_u64))
+            .saturating_add(T::DbWeight::get().writes(101_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `IPFSIntegration::OwnerContent`,
+    //     `IPFSIntegration::IPFSContents` x100, `IPFSIntegration::OwnerErasures`
+    // Writes: `IPFSIntegration::IPFSContents` x100, `IPFSIntegration::OwnerErasures`
+    fn continue_erasure() -> Weight {
+        Weight::from_parts(1_217_000_000, 1_019_729)
+            .saturating_add(T::DbWeight::get().reads(103_u64))
+            .saturating_add(T::DbWeight::get().writes(101_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn pin_content() -> Weight {
+        Weight::from_parts(174_000_000, 650_529)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn unpin_content() -> Weight {
+        Weight::from_parts(19_000_000, 3_692)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn add_node() -> Weight {
+        Weight::from_parts(31_000_000, 33_214)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_node() -> Weight {
+        Weight::from_parts(34_000_000, 33_214)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn erase_owner_content() -> Weight {
+        Weight::from_parts(1_214_000_000, 1_019_729)
+            .saturating_add(RocksDbWeight::get().reads(103_u64))
+            .saturating_add(RocksDbWeight::get().writes(101_u64))
+    }
+
+    fn continue_erasure() -> Weight {
+        Weight::from_parts(1_217_000_000, 1_019_729)
+            .saturating_add(RocksDbWeight::get().reads(103_u64))
+            .saturating_add(RocksDbWeight::get().writes(101_u64))
+    }
+}
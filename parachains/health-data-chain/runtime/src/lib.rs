@@ -35,11 +35,15 @@ use sp_version::RuntimeVersion;
 
 // XCM imports
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
+use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
+use frame_support::traits::TransformOrigin;
+use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
+use polkadot_runtime_common::xcm_sender::NoPriceForMessageDelivery;
 use xcm::latest::prelude::*;
 use xcm_builder::{
     AccountId32Aliases, AllowUnpaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
     ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SignedAccountId32AsNative,
-    SovereignSignedViaLocation,
+    SovereignSignedViaLocation, XcmPassthrough,
 };
 use xcm_executor::XcmExecutor;
 
@@ -109,6 +113,12 @@ pub type SignedExtra = (
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+    pallet_health_records::migrations::v1::MigrateToV1<Runtime>,
+    pallet_access_control::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -116,6 +126,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
@@ -281,10 +292,10 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnSystemEvent = ();
     type SelfParaId = parachain_info::Pallet<Runtime>;
-    type OutboundXcmpMessageSource = ();
+    type OutboundXcmpMessageSource = XcmpQueue;
     type DmpMessageHandler = ();
     type ReservedDmpWeight = ReservedDmpWeight;
-    type XcmpMessageHandler = ();
+    type XcmpMessageHandler = XcmpQueue;
     type ReservedXcmpWeight = ReservedXcmpWeight;
     type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
     type WeightInfo = ();
@@ -312,14 +323,22 @@ pub type XcmOriginToCallOrigin = (
     RelayChainAsNative<RelayChainOrigin, RuntimeOrigin>,
     SiblingParachainAsNative<cumulus_pallet_xcm::Origin, RuntimeOrigin>,
     SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+    // Keeps the XCM origin of sibling calls (e.g. account migrations) as `pallet_xcm::Origin`
+    XcmPassthrough<RuntimeOrigin>,
 );
 
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
+/// Routes XCM to the relay chain (UMP) and to sibling parachains (XCMP)
+pub type XcmRouter = (
+    cumulus_primitives_utility::ParentAsUmp<ParachainSystem, (), ()>,
+    XcmpQueue,
+);
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
     type RuntimeCall = RuntimeCall;
-    type XcmSender = ();
+    type XcmSender = XcmRouter;
     type AssetTransactor = ();
     type OriginConverter = XcmOriginToCallOrigin;
     type IsReserve = ();
@@ -328,7 +347,7 @@ impl xcm_executor::Config for XcmConfig {
     type Barrier = Barrier;
     type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
     type Trader = ();
-    type ResponseHandler = ();
+    type ResponseHandler = PolkadotXcm;
     type AssetTrap = ();
     type AssetClaims = ();
     type SubscriptionService = ();
@@ -347,7 +366,7 @@ impl xcm_executor::Config for XcmConfig {
 impl pallet_xcm::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, ()>;
-    type XcmRouter = ();
+    type XcmRouter = XcmRouter;
     type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocationToAccountId>;
     type XcmExecuteFilter = Everything;
     type XcmExecutor = XcmExecutor<XcmConfig>;
@@ -375,11 +394,118 @@ impl cumulus_pallet_xcm::Config for Runtime {
     type XcmExecutor = XcmExecutor<XcmConfig>;
 }
 
+impl cumulus_pallet_xcmp_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type ChannelInfo = ParachainSystem;
+    type VersionWrapper = PolkadotXcm;
+    type XcmpQueue = TransformOrigin<MessageQueue, AggregateMessageOrigin, ParaId, ParaIdToSibling>;
+    type MaxInboundSuspended = ConstU32<1_000>;
+    type MaxActiveOutboundChannels = ConstU32<128>;
+    type MaxPageSize = ConstU32<{ 1 << 16 }>;
+    type ControllerOrigin = EnsureRoot<AccountId>;
+    type ControllerOriginConverter = XcmOriginToCallOrigin;
+    type WeightInfo = ();
+    type PriceForSiblingDelivery = NoPriceForMessageDelivery<ParaId>;
+}
+
+parameter_types! {
+    /// A quarter of the block's execution time and proof size for incoming messages
+    pub MessageQueueServiceWeight: Weight = Weight::from_parts(
+        WEIGHT_REF_TIME_PER_SECOND / 4,
+        cumulus_primitives_core::relay_chain::MAX_POV_SIZE as u64 / 4,
+    );
+}
+
+impl pallet_message_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MessageProcessor = xcm_builder::ProcessXcmMessage<
+        AggregateMessageOrigin,
+        XcmExecutor<XcmConfig>,
+        RuntimeCall,
+    >;
+    type Size = u32;
+    type QueueChangeHandler = NarrowOriginToSibling<XcmpQueue>;
+    type QueuePausedQuery = NarrowOriginToSibling<XcmpQueue>;
+    type HeapSize = ConstU32<{ 64 * 1024 }>;
+    type MaxStale = ConstU32<8>;
+    type ServiceWeight = MessageQueueServiceWeight;
+    type IdleMaxServiceWeight = ();
+}
+
 // Custom pallets configuration
 
+parameter_types! {
+    pub const MaxRecordsPerPatient: u32 = 10_000;
+    pub const AccessLogPageSize: u32 = 100;
+    pub const MaxMigrationBatch: u32 = 100;
+    pub const MaxErasureBatch: u32 = 100;
+    pub const MaxVersionErasureBatch: u32 = 500;
+}
+
+/// Origin of account migrations and erasures requested by the IdentityConsent chain
+pub type IdentityConsentOrigin =
+    pallet_xcm::EnsureXcm<frame_support::traits::Equals<IdentityConsentLocation>>;
+
 impl pallet_health_records::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
+    type MaxRecordsPerPatient = MaxRecordsPerPatient;
+    type AccessLogPageSize = AccessLogPageSize;
+    type AccessCheck = HealthRecordsAccess;
+    type MigrationOrigin = IdentityConsentOrigin;
+    type MaxMigrationBatch = MaxMigrationBatch;
+    type ErasureOrigin = IdentityConsentOrigin;
+    type MaxErasureBatch = MaxErasureBatch;
+    type MaxVersionErasureBatch = MaxVersionErasureBatch;
+    type WeightInfo = pallet_health_records::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = HealthRecordsAccess;
+}
+
+/// Checks `pallet_access_control` record grants and `pallet_encryption` key grants for
+/// `pallet_health_records`
+pub struct HealthRecordsAccess;
+
+impl pallet_health_records::RecordAccess<AccountId> for HealthRecordsAccess {
+    fn has_access(
+        record_id: &sp_core::H256,
+        accessor: &AccountId,
+        version: u32,
+        encryption_key_id: Option<&sp_core::H256>,
+        now: u64,
+    ) -> bool {
+        AccessControl::has_version_access(record_id, accessor, version, now)
+            || encryption_key_id
+                .map_or(false, |key_id| Encryption::has_key_access(key_id, accessor, now))
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_health_records::BenchmarkHelper<AccountId> for HealthRecordsAccess {
+    fn grant_access(
+        record_id: &sp_core::H256,
+        encryption_key_id: Option<&sp_core::H256>,
+        accessor: &AccountId,
+    ) {
+        // A key grant is checked last, so it is the most expensive path
+        match encryption_key_id {
+            Some(key_id) => pallet_encryption::KeyAccessGrants::<Runtime>::insert(
+                key_id,
+                accessor,
+                pallet_encryption::KeyAccess {
+                    grantee: accessor.clone(),
+                    granted_at: 0,
+                    expires_at: None,
+                },
+            ),
+            None => pallet_access_control::AccessGrants::<Runtime>::insert(
+                record_id,
+                accessor,
+                u64::MAX,
+            ),
+        }
+    }
 }
 
 parameter_types! {
@@ -389,12 +515,155 @@ parameter_types! {
 impl pallet_ipfs_integration::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
-    type MaxIPFSNodes = MaxIPFSNodes;
+    type MaxNodes = MaxIPFSNodes;
+    type ErasureOrigin = IdentityConsentOrigin;
+    type MaxErasureBatch = MaxErasureBatch;
+    type WeightInfo = pallet_ipfs_integration::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    /// IdentityConsent chain (Para ID 2000), answering consent queries
+    pub IdentityConsentLocation: Location = Location::new(1, [Parachain(2000)]);
+    pub const ConsentManagerPalletIndex: u8 = 51;
+    pub const ConsentQueryWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+    pub const ConsentQueryTimeout: BlockNumber = 10 * MINUTES;
+    pub const AutoGrantOnConsent: bool = true;
+    pub const DefaultGrantDuration: u64 = 30 * DAYS as u64 * MILLISECS_PER_BLOCK;
+    pub const MaxPendingRequests: u32 = 100;
+    pub const PendingRequestTimeout: BlockNumber = 7 * DAYS;
+    pub const MaxBulkRequests: u32 = 100;
+    pub const MaxRequestExpiriesPerBlock: u32 = 50;
+    pub const BreakGlassDuration: u64 = 4 * 60 * 60 * 1000; // 4 hours (ms)
 }
 
 impl pallet_access_control::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
     type TimeProvider = Timestamp;
+    type XcmSender = XcmRouter;
+    type ConsentChainLocation = IdentityConsentLocation;
+    type ConsentManagerPalletIndex = ConsentManagerPalletIndex;
+    type ConsentQueryWeight = ConsentQueryWeight;
+    type ConsentQueryTimeout = ConsentQueryTimeout;
+    type ConsentResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+    type AutoGrantOnConsent = AutoGrantOnConsent;
+    type DefaultGrantDuration = DefaultGrantDuration;
+    type RecordCategory = pallet_health_records::RecordCategory;
+    type Records = HealthRecordsInspect;
+    type MaxPendingRequests = MaxPendingRequests;
+    type PendingRequestTimeout = PendingRequestTimeout;
+    type MaxBulkRequests = MaxBulkRequests;
+    type MaxExpiriesPerBlock = MaxRequestExpiriesPerBlock;
+    type RegistryOrigin =
+        frame_support::traits::EitherOfDiverse<EnsureRoot<AccountId>, IdentityConsentOrigin>;
+    type BreakGlassDuration = BreakGlassDuration;
+    type WardCalls = WardCalls;
+    type WeightInfo = pallet_access_control::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = AccessControlBenchmarkHelper;
+}
+
+/// Calls a legal guardian may dispatch on behalf of their ward: patient actions on health
+/// records, access grants, delegates and encryption key grants. The ward's clinician and
+/// auditor rights, account migration and `act_for_ward` itself are not among them.
+pub struct WardCalls;
+
+impl frame_support::traits::Contains<RuntimeCall> for WardCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        use pallet_access_control::Call as AccessControlCall;
+        use pallet_encryption::Call as EncryptionCall;
+        use pallet_health_records::Call as HealthRecordsCall;
+        matches!(
+            call,
+            RuntimeCall::HealthRecords(
+                HealthRecordsCall::upload_record { .. }
+                    | HealthRecordsCall::update_record { .. }
+                    | HealthRecordsCall::amend_record { .. }
+                    | HealthRecordsCall::deactivate_record { .. }
+            ) | RuntimeCall::AccessControl(
+                AccessControlCall::grant_access { .. }
+                    | AccessControlCall::deny_access { .. }
+                    | AccessControlCall::bulk_grant_access { .. }
+                    | AccessControlCall::bulk_deny_access { .. }
+                    | AccessControlCall::revoke_access { .. }
+                    | AccessControlCall::add_delegate { .. }
+                    | AccessControlCall::remove_delegate { .. }
+                    | AccessControlCall::pin_grant_version { .. }
+            ) | RuntimeCall::Encryption(
+                EncryptionCall::grant_key_access { .. } | EncryptionCall::revoke_key_access { .. }
+            )
+        )
+    }
+}
+
+/// Exposes `pallet_health_records` metadata to `pallet_access_control`
+pub struct HealthRecordsInspect;
+
+impl pallet_access_control::RecordInspect<AccountId, pallet_health_records::RecordCategory>
+    for HealthRecordsInspect
+{
+    fn record_owner(record_id: &sp_core::H256) -> Option<AccountId> {
+        pallet_health_records::HealthRecords::<Runtime>::get(record_id)
+            .map(|record| record.patient)
+    }
+
+    fn record_category(
+        record_id: &sp_core::H256,
+    ) -> Option<pallet_health_records::RecordCategory> {
+        pallet_health_records::HealthRecords::<Runtime>::get(record_id)
+            .map(|record| record.category)
+    }
+
+    fn record_created_at(record_id: &sp_core::H256) -> Option<u64> {
+        pallet_health_records::HealthRecords::<Runtime>::get(record_id)
+            .map(|record| record.uploaded_at)
+    }
+
+    fn record_version(record_id: &sp_core::H256) -> Option<u32> {
+        Some(pallet_health_records::LatestVersions::<Runtime>::get(record_id))
+            .filter(|version| *version > 0)
+    }
+}
+
+/// Opens the HRMP channel to the IdentityConsent chain and stores health records for
+/// access-control benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AccessControlBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_access_control::BenchmarkHelper<RuntimeOrigin, AccountId>
+    for AccessControlBenchmarkHelper
+{
+    fn open_consent_channel() {
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2000));
+    }
+
+    fn consent_response_origin() -> RuntimeOrigin {
+        pallet_xcm::Origin::Response(IdentityConsentLocation::get()).into()
+    }
+
+    fn create_record(owner: &AccountId) -> sp_core::H256 {
+        let record_id = sp_core::H256::repeat_byte(0xbe);
+        pallet_health_records::HealthRecords::<Runtime>::insert(
+            record_id,
+            pallet_health_records::HealthRecord {
+                record_id,
+                patient: owner.clone(),
+                ipfs_hash: Default::default(),
+                category: pallet_health_records::RecordCategory::LabResults,
+                format: pallet_health_records::DataFormat::FHIR,
+                title: Default::default(),
+                file_size: 0,
+                encryption_key_id: None,
+                uploaded_at: 0,
+                last_accessed: None,
+                access_count: 0,
+                active: true,
+            },
+        );
+        pallet_health_records::LatestVersions::<Runtime>::insert(record_id, 1);
+        record_id
+    }
 }
 
 parameter_types! {
@@ -407,6 +676,9 @@ impl pallet_encryption::Config for Runtime {
     type TimeProvider = Timestamp;
     type MaxKeysPerAccount = MaxKeysPerAccount;
     type MaxAccessGrantsPerKey = MaxAccessGrantsPerKey;
+    type MigrationOrigin = IdentityConsentOrigin;
+    type ErasureOrigin = IdentityConsentOrigin;
+    type WeightInfo = pallet_encryption::weights::SubstrateWeight<Runtime>;
 }
 
 // Use trait to avoid cyclic dependency
@@ -434,8 +706,10 @@ construct_runtime!(
         ParachainInfo: parachain_info = 31,
 
         // XCM
-        XcmpQueue: cumulus_pallet_xcm = 40,
+        CumulusXcm: cumulus_pallet_xcm = 40,
         PolkadotXcm: pallet_xcm = 41,
+        XcmpQueue: cumulus_pallet_xcmp_queue = 42,
+        MessageQueue: pallet_message_queue = 43,
 
         // Custom pallets
         HealthRecords: pallet_health_records = 50,
@@ -445,6 +719,16 @@ construct_runtime!(
     }
 );
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benches {
+    frame_benchmarking::define_benchmarks!(
+        [pallet_health_records, HealthRecords]
+        [pallet_ipfs_integration, IPFSIntegration]
+        [pallet_access_control, AccessControl]
+        [pallet_encryption, Encryption]
+    );
+}
+
 /// The type used to represent the kinds of proxying allowed.
 pub type RuntimeGenesisConfig = frame_support::genesis_config_prelude::RuntimeGenesisConfig;
 
@@ -542,4 +826,36 @@ impl_runtime_apis! {
             ParachainSystem::collect_collation_info(header)
         }
     }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn benchmark_metadata(extra: bool) -> (
+            Vec<frame_benchmarking::BenchmarkList>,
+            Vec<frame_support::traits::StorageInfo>,
+        ) {
+            use frame_benchmarking::{Benchmarking, BenchmarkList};
+            use frame_support::traits::StorageInfoTrait;
+
+            let mut list = Vec::<BenchmarkList>::new();
+            list_benchmarks!(list, extra);
+
+            let storage_info = AllPalletsWithSystem::storage_info();
+            (list, storage_info)
+        }
+
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+            use frame_benchmarking::{BenchmarkBatch, Benchmarking};
+            use frame_support::traits::WhitelistedStorageKeys;
+
+            let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+            add_benchmarks!(params, batches);
+
+            Ok(batches)
+        }
+    }
 }
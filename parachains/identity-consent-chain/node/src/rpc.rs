@@ -43,8 +43,10 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: BlockBuilder<Block>,
+    C::Api: pallet_identity_registry_rpc::DidRuntimeApi<Block>,
     P: TransactionPool + Sync + Send + 'static,
 {
+    use pallet_identity_registry_rpc::{Did, DidApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -54,6 +56,9 @@ where
     // System RPC
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
 
+    // DID resolution RPC
+    module.merge(Did::new(client.clone()).into_rpc())?;
+
     // Transaction payment RPC
     module.merge(TransactionPayment::new(client).into_rpc())?;
 
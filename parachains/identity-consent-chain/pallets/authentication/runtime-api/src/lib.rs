@@ -0,0 +1,23 @@
+//! Runtime API for verifying requests signed with API keys
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_authentication::{ApiKeyError, ApiRequest};
+use sp_core::sr25519;
+
+sp_api::decl_runtime_apis! {
+    /// Verification of requests signed with API keys registered in the authentication pallet
+    pub trait AuthenticationApi<AccountId> where AccountId: Codec {
+        /// Check a signed request's key, scope, expiry, nonce and signature, returning the
+        /// account the key acts for
+        ///
+        /// Does not record the key's use or consume the nonce, so this check alone is not
+        /// replay-safe: the same request verifies again until a later one is authenticated.
+        /// Callers must track accepted nonces themselves, or submit `authenticate_api_request`.
+        fn verify_api_request(
+            request: ApiRequest,
+            signature: sr25519::Signature,
+        ) -> Result<AccountId, ApiKeyError>;
+    }
+}
@@ -0,0 +1,179 @@
+//! Benchmarking setup for pallet-authentication
+
+use super::*;
+
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use pallet_identity_registry::UserRole;
+use sp_core::{crypto::KeyTypeId, sr25519, H256};
+use sp_std::prelude::*;
+
+/// Hex digits used to build DIDs from account IDs
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Key type of the API keys generated in the benchmark keystore
+const API_KEY_TYPE: KeyTypeId = KeyTypeId(*b"apik");
+
+/// All API key scopes
+fn all_scopes() -> ApiKeyScopes {
+    BoundedVec::truncate_from(vec![
+        ApiKeyScope::ReadOnly,
+        ApiKeyScope::ConsentQuery,
+        ApiKeyScope::MarketplaceTrade,
+    ])
+}
+
+/// Register an active identity for an account
+fn register<T: Config>(who: &T::AccountId) {
+    let mut did = b"did:patientx:".to_vec();
+    for byte in who.encode() {
+        did.extend_from_slice(&[HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]);
+    }
+
+    pallet_identity_registry::Pallet::<T>::register_identity(
+        RawOrigin::Signed(who.clone()).into(),
+        BoundedVec::truncate_from(did),
+        UserRole::Patient,
+        BoundedVec::truncate_from(b"Benchmark".to_vec()),
+        H256::repeat_byte(1),
+    )
+    .expect("identity registration must succeed");
+}
+
+/// Device with the longest description
+fn device() -> DeviceInfo {
+    DeviceInfo {
+        name: BoundedVec::truncate_from(vec![b'd'; 32]),
+        platform: BoundedVec::truncate_from(vec![b'p'; 32]),
+    }
+}
+
+/// Fill the account's session list with sessions expiring at `expires_at`
+fn fill_sessions<T: Config>(who: &T::AccountId, expires_at: u64) {
+    let ids: Vec<H256> = (0..10u64).map(H256::from_low_u64_be).collect();
+    for session_id in ids.iter() {
+        Sessions::<T>::insert(
+            session_id,
+            Session {
+                account: who.clone(),
+                session_id: *session_id,
+                created_at: 0,
+                expires_at,
+                active: true,
+                device: device(),
+            },
+        );
+    }
+    AccountSessions::<T>::insert(who, BoundedVec::truncate_from(ids));
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_session() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller);
+
+        // A full list of expired sessions, all of which are pruned
+        fill_sessions::<T>(&caller, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), device(), true);
+
+        assert_eq!(AccountSessions::<T>::get(&caller).len(), 1);
+    }
+
+    #[benchmark]
+    fn revoke_session() {
+        let caller: T::AccountId = whitelisted_caller();
+        fill_sessions::<T>(&caller, u64::MAX);
+        let session_id = AccountSessions::<T>::get(&caller)[9];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), session_id);
+
+        assert!(Sessions::<T>::get(session_id).is_none());
+        assert_eq!(AccountSessions::<T>::get(&caller).len(), 9);
+    }
+
+    #[benchmark]
+    fn revoke_all_sessions() {
+        let caller: T::AccountId = whitelisted_caller();
+        fill_sessions::<T>(&caller, u64::MAX);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(AccountSessions::<T>::get(&caller).is_empty());
+    }
+
+    #[benchmark]
+    fn create_api_key() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller);
+        let public_key = sr25519::Public::from_raw([7; 32]);
+        let name = BoundedVec::truncate_from(vec![b'k'; 32]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), public_key, name, all_scopes(), Some(u64::MAX));
+
+        assert!(ApiKeys::<T>::contains_key(Pallet::<T>::api_key_hash(&public_key)));
+    }
+
+    #[benchmark]
+    fn revoke_api_key() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller);
+        let public_key = sr25519::Public::from_raw([7; 32]);
+        let key_hash = Pallet::<T>::api_key_hash(&public_key);
+        Pallet::<T>::create_api_key(
+            RawOrigin::Signed(caller.clone()).into(),
+            public_key,
+            BoundedVec::truncate_from(vec![b'k'; 32]),
+            all_scopes(),
+            None,
+        )
+        .expect("api key creation must succeed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), key_hash);
+
+        assert!(!ApiKeys::<T>::get(key_hash).unwrap().active);
+    }
+
+    #[benchmark]
+    fn authenticate_api_request() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller);
+        let public_key = sp_io::crypto::sr25519_generate(API_KEY_TYPE, None);
+        let key_hash = Pallet::<T>::api_key_hash(&public_key);
+        Pallet::<T>::create_api_key(
+            RawOrigin::Signed(caller.clone()).into(),
+            public_key,
+            BoundedVec::truncate_from(vec![b'k'; 32]),
+            all_scopes(),
+            Some(u64::MAX),
+        )
+        .expect("api key creation must succeed");
+
+        // The last of the key's scopes, the most expensive to look up
+        let request = ApiRequest {
+            key_hash,
+            scope: ApiKeyScope::MarketplaceTrade,
+            nonce: 1,
+            payload_hash: H256::repeat_byte(2),
+        };
+        let payload = Pallet::<T>::api_request_payload(&request);
+        let signature = sp_io::crypto::sr25519_sign(API_KEY_TYPE, &public_key, &payload)
+            .expect("the key was generated in the keystore");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), request, signature);
+
+        assert_eq!(ApiKeys::<T>::get(key_hash).unwrap().nonce, 1);
+    }
+}
@@ -5,6 +5,33 @@
 //! The Authentication pallet provides session management and API key authentication
 //! for the Patient X platform.
 //!
+//! ## Sessions
+//!
+//! An account holds at most 10 sessions, each recording the device it was created on. Revoked
+//! sessions are removed right away, and expired ones are pruned when the account creates a new
+//! session; if all 10 are still live, `create_session` fails unless the caller asks for its
+//! oldest session to be evicted. `revoke_all_sessions` logs an account out everywhere. Session
+//! IDs include a per-account nonce, so two sessions created in the same millisecond get
+//! different IDs.
+//!
+//! ## API keys
+//!
+//! Backend services act for a user through an API key instead of the user's account key. An
+//! API key is an sr25519 key pair registered with `create_api_key`, limited to a set of scopes
+//! (read-only, consent queries, marketplace trades) and optionally to an expiry. A service signs
+//! an `ApiRequest` naming the key, scope, a nonce higher than the key's last one and the hash of
+//! the request payload. The signed bytes are the SCALE encoding of `API_REQUEST_CONTEXT`, the
+//! chain's genesis hash and the request (see `api_request_payload`), so a signature cannot be
+//! replayed as any other message or on another chain. `verify_api_request` (exposed through
+//! `AuthenticationApi`) checks such a request without changing state;
+//! `authenticate_api_request`, which any account can submit, also records the key's use and
+//! consumes the nonce. Revoked and expired keys, and keys of deactivated identities, are refused.
+//! Hash-only keys registered before key pairs were required were removed on upgrade.
+//!
+//! The runtime API alone is not replay-safe: it never consumes the nonce, so the same signed
+//! request verifies again until a later request is authenticated on chain. Services relying on
+//! it must track the nonces they have accepted themselves.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "IdentityConsent Chain - Authentication"
 
@@ -12,15 +39,37 @@
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
-    use sp_core::H256;
+    use sp_core::{sr25519, H256};
+    use sp_runtime::traits::{Verify, Zero};
     use pallet_identity_registry::Pallet as IdentityRegistry;
+    use crate::weights::WeightInfo;
+
+    /// Context tag prefixed to every request signed with an API key
+    pub const API_REQUEST_CONTEXT: &[u8] = b"patient-x/api-request";
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Authentication session
@@ -32,20 +81,88 @@ pub mod pallet {
         pub created_at: u64,
         pub expires_at: u64,
         pub active: bool,
+        /// Device the session was created on
+        pub device: DeviceInfo,
     }
 
+    /// Device a session was created on, as reported by the client (empty when unknown)
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct DeviceInfo {
+        /// Display name (e.g. "Alice's iPhone")
+        pub name: BoundedVec<u8, ConstU32<32>>,
+        /// Platform and app version (e.g. "iOS 17.4 / 2.3.1")
+        pub platform: BoundedVec<u8, ConstU32<32>>,
+    }
+
+    /// What an API key may be used for
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum ApiKeyScope {
+        /// Read the account's data
+        ReadOnly,
+        /// Query and verify consents
+        ConsentQuery,
+        /// Trade on the marketplace
+        MarketplaceTrade,
+    }
+
+    /// Scopes of an API key, without duplicates
+    pub type ApiKeyScopes = BoundedVec<ApiKeyScope, ConstU32<3>>;
+
     /// API key for programmatic access
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
     pub struct ApiKey<T: Config> {
         pub account: T::AccountId,
+        /// Hash of `public_key`, identifying the key
         pub key_hash: H256,
+        /// Key requests are signed with
+        pub public_key: sr25519::Public,
         pub name: BoundedVec<u8, ConstU32<32>>,
+        /// Scopes requests can be made for
+        pub scopes: ApiKeyScopes,
         pub created_at: u64,
+        /// Expiry timestamp (if any)
+        pub expires_at: Option<u64>,
         pub last_used: Option<u64>,
+        /// Nonce of the last authenticated request
+        pub nonce: u64,
         pub active: bool,
     }
 
+    /// Request signed by an API key
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct ApiRequest {
+        /// Key the request is signed with
+        pub key_hash: H256,
+        /// Scope the request needs
+        pub scope: ApiKeyScope,
+        /// Must exceed the nonce of the key's last authenticated request
+        pub nonce: u64,
+        /// Hash of the request payload
+        pub payload_hash: H256,
+    }
+
+    /// Reason an API request is refused
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub enum ApiKeyError {
+        /// No API key is registered with this hash
+        UnknownKey,
+        /// The key has been revoked
+        Revoked,
+        /// The key has expired
+        Expired,
+        /// The key's account has no active identity
+        InactiveIdentity,
+        /// The key does not allow the requested scope
+        ScopeNotAllowed,
+        /// The nonce has already been used
+        StaleNonce,
+        /// The signature does not match the key
+        InvalidSignature,
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_identity_registry::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -53,15 +170,20 @@ pub mod pallet {
 
         #[pallet::constant]
         type SessionDuration: Get<u64>;
+
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::storage]
+    #[pallet::getter(fn sessions)]
     pub type Sessions<T: Config> = StorageMap<_, Blake2_128Concat, H256, Session<T>>;
 
     #[pallet::storage]
+    #[pallet::getter(fn api_keys)]
     pub type ApiKeys<T: Config> = StorageMap<_, Blake2_128Concat, H256, ApiKey<T>>;
 
     #[pallet::storage]
+    #[pallet::getter(fn account_sessions)]
     pub type AccountSessions<T: Config> = StorageMap<
         _,
         Blake2_128Concat,
@@ -70,13 +192,31 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Number of sessions ever created per account, used to derive session IDs
+    #[pallet::storage]
+    pub type SessionNonces<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         SessionCreated { account: T::AccountId, session_id: H256 },
         SessionRevoked { session_id: H256 },
+        /// Expired sessions removed [account, count]
+        SessionsPruned { account: T::AccountId, count: u32 },
+        /// Oldest session evicted to make room for a new one [account, session_id]
+        SessionEvicted { account: T::AccountId, session_id: H256 },
+        /// All sessions of an account revoked [account, count]
+        AllSessionsRevoked { account: T::AccountId, count: u32 },
         ApiKeyCreated { account: T::AccountId, key_hash: H256 },
         ApiKeyRevoked { key_hash: H256 },
+        /// Request signed with an API key authenticated [key_hash, account, scope, payload_hash]
+        ApiRequestAuthenticated {
+            key_hash: H256,
+            account: T::AccountId,
+            scope: ApiKeyScope,
+            payload_hash: H256,
+        },
     }
 
     #[pallet::error]
@@ -88,13 +228,50 @@ pub mod pallet {
         MaxSessionsReached,
         ApiKeyNotFound,
         ApiKeyInactive,
+        /// An API key with this public key already exists
+        ApiKeyAlreadyExists,
+        /// Scopes must be non-empty and without duplicates
+        InvalidScopes,
+        /// Expiry must be in the future
+        InvalidExpiry,
+        /// API key has expired
+        ApiKeyExpired,
+        /// API key does not allow the requested scope
+        ScopeNotAllowed,
+        /// Request nonce has already been used
+        StaleNonce,
+        /// Request signature does not match the API key
+        InvalidSignature,
+    }
+
+    impl<T> From<ApiKeyError> for Error<T> {
+        fn from(error: ApiKeyError) -> Self {
+            match error {
+                ApiKeyError::UnknownKey => Error::<T>::ApiKeyNotFound,
+                ApiKeyError::Revoked => Error::<T>::ApiKeyInactive,
+                ApiKeyError::Expired => Error::<T>::ApiKeyExpired,
+                ApiKeyError::InactiveIdentity => Error::<T>::InvalidIdentity,
+                ApiKeyError::ScopeNotAllowed => Error::<T>::ScopeNotAllowed,
+                ApiKeyError::StaleNonce => Error::<T>::StaleNonce,
+                ApiKeyError::InvalidSignature => Error::<T>::InvalidSignature,
+            }
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Create a session for the caller on `device`
+        ///
+        /// Expired sessions of the caller are pruned first. If the caller still has the maximum
+        /// number of sessions, the oldest one is evicted when `evict_oldest` is set; otherwise
+        /// the call fails with `MaxSessionsReached`.
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
-        pub fn create_session(origin: OriginFor<T>) -> DispatchResult {
+        #[pallet::weight(<T as Config>::WeightInfo::create_session())]
+        pub fn create_session(
+            origin: OriginFor<T>,
+            device: DeviceInfo,
+            evict_oldest: bool,
+        ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             ensure!(
@@ -103,7 +280,37 @@ pub mod pallet {
             );
 
             let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
-            let session_id = Self::generate_session_id(&who, now);
+            let mut sessions = AccountSessions::<T>::get(&who);
+
+            // Prune sessions that can no longer be used
+            let before = sessions.len();
+            sessions.retain(|id| {
+                let live = Self::is_session_valid(id, now);
+                if !live {
+                    Sessions::<T>::remove(id);
+                }
+                live
+            });
+            let pruned = (before - sessions.len()) as u32;
+            if pruned > 0 {
+                Self::deposit_event(Event::SessionsPruned { account: who.clone(), count: pruned });
+            }
+
+            if sessions.is_full() {
+                ensure!(evict_oldest, Error::<T>::MaxSessionsReached);
+                let oldest = sessions.remove(0);
+                Sessions::<T>::remove(oldest);
+                Self::deposit_event(Event::SessionEvicted {
+                    account: who.clone(),
+                    session_id: oldest,
+                });
+            }
+
+            let nonce = SessionNonces::<T>::mutate(&who, |nonce| {
+                *nonce = nonce.saturating_add(1);
+                *nonce
+            });
+            let session_id = Self::generate_session_id(&who, now, nonce);
 
             let session = Session {
                 account: who.clone(),
@@ -111,43 +318,65 @@ pub mod pallet {
                 created_at: now,
                 expires_at: now + T::SessionDuration::get(),
                 active: true,
+                device,
             };
 
             Sessions::<T>::insert(session_id, session);
-
-            AccountSessions::<T>::try_mutate(&who, |sessions| -> DispatchResult {
-                sessions.try_push(session_id).map_err(|_| Error::<T>::MaxSessionsReached)?;
-                Ok(())
-            })?;
+            sessions.try_push(session_id).map_err(|_| Error::<T>::MaxSessionsReached)?;
+            AccountSessions::<T>::insert(&who, sessions);
 
             Self::deposit_event(Event::SessionCreated { account: who, session_id });
 
             Ok(())
         }
 
+        /// Revoke one of the caller's sessions, removing it from storage
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_session())]
         pub fn revoke_session(origin: OriginFor<T>, session_id: H256) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            Sessions::<T>::try_mutate(session_id, |maybe_session| -> DispatchResult {
-                let session = maybe_session.as_mut().ok_or(Error::<T>::SessionNotFound)?;
-                ensure!(session.account == who, Error::<T>::NotAuthorized);
-                session.active = false;
-                Ok(())
-            })?;
+            let session = Sessions::<T>::get(session_id).ok_or(Error::<T>::SessionNotFound)?;
+            ensure!(session.account == who, Error::<T>::NotAuthorized);
+
+            Sessions::<T>::remove(session_id);
+            AccountSessions::<T>::mutate(&who, |sessions| sessions.retain(|id| *id != session_id));
 
             Self::deposit_event(Event::SessionRevoked { session_id });
 
             Ok(())
         }
 
+        /// Revoke all sessions of the caller, e.g. after losing a device
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_all_sessions())]
+        pub fn revoke_all_sessions(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let sessions = AccountSessions::<T>::take(&who);
+            for session_id in sessions.iter() {
+                Sessions::<T>::remove(session_id);
+            }
+
+            Self::deposit_event(Event::AllSessionsRevoked {
+                account: who,
+                count: sessions.len() as u32,
+            });
+
+            Ok(())
+        }
+
+        /// Register an API key acting for the caller
+        ///
+        /// The key is identified by the hash of `public_key` (see `api_key_hash`).
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_api_key())]
         pub fn create_api_key(
             origin: OriginFor<T>,
-            key_hash: H256,
+            public_key: sr25519::Public,
             name: BoundedVec<u8, ConstU32<32>>,
+            scopes: ApiKeyScopes,
+            expires_at: Option<u64>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -155,15 +384,28 @@ pub mod pallet {
                 IdentityRegistry::<T>::is_active_identity(&who),
                 Error::<T>::InvalidIdentity
             );
+            ensure!(
+                !scopes.is_empty()
+                    && scopes.iter().enumerate().all(|(i, s)| !scopes[..i].contains(s)),
+                Error::<T>::InvalidScopes
+            );
 
             let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(expires_at.map_or(true, |expiry| expiry > now), Error::<T>::InvalidExpiry);
+
+            let key_hash = Self::api_key_hash(&public_key);
+            ensure!(!ApiKeys::<T>::contains_key(key_hash), Error::<T>::ApiKeyAlreadyExists);
 
             let api_key = ApiKey {
                 account: who.clone(),
                 key_hash,
+                public_key,
                 name,
+                scopes,
                 created_at: now,
+                expires_at,
                 last_used: None,
+                nonce: 0,
                 active: true,
             };
 
@@ -175,7 +417,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_api_key())]
         pub fn revoke_api_key(origin: OriginFor<T>, key_hash: H256) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -190,14 +432,82 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Authenticate a request signed with an API key
+        ///
+        /// Can be submitted by any account (e.g. the backend service's own); the authority
+        /// comes from the API key's signature over `api_request_payload`. Records the key's
+        /// use and consumes the request nonce.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::authenticate_api_request())]
+        pub fn authenticate_api_request(
+            origin: OriginFor<T>,
+            request: ApiRequest,
+            signature: sr25519::Signature,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let mut key =
+                Self::verify_api_request(&request, &signature).map_err(Error::<T>::from)?;
+
+            key.last_used = Some(T::TimeProvider::now().try_into().ok().unwrap_or(0));
+            key.nonce = request.nonce;
+            let account = key.account.clone();
+            ApiKeys::<T>::insert(request.key_hash, key);
+
+            Self::deposit_event(Event::ApiRequestAuthenticated {
+                key_hash: request.key_hash,
+                account,
+                scope: request.scope,
+                payload_hash: request.payload_hash,
+            });
+
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
-        fn generate_session_id(account: &T::AccountId, timestamp: u64) -> H256 {
-            use sp_runtime::traits::Hash;
-            let mut data = account.encode();
-            data.extend_from_slice(&timestamp.encode());
-            T::Hashing::hash(&data)
+        fn generate_session_id(account: &T::AccountId, timestamp: u64, nonce: u64) -> H256 {
+            H256(sp_io::hashing::blake2_256(&(account, timestamp, nonce).encode()))
+        }
+
+        /// Hash identifying the API key with the given public key
+        pub fn api_key_hash(public_key: &sr25519::Public) -> H256 {
+            H256(sp_io::hashing::blake2_256(public_key.as_ref()))
+        }
+
+        /// Bytes an API key signs for `request`: `API_REQUEST_CONTEXT`, this chain's genesis
+        /// hash and the request, SCALE encoded
+        pub fn api_request_payload(request: &ApiRequest) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+            (API_REQUEST_CONTEXT, genesis_hash, request).encode()
+        }
+
+        /// Check a request signed with an API key, returning the key
+        ///
+        /// Does not change state: the key's `last_used` and nonce are only updated by
+        /// `authenticate_api_request`, so a request accepted here alone can be replayed.
+        pub fn verify_api_request(
+            request: &ApiRequest,
+            signature: &sr25519::Signature,
+        ) -> Result<ApiKey<T>, ApiKeyError> {
+            let key = ApiKeys::<T>::get(request.key_hash).ok_or(ApiKeyError::UnknownKey)?;
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            ensure!(key.active, ApiKeyError::Revoked);
+            ensure!(key.expires_at.map_or(true, |expiry| expiry > now), ApiKeyError::Expired);
+            ensure!(
+                IdentityRegistry::<T>::is_active_identity(&key.account),
+                ApiKeyError::InactiveIdentity
+            );
+            ensure!(key.scopes.contains(&request.scope), ApiKeyError::ScopeNotAllowed);
+            ensure!(request.nonce > key.nonce, ApiKeyError::StaleNonce);
+            ensure!(
+                signature.verify(&Self::api_request_payload(request)[..], &key.public_key),
+                ApiKeyError::InvalidSignature
+            );
+
+            Ok(key)
         }
 
         pub fn is_session_valid(session_id: &H256, now: u64) -> bool {
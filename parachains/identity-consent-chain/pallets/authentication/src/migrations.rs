@@ -0,0 +1,74 @@
+//! Storage migrations for the authentication pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Version 1 records the device of each session, removes revoked sessions, and replaces
+/// hash-only API keys with sr25519 keys limited to scopes
+pub mod v1 {
+    use super::*;
+
+    /// Session of version 0, before devices were recorded
+    #[derive(Encode, Decode)]
+    pub struct OldSession<T: Config> {
+        pub account: T::AccountId,
+        pub session_id: H256,
+        pub created_at: u64,
+        pub expires_at: u64,
+        pub active: bool,
+    }
+
+    /// Give each live session an unknown device, remove revoked ones, and remove every API key
+    /// of version 0
+    ///
+    /// A version 0 key is only the hash of a secret, which cannot verify a signed request, so
+    /// it is dropped rather than translated. Its holder registers a key pair instead.
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut sessions = 0u64;
+            let mut revoked = Vec::new();
+            Sessions::<T>::translate::<OldSession<T>, _>(|session_id, old| {
+                sessions += 1;
+                if !old.active {
+                    revoked.push((old.account, session_id));
+                    return None;
+                }
+
+                Some(Session {
+                    account: old.account,
+                    session_id: old.session_id,
+                    created_at: old.created_at,
+                    expires_at: old.expires_at,
+                    active: true,
+                    device: DeviceInfo::default(),
+                })
+            });
+
+            // Revoked sessions used to stay listed under their account
+            let mut writes = sessions + revoked.len() as u64;
+            for (account, session_id) in revoked {
+                AccountSessions::<T>::mutate(account, |ids| ids.retain(|id| *id != session_id));
+            }
+
+            let removed = ApiKeys::<T>::clear(u32::MAX, None).unique as u64;
+            writes += removed;
+
+            T::DbWeight::get().reads_writes(writes, writes)
+        }
+    }
+
+    /// Migrate `Sessions` and `ApiKeys` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -5,7 +5,7 @@ use frame_support::{
     parameter_types,
     traits::{ConstU16, ConstU32, ConstU64},
 };
-use sp_core::H256;
+use sp_core::{sr25519, Pair, H256};
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
     BuildStorage,
@@ -65,6 +65,17 @@ impl pallet_identity_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxIdentitiesPerAccount = ConstU32<1>;
+    type VerifierOrigin = frame_system::EnsureRoot<u64>;
+    type MaxVerifiers = ConstU32<4>;
+    type MaxDidControllers = ConstU32<2>;
+    type MaxVerificationMethods = ConstU32<2>;
+    type MaxServices = ConstU32<2>;
+    type MaxGuardians = ConstU32<3>;
+    type MaxGuardianships = ConstU32<2>;
+    type RecoveryHandler = ();
+    type GuardianshipOrigin = frame_system::EnsureRoot<u64>;
+    type GuardianshipHandler = ();
+    type WeightInfo = ();
 }
 
 parameter_types! {
@@ -75,6 +86,7 @@ impl pallet_authentication::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type SessionDuration = SessionDuration;
+    type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -87,7 +99,9 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .assimilate_storage(&mut t)
         .unwrap();
 
-    t.into()
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
 }
 
 // Helper function to advance time
@@ -130,8 +144,64 @@ pub fn register_user(account: u64) {
     );
 }
 
-// Helper function to create API key hash
-pub fn create_key_hash(key: &str) -> H256 {
-    use sp_runtime::traits::Hash;
-    BlakeTwo256::hash(key.as_bytes())
+// Helper function to describe a device
+pub fn device(name: &str) -> crate::DeviceInfo {
+    crate::DeviceInfo {
+        name: sp_runtime::BoundedVec::try_from(name.as_bytes().to_vec()).unwrap(),
+        platform: sp_runtime::BoundedVec::try_from(b"iOS 17.4 / 2.3.1".to_vec()).unwrap(),
+    }
+}
+
+// Helper function to create a session on a phone, returning its ID
+pub fn create_session(account: u64, evict_oldest: bool) -> H256 {
+    frame_support::assert_ok!(Authentication::create_session(
+        RuntimeOrigin::signed(account),
+        device("Phone"),
+        evict_oldest,
+    ));
+
+    *Authentication::account_sessions(account).last().unwrap()
+}
+
+// Helper function to derive an API key pair from a seed phrase
+pub fn api_key_pair(seed: &str) -> sr25519::Pair {
+    sr25519::Pair::from_string(&format!("//{}", seed), None).unwrap()
+}
+
+// Helper function to build a scope set
+pub fn scopes(scopes: &[crate::ApiKeyScope]) -> crate::ApiKeyScopes {
+    sp_runtime::BoundedVec::try_from(scopes.to_vec()).unwrap()
+}
+
+// Helper function to register an API key for `seed` with all scopes, returning its hash
+pub fn create_api_key(account: u64, seed: &str, expires_at: Option<u64>) -> H256 {
+    use crate::ApiKeyScope::*;
+    let public_key = api_key_pair(seed).public();
+
+    frame_support::assert_ok!(Authentication::create_api_key(
+        RuntimeOrigin::signed(account),
+        public_key,
+        sp_runtime::BoundedVec::try_from(b"Backend".to_vec()).unwrap(),
+        scopes(&[ReadOnly, ConsentQuery, MarketplaceTrade]),
+        expires_at,
+    ));
+
+    Authentication::api_key_hash(&public_key)
+}
+
+// Helper function to build a request signed with the API key of `seed`
+pub fn sign_request(
+    seed: &str,
+    scope: crate::ApiKeyScope,
+    nonce: u64,
+) -> (crate::ApiRequest, sr25519::Signature) {
+    let pair = api_key_pair(seed);
+    let request = crate::ApiRequest {
+        key_hash: Authentication::api_key_hash(&pair.public()),
+        scope,
+        nonce,
+        payload_hash: H256::repeat_byte(1),
+    };
+    let signature = pair.sign(&Authentication::api_request_payload(&request));
+    (request, signature)
 }
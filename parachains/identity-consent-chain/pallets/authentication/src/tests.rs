@@ -1,7 +1,18 @@
 //! Tests for authentication pallet
 
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations::v1::{MigrateToV1, OldSession},
+    mock::*,
+    AccountSessions, ApiKeyError, ApiKeyScope, ApiKeys, DeviceInfo, Error, Event, Sessions,
+    API_REQUEST_CONTEXT,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::unhashed,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_core::{Pair, H256};
 
 #[test]
 fn create_session_works() {
@@ -15,7 +26,11 @@ fn create_session_works() {
         Timestamp::set_timestamp(1000);
 
         // Create session
-        assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+        assert_ok!(Authentication::create_session(
+            RuntimeOrigin::signed(account),
+            device("Phone"),
+            false,
+        ));
 
         // Get session ID from event
         let events = System::events();
@@ -35,6 +50,7 @@ fn create_session_works() {
         assert_eq!(session.created_at, 1000);
         assert_eq!(session.expires_at, 1000 + 86400000); // 24 hours later
         assert!(session.active);
+        assert_eq!(session.device, device("Phone"));
 
         // Verify session is in account's session list
         assert!(Authentication::account_sessions(account).contains(&session_id));
@@ -47,7 +63,7 @@ fn create_session_fails_without_identity() {
         let account = 1u64;
 
         assert_noop!(
-            Authentication::create_session(RuntimeOrigin::signed(account)),
+            Authentication::create_session(RuntimeOrigin::signed(account), device("Phone"), false),
             Error::<Test>::InvalidIdentity
         );
     });
@@ -61,15 +77,88 @@ fn create_multiple_sessions_works() {
 
         // Create multiple sessions
         for _ in 0..5 {
-            assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+            assert_ok!(Authentication::create_session(
+                RuntimeOrigin::signed(account),
+                device("Phone"),
+                false,
+            ));
         }
 
-        // Verify all sessions exist
-        let sessions = Authentication::account_sessions(account);
+        // Verify all sessions exist, with distinct IDs despite sharing a timestamp
+        let mut sessions = Authentication::account_sessions(account).into_inner();
+        assert_eq!(sessions.len(), 5);
+        sessions.dedup();
         assert_eq!(sessions.len(), 5);
     });
 }
 
+#[test]
+fn revoked_and_expired_sessions_are_removed() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+
+        Timestamp::set_timestamp(1000);
+        let revoked = create_session(account, false);
+        for _ in 0..9 {
+            create_session(account, false);
+        }
+        assert_ok!(Authentication::revoke_session(RuntimeOrigin::signed(account), revoked));
+
+        // The revoked session is removed right away, making room for a new one
+        assert_eq!(Authentication::sessions(revoked), None);
+        assert!(!Authentication::account_sessions(account).contains(&revoked));
+        create_session(account, false);
+        assert_eq!(Authentication::account_sessions(account).len(), 10);
+
+        // Once expired, all sessions are pruned
+        Timestamp::set_timestamp(1000 + 86400000);
+        let session_id = create_session(account, false);
+        assert_eq!(Authentication::account_sessions(account).to_vec(), vec![session_id]);
+        System::assert_has_event(Event::SessionsPruned { account, count: 10 }.into());
+    });
+}
+
+#[test]
+fn oldest_session_is_evicted_on_request() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+
+        let oldest = create_session(account, false);
+        for _ in 0..9 {
+            create_session(account, false);
+        }
+
+        let session_id = create_session(account, true);
+
+        let sessions = Authentication::account_sessions(account);
+        assert_eq!(sessions.len(), 10);
+        assert!(!sessions.contains(&oldest));
+        assert_eq!(sessions.last(), Some(&session_id));
+        assert_eq!(Authentication::sessions(oldest), None);
+        System::assert_has_event(Event::SessionEvicted { account, session_id: oldest }.into());
+    });
+}
+
+#[test]
+fn revoke_all_sessions_works() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+
+        let first = create_session(account, false);
+        let second = create_session(account, false);
+
+        assert_ok!(Authentication::revoke_all_sessions(RuntimeOrigin::signed(account)));
+
+        assert!(Authentication::account_sessions(account).is_empty());
+        assert!(!Authentication::is_session_valid(&first, 0));
+        assert!(!Authentication::is_session_valid(&second, 0));
+        System::assert_last_event(Event::AllSessionsRevoked { account, count: 2 }.into());
+    });
+}
+
 #[test]
 fn revoke_session_works() {
     new_test_ext().execute_with(|| {
@@ -77,7 +166,11 @@ fn revoke_session_works() {
         register_user(account);
 
         // Create session
-        assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+        assert_ok!(Authentication::create_session(
+            RuntimeOrigin::signed(account),
+            device("Phone"),
+            false,
+        ));
 
         let events = System::events();
         let session_id = if let RuntimeEvent::Authentication(Event::SessionCreated {
@@ -96,9 +189,9 @@ fn revoke_session_works() {
             session_id
         ));
 
-        // Verify session is inactive
-        let session = Authentication::sessions(session_id).unwrap();
-        assert!(!session.active);
+        // Verify session is removed
+        assert_eq!(Authentication::sessions(session_id), None);
+        assert!(Authentication::account_sessions(account).is_empty());
 
         // Check event
         System::assert_last_event(Event::SessionRevoked { session_id }.into());
@@ -115,9 +208,11 @@ fn revoke_session_fails_when_not_owner() {
         register_user(account2);
 
         // Create session for account1
-        assert_ok!(Authentication::create_session(RuntimeOrigin::signed(
-            account1
-        )));
+        assert_ok!(Authentication::create_session(
+            RuntimeOrigin::signed(account1),
+            device("Phone"),
+            false,
+        ));
 
         let events = System::events();
         let session_id = if let RuntimeEvent::Authentication(Event::SessionCreated {
@@ -159,7 +254,8 @@ fn create_api_key_works() {
         let account = 1u64;
         register_user(account);
 
-        let key_hash = create_key_hash("my_secret_key");
+        let public_key = api_key_pair("backend").public();
+        let key_hash = Authentication::api_key_hash(&public_key);
         let name = sp_runtime::BoundedVec::try_from(b"Production API".to_vec()).unwrap();
 
         // Set timestamp
@@ -168,15 +264,20 @@ fn create_api_key_works() {
         // Create API key
         assert_ok!(Authentication::create_api_key(
             RuntimeOrigin::signed(account),
-            key_hash,
-            name.clone()
+            public_key,
+            name.clone(),
+            scopes(&[ApiKeyScope::ReadOnly]),
+            Some(10000),
         ));
 
         // Verify API key was created
         let api_key = Authentication::api_keys(key_hash).unwrap();
         assert_eq!(api_key.account, account);
+        assert_eq!(api_key.public_key, public_key);
         assert_eq!(api_key.name, name);
+        assert_eq!(api_key.scopes, scopes(&[ApiKeyScope::ReadOnly]));
         assert_eq!(api_key.created_at, 5000);
+        assert_eq!(api_key.expires_at, Some(10000));
         assert!(api_key.last_used.is_none());
         assert!(api_key.active);
 
@@ -195,16 +296,57 @@ fn create_api_key_works() {
 fn create_api_key_fails_without_identity() {
     new_test_ext().execute_with(|| {
         let account = 1u64;
-        let key_hash = create_key_hash("my_secret_key");
         let name = sp_runtime::BoundedVec::try_from(b"Test".to_vec()).unwrap();
 
         assert_noop!(
-            Authentication::create_api_key(RuntimeOrigin::signed(account), key_hash, name),
+            Authentication::create_api_key(
+                RuntimeOrigin::signed(account),
+                api_key_pair("backend").public(),
+                name,
+                scopes(&[ApiKeyScope::ReadOnly]),
+                None,
+            ),
             Error::<Test>::InvalidIdentity
         );
     });
 }
 
+#[test]
+fn create_api_key_validates_parameters() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+        Timestamp::set_timestamp(5000);
+
+        let public_key = api_key_pair("backend").public();
+        let create = |scopes, expires_at| {
+            Authentication::create_api_key(
+                RuntimeOrigin::signed(account),
+                public_key,
+                sp_runtime::BoundedVec::try_from(b"Test".to_vec()).unwrap(),
+                scopes,
+                expires_at,
+            )
+        };
+
+        assert_noop!(create(scopes(&[]), None), Error::<Test>::InvalidScopes);
+        assert_noop!(
+            create(scopes(&[ApiKeyScope::ReadOnly, ApiKeyScope::ReadOnly]), None),
+            Error::<Test>::InvalidScopes
+        );
+        assert_noop!(
+            create(scopes(&[ApiKeyScope::ReadOnly]), Some(5000)),
+            Error::<Test>::InvalidExpiry
+        );
+
+        assert_ok!(create(scopes(&[ApiKeyScope::ReadOnly]), None));
+        assert_noop!(
+            create(scopes(&[ApiKeyScope::ConsentQuery]), None),
+            Error::<Test>::ApiKeyAlreadyExists
+        );
+    });
+}
+
 #[test]
 fn create_multiple_api_keys_works() {
     new_test_ext().execute_with(|| {
@@ -213,15 +355,8 @@ fn create_multiple_api_keys_works() {
 
         // Create multiple API keys
         for i in 0..3 {
-            let key_hash = create_key_hash(&format!("key_{}", i));
-            let name = sp_runtime::BoundedVec::try_from(format!("Key {}", i).as_bytes().to_vec())
-                .unwrap();
-
-            assert_ok!(Authentication::create_api_key(
-                RuntimeOrigin::signed(account),
-                key_hash,
-                name
-            ));
+            let key_hash = create_api_key(account, &format!("key_{}", i), None);
+            assert_eq!(Authentication::api_keys(key_hash).unwrap().account, account);
         }
     });
 }
@@ -232,15 +367,8 @@ fn revoke_api_key_works() {
         let account = 1u64;
         register_user(account);
 
-        let key_hash = create_key_hash("my_secret_key");
-        let name = sp_runtime::BoundedVec::try_from(b"Test Key".to_vec()).unwrap();
-
         // Create API key
-        assert_ok!(Authentication::create_api_key(
-            RuntimeOrigin::signed(account),
-            key_hash,
-            name
-        ));
+        let key_hash = create_api_key(account, "backend", None);
 
         // Revoke API key
         assert_ok!(Authentication::revoke_api_key(
@@ -266,15 +394,8 @@ fn revoke_api_key_fails_when_not_owner() {
         register_user(account1);
         register_user(account2);
 
-        let key_hash = create_key_hash("my_secret_key");
-        let name = sp_runtime::BoundedVec::try_from(b"Test Key".to_vec()).unwrap();
-
         // Create API key for account1
-        assert_ok!(Authentication::create_api_key(
-            RuntimeOrigin::signed(account1),
-            key_hash,
-            name
-        ));
+        let key_hash = create_api_key(account1, "backend", None);
 
         // Try to revoke as account2
         assert_noop!(
@@ -290,7 +411,7 @@ fn revoke_api_key_fails_when_not_found() {
         let account = 1u64;
         register_user(account);
 
-        let fake_key_hash = create_key_hash("nonexistent_key");
+        let fake_key_hash = Authentication::api_key_hash(&api_key_pair("nonexistent").public());
 
         assert_noop!(
             Authentication::revoke_api_key(RuntimeOrigin::signed(account), fake_key_hash),
@@ -299,6 +420,151 @@ fn revoke_api_key_fails_when_not_found() {
     });
 }
 
+#[test]
+fn authenticate_api_request_works() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        let relayer = 9u64;
+        register_user(account);
+        let key_hash = create_api_key(account, "backend", None);
+
+        Timestamp::set_timestamp(7000);
+        let (request, signature) = sign_request("backend", ApiKeyScope::ConsentQuery, 1);
+
+        // Verification alone leaves the key untouched
+        assert_eq!(
+            Authentication::verify_api_request(&request, &signature).map(|key| key.account),
+            Ok(account)
+        );
+        assert!(Authentication::api_keys(key_hash).unwrap().last_used.is_none());
+
+        assert_ok!(Authentication::authenticate_api_request(
+            RuntimeOrigin::signed(relayer),
+            request.clone(),
+            signature.clone(),
+        ));
+
+        let api_key = Authentication::api_keys(key_hash).unwrap();
+        assert_eq!(api_key.last_used, Some(7000));
+        assert_eq!(api_key.nonce, 1);
+        System::assert_last_event(
+            Event::ApiRequestAuthenticated {
+                key_hash,
+                account,
+                scope: ApiKeyScope::ConsentQuery,
+                payload_hash: request.payload_hash,
+            }
+            .into(),
+        );
+
+        // The same request cannot be replayed
+        assert_noop!(
+            Authentication::authenticate_api_request(
+                RuntimeOrigin::signed(relayer),
+                request,
+                signature
+            ),
+            Error::<Test>::StaleNonce
+        );
+    });
+}
+
+#[test]
+fn api_request_signature_is_bound_to_context_and_chain() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+        create_api_key(account, "backend", None);
+        let pair = api_key_pair("backend");
+        let (request, _) = sign_request("backend", ApiKeyScope::ConsentQuery, 1);
+        let verify = |payload: Vec<u8>| {
+            Authentication::verify_api_request(&request, &pair.sign(&payload))
+                .map(|key| key.account)
+        };
+
+        // Signatures over the bare request, or for another chain, are refused
+        assert_eq!(verify(request.encode()), Err(ApiKeyError::InvalidSignature));
+        assert_eq!(
+            verify((API_REQUEST_CONTEXT, H256::repeat_byte(0xff), &request).encode()),
+            Err(ApiKeyError::InvalidSignature)
+        );
+
+        let genesis_hash = System::block_hash(0);
+        assert_eq!(verify((API_REQUEST_CONTEXT, genesis_hash, &request).encode()), Ok(account));
+    });
+}
+
+#[test]
+fn authenticate_api_request_checks_key() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+
+        let public_key = api_key_pair("backend").public();
+        assert_ok!(Authentication::create_api_key(
+            RuntimeOrigin::signed(account),
+            public_key,
+            sp_runtime::BoundedVec::try_from(b"Reader".to_vec()).unwrap(),
+            scopes(&[ApiKeyScope::ReadOnly]),
+            Some(10000),
+        ));
+        let key_hash = Authentication::api_key_hash(&public_key);
+        let authenticate = |(request, signature)| {
+            Authentication::authenticate_api_request(RuntimeOrigin::signed(9), request, signature)
+        };
+
+        // Scope not granted to the key
+        assert_noop!(
+            authenticate(sign_request("backend", ApiKeyScope::MarketplaceTrade, 1)),
+            Error::<Test>::ScopeNotAllowed
+        );
+
+        // Signed with another key
+        let (request, _) = sign_request("backend", ApiKeyScope::ReadOnly, 1);
+        let (_, signature) = sign_request("intruder", ApiKeyScope::ReadOnly, 1);
+        assert_noop!(authenticate((request, signature)), Error::<Test>::InvalidSignature);
+
+        // Unknown key
+        assert_noop!(
+            authenticate(sign_request("intruder", ApiKeyScope::ReadOnly, 1)),
+            Error::<Test>::ApiKeyNotFound
+        );
+
+        // Expired key
+        Timestamp::set_timestamp(10000);
+        assert_noop!(
+            authenticate(sign_request("backend", ApiKeyScope::ReadOnly, 1)),
+            Error::<Test>::ApiKeyExpired
+        );
+
+        // Revoked key
+        Timestamp::set_timestamp(5000);
+        assert_ok!(authenticate(sign_request("backend", ApiKeyScope::ReadOnly, 1)));
+        assert_ok!(Authentication::revoke_api_key(RuntimeOrigin::signed(account), key_hash));
+        assert_noop!(
+            authenticate(sign_request("backend", ApiKeyScope::ReadOnly, 2)),
+            Error::<Test>::ApiKeyInactive
+        );
+    });
+}
+
+#[test]
+fn api_keys_of_deactivated_identities_are_refused() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        register_user(account);
+        create_api_key(account, "backend", None);
+
+        assert_ok!(IdentityRegistry::deactivate_identity(RuntimeOrigin::signed(account)));
+
+        let (request, signature) = sign_request("backend", ApiKeyScope::ReadOnly, 1);
+        assert_eq!(
+            Authentication::verify_api_request(&request, &signature).map(|key| key.account),
+            Err(ApiKeyError::InactiveIdentity)
+        );
+    });
+}
+
 #[test]
 fn session_validation_helper_works() {
     new_test_ext().execute_with(|| {
@@ -309,7 +575,11 @@ fn session_validation_helper_works() {
         Timestamp::set_timestamp(1000);
 
         // Create session
-        assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+        assert_ok!(Authentication::create_session(
+            RuntimeOrigin::signed(account),
+            device("Phone"),
+            false,
+        ));
 
         let events = System::events();
         let session_id = if let RuntimeEvent::Authentication(Event::SessionCreated {
@@ -356,7 +626,11 @@ fn session_expires_correctly() {
 
         // Create session at t=1000
         Timestamp::set_timestamp(1000);
-        assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+        assert_ok!(Authentication::create_session(
+            RuntimeOrigin::signed(account),
+            device("Phone"),
+            false,
+        ));
 
         let events = System::events();
         let session_id = if let RuntimeEvent::Authentication(Event::SessionCreated {
@@ -400,12 +674,16 @@ fn max_sessions_can_be_reached() {
 
         // Create maximum number of sessions (10)
         for _ in 0..10 {
-            assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+            assert_ok!(Authentication::create_session(
+                RuntimeOrigin::signed(account),
+                device("Phone"),
+                false,
+            ));
         }
 
         // Try to create one more - should fail
         assert_noop!(
-            Authentication::create_session(RuntimeOrigin::signed(account)),
+            Authentication::create_session(RuntimeOrigin::signed(account), device("Phone"), false),
             Error::<Test>::MaxSessionsReached
         );
     });
@@ -426,13 +704,14 @@ fn api_key_name_variations_work() {
         ];
 
         for (i, name_str) in test_names.iter().enumerate() {
-            let key_hash = create_key_hash(&format!("key_{}", i));
             let name = sp_runtime::BoundedVec::try_from(name_str.as_bytes().to_vec()).unwrap();
 
             assert_ok!(Authentication::create_api_key(
                 RuntimeOrigin::signed(account),
-                key_hash,
-                name
+                api_key_pair(&format!("key_{}", i)).public(),
+                name,
+                scopes(&[ApiKeyScope::ReadOnly]),
+                None,
             ));
         }
     });
@@ -446,7 +725,11 @@ fn session_lifecycle_complete_test() {
 
         // 1. Create session
         Timestamp::set_timestamp(5000);
-        assert_ok!(Authentication::create_session(RuntimeOrigin::signed(account)));
+        assert_ok!(Authentication::create_session(
+            RuntimeOrigin::signed(account),
+            device("Phone"),
+            false,
+        ));
 
         let events = System::events();
         let session_id = if let RuntimeEvent::Authentication(Event::SessionCreated {
@@ -476,8 +759,7 @@ fn session_lifecycle_complete_test() {
         ));
 
         // 5. Verify session is revoked
-        let session = Authentication::sessions(session_id).unwrap();
-        assert!(!session.active);
+        assert_eq!(Authentication::sessions(session_id), None);
         assert!(!Authentication::is_session_valid(&session_id, 5000));
     });
 }
@@ -489,14 +771,17 @@ fn api_key_lifecycle_complete_test() {
         register_user(account);
 
         // 1. Create API key
-        let key_hash = create_key_hash("production_key");
+        let public_key = api_key_pair("production_key").public();
+        let key_hash = Authentication::api_key_hash(&public_key);
         let name = sp_runtime::BoundedVec::try_from(b"Production".to_vec()).unwrap();
 
         Timestamp::set_timestamp(10000);
         assert_ok!(Authentication::create_api_key(
             RuntimeOrigin::signed(account),
-            key_hash,
-            name.clone()
+            public_key,
+            name.clone(),
+            scopes(&[ApiKeyScope::ReadOnly]),
+            None,
         ));
 
         // 2. Verify API key details
@@ -518,3 +803,56 @@ fn api_key_lifecycle_complete_test() {
         assert!(!api_key.active);
     });
 }
+
+#[test]
+fn migration_to_v1_drops_hash_only_api_keys() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Authentication>();
+        let key_hash = H256::repeat_byte(1);
+
+        // Version 0 layout: account, key_hash, name, created_at, last_used, active
+        let old = (1u64, key_hash, create_name("backend"), 1000u64, None::<u64>, true);
+        unhashed::put(&ApiKeys::<Test>::hashed_key_for(key_hash), &old);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Authentication::on_chain_storage_version(), 1);
+        assert!(!unhashed::exists(&ApiKeys::<Test>::hashed_key_for(key_hash)));
+        assert!(Authentication::api_keys(key_hash).is_none());
+    });
+}
+
+#[test]
+fn migration_to_v1_gives_sessions_an_unknown_device() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Authentication>();
+        let account = 1u64;
+        let live = H256::repeat_byte(1);
+        let revoked = H256::repeat_byte(2);
+        for (session_id, active) in [(live, true), (revoked, false)] {
+            let old = OldSession::<Test> {
+                account,
+                session_id,
+                created_at: 1000,
+                expires_at: 5000,
+                active,
+            };
+            unhashed::put(&Sessions::<Test>::hashed_key_for(session_id), &old);
+        }
+        AccountSessions::<Test>::insert(
+            account,
+            sp_runtime::BoundedVec::truncate_from(vec![live, revoked]),
+        );
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let session = Authentication::sessions(live).unwrap();
+        assert_eq!(session.account, account);
+        assert_eq!((session.created_at, session.expires_at), (1000, 5000));
+        assert_eq!(session.device, DeviceInfo::default());
+
+        // Revoked sessions are removed, as revocation does today
+        assert!(Authentication::sessions(revoked).is_none());
+        assert_eq!(Authentication::account_sessions(account).to_vec(), vec![live]);
+    });
+}
@@ -0,0 +1,124 @@
+//! Estimated weights for `pallet_authentication`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/identity-consent-node benchmark pallet \
+//!     --chain dev --pallet pallet_authentication --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/authentication/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_authentication`.
+pub trait WeightInfo {
+    fn create_session() -> Weight;
+    fn revoke_session() -> Weight;
+    fn revoke_all_sessions() -> Weight;
+    fn create_api_key() -> Weight;
+    fn revoke_api_key() -> Weight;
+    fn authenticate_api_request() -> Weight;
+}
+
+/// Estimated weights for `pallet_authentication`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `IdentityRegistry::Identities`, `Timestamp::Now`, `Authentication::AccountSessions`,
+    //     `Authentication::Sessions` x10, `Authentication::SessionNonces`
+    // Writes: `Authentication::AccountSessions`, `Authentication::Sessions` x11,
+    //     `Authentication::SessionNonces`
+    fn create_session() -> Weight {
+        Weight::from_parts(68_000_000, 26_980)
+            .saturating_add(T::DbWeight::get().reads(14_u64))
+            .saturating_add(T::DbWeight::get().writes(13_u64))
+    }
+
+    // Reads: `Authentication::Sessions`, `Authentication::AccountSessions`
+    // Writes: `Authentication::Sessions`, `Authentication::AccountSessions`
+    fn revoke_session() -> Weight {
+        Weight::from_parts(24_000_000, 7_546)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `Authentication::AccountSessions`
+    // Writes: `Authentication::AccountSessions`, `Authentication::Sessions` x10
+    fn revoke_all_sessions() -> Weight {
+        Weight::from_parts(42_000_000, 3_855)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(11_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `Timestamp::Now`, `Authentication::ApiKeys`
+    // Writes: `Authentication::ApiKeys`
+    fn create_api_key() -> Weight {
+        Weight::from_parts(24_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Authentication::ApiKeys`
+    // Writes: `Authentication::ApiKeys`
+    fn revoke_api_key() -> Weight {
+        Weight::from_parts(16_000_000, 3_720)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Authentication::ApiKeys`, `System::BlockHash`, `Timestamp::Now`,
+    //     `IdentityRegistry::Identities`
+    // Writes: `Authentication::ApiKeys`
+    fn authenticate_api_request() -> Weight {
+        Weight::from_parts(74_000_000, 6_277)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn create_session() -> Weight {
+        Weight::from_parts(68_000_000, 26_980)
+            .saturating_add(RocksDbWeight::get().reads(14_u64))
+            .saturating_add(RocksDbWeight::get().writes(13_u64))
+    }
+
+    fn revoke_session() -> Weight {
+        Weight::from_parts(24_000_000, 7_546)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn revoke_all_sessions() -> Weight {
+        Weight::from_parts(42_000_000, 3_855)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(11_u64))
+    }
+
+    fn create_api_key() -> Weight {
+        Weight::from_parts(24_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn revoke_api_key() -> Weight {
+        Weight::from_parts(16_000_000, 3_720)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn authenticate_api_request() -> Weight {
+        Weight::from_parts(74_000_000, 6_277)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+}
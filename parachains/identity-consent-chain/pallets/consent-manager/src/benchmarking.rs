@@ -0,0 +1,714 @@
+//! Benchmarking setup for pallet-consent-manager
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ConsentManager;
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{
+    traits::{ConstU32, EnsureOrigin},
+    BoundedVec,
+};
+use frame_system::RawOrigin;
+use pallet_identity_registry::{
+    Attestation, AttestationKind, OnGuardianshipChanged, UserRole, VerificationStatus,
+};
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Hex digits used to build DIDs from account IDs
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Register an identity with the given role
+fn register<T: Config>(who: &T::AccountId, role: UserRole) {
+    let mut did = b"did:patientx:".to_vec();
+    for byte in who.encode() {
+        did.extend_from_slice(&[HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]);
+    }
+
+    pallet_identity_registry::Pallet::<T>::register_identity(
+        RawOrigin::Signed(who.clone()).into(),
+        BoundedVec::truncate_from(did),
+        role.clone(),
+        BoundedVec::truncate_from(b"Benchmark".to_vec()),
+        H256::repeat_byte(1),
+    )
+    .expect("identity registration must succeed");
+
+    // Hold the requested role as if it had been verified
+    pallet_identity_registry::RoleRequests::<T>::remove(who, &role);
+    pallet_identity_registry::Identities::<T>::mutate(who, |identity| {
+        if let Some(identity) = identity {
+            identity.roles = BoundedVec::truncate_from(vec![role]);
+        }
+    });
+}
+
+/// All data types, the largest set a consent can hold
+fn all_data_types() -> BoundedVec<DataType, ConstU32<10>> {
+    BoundedVec::truncate_from(vec![
+        DataType::All,
+        DataType::LabResults,
+        DataType::Imaging,
+        DataType::Prescriptions,
+        DataType::Diagnosis,
+        DataType::Genomic,
+        DataType::Vitals,
+        DataType::Demographics,
+    ])
+}
+
+/// Create a consent between a fresh patient and researcher
+fn setup_consent<T: Config>() -> (T::AccountId, T::AccountId, H256) {
+    let patient: T::AccountId = whitelisted_caller();
+    let researcher: T::AccountId = account("researcher", 0, 0);
+    register::<T>(&patient, UserRole::Patient);
+    register::<T>(&researcher, UserRole::Researcher);
+
+    Pallet::<T>::create_consent(
+        RawOrigin::Signed(patient.clone()).into(),
+        researcher.clone(),
+        DataPurpose::Research,
+        all_data_types(),
+        0,
+        H256::repeat_byte(2),
+    )
+    .expect("consent creation must succeed");
+
+    let consent_id = *OwnerConsents::<T>::get(&patient).last().expect("consent was just created");
+    (patient, researcher, consent_id)
+}
+
+/// Fill the owned, policy and held consent lists of the whitelisted patient, leaving more
+/// consents than a migration batch moves
+fn full_consent_lists<T: Config>() -> T::AccountId {
+    let (patient, _, consent_id) = setup_consent::<T>();
+    let consent = Consents::<T>::get(consent_id).expect("consent was just created");
+
+    let consents: Vec<H256> = (0..1000u64).map(H256::from_low_u64_be).collect();
+    let obtained = policy_consent_ids::<T>();
+    for id in consents.iter().chain(obtained.iter()) {
+        Consents::<T>::insert(id, Consent { consent_id: *id, ..consent.clone() });
+    }
+    OwnerConsents::<T>::insert(&patient, BoundedVec::truncate_from(consents.clone()));
+    OwnerPolicyConsents::<T>::insert(&patient, BoundedVec::truncate_from(obtained));
+    ConsumerConsents::<T>::insert(&patient, BoundedVec::truncate_from(consents));
+
+    patient
+}
+
+/// A full list of policy consent IDs, apart from the IDs of other benchmark consents
+fn policy_consent_ids<T: Config>() -> Vec<H256> {
+    (0..T::MaxPolicyConsentsPerOwner::get() as u64)
+        .map(|i| H256::from_low_u64_be(u64::MAX - i))
+        .collect()
+}
+
+/// Add a member acting for `organization`, the most expensive accessor to authorize
+fn member<T: Config>(organization: &T::AccountId) -> T::AccountId {
+    let member: T::AccountId = account("member", 0, 0);
+    register::<T>(&member, UserRole::Researcher);
+    T::BenchmarkHelper::add_member(organization, &member);
+    member
+}
+
+/// The most expensive scope to evaluate: every restriction set and bounds filled
+fn full_scope() -> ConsentScope {
+    ConsentScope {
+        record_ids: BoundedVec::truncate_from((0..32u64).map(H256::from_low_u64_be).collect()),
+        records_from: Some(0),
+        records_until: Some(u64::MAX),
+        excluded_categories: BoundedVec::truncate_from(vec![
+            RecordCategory::Imaging,
+            RecordCategory::Prescription,
+            RecordCategory::Diagnosis,
+            RecordCategory::Genomic,
+            RecordCategory::Vitals,
+            RecordCategory::Immunization,
+            RecordCategory::Surgery,
+            RecordCategory::Other,
+        ]),
+        de_identified_only: true,
+        no_resharing: true,
+        max_accesses: Some(u32::MAX),
+        record_versions: BoundedVec::truncate_from(
+            (0..32u64).map(|id| (H256::from_low_u64_be(id), 1)).collect(),
+        ),
+    }
+}
+
+/// Restrict a consent to `full_scope`, returning a record matching it last
+fn restrict<T: Config>(consent_id: H256) -> Option<RecordContext> {
+    Consents::<T>::mutate(consent_id, |consent| {
+        consent.as_mut().expect("consent was just created").scope = full_scope();
+    });
+
+    Some(RecordContext {
+        record_id: H256::from_low_u64_be(31),
+        category: RecordCategory::LabResults,
+        created_at: 1,
+        de_identified: true,
+        version: Some(1),
+    })
+}
+
+/// Register a verified institution
+fn verified_institution<T: Config>() -> T::AccountId {
+    let institution: T::AccountId = account("institution", 0, 0);
+    register::<T>(&institution, UserRole::Institution);
+    let verifier: T::AccountId = account("verifier", 0, 0);
+    pallet_identity_registry::Verifiers::<T>::put(BoundedVec::truncate_from(vec![
+        verifier.clone(),
+    ]));
+    pallet_identity_registry::Attestations::<T>::insert(
+        &institution,
+        &verifier,
+        Attestation {
+            kind: AttestationKind::InstitutionalAccreditation,
+            evidence_hash: H256::repeat_byte(1),
+            attested_at: 0,
+            expires_at: None,
+        },
+    );
+    pallet_identity_registry::Identities::<T>::mutate(&institution, |identity| {
+        if let Some(identity) = identity {
+            identity.verification_status = VerificationStatus::Verified;
+        }
+    });
+    institution
+}
+
+/// Publish a template from a verified institution, superseding `supersedes` if given
+fn publish<T: Config>(institution: &T::AccountId, supersedes: Option<H256>) -> H256 {
+    Pallet::<T>::publish_template(
+        RawOrigin::Signed(institution.clone()).into(),
+        DataPurpose::Research,
+        all_data_types(),
+        MAX_DURATION,
+        H256::repeat_byte(4),
+        supersedes,
+    )
+    .expect("template publication must succeed");
+
+    ConsentTemplates::<T>::iter()
+        .find(|(_, template)| template.supersedes == supersedes)
+        .map(|(template_id, _)| template_id)
+        .expect("template was just published")
+}
+
+/// Publish and approve a template
+fn approved_template<T: Config>(institution: &T::AccountId) -> Result<H256, BenchmarkError> {
+    let template_id = publish::<T>(institution, None);
+    let origin =
+        T::TemplateApprovalOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    Pallet::<T>::approve_template(origin, template_id).expect("template approval must succeed");
+    Ok(template_id)
+}
+
+/// Maximum duration of benchmark templates (30 days)
+const MAX_DURATION: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Register a patient whose policies are filled up to one below their bound
+fn patient_with_policies<T: Config>() -> T::AccountId {
+    let patient: T::AccountId = whitelisted_caller();
+    register::<T>(&patient, UserRole::Patient);
+
+    let existing: Vec<H256> =
+        (1..T::MaxPoliciesPerOwner::get() as u64).map(H256::from_low_u64_be).collect();
+    OwnerPolicies::<T>::insert(&patient, BoundedVec::truncate_from(existing));
+    patient
+}
+
+/// Schema of the credential benchmark policies require
+const POLICY_CREDENTIAL: H256 = H256::repeat_byte(6);
+
+/// Publish a policy open to verified, credentialed institutions, returning its ID
+fn publish_policy_for<T: Config>(patient: &T::AccountId) -> H256 {
+    Pallet::<T>::publish_policy(
+        RawOrigin::Signed(patient.clone()).into(),
+        UserRole::Institution,
+        true,
+        Some(POLICY_CREDENTIAL),
+        DataPurpose::PublicHealth,
+        all_data_types(),
+        MAX_DURATION,
+        full_scope(),
+        H256::repeat_byte(5),
+    )
+    .expect("policy publication must succeed");
+
+    *OwnerPolicies::<T>::get(patient).last().expect("policy was just published")
+}
+
+/// Fill the first page of the consent's access log, leaving room for `room` entries
+fn fill_logs<T: Config>(consent_id: H256, accessor: &T::AccountId, room: u32) {
+    let logs: Vec<AccessLog<T>> = (0..T::AccessLogPageSize::get().saturating_sub(room))
+        .map(|_| AccessLog {
+            consent_id,
+            accessor: accessor.clone(),
+            organization: Some(accessor.clone()),
+            accessed_at: 0,
+            data_hash: H256::zero(),
+            approved: true,
+        })
+        .collect();
+    AccessLogs::<T>::insert(consent_id, 0, BoundedVec::truncate_from(logs));
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_consent() {
+        let patient: T::AccountId = whitelisted_caller();
+        let researcher: T::AccountId = account("researcher", 0, 0);
+        register::<T>(&patient, UserRole::Patient);
+        register::<T>(&researcher, UserRole::Researcher);
+
+        // Fill both indices up to one below their bound
+        let existing: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+        OwnerConsents::<T>::insert(&patient, BoundedVec::truncate_from(existing.clone()));
+        ConsumerConsents::<T>::insert(&researcher, BoundedVec::truncate_from(existing));
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(patient.clone()),
+            researcher,
+            DataPurpose::Research,
+            all_data_types(),
+            0,
+            H256::repeat_byte(2),
+        );
+
+        assert_eq!(OwnerConsents::<T>::get(&patient).len(), 1000);
+    }
+
+    #[benchmark]
+    fn revoke_consent() {
+        let (patient, _, consent_id) = setup_consent::<T>();
+        OwnerPolicyConsents::<T>::insert(
+            &patient,
+            BoundedVec::truncate_from(policy_consent_ids::<T>()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), consent_id);
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().status, ConsentStatus::Revoked);
+    }
+
+    #[benchmark]
+    fn act_for_ward() {
+        let (ward, _, consent_id) = setup_consent::<T>();
+        let guardian: T::AccountId = whitelisted_caller();
+        register::<T>(&guardian, UserRole::Patient);
+        pallet_identity_registry::Guardianships::<T>::insert(
+            &ward,
+            &guardian,
+            pallet_identity_registry::Guardianship {
+                kind: pallet_identity_registry::GuardianshipKind::Parent,
+                evidence_hash: H256::repeat_byte(7),
+                handover_at: None,
+                registered_at: 0,
+            },
+        );
+        let call = Box::new(Call::<T>::revoke_consent { consent_id });
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(guardian), ward, call);
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().status, ConsentStatus::Revoked);
+    }
+
+    #[benchmark]
+    fn update_consent() {
+        let (patient, _, consent_id) = setup_consent::<T>();
+        let data_types = BoundedVec::truncate_from(vec![DataType::Genomic]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), consent_id, Some(u64::MAX), Some(data_types));
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().expires_at, u64::MAX);
+    }
+
+    #[benchmark]
+    fn log_access() {
+        let (_, researcher, consent_id) = setup_consent::<T>();
+        fill_logs::<T>(consent_id, &researcher, 1);
+        let record = restrict::<T>(consent_id);
+        let member = member::<T>(&researcher);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(member), consent_id, H256::repeat_byte(3), record);
+
+        assert_eq!(AccessLogs::<T>::get(consent_id, 0).len() as u32, T::AccessLogPageSize::get());
+    }
+
+    #[benchmark]
+    fn check_consent() {
+        let (_, researcher, consent_id) = setup_consent::<T>();
+        let caller: T::AccountId = account("caller", 0, 0);
+        let record = restrict::<T>(consent_id);
+        let member = member::<T>(&researcher);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), consent_id, member, record);
+    }
+
+    #[benchmark]
+    fn verify_consent() -> Result<(), BenchmarkError> {
+        let (patient, researcher, consent_id) = setup_consent::<T>();
+        let record = restrict::<T>(consent_id);
+        let member = member::<T>(&researcher);
+        let origin =
+            T::ConsentQueryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, consent_id, patient, member, record);
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().access_count, 1);
+
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(ConsentManager, crate::mock::new_test_ext(), crate::mock::Test);
+
+    #[benchmark]
+    fn archive_access_logs() {
+        let (patient, researcher, consent_id) = setup_consent::<T>();
+        fill_logs::<T>(consent_id, &researcher, 0);
+        AccessLogIndex::<T>::insert(
+            consent_id,
+            LogIndex { first_page: 0, current_page: 1, total: T::AccessLogPageSize::get().into() },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), consent_id);
+
+        assert!(ArchivedAccessLogs::<T>::contains_key(consent_id, 0));
+    }
+
+    #[benchmark]
+    fn publish_template() {
+        let institution = verified_institution::<T>();
+        let previous = publish::<T>(&institution, None);
+
+        // Publishing a new version also reads the previous one
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(institution),
+            DataPurpose::Research,
+            all_data_types(),
+            MAX_DURATION,
+            H256::repeat_byte(5),
+            Some(previous),
+        );
+
+        assert_eq!(TemplateCount::<T>::get(), 2);
+    }
+
+    #[benchmark]
+    fn approve_template() -> Result<(), BenchmarkError> {
+        let institution = verified_institution::<T>();
+        let previous = approved_template::<T>(&institution)?;
+        let template_id = publish::<T>(&institution, Some(previous));
+        let origin = T::TemplateApprovalOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        // Approving a new version retires the previous one
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, template_id);
+
+        assert_eq!(ConsentTemplates::<T>::get(previous).unwrap().status, TemplateStatus::Retired);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn retire_template() -> Result<(), BenchmarkError> {
+        let institution = verified_institution::<T>();
+        let template_id = approved_template::<T>(&institution)?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(institution), template_id);
+
+        let template = ConsentTemplates::<T>::get(template_id).unwrap();
+        assert_eq!(template.status, TemplateStatus::Retired);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn create_consent_from_template() -> Result<(), BenchmarkError> {
+        let institution = verified_institution::<T>();
+        let template_id = approved_template::<T>(&institution)?;
+        let patient: T::AccountId = whitelisted_caller();
+        register::<T>(&patient, UserRole::Patient);
+
+        // Fill both indices up to one below their bound
+        let existing: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+        OwnerConsents::<T>::insert(&patient, BoundedVec::truncate_from(existing.clone()));
+        ConsumerConsents::<T>::insert(&institution, BoundedVec::truncate_from(existing));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient.clone()), template_id, institution, MAX_DURATION);
+
+        assert_eq!(OwnerConsents::<T>::get(&patient).len(), 1000);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_consent_scope() {
+        let (patient, _, consent_id) = setup_consent::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), consent_id, full_scope());
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().scope, full_scope());
+    }
+
+    #[benchmark]
+    fn publish_policy() {
+        let patient = patient_with_policies::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(patient.clone()),
+            UserRole::Institution,
+            true,
+            Some(POLICY_CREDENTIAL),
+            DataPurpose::PublicHealth,
+            all_data_types(),
+            MAX_DURATION,
+            full_scope(),
+            H256::repeat_byte(5),
+        );
+
+        assert_eq!(OwnerPolicies::<T>::get(&patient).len() as u32, T::MaxPoliciesPerOwner::get());
+    }
+
+    #[benchmark]
+    fn withdraw_policy() {
+        let patient = patient_with_policies::<T>();
+        let policy_id = publish_policy_for::<T>(&patient);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), policy_id);
+
+        assert!(!ConsentPolicies::<T>::get(policy_id).unwrap().active);
+    }
+
+    #[benchmark]
+    fn exclude_consumer() {
+        let patient = patient_with_policies::<T>();
+        let policy_id = publish_policy_for::<T>(&patient);
+        let institution = verified_institution::<T>();
+        Pallet::<T>::request_policy_consent(
+            RawOrigin::Signed(institution.clone()).into(),
+            policy_id,
+        )
+        .expect("policy consent must succeed");
+        let consent_id = PolicyConsents::<T>::get(policy_id, &institution).unwrap();
+
+        // The consent is the last of a full index
+        let mut obtained = policy_consent_ids::<T>();
+        obtained.pop();
+        obtained.push(consent_id);
+        OwnerPolicyConsents::<T>::insert(&patient, BoundedVec::truncate_from(obtained));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient.clone()), policy_id, institution.clone());
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().status, ConsentStatus::Revoked);
+        assert!(!OwnerPolicyConsents::<T>::get(&patient).contains(&consent_id));
+    }
+
+    #[benchmark]
+    fn readmit_consumer() {
+        let patient = patient_with_policies::<T>();
+        let policy_id = publish_policy_for::<T>(&patient);
+        let institution = verified_institution::<T>();
+        PolicyOptOuts::<T>::insert(policy_id, &institution, ());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient), policy_id, institution.clone());
+
+        assert!(!PolicyOptOuts::<T>::contains_key(policy_id, &institution));
+    }
+
+    #[benchmark]
+    fn request_policy_consent() {
+        let patient = patient_with_policies::<T>();
+        let policy_id = publish_policy_for::<T>(&patient);
+        let institution = verified_institution::<T>();
+        T::BenchmarkHelper::grant_credential(&institution, &POLICY_CREDENTIAL);
+
+        // A lapsed consent from the policy has to be looked up first
+        Pallet::<T>::request_policy_consent(
+            RawOrigin::Signed(institution.clone()).into(),
+            policy_id,
+        )
+        .expect("policy consent must succeed");
+        let previous = PolicyConsents::<T>::get(policy_id, &institution).unwrap();
+        Consents::<T>::mutate(previous, |consent| {
+            consent.as_mut().expect("consent was just created").status = ConsentStatus::Revoked;
+        });
+
+        // Fill both indices up to one below their bound, the lapsed consent last
+        let mut obtained = policy_consent_ids::<T>();
+        obtained.truncate(obtained.len().saturating_sub(2));
+        obtained.push(previous);
+        OwnerPolicyConsents::<T>::insert(&patient, BoundedVec::truncate_from(obtained));
+        let existing: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+        ConsumerConsents::<T>::insert(&institution, BoundedVec::truncate_from(existing));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(institution.clone()), policy_id);
+
+        assert_ne!(PolicyConsents::<T>::get(policy_id, &institution), Some(previous));
+        assert!(!OwnerPolicyConsents::<T>::get(&patient).contains(&previous));
+    }
+
+    #[benchmark]
+    fn request_erasure() {
+        let (patient, _, consent_id) = setup_consent::<T>();
+        let consent = Consents::<T>::get(consent_id).expect("consent was just created");
+
+        // Full lists of active consents, all of which are revoked
+        let consents: Vec<H256> = (0..1000u64).map(H256::from_low_u64_be).collect();
+        let obtained = policy_consent_ids::<T>();
+        for id in consents.iter().chain(obtained.iter()) {
+            Consents::<T>::insert(id, Consent { consent_id: *id, ..consent.clone() });
+        }
+        OwnerConsents::<T>::insert(&patient, BoundedVec::truncate_from(consents));
+        OwnerPolicyConsents::<T>::insert(&patient, BoundedVec::truncate_from(obtained));
+
+        // A full list of active policies, all of which are withdrawn
+        let policy_id = publish_policy_for::<T>(&patient);
+        let policy = ConsentPolicies::<T>::get(policy_id).expect("policy was just published");
+        let policies: Vec<H256> =
+            (0..T::MaxPoliciesPerOwner::get() as u64).map(H256::from_low_u64_be).collect();
+        for id in policies.iter() {
+            ConsentPolicies::<T>::insert(id, ConsentPolicy { policy_id: *id, ..policy.clone() });
+        }
+        OwnerPolicies::<T>::insert(&patient, BoundedVec::truncate_from(policies));
+
+        // Attestations from every verifier to clear
+        for i in 0..<T as pallet_identity_registry::Config>::MaxVerifiers::get() {
+            let verifier: T::AccountId = account("verifier", i, 0);
+            pallet_identity_registry::Attestations::<T>::insert(
+                &patient,
+                &verifier,
+                Attestation {
+                    kind: AttestationKind::LegalIdentity,
+                    evidence_hash: H256::repeat_byte(1),
+                    attested_at: 0,
+                    expires_at: None,
+                },
+            );
+        }
+
+        // Guardianships to end, as ward and as guardian
+        let guardianship = pallet_identity_registry::Guardianship {
+            kind: pallet_identity_registry::GuardianshipKind::Parent,
+            evidence_hash: H256::repeat_byte(7),
+            handover_at: None,
+            registered_at: 0,
+        };
+        for i in 0..<T as pallet_identity_registry::Config>::MaxGuardianships::get() {
+            let other: T::AccountId = account("guardianship", i, 0);
+            pallet_identity_registry::Guardianships::<T>::insert(
+                &patient,
+                &other,
+                guardianship.clone(),
+            );
+            pallet_identity_registry::Wards::<T>::insert(&other, &patient, ());
+            pallet_identity_registry::Guardianships::<T>::insert(
+                &other,
+                &patient,
+                guardianship.clone(),
+            );
+            pallet_identity_registry::Wards::<T>::insert(&patient, &other, ());
+        }
+        <T as pallet_identity_registry::Config>::GuardianshipHandler::ensure_successful();
+        T::ErasureHandler::ensure_successful();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(patient.clone()));
+
+        assert_eq!(
+            ErasureReceipts::<T>::get(&patient).unwrap().consents_revoked,
+            1000 + T::MaxPolicyConsentsPerOwner::get()
+        );
+    }
+
+    #[benchmark]
+    fn migrate_account() -> Result<(), BenchmarkError> {
+        let old = full_consent_lists::<T>();
+        let new: T::AccountId = account("new", 0, 0);
+
+        // A full list of policies, all of which are moved
+        let policy_id = publish_policy_for::<T>(&old);
+        let policy = ConsentPolicies::<T>::get(policy_id).expect("policy was just published");
+        let policies: Vec<H256> =
+            (0..T::MaxPoliciesPerOwner::get() as u64).map(H256::from_low_u64_be).collect();
+        for id in policies.iter() {
+            ConsentPolicies::<T>::insert(id, ConsentPolicy { policy_id: *id, ..policy.clone() });
+        }
+        OwnerPolicies::<T>::insert(&old, BoundedVec::truncate_from(policies));
+
+        let origin =
+            T::MigrationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, old.clone(), new.clone());
+
+        assert_eq!(OwnerPolicies::<T>::get(&new).len() as u32, T::MaxPoliciesPerOwner::get());
+        assert_eq!(AccountMigrations::<T>::get(&old), Some(new));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn continue_migration() {
+        let old = full_consent_lists::<T>();
+        let new: T::AccountId = account("new", 0, 0);
+        AccountMigrations::<T>::insert(&old, &new);
+        let caller: T::AccountId = account("caller", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), old.clone());
+
+        assert_eq!(
+            OwnerConsents::<T>::get(&new).len() as u32,
+            T::MaxMigrationBatch::get().min(1000)
+        );
+    }
+    #[benchmark]
+    fn expire_consent() {
+        let (patient, researcher, consent_id) = setup_consent::<T>();
+        Consents::<T>::mutate(consent_id, |consent| {
+            if let Some(consent) = consent {
+                consent.expires_at = 1;
+            }
+        });
+
+        // The consent is the last of full owner and consumer indexes
+        let mut consents: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+        consents.push(consent_id);
+        OwnerConsents::<T>::insert(&patient, BoundedVec::truncate_from(consents.clone()));
+        ConsumerConsents::<T>::insert(&researcher, BoundedVec::truncate_from(consents));
+        OwnerPolicyConsents::<T>::insert(
+            &patient,
+            BoundedVec::truncate_from(policy_consent_ids::<T>()),
+        );
+
+        #[block]
+        {
+            Pallet::<T>::expire_consent(consent_id, 2);
+        }
+
+        assert_eq!(Consents::<T>::get(consent_id).unwrap().status, ConsentStatus::Expired);
+        assert!(!OwnerConsents::<T>::get(&patient).contains(&consent_id));
+    }
+}
@@ -6,9 +6,93 @@
 //! It provides:
 //! - Granular consent creation with purpose, duration, and data type specifications
 //! - Consent revocation and expiry management
-//! - Cross-chain consent queries (via XCM)
+//! - Cross-chain consent queries (via XCM `Transact` from the HealthData chain)
 //! - Audit trail for all consent operations
 //!
+//! ## Consent templates
+//!
+//! Verified `Institution` identities publish consent templates fixing the purpose, data types,
+//! maximum duration and terms document of a consent. A template can be used by patients through
+//! `create_consent_from_template` once `TemplateApprovalOrigin` has approved it. Publishing a
+//! template that supersedes an older one creates its next version; approving it retires the
+//! older version. Consents keep the ID of the template they were created from.
+//!
+//! ## Consent scopes
+//!
+//! The data owner can narrow a consent with `set_consent_scope`: particular record IDs, a
+//! range of record creation dates, excluded record categories (e.g. `Genomic`),
+//! de-identified-only access, pinned record versions and a cap on the number of accesses. A
+//! record pinned to a version is only covered for accesses to that version; other records follow
+//! their latest version. Record-level restrictions are checked against the `RecordContext`
+//! passed to `log_access`, `check_consent` and `verify_consent`; the HealthData chain fills it in
+//! from the `HealthRecord` being requested, with the version the access request names.
+//! An access without a record context only passes a scope that covers all records. Every
+//! successful `verify_consent` and `log_access` consumes one of the capped accesses; only the
+//! consumer and its organization members can log an access. The no-resharing flag is recorded
+//! for the consumer and is not enforced on-chain.
+//!
+//! ## Standing policies
+//!
+//! Instead of signing each consent, a patient can publish a standing policy with
+//! `publish_policy`, e.g. "any verified Researcher may use my de-identified Vitals for
+//! PublicHealth for 90 days". A consumer holding the required role (and verified identity, if
+//! required) calls `request_policy_consent` to obtain a regular `Consent` carrying the policy's
+//! terms and scope. A policy can also require a valid credential of a given schema (e.g. "IRB
+//! member"), checked through `Config::Credentials`. The patient can exclude individual consumers
+//! with `exclude_consumer`, which also revokes the consent they obtained, and stop new consents
+//! with `withdraw_policy`.
+//!
+//! Consents obtained from policies are indexed in `OwnerPolicyConsents`, apart from the
+//! patient's own consents in `OwnerConsents`, so that consumers requesting them can never use up
+//! the room for the patient's own consents. Once `MaxPolicyConsentsPerOwner` of them are in
+//! force, further requests fail until some expire or are revoked.
+//!
+//! ## Organizations
+//!
+//! A consent granted to an organization's `Institution` account also covers the organization's
+//! members holding the data access permission, checked through `Config::Organizations`.
+//! Accesses logged by a member record both the member and the organization.
+//!
+//! ## Consent expiry
+//!
+//! Every consent with an expiry is scheduled in `ConsentExpiries` under the time bucket
+//! (`ExpiryBucketDuration` long) its `expires_at` falls in, ordered by `expires_at` within the
+//! bucket. `on_idle` walks the buckets up to the current one (at most `MaxExpiriesPerBlock`
+//! entries per block) and stops at the first consent that has not expired yet. Expired consents
+//! are marked `Expired` and removed from the `OwnerConsents`, `OwnerPolicyConsents` and
+//! `ConsumerConsents` indexes.
+//!
+//! ## Access logs
+//!
+//! Access logs are stored in pages of `AccessLogPageSize` entries, so a consent never stops
+//! accepting accesses. The data owner may archive the oldest full page with
+//! `archive_access_logs`: the page is removed and only its hash is kept on-chain.
+//!
+//! ## Erasure
+//!
+//! A patient exercises the right to erasure with `request_erasure`. Their identity is
+//! tombstoned in the Identity Registry, every consent they own is revoked and their standing
+//! policies are withdrawn. `ErasureHandler` then has the other chains erase the rest of their
+//! data: the runtime asks the HealthData chain over XCM to deactivate and wipe their records,
+//! unpin their IPFS content and revoke their encryption keys, and the Marketplace chain to
+//! delist their listings. An `ErasureReceipt` holding the hash of the erased identity is kept
+//! under the patient's account, and an account can be erased only once.
+//!
+//! ## Identity recovery
+//!
+//! When an identity is recovered to a new account, `MigrationOrigin` (the runtime's recovery
+//! handler) calls `migrate_account` to move the consents the lost account owns or holds, and
+//! its standing policies, to the new account. Consents are moved `MaxMigrationBatch` at a time;
+//! further batches are moved by anyone with `continue_migration`.
+//!
+//! ## Guardianship
+//!
+//! A legal guardian registered in the Identity Registry (e.g. the parent of a minor) manages
+//! the consents of their ward with `act_for_ward`, which dispatches a call of this pallet with
+//! the ward's signed origin. Only calls admitted by `WardCalls` can be dispatched, and never
+//! `request_erasure`: erasing a ward's data is left to the ward. Guardianship lapses at the
+//! ward's handover date.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "IdentityConsent Chain - Consent Management"
 
@@ -22,15 +106,32 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Time};
+    use frame_support::{
+        dispatch::GetDispatchInfo,
+        pallet_prelude::*,
+        traits::{Contains, Time, UnfilteredDispatchable},
+    };
+    use sp_runtime::traits::Hash;
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
     use pallet_identity_registry::{UserRole, Pallet as IdentityRegistry};
+    use crate::weights::WeightInfo;
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Purpose of data access
@@ -73,6 +174,105 @@ pub mod pallet {
         Demographics,
     }
 
+    /// Category of a health record on the HealthData chain
+    ///
+    /// Mirrors `pallet_health_records::RecordCategory`, which the HealthData chain encodes into
+    /// its consent queries; the variant order must match.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RecordCategory {
+        /// Lab test results
+        LabResults,
+        /// Medical imaging
+        Imaging,
+        /// Prescription records
+        Prescription,
+        /// Diagnosis and treatment notes
+        Diagnosis,
+        /// Genomic data
+        Genomic,
+        /// Vital signs and monitoring
+        Vitals,
+        /// Immunization records
+        Immunization,
+        /// Surgery records
+        Surgery,
+        /// Other medical records
+        Other,
+    }
+
+    /// Health record an access applies to, as described by the HealthData chain
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RecordContext {
+        /// Record ID
+        pub record_id: H256,
+        /// Record category
+        pub category: RecordCategory,
+        /// Record upload timestamp
+        pub created_at: u64,
+        /// Whether only de-identified data is released
+        pub de_identified: bool,
+        /// Record version accessed (`None` when following the latest version)
+        pub version: Option<u32>,
+    }
+
+    /// Restrictions on a consent beyond its purpose and data types (none by default)
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct ConsentScope {
+        /// Records covered (all of the owner's records when empty)
+        pub record_ids: BoundedVec<H256, ConstU32<32>>,
+        /// Only records created at or after this timestamp
+        pub records_from: Option<u64>,
+        /// Only records created at or before this timestamp
+        pub records_until: Option<u64>,
+        /// Record categories never covered
+        pub excluded_categories: BoundedVec<RecordCategory, ConstU32<9>>,
+        /// Only de-identified data may be released
+        pub de_identified_only: bool,
+        /// The consumer may not share the data with third parties
+        pub no_resharing: bool,
+        /// Maximum number of verified or logged accesses (`None` = unlimited)
+        pub max_accesses: Option<u32>,
+        /// Versions some records are pinned to (other records follow their latest version)
+        pub record_versions: BoundedVec<(H256, u32), ConstU32<32>>,
+    }
+
+    impl ConsentScope {
+        /// Whether the date range, if any, does not end before it starts
+        pub fn is_valid(&self) -> bool {
+            self.records_from.zip(self.records_until).map_or(true, |(from, until)| from <= until)
+        }
+
+        /// Whether the scope only covers some of the owner's records
+        pub fn is_record_specific(&self) -> bool {
+            !self.record_ids.is_empty()
+                || self.records_from.is_some()
+                || self.records_until.is_some()
+                || !self.excluded_categories.is_empty()
+                || self.de_identified_only
+                || !self.record_versions.is_empty()
+        }
+
+        /// Whether an access to `record` is covered (`None` when the record is not known)
+        pub fn covers(&self, record: Option<&RecordContext>) -> bool {
+            let Some(record) = record else {
+                return !self.is_record_specific();
+            };
+
+            (self.record_ids.is_empty() || self.record_ids.contains(&record.record_id))
+                && self.records_from.map_or(true, |from| record.created_at >= from)
+                && self.records_until.map_or(true, |until| record.created_at <= until)
+                && !self.excluded_categories.contains(&record.category)
+                && (!self.de_identified_only || record.de_identified)
+                && self
+                    .record_versions
+                    .iter()
+                    .find(|(record_id, _)| *record_id == record.record_id)
+                    .map_or(true, |(_, version)| record.version == Some(*version))
+        }
+    }
+
     /// Consent status
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub enum ConsentStatus {
@@ -108,12 +308,148 @@ pub mod pallet {
         pub status: ConsentStatus,
         /// Revocation timestamp (if revoked)
         pub revoked_at: Option<u64>,
-        /// Access count
+        /// Number of verified or logged accesses
         pub access_count: u32,
         /// Last accessed timestamp
         pub last_accessed: Option<u64>,
         /// Additional constraints (hash of terms)
         pub terms_hash: H256,
+        /// Template the consent was created from (if any)
+        pub template_id: Option<H256>,
+        /// Record-level restrictions
+        pub scope: ConsentScope,
+    }
+
+    /// Standing rule under which a patient's consent is given to any matching consumer
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ConsentPolicy<T: Config> {
+        /// Unique policy ID
+        pub policy_id: H256,
+        /// Data owner (patient)
+        pub owner: T::AccountId,
+        /// Role a consumer must hold (`Researcher` or `Institution`)
+        pub consumer_role: UserRole,
+        /// Only consumers with a verified identity qualify
+        pub verified_only: bool,
+        /// Schema of a credential consumers must hold
+        pub required_credential: Option<H256>,
+        /// Purpose of data access
+        pub purpose: DataPurpose,
+        /// Allowed data types
+        pub data_types: BoundedVec<DataType, ConstU32<10>>,
+        /// Duration of the consents created from the policy in milliseconds (0 = no expiry)
+        pub consent_duration: u64,
+        /// Scope of the consents created from the policy
+        pub scope: ConsentScope,
+        /// Hash of the terms document
+        pub terms_hash: H256,
+        /// Whether consumers can still obtain consents
+        pub active: bool,
+        /// Publication timestamp
+        pub created_at: u64,
+    }
+
+    /// Credentials held outside this pallet that consent policies can require
+    pub trait CredentialInspect<AccountId> {
+        /// Check if `holder` holds a valid credential of `schema_id`
+        fn has_credential(holder: &AccountId, schema_id: &H256) -> bool;
+    }
+
+    impl<AccountId> CredentialInspect<AccountId> for () {
+        fn has_credential(_holder: &AccountId, _schema_id: &H256) -> bool {
+            false
+        }
+    }
+
+    /// Organizations whose members act under the consents granted to them
+    pub trait OrganizationInspect<AccountId> {
+        /// Check if `member` may access data on behalf of `organization`
+        fn can_access_data(organization: &AccountId, member: &AccountId) -> bool;
+    }
+
+    impl<AccountId> OrganizationInspect<AccountId> for () {
+        fn can_access_data(_organization: &AccountId, _member: &AccountId) -> bool {
+            false
+        }
+    }
+
+    /// Handler erasing a patient's data held outside the IdentityConsent chain
+    pub trait OnDataErased<AccountId> {
+        /// Called once `owner`'s data on this chain is erased; an error aborts the erasure
+        fn on_data_erased(owner: &AccountId) -> DispatchResult;
+
+        /// Make `on_data_erased` succeed in benchmarks (e.g. open the channels it uses)
+        #[cfg(feature = "runtime-benchmarks")]
+        fn ensure_successful() {}
+    }
+
+    impl<AccountId> OnDataErased<AccountId> for () {
+        fn on_data_erased(_owner: &AccountId) -> DispatchResult {
+            Ok(())
+        }
+    }
+
+    /// Receipt of a completed erasure request
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ErasureReceipt {
+        /// Hash of the identity as it was before erasure
+        pub identity_hash: H256,
+        /// Erasure timestamp
+        pub erased_at: u64,
+        /// Number of consents revoked
+        pub consents_revoked: u32,
+        /// Number of standing policies withdrawn
+        pub policies_withdrawn: u32,
+    }
+
+    /// Runtime-specific setup needed to benchmark credential-gated policies and organizations
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper<AccountId> {
+        /// Give `holder` a valid credential of `schema_id` through `Config::Credentials`
+        fn grant_credential(holder: &AccountId, schema_id: &H256);
+
+        /// Let `member` access data on behalf of `organization` through `Config::Organizations`
+        fn add_member(organization: &AccountId, member: &AccountId);
+    }
+
+    /// Consent template status
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TemplateStatus {
+        /// Awaiting governance approval
+        Proposed,
+        /// Usable by patients
+        Approved,
+        /// Superseded or withdrawn, no longer usable
+        Retired,
+    }
+
+    /// Standardized consent terms published by an institution
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ConsentTemplate<T: Config> {
+        /// Unique template ID
+        pub template_id: H256,
+        /// Publishing institution
+        pub publisher: T::AccountId,
+        /// Version number (1 for a new template)
+        pub version: u32,
+        /// Previous version of this template (if any)
+        pub supersedes: Option<H256>,
+        /// Purpose of data access
+        pub purpose: DataPurpose,
+        /// Allowed data types
+        pub data_types: BoundedVec<DataType, ConstU32<10>>,
+        /// Maximum consent duration in milliseconds (0 = no limit)
+        pub max_duration: u64,
+        /// Hash of the terms document
+        pub terms_hash: H256,
+        /// Current status
+        pub status: TemplateStatus,
+        /// Publication timestamp
+        pub created_at: u64,
+        /// Approval timestamp
+        pub approved_at: Option<u64>,
     }
 
     /// Consent access log entry
@@ -124,6 +460,8 @@ pub mod pallet {
         pub consent_id: H256,
         /// Accessor account
         pub accessor: T::AccountId,
+        /// Organization the accessor acted for (if accessing as a member)
+        pub organization: Option<T::AccountId>,
         /// Access timestamp
         pub accessed_at: u64,
         /// Data accessed (hash)
@@ -132,6 +470,19 @@ pub mod pallet {
         pub approved: bool,
     }
 
+    /// Position of a consent's access log pages
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+    )]
+    pub struct LogIndex {
+        /// Oldest page still stored on-chain
+        pub first_page: u32,
+        /// Page new entries are appended to
+        pub current_page: u32,
+        /// Number of entries ever logged
+        pub total: u64,
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_identity_registry::Config {
         /// The overarching event type
@@ -144,9 +495,58 @@ pub mod pallet {
         #[pallet::constant]
         type MaxDataTypes: Get<u32>;
 
-        /// Maximum number of access logs to store per consent
+        /// Number of access log entries per page
+        #[pallet::constant]
+        type AccessLogPageSize: Get<u32>;
+
+        /// Length of the time buckets consent expiries are grouped in
+        #[pallet::constant]
+        type ExpiryBucketDuration: Get<u64>;
+
+        /// Maximum consent expiries processed per block
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+
+        /// Origin allowed to run consent verification queries on behalf of other chains
+        /// (the HealthData chain's XCM origin in production)
+        type ConsentQueryOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of active standing policies per patient
+        #[pallet::constant]
+        type MaxPoliciesPerOwner: Get<u32>;
+
+        /// Maximum number of consents obtained from a patient's standing policies
+        #[pallet::constant]
+        type MaxPolicyConsentsPerOwner: Get<u32>;
+
+        /// Governance origin approving and retiring consent templates
+        type TemplateApprovalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Credentials consent policies can require consumers to hold
+        type Credentials: CredentialInspect<Self::AccountId>;
+
+        /// Organizations whose members are covered by the consents granted to them
+        type Organizations: OrganizationInspect<Self::AccountId>;
+
+        /// Handler erasing a patient's data on the other chains
+        type ErasureHandler: OnDataErased<Self::AccountId>;
+
+        /// Origin moving the consents of a recovered identity to its new account
+        type MigrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum consents moved per migration call
         #[pallet::constant]
-        type MaxAccessLogs: Get<u32>;
+        type MaxMigrationBatch: Get<u32>;
+
+        /// Calls a guardian may dispatch on behalf of their ward
+        type WardCalls: Contains<Call<Self>>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+
+        /// Benchmark setup for credential-gated policies and organizations
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId>;
     }
 
     /// Storage for consents by consent_id
@@ -176,22 +576,137 @@ pub mod pallet {
         ValueQuery,
     >;
 
-    /// Storage for access logs
+    /// Consents due to expire, by time bucket (bucket -> (expires_at, consent_id))
+    ///
+    /// The expiry is stored big-endian under the identity hasher, so that each bucket's entries
+    /// are iterated in the order they expire.
+    #[pallet::storage]
+    #[pallet::getter(fn consent_expiries)]
+    pub type ConsentExpiries<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u64, Identity, ([u8; 8], H256), (), OptionQuery>;
+
+    /// Next bucket of `ConsentExpiries` to process
+    #[pallet::storage]
+    #[pallet::getter(fn next_expiry_bucket)]
+    pub type NextExpiryBucket<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+    /// Storage for access log pages (consent_id -> page -> entries)
     #[pallet::storage]
     #[pallet::getter(fn access_logs)]
-    pub type AccessLogs<T: Config> = StorageMap<
+    pub type AccessLogs<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        H256,
-        BoundedVec<AccessLog<T>, ConstU32<1000>>,
+        H256, // consent_id
+        Twox64Concat,
+        u32, // page
+        BoundedVec<AccessLog<T>, T::AccessLogPageSize>,
         ValueQuery,
     >;
 
+    /// Access log page positions by consent_id
+    #[pallet::storage]
+    #[pallet::getter(fn access_log_index)]
+    pub type AccessLogIndex<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256, LogIndex, ValueQuery>;
+
+    /// Hashes of archived access log pages (consent_id -> page -> hash)
+    #[pallet::storage]
+    #[pallet::getter(fn archived_access_logs)]
+    pub type ArchivedAccessLogs<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256, // consent_id
+        Twox64Concat,
+        u32, // page
+        T::Hash,
+    >;
+
     /// Consent counter for generating unique IDs
     #[pallet::storage]
     #[pallet::getter(fn consent_count)]
     pub type ConsentCount<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+    /// Storage for consent templates by template_id
+    #[pallet::storage]
+    #[pallet::getter(fn consent_templates)]
+    pub type ConsentTemplates<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256, ConsentTemplate<T>>;
+
+    /// Template counter for generating unique IDs
+    #[pallet::storage]
+    #[pallet::getter(fn template_count)]
+    pub type TemplateCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Standing consent policies by policy_id
+    #[pallet::storage]
+    #[pallet::getter(fn consent_policies)]
+    pub type ConsentPolicies<T: Config> = StorageMap<_, Blake2_128Concat, H256, ConsentPolicy<T>>;
+
+    /// Active policy IDs by data owner
+    #[pallet::storage]
+    #[pallet::getter(fn owner_policies)]
+    pub type OwnerPolicies<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<H256, T::MaxPoliciesPerOwner>,
+        ValueQuery,
+    >;
+
+    /// Consent IDs obtained from a data owner's standing policies, apart from `OwnerConsents`
+    #[pallet::storage]
+    #[pallet::getter(fn owner_policy_consents)]
+    pub type OwnerPolicyConsents<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<H256, T::MaxPolicyConsentsPerOwner>,
+        ValueQuery,
+    >;
+
+    /// Consumers excluded from a policy by its owner (policy_id -> consumer)
+    #[pallet::storage]
+    #[pallet::getter(fn policy_opt_outs)]
+    pub type PolicyOptOuts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Latest consent created from a policy for a consumer (policy_id -> consumer -> consent_id)
+    #[pallet::storage]
+    #[pallet::getter(fn policy_consents)]
+    pub type PolicyConsents<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256,
+        Blake2_128Concat,
+        T::AccountId,
+        H256,
+        OptionQuery,
+    >;
+
+    /// Policy counter for generating unique IDs
+    #[pallet::storage]
+    #[pallet::getter(fn policy_count)]
+    pub type PolicyCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Receipts of completed erasure requests by data owner
+    #[pallet::storage]
+    #[pallet::getter(fn erasure_receipts)]
+    pub type ErasureReceipts<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ErasureReceipt>;
+
+    /// Consent migrations still in progress (old account -> new account)
+    #[pallet::storage]
+    #[pallet::getter(fn account_migrations)]
+    pub type AccountMigrations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -204,6 +719,8 @@ pub mod pallet {
         },
         /// Consent updated [consent_id]
         ConsentUpdated { consent_id: H256 },
+        /// Consent scope replaced [consent_id]
+        ConsentScopeUpdated { consent_id: H256 },
         /// Consent revoked [consent_id, revoker]
         ConsentRevoked {
             consent_id: H256,
@@ -222,6 +739,78 @@ pub mod pallet {
             accessor: T::AccountId,
             reason: BoundedVec<u8, ConstU32<64>>,
         },
+        /// Consent verified for a cross-chain query [consent_id, data_owner, accessor]
+        ConsentVerified {
+            consent_id: H256,
+            data_owner: T::AccountId,
+            accessor: T::AccountId,
+        },
+        /// Consent template published [template_id, publisher, version]
+        TemplatePublished {
+            template_id: H256,
+            publisher: T::AccountId,
+            version: u32,
+        },
+        /// Consent template approved [template_id]
+        TemplateApproved { template_id: H256 },
+        /// Consent template retired [template_id]
+        TemplateRetired { template_id: H256 },
+        /// Consent created from a template [consent_id, template_id, version]
+        ConsentCreatedFromTemplate {
+            consent_id: H256,
+            template_id: H256,
+            version: u32,
+        },
+        /// Access log page archived [consent_id, page, page_hash]
+        AccessLogArchived {
+            consent_id: H256,
+            page: u32,
+            page_hash: T::Hash,
+        },
+        /// Standing policy published [policy_id, owner]
+        PolicyPublished {
+            policy_id: H256,
+            owner: T::AccountId,
+        },
+        /// Standing policy withdrawn [policy_id]
+        PolicyWithdrawn { policy_id: H256 },
+        /// Consumer excluded from a policy [policy_id, consumer]
+        PolicyConsumerExcluded {
+            policy_id: H256,
+            consumer: T::AccountId,
+        },
+        /// Consumer admitted to a policy again [policy_id, consumer]
+        PolicyConsumerReadmitted {
+            policy_id: H256,
+            consumer: T::AccountId,
+        },
+        /// Consent created from a policy [consent_id, policy_id, consumer]
+        ConsentCreatedFromPolicy {
+            consent_id: H256,
+            policy_id: H256,
+            consumer: T::AccountId,
+        },
+        /// Data owner's personal data erased [owner, identity_hash]
+        DataErased {
+            owner: T::AccountId,
+            identity_hash: H256,
+        },
+        /// Call dispatched by a guardian on behalf of their ward [ward, guardian]
+        ActedForWard {
+            ward: T::AccountId,
+            guardian: T::AccountId,
+        },
+        /// Batch of consents moved to a recovered identity's new account [old, new, consents]
+        ConsentsMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+            consents: u32,
+        },
+        /// All consents and policies of an account moved to its new account [old, new]
+        AccountMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+        },
     }
 
     #[pallet::error]
@@ -232,7 +821,7 @@ pub mod pallet {
         NotAuthorized,
         /// Invalid identity (not registered or not patient role)
         InvalidIdentity,
-        /// Invalid consumer (not researcher or institution)
+        /// Invalid consumer (not researcher or institution, or the data owner)
         InvalidConsumer,
         /// Consent already revoked
         AlreadyRevoked,
@@ -242,10 +831,61 @@ pub mod pallet {
         InvalidExpiryTime,
         /// Maximum consent limit reached
         MaxConsentsReached,
-        /// Maximum access logs reached
-        MaxAccessLogsReached,
+        /// No full access log page left to archive
+        NothingToArchive,
         /// Invalid data types
         InvalidDataTypes,
+        /// Consent template not found
+        TemplateNotFound,
+        /// Template is not approved for use
+        TemplateNotApproved,
+        /// Template is not awaiting approval
+        TemplateNotProposed,
+        /// Template already retired
+        TemplateRetired,
+        /// Publisher is not a verified institution
+        NotVerifiedInstitution,
+        /// Expiry is beyond the template's maximum duration
+        ExceedsTemplateDuration,
+        /// Access falls outside the consent scope
+        OutOfScope,
+        /// Consent scope allows no further accesses
+        AccessLimitReached,
+        /// Scope date range ends before it starts
+        InvalidScope,
+        /// Policy not found
+        PolicyNotFound,
+        /// Policy has been withdrawn
+        PolicyInactive,
+        /// Owner already has the maximum number of active policies
+        TooManyPolicies,
+        /// Policies can only target researchers or institutions
+        InvalidConsumerRole,
+        /// Consumer was excluded from the policy by its owner
+        ConsumerExcluded,
+        /// Consumer does not meet the policy's role or verification requirement
+        NotEligible,
+        /// Consumer already holds an active consent from the policy
+        AlreadyConsented,
+        /// Account's data has already been erased
+        AlreadyErased,
+        /// Caller is not a legal guardian of the ward
+        NotLegalGuardian,
+        /// The call cannot be dispatched on behalf of a ward
+        CallNotAllowed,
+        /// Accounts of a migration must differ
+        InvalidMigration,
+        /// The account's consents are already being migrated
+        MigrationInProgress,
+        /// No migration is pending for the account
+        NoMigrationPending,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::process_expiries(remaining_weight)
+        }
     }
 
     #[pallet::call]
@@ -260,7 +900,7 @@ pub mod pallet {
         /// - `expires_at`: Expiry timestamp (0 for no expiry)
         /// - `terms_hash`: Hash of detailed terms and conditions
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_consent())]
         pub fn create_consent(
             origin: OriginFor<T>,
             consumer: T::AccountId,
@@ -271,74 +911,23 @@ pub mod pallet {
         ) -> DispatchResult {
             let owner = ensure_signed(origin)?;
 
-            // Verify owner is a patient
-            ensure!(
-                IdentityRegistry::<T>::has_role(&owner, UserRole::Patient),
-                Error::<T>::InvalidIdentity
-            );
-
-            // Verify consumer is researcher or institution
-            let is_valid_consumer = IdentityRegistry::<T>::has_role(&consumer, UserRole::Researcher)
-                || IdentityRegistry::<T>::has_role(&consumer, UserRole::Institution);
-            ensure!(is_valid_consumer, Error::<T>::InvalidConsumer);
-
-            // Validate data types
-            ensure!(!data_types.is_empty(), Error::<T>::InvalidDataTypes);
-
-            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
-
-            // Validate expiry
-            if expires_at > 0 {
-                ensure!(expires_at > now, Error::<T>::InvalidExpiryTime);
-            }
-
-            // Generate unique consent ID
-            let count = ConsentCount::<T>::get();
-            let consent_id = Self::generate_consent_id(&owner, &consumer, count);
-            ConsentCount::<T>::put(count.saturating_add(1));
-
-            let consent = Consent {
-                consent_id,
-                data_owner: owner.clone(),
-                data_consumer: consumer.clone(),
-                purpose: purpose.clone(),
+            let consent_id = Self::do_create_consent(
+                owner.clone(),
+                consumer,
+                purpose,
                 data_types,
-                created_at: now,
                 expires_at,
-                status: ConsentStatus::Active,
-                revoked_at: None,
-                access_count: 0,
-                last_accessed: None,
                 terms_hash,
-            };
-
-            // Store consent
-            Consents::<T>::insert(consent_id, consent);
-
-            // Update indices
-            OwnerConsents::<T>::try_mutate(&owner, |consents| -> DispatchResult {
-                consents.try_push(consent_id).map_err(|_| Error::<T>::MaxConsentsReached)?;
-                Ok(())
-            })?;
-
-            ConsumerConsents::<T>::try_mutate(&consumer, |consents| -> DispatchResult {
-                consents.try_push(consent_id).map_err(|_| Error::<T>::MaxConsentsReached)?;
-                Ok(())
-            })?;
-
-            Self::deposit_event(Event::ConsentCreated {
-                consent_id,
-                owner,
-                consumer,
-                purpose,
-            });
+                None,
+            )?;
+            Self::add_owned_consent(&owner, consent_id)?;
 
             Ok(())
         }
 
         /// Revoke an existing consent
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_consent())]
         pub fn revoke_consent(origin: OriginFor<T>, consent_id: H256) -> DispatchResult {
             let revoker = ensure_signed(origin)?;
 
@@ -358,6 +947,11 @@ pub mod pallet {
                 consent.status = ConsentStatus::Revoked;
                 consent.revoked_at = Some(now);
 
+                // A revoked policy consent frees its room for other consumers
+                OwnerPolicyConsents::<T>::mutate(&revoker, |ids| {
+                    ids.retain(|id| *id != consent_id)
+                });
+
                 Self::deposit_event(Event::ConsentRevoked { consent_id, revoker });
 
                 Ok(())
@@ -366,7 +960,7 @@ pub mod pallet {
 
         /// Update consent (extend expiry, modify data types)
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::update_consent())]
         pub fn update_consent(
             origin: OriginFor<T>,
             consent_id: H256,
@@ -394,6 +988,9 @@ pub mod pallet {
                         ensure!(expires_at > now, Error::<T>::InvalidExpiryTime);
                     }
                     consent.expires_at = expires_at;
+
+                    // The entry for the previous expiry is skipped when its bucket is processed
+                    Self::schedule_expiry(consent_id, expires_at);
                 }
 
                 if let Some(data_types) = new_data_types {
@@ -408,12 +1005,16 @@ pub mod pallet {
         }
 
         /// Log data access (called by HealthData chain via XCM or directly)
+        ///
+        /// `record` describes the accessed health record; it is required when the consent scope
+        /// only covers some records.
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::log_access())]
         pub fn log_access(
             origin: OriginFor<T>,
             consent_id: H256,
             data_hash: H256,
+            record: Option<RecordContext>,
         ) -> DispatchResult {
             let accessor = ensure_signed(origin)?;
 
@@ -439,23 +1040,30 @@ pub mod pallet {
                     return Err(Error::<T>::ConsentExpired.into());
                 }
 
+                // Only the consumer and its members may use up the consent's accesses
+                ensure!(Self::is_covered(consent, &accessor), Error::<T>::NotAuthorized);
+                Self::ensure_in_scope(consent, record.as_ref())?;
+
                 // Update access stats
                 consent.access_count = consent.access_count.saturating_add(1);
                 consent.last_accessed = Some(now);
 
+                // Attribute accesses by organization members to the organization as well
+                let organization = (consent.data_consumer != accessor
+                    && T::Organizations::can_access_data(&consent.data_consumer, &accessor))
+                .then(|| consent.data_consumer.clone());
+
                 // Add to access log
                 let log_entry = AccessLog {
                     consent_id,
                     accessor: accessor.clone(),
+                    organization,
                     accessed_at: now,
                     data_hash,
                     approved: true,
                 };
 
-                AccessLogs::<T>::try_mutate(consent_id, |logs| -> DispatchResult {
-                    logs.try_push(log_entry).map_err(|_| Error::<T>::MaxAccessLogsReached)?;
-                    Ok(())
-                })?;
+                Self::append_log(consent_id, log_entry);
 
                 Self::deposit_event(Event::ConsentAccessed { consent_id, accessor });
 
@@ -465,11 +1073,12 @@ pub mod pallet {
 
         /// Check if consent is valid (used by other chains via XCM)
         #[pallet::call_index(4)]
-        #[pallet::weight(5_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::check_consent())]
         pub fn check_consent(
             origin: OriginFor<T>,
             consent_id: H256,
             accessor: T::AccountId,
+            record: Option<RecordContext>,
         ) -> DispatchResult {
             ensure_signed(origin)?;
 
@@ -486,50 +1095,1054 @@ pub mod pallet {
                 return Err(Error::<T>::ConsentExpired.into());
             }
 
-            // Check accessor is the designated consumer
-            ensure!(consent.data_consumer == accessor, Error::<T>::NotAuthorized);
+            // Check accessor is the designated consumer or one of its members
+            ensure!(Self::is_covered(&consent, &accessor), Error::<T>::NotAuthorized);
+
+            Self::ensure_in_scope(&consent, record.as_ref())?;
 
             Ok(())
         }
+
+        /// Verify a consent on behalf of another chain
+        ///
+        /// Dispatched through an XCM `Transact` sent by the HealthData chain. The querying
+        /// chain learns the outcome from the dispatch result reported back with
+        /// `ReportTransactStatus`, so an invalid consent must fail the call. A successful
+        /// verification grants access and so consumes one access of the consent.
+        ///
+        /// Parameters:
+        /// - `origin`: Must satisfy `ConsentQueryOrigin`
+        /// - `consent_id`: Consent referenced by the access request
+        /// - `data_owner`: Patient owning the requested record
+        /// - `accessor`: Account requesting access
+        /// - `record`: Requested health record (if known to the querying chain)
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::verify_consent())]
+        pub fn verify_consent(
+            origin: OriginFor<T>,
+            consent_id: H256,
+            data_owner: T::AccountId,
+            accessor: T::AccountId,
+            record: Option<RecordContext>,
+        ) -> DispatchResult {
+            T::ConsentQueryOrigin::ensure_origin(origin)?;
+
+            Consents::<T>::try_mutate(consent_id, |maybe_consent| -> DispatchResult {
+                let consent = maybe_consent.as_mut().ok_or(Error::<T>::ConsentNotFound)?;
+
+                // The consent must have been given by the owner of the record
+                ensure!(consent.data_owner == data_owner, Error::<T>::NotAuthorized);
+
+                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(Self::is_active(consent, now), Error::<T>::ConsentExpired);
+
+                ensure!(Self::is_covered(consent, &accessor), Error::<T>::NotAuthorized);
+
+                Self::ensure_in_scope(consent, record.as_ref())?;
+
+                consent.access_count = consent.access_count.saturating_add(1);
+                consent.last_accessed = Some(now);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ConsentVerified {
+                consent_id,
+                data_owner,
+                accessor,
+            });
+
+            Ok(())
+        }
+
+        /// Archive the oldest full access log page of a consent
+        ///
+        /// The page is removed from storage and its hash kept in `ArchivedAccessLogs`. The page
+        /// currently being written to is never archived.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::archive_access_logs())]
+        pub fn archive_access_logs(origin: OriginFor<T>, consent_id: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let consent = Consents::<T>::get(consent_id).ok_or(Error::<T>::ConsentNotFound)?;
+
+            // Only data owner can archive
+            ensure!(consent.data_owner == who, Error::<T>::NotAuthorized);
+
+            AccessLogIndex::<T>::try_mutate(consent_id, |index| -> DispatchResult {
+                ensure!(index.first_page < index.current_page, Error::<T>::NothingToArchive);
+
+                let page = index.first_page;
+                let page_hash = T::Hashing::hash_of(&AccessLogs::<T>::take(consent_id, page));
+                ArchivedAccessLogs::<T>::insert(consent_id, page, page_hash);
+                index.first_page = page.saturating_add(1);
+
+                Self::deposit_event(Event::AccessLogArchived { consent_id, page, page_hash });
+
+                Ok(())
+            })
+        }
+
+        /// Publish a consent template for governance approval
+        ///
+        /// Parameters:
+        /// - `origin`: Verified institution
+        /// - `purpose`: Purpose of data access
+        /// - `data_types`: Allowed data types
+        /// - `max_duration`: Maximum consent duration in milliseconds (0 for no limit)
+        /// - `terms_hash`: Hash of the terms document
+        /// - `supersedes`: Previous version of the template, published by the same institution
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::publish_template())]
+        pub fn publish_template(
+            origin: OriginFor<T>,
+            purpose: DataPurpose,
+            data_types: BoundedVec<DataType, ConstU32<10>>,
+            max_duration: u64,
+            terms_hash: H256,
+            supersedes: Option<H256>,
+        ) -> DispatchResult {
+            let publisher = ensure_signed(origin)?;
+
+            // Verify publisher is a verified institution
+            ensure!(
+                IdentityRegistry::<T>::has_role(&publisher, UserRole::Institution)
+                    && IdentityRegistry::<T>::is_verified(&publisher),
+                Error::<T>::NotVerifiedInstitution
+            );
+
+            ensure!(!data_types.is_empty(), Error::<T>::InvalidDataTypes);
+
+            let version = match supersedes {
+                Some(previous_id) => {
+                    let previous = ConsentTemplates::<T>::get(previous_id)
+                        .ok_or(Error::<T>::TemplateNotFound)?;
+                    ensure!(previous.publisher == publisher, Error::<T>::NotAuthorized);
+                    ensure!(
+                        previous.status != TemplateStatus::Retired,
+                        Error::<T>::TemplateRetired
+                    );
+                    previous.version.saturating_add(1)
+                },
+                None => 1,
+            };
+
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            // Generate unique template ID
+            let count = TemplateCount::<T>::get();
+            let template_id = Self::generate_template_id(&publisher, count);
+            TemplateCount::<T>::put(count.saturating_add(1));
+
+            let template = ConsentTemplate {
+                template_id,
+                publisher: publisher.clone(),
+                version,
+                supersedes,
+                purpose,
+                data_types,
+                max_duration,
+                terms_hash,
+                status: TemplateStatus::Proposed,
+                created_at: now,
+                approved_at: None,
+            };
+
+            ConsentTemplates::<T>::insert(template_id, template);
+
+            Self::deposit_event(Event::TemplatePublished { template_id, publisher, version });
+
+            Ok(())
+        }
+
+        /// Approve a proposed consent template, retiring the version it supersedes
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve_template())]
+        pub fn approve_template(origin: OriginFor<T>, template_id: H256) -> DispatchResult {
+            T::TemplateApprovalOrigin::ensure_origin(origin)?;
+
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            let supersedes = ConsentTemplates::<T>::try_mutate(template_id, |maybe_template| {
+                let template = maybe_template.as_mut().ok_or(Error::<T>::TemplateNotFound)?;
+                ensure!(
+                    template.status == TemplateStatus::Proposed,
+                    Error::<T>::TemplateNotProposed
+                );
+
+                template.status = TemplateStatus::Approved;
+                template.approved_at = Some(now);
+
+                Ok::<_, DispatchError>(template.supersedes)
+            })?;
+
+            Self::deposit_event(Event::TemplateApproved { template_id });
+
+            // Consents created from the previous version keep referring to it
+            if let Some(previous_id) = supersedes {
+                Self::do_retire_template(previous_id)?;
+            }
+
+            Ok(())
+        }
+
+        /// Retire a consent template so that no new consents can use it
+        ///
+        /// Callable by `TemplateApprovalOrigin` or the publishing institution. Existing consents
+        /// are not affected.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::retire_template())]
+        pub fn retire_template(origin: OriginFor<T>, template_id: H256) -> DispatchResult {
+            if let Err(origin) = T::TemplateApprovalOrigin::try_origin(origin) {
+                let who = ensure_signed(origin)?;
+                let template =
+                    ConsentTemplates::<T>::get(template_id).ok_or(Error::<T>::TemplateNotFound)?;
+                ensure!(template.publisher == who, Error::<T>::NotAuthorized);
+            }
+
+            Self::do_retire_template(template_id)
+        }
+
+        /// Create a consent following an approved template
+        ///
+        /// Parameters:
+        /// - `origin`: Data owner (patient)
+        /// - `template_id`: Approved template fixing purpose, data types and terms
+        /// - `consumer`: Data consumer (researcher/institution)
+        /// - `expires_at`: Expiry timestamp (0 for no expiry), within the template's maximum
+        ///   duration
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_consent_from_template())]
+        pub fn create_consent_from_template(
+            origin: OriginFor<T>,
+            template_id: H256,
+            consumer: T::AccountId,
+            expires_at: u64,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            let template =
+                ConsentTemplates::<T>::get(template_id).ok_or(Error::<T>::TemplateNotFound)?;
+            ensure!(template.status == TemplateStatus::Approved, Error::<T>::TemplateNotApproved);
+
+            // Without an expiry the consent would outlive any maximum duration
+            if template.max_duration > 0 {
+                let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(
+                    expires_at > 0 && expires_at <= now.saturating_add(template.max_duration),
+                    Error::<T>::ExceedsTemplateDuration
+                );
+            }
+
+            let consent_id = Self::do_create_consent(
+                owner.clone(),
+                consumer,
+                template.purpose,
+                template.data_types,
+                expires_at,
+                template.terms_hash,
+                Some(template_id),
+            )?;
+            Self::add_owned_consent(&owner, consent_id)?;
+
+            Self::deposit_event(Event::ConsentCreatedFromTemplate {
+                consent_id,
+                template_id,
+                version: template.version,
+            });
+
+            Ok(())
+        }
+
+        /// Replace the scope of an active consent
+        ///
+        /// Parameters:
+        /// - `origin`: Data owner (patient)
+        /// - `consent_id`: Consent to restrict
+        /// - `scope`: New record-level restrictions
+        #[pallet::call_index(11)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_consent_scope())]
+        pub fn set_consent_scope(
+            origin: OriginFor<T>,
+            consent_id: H256,
+            scope: ConsentScope,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(scope.is_valid(), Error::<T>::InvalidScope);
+
+            Consents::<T>::try_mutate(consent_id, |maybe_consent| -> DispatchResult {
+                let consent = maybe_consent.as_mut().ok_or(Error::<T>::ConsentNotFound)?;
+
+                ensure!(consent.data_owner == who, Error::<T>::NotAuthorized);
+
+                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(Self::is_active(consent, now), Error::<T>::ConsentExpired);
+
+                consent.scope = scope;
+
+                Self::deposit_event(Event::ConsentScopeUpdated { consent_id });
+
+                Ok(())
+            })
+        }
+
+        /// Publish a standing policy giving consent to any eligible consumer
+        ///
+        /// Parameters:
+        /// - `origin`: Data owner (patient)
+        /// - `consumer_role`: Role a consumer must hold (`Researcher` or `Institution`)
+        /// - `verified_only`: Whether consumers must have a verified identity
+        /// - `required_credential`: Schema of a credential consumers must hold, if any
+        /// - `purpose`: Purpose of data access
+        /// - `data_types`: Allowed data types
+        /// - `consent_duration`: Validity of each created consent in milliseconds (0 for none)
+        /// - `scope`: Scope of each created consent
+        /// - `terms_hash`: Hash of detailed terms and conditions
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::publish_policy())]
+        pub fn publish_policy(
+            origin: OriginFor<T>,
+            consumer_role: UserRole,
+            verified_only: bool,
+            required_credential: Option<H256>,
+            purpose: DataPurpose,
+            data_types: BoundedVec<DataType, ConstU32<10>>,
+            consent_duration: u64,
+            scope: ConsentScope,
+            terms_hash: H256,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            ensure!(
+                IdentityRegistry::<T>::has_role(&owner, UserRole::Patient),
+                Error::<T>::InvalidIdentity
+            );
+            ensure!(
+                matches!(consumer_role, UserRole::Researcher | UserRole::Institution),
+                Error::<T>::InvalidConsumerRole
+            );
+            ensure!(!data_types.is_empty(), Error::<T>::InvalidDataTypes);
+            ensure!(scope.is_valid(), Error::<T>::InvalidScope);
+
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            // Generate unique policy ID
+            let count = PolicyCount::<T>::get();
+            let policy_id = Self::generate_policy_id(&owner, count);
+            PolicyCount::<T>::put(count.saturating_add(1));
+
+            OwnerPolicies::<T>::try_mutate(&owner, |policies| {
+                policies.try_push(policy_id).map_err(|_| Error::<T>::TooManyPolicies)
+            })?;
+
+            let policy = ConsentPolicy {
+                policy_id,
+                owner: owner.clone(),
+                consumer_role,
+                verified_only,
+                required_credential,
+                purpose,
+                data_types,
+                consent_duration,
+                scope,
+                terms_hash,
+                active: true,
+                created_at: now,
+            };
+
+            ConsentPolicies::<T>::insert(policy_id, policy);
+
+            Self::deposit_event(Event::PolicyPublished { policy_id, owner });
+
+            Ok(())
+        }
+
+        /// Withdraw a standing policy
+        ///
+        /// Consents already created from the policy stay in force until revoked or expired.
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_policy())]
+        pub fn withdraw_policy(origin: OriginFor<T>, policy_id: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ConsentPolicies::<T>::try_mutate(policy_id, |maybe_policy| -> DispatchResult {
+                let policy = maybe_policy.as_mut().ok_or(Error::<T>::PolicyNotFound)?;
+                ensure!(policy.owner == who, Error::<T>::NotAuthorized);
+                ensure!(policy.active, Error::<T>::PolicyInactive);
+
+                policy.active = false;
+                OwnerPolicies::<T>::mutate(&who, |policies| policies.retain(|id| *id != policy_id));
+
+                Self::deposit_event(Event::PolicyWithdrawn { policy_id });
+
+                Ok(())
+            })
+        }
+
+        /// Exclude a consumer from a policy
+        ///
+        /// An active consent the consumer already obtained from the policy is revoked.
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::exclude_consumer())]
+        pub fn exclude_consumer(
+            origin: OriginFor<T>,
+            policy_id: H256,
+            consumer: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let policy = ConsentPolicies::<T>::get(policy_id).ok_or(Error::<T>::PolicyNotFound)?;
+            ensure!(policy.owner == who, Error::<T>::NotAuthorized);
+
+            PolicyOptOuts::<T>::insert(policy_id, &consumer, ());
+
+            if let Some(consent_id) = PolicyConsents::<T>::get(policy_id, &consumer) {
+                OwnerPolicyConsents::<T>::mutate(&who, |ids| ids.retain(|id| *id != consent_id));
+                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                Consents::<T>::mutate(consent_id, |maybe_consent| {
+                    let Some(consent) = maybe_consent else { return };
+                    if matches!(consent.status, ConsentStatus::Active) {
+                        consent.status = ConsentStatus::Revoked;
+                        consent.revoked_at = Some(now);
+                        Self::deposit_event(Event::ConsentRevoked { consent_id, revoker: who });
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::PolicyConsumerExcluded { policy_id, consumer });
+
+            Ok(())
+        }
+
+        /// Lift the exclusion of a consumer from a policy
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::readmit_consumer())]
+        pub fn readmit_consumer(
+            origin: OriginFor<T>,
+            policy_id: H256,
+            consumer: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let policy = ConsentPolicies::<T>::get(policy_id).ok_or(Error::<T>::PolicyNotFound)?;
+            ensure!(policy.owner == who, Error::<T>::NotAuthorized);
+
+            PolicyOptOuts::<T>::remove(policy_id, &consumer);
+
+            Self::deposit_event(Event::PolicyConsumerReadmitted { policy_id, consumer });
+
+            Ok(())
+        }
+
+        /// Obtain a consent from a standing policy
+        ///
+        /// The consent is created on the policy owner's behalf with the policy's terms and scope,
+        /// expiring `consent_duration` after the call.
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_policy_consent())]
+        pub fn request_policy_consent(origin: OriginFor<T>, policy_id: H256) -> DispatchResult {
+            let consumer = ensure_signed(origin)?;
+
+            let policy = ConsentPolicies::<T>::get(policy_id).ok_or(Error::<T>::PolicyNotFound)?;
+            ensure!(policy.active, Error::<T>::PolicyInactive);
+            ensure!(
+                !PolicyOptOuts::<T>::contains_key(policy_id, &consumer),
+                Error::<T>::ConsumerExcluded
+            );
+
+            let credentialed = policy
+                .required_credential
+                .map_or(true, |schema_id| T::Credentials::has_credential(&consumer, &schema_id));
+            let eligible = IdentityRegistry::<T>::has_role(&consumer, policy.consumer_role.clone())
+                && (!policy.verified_only || IdentityRegistry::<T>::is_verified(&consumer))
+                && credentialed;
+            ensure!(eligible, Error::<T>::NotEligible);
+
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            // One consent per consumer at a time; a new one can be obtained once it lapses, and
+            // replaces the lapsed one in the owner's index
+            if let Some(previous) = PolicyConsents::<T>::get(policy_id, &consumer) {
+                let active =
+                    Consents::<T>::get(previous).map_or(false, |c| Self::is_active(&c, now));
+                ensure!(!active, Error::<T>::AlreadyConsented);
+                OwnerPolicyConsents::<T>::mutate(&policy.owner, |ids| {
+                    ids.retain(|id| *id != previous)
+                });
+            }
+
+            let expires_at = match policy.consent_duration {
+                0 => 0,
+                duration => now.saturating_add(duration),
+            };
+
+            let consent_id = Self::do_create_consent(
+                policy.owner.clone(),
+                consumer.clone(),
+                policy.purpose,
+                policy.data_types,
+                expires_at,
+                policy.terms_hash,
+                None,
+            )?;
+            OwnerPolicyConsents::<T>::try_mutate(&policy.owner, |consents| {
+                consents.try_push(consent_id).map_err(|_| Error::<T>::MaxConsentsReached)
+            })?;
+            Consents::<T>::mutate(consent_id, |maybe_consent| {
+                if let Some(consent) = maybe_consent {
+                    consent.scope = policy.scope;
+                }
+            });
+            PolicyConsents::<T>::insert(policy_id, &consumer, consent_id);
+
+            Self::deposit_event(Event::ConsentCreatedFromPolicy {
+                consent_id,
+                policy_id,
+                consumer,
+            });
+
+            Ok(())
+        }
+
+        /// Erase the caller's personal data (right to erasure)
+        ///
+        /// Tombstones the caller's identity, revokes every consent they own and withdraws their
+        /// standing policies, then has `ErasureHandler` erase their data on the other chains.
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_erasure())]
+        pub fn request_erasure(origin: OriginFor<T>) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+
+            ensure!(!ErasureReceipts::<T>::contains_key(&owner), Error::<T>::AlreadyErased);
+
+            let identity_hash = IdentityRegistry::<T>::erase_identity(&owner)?;
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            let mut consents_revoked = 0u32;
+            let owned = OwnerConsents::<T>::get(&owner);
+            let obtained = OwnerPolicyConsents::<T>::get(&owner);
+            for consent_id in owned.into_iter().chain(obtained) {
+                Consents::<T>::mutate(consent_id, |maybe_consent| {
+                    let Some(consent) = maybe_consent else { return };
+                    if matches!(consent.status, ConsentStatus::Active | ConsentStatus::Pending) {
+                        consent.status = ConsentStatus::Revoked;
+                        consent.revoked_at = Some(now);
+                        consents_revoked = consents_revoked.saturating_add(1);
+                        Self::deposit_event(Event::ConsentRevoked {
+                            consent_id,
+                            revoker: owner.clone(),
+                        });
+                    }
+                });
+            }
+
+            let policies = OwnerPolicies::<T>::take(&owner);
+            for policy_id in policies.iter() {
+                ConsentPolicies::<T>::mutate(policy_id, |maybe_policy| {
+                    if let Some(policy) = maybe_policy {
+                        policy.active = false;
+                    }
+                });
+                Self::deposit_event(Event::PolicyWithdrawn { policy_id: *policy_id });
+            }
+
+            T::ErasureHandler::on_data_erased(&owner)?;
+
+            ErasureReceipts::<T>::insert(
+                &owner,
+                ErasureReceipt {
+                    identity_hash,
+                    erased_at: now,
+                    consents_revoked,
+                    policies_withdrawn: policies.len() as u32,
+                },
+            );
+
+            Self::deposit_event(Event::DataErased { owner, identity_hash });
+
+            Ok(())
+        }
+
+        /// Dispatch a call of this pallet on behalf of a ward, as their legal guardian
+        ///
+        /// The call runs with the ward's signed origin, so that a guardian can manage the consents
+        /// of a minor or incapacitated patient. It must be admitted by `WardCalls` and cannot be
+        /// `request_erasure`.
+        #[pallet::call_index(18)]
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            <T as Config>::WeightInfo::act_for_ward().saturating_add(dispatch_info.weight)
+        })]
+        pub fn act_for_ward(
+            origin: OriginFor<T>,
+            ward: T::AccountId,
+            call: Box<Call<T>>,
+        ) -> DispatchResult {
+            let guardian = ensure_signed(origin)?;
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(
+                IdentityRegistry::<T>::is_legal_guardian(&ward, &guardian, now),
+                Error::<T>::NotLegalGuardian
+            );
+            ensure!(
+                !matches!(*call, Call::request_erasure {}) && T::WardCalls::contains(&call),
+                Error::<T>::CallNotAllowed
+            );
+
+            call.dispatch_bypass_filter(frame_system::RawOrigin::Signed(ward.clone()).into())
+                .map_err(|e| e.error)?;
+
+            Self::deposit_event(Event::ActedForWard { ward, guardian });
+
+            Ok(())
+        }
+
+        /// Move the consents and standing policies of a recovered identity to its new account
+        ///
+        /// Moves the policies and the first `MaxMigrationBatch` consents; the rest are moved with
+        /// `continue_migration`.
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::migrate_account())]
+        pub fn migrate_account(
+            origin: OriginFor<T>,
+            old: T::AccountId,
+            new: T::AccountId,
+        ) -> DispatchResult {
+            T::MigrationOrigin::ensure_origin(origin)?;
+
+            ensure!(old != new, Error::<T>::InvalidMigration);
+            ensure!(!AccountMigrations::<T>::contains_key(&old), Error::<T>::MigrationInProgress);
+
+            let policies = OwnerPolicies::<T>::take(&old);
+            OwnerPolicies::<T>::try_mutate(&new, |owned| -> DispatchResult {
+                for policy_id in policies.iter() {
+                    owned.try_push(*policy_id).map_err(|_| Error::<T>::TooManyPolicies)?;
+                }
+                Ok(())
+            })?;
+            for policy_id in policies.iter() {
+                ConsentPolicies::<T>::mutate(policy_id, |maybe_policy| {
+                    if let Some(policy) = maybe_policy {
+                        policy.owner = new.clone();
+                    }
+                });
+            }
+
+            AccountMigrations::<T>::insert(&old, &new);
+            Self::migrate_consents(&old, &new)
+        }
+
+        /// Move the next batch of consents of a pending migration
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config>::WeightInfo::continue_migration())]
+        pub fn continue_migration(origin: OriginFor<T>, old: T::AccountId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let new = AccountMigrations::<T>::get(&old).ok_or(Error::<T>::NoMigrationPending)?;
+            Self::migrate_consents(&old, &new)
+        }
     }
 
     // Helper functions
     impl<T: Config> Pallet<T> {
+        /// Move the next `MaxMigrationBatch` consents owned, obtained from policies, then held,
+        /// by `old` to `new`, completing the migration once none are left
+        fn migrate_consents(old: &T::AccountId, new: &T::AccountId) -> DispatchResult {
+            let batch = T::MaxMigrationBatch::get() as usize;
+
+            let mut owned = OwnerConsents::<T>::get(old).into_inner();
+            let moved_owned: Vec<H256> = owned.drain(..owned.len().min(batch)).collect();
+            let mut obtained = OwnerPolicyConsents::<T>::get(old).into_inner();
+            let moved_obtained: Vec<H256> =
+                obtained.drain(..obtained.len().min(batch - moved_owned.len())).collect();
+            let mut held = ConsumerConsents::<T>::get(old).into_inner();
+            let moved_held: Vec<H256> = held
+                .drain(..held.len().min(batch - moved_owned.len() - moved_obtained.len()))
+                .collect();
+
+            OwnerConsents::<T>::try_mutate(new, |consents| -> DispatchResult {
+                for consent_id in moved_owned.iter() {
+                    consents.try_push(*consent_id).map_err(|_| Error::<T>::MaxConsentsReached)?;
+                }
+                Ok(())
+            })?;
+            OwnerPolicyConsents::<T>::try_mutate(new, |consents| -> DispatchResult {
+                for consent_id in moved_obtained.iter() {
+                    consents.try_push(*consent_id).map_err(|_| Error::<T>::MaxConsentsReached)?;
+                }
+                Ok(())
+            })?;
+            ConsumerConsents::<T>::try_mutate(new, |consents| -> DispatchResult {
+                for consent_id in moved_held.iter() {
+                    consents.try_push(*consent_id).map_err(|_| Error::<T>::MaxConsentsReached)?;
+                }
+                Ok(())
+            })?;
+
+            for consent_id in moved_owned.iter().chain(moved_obtained.iter()) {
+                Consents::<T>::mutate(consent_id, |maybe_consent| {
+                    if let Some(consent) = maybe_consent {
+                        consent.data_owner = new.clone();
+                    }
+                });
+            }
+            for consent_id in moved_held.iter() {
+                Consents::<T>::mutate(consent_id, |maybe_consent| {
+                    if let Some(consent) = maybe_consent {
+                        consent.data_consumer = new.clone();
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::ConsentsMigrated {
+                old: old.clone(),
+                new: new.clone(),
+                consents: (moved_owned.len() + moved_obtained.len() + moved_held.len()) as u32,
+            });
+
+            if owned.is_empty() && obtained.is_empty() && held.is_empty() {
+                OwnerConsents::<T>::remove(old);
+                OwnerPolicyConsents::<T>::remove(old);
+                ConsumerConsents::<T>::remove(old);
+                AccountMigrations::<T>::remove(old);
+                Self::deposit_event(Event::AccountMigrated { old: old.clone(), new: new.clone() });
+            } else {
+                OwnerConsents::<T>::insert(old, BoundedVec::truncate_from(owned));
+                OwnerPolicyConsents::<T>::insert(old, BoundedVec::truncate_from(obtained));
+                ConsumerConsents::<T>::insert(old, BoundedVec::truncate_from(held));
+            }
+
+            Ok(())
+        }
+
+        /// Validate and store a new consent, returning its ID
+        fn do_create_consent(
+            owner: T::AccountId,
+            consumer: T::AccountId,
+            purpose: DataPurpose,
+            data_types: BoundedVec<DataType, ConstU32<10>>,
+            expires_at: u64,
+            terms_hash: H256,
+            template_id: Option<H256>,
+        ) -> Result<H256, DispatchError> {
+            // Verify owner is a patient
+            ensure!(
+                IdentityRegistry::<T>::has_role(&owner, UserRole::Patient),
+                Error::<T>::InvalidIdentity
+            );
+
+            // Verify consumer is researcher or institution, and not the owner (identities can
+            // hold both Patient and a consumer role)
+            let is_valid_consumer = IdentityRegistry::<T>::has_any_role(
+                &consumer,
+                &[UserRole::Researcher, UserRole::Institution],
+            );
+            ensure!(is_valid_consumer && consumer != owner, Error::<T>::InvalidConsumer);
+
+            // Validate data types
+            ensure!(!data_types.is_empty(), Error::<T>::InvalidDataTypes);
+
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            // Validate expiry
+            if expires_at > 0 {
+                ensure!(expires_at > now, Error::<T>::InvalidExpiryTime);
+            }
+
+            // Generate unique consent ID
+            let count = ConsentCount::<T>::get();
+            let consent_id = Self::generate_consent_id(&owner, &consumer, count);
+            ConsentCount::<T>::put(count.saturating_add(1));
+
+            let consent = Consent {
+                consent_id,
+                data_owner: owner.clone(),
+                data_consumer: consumer.clone(),
+                purpose: purpose.clone(),
+                data_types,
+                created_at: now,
+                expires_at,
+                status: ConsentStatus::Active,
+                revoked_at: None,
+                access_count: 0,
+                last_accessed: None,
+                terms_hash,
+                template_id,
+                scope: ConsentScope::default(),
+            };
+
+            // Store consent
+            Consents::<T>::insert(consent_id, consent);
+            Self::schedule_expiry(consent_id, expires_at);
+
+            // Update the consumer's index; the owner's is updated by the caller
+            ConsumerConsents::<T>::try_mutate(&consumer, |consents| -> DispatchResult {
+                consents.try_push(consent_id).map_err(|_| Error::<T>::MaxConsentsReached)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ConsentCreated {
+                consent_id,
+                owner,
+                consumer,
+                purpose,
+            });
+
+            Ok(consent_id)
+        }
+
+        /// Add a consent the owner gave themselves to their index
+        fn add_owned_consent(owner: &T::AccountId, consent_id: H256) -> DispatchResult {
+            OwnerConsents::<T>::try_mutate(owner, |consents| {
+                consents.try_push(consent_id).map_err(|_| Error::<T>::MaxConsentsReached.into())
+            })
+        }
+
         /// Generate unique consent ID
         fn generate_consent_id(owner: &T::AccountId, consumer: &T::AccountId, nonce: u64) -> H256 {
-            use sp_runtime::traits::Hash;
             let mut data = owner.encode();
             data.extend_from_slice(&consumer.encode());
             data.extend_from_slice(&nonce.encode());
             T::Hashing::hash(&data)
         }
 
+        /// Time bucket a timestamp falls in
+        fn expiry_bucket(timestamp: u64) -> u64 {
+            timestamp / T::ExpiryBucketDuration::get().max(1)
+        }
+
+        /// Schedule a consent for expiry (consents without an expiry are not scheduled)
+        fn schedule_expiry(consent_id: H256, expires_at: u64) {
+            if expires_at == 0 {
+                return;
+            }
+
+            let bucket = Self::expiry_bucket(expires_at);
+            ConsentExpiries::<T>::insert(bucket, (expires_at.to_be_bytes(), consent_id), ());
+            NextExpiryBucket::<T>::mutate(|next| {
+                if next.map_or(true, |next| bucket < next) {
+                    *next = Some(bucket);
+                }
+            });
+        }
+
+        /// Expire consents past their expiry, bounded by `MaxExpiriesPerBlock` and
+        /// `remaining_weight`
+        fn process_expiries(remaining_weight: Weight) -> Weight {
+            let db_weight = <T as frame_system::Config>::DbWeight::get();
+            let step_weight = db_weight.reads_writes(1, 0);
+            let expiry_weight = <T as Config>::WeightInfo::expire_consent();
+
+            let mut used = db_weight.reads_writes(2, 1);
+            if remaining_weight.any_lt(used) {
+                return Weight::zero();
+            }
+
+            let Some(mut bucket) = NextExpiryBucket::<T>::get() else {
+                return db_weight.reads(1);
+            };
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let current = Self::expiry_bucket(now);
+            let mut budget = T::MaxExpiriesPerBlock::get();
+
+            'buckets: while bucket <= current {
+                if budget == 0 || remaining_weight.any_lt(used.saturating_add(step_weight)) {
+                    break;
+                }
+                used = used.saturating_add(step_weight);
+
+                let due: Vec<([u8; 8], H256)> =
+                    ConsentExpiries::<T>::iter_key_prefix(bucket).take(budget as usize).collect();
+                if due.is_empty() {
+                    budget -= 1;
+                    bucket = bucket.saturating_add(1);
+                    continue;
+                }
+
+                for (expires_at, consent_id) in due {
+                    // Entries are in expiry order, so the rest of the bucket is still valid too
+                    if u64::from_be_bytes(expires_at) >= now {
+                        break 'buckets;
+                    }
+                    if remaining_weight.any_lt(used.saturating_add(expiry_weight)) {
+                        break 'buckets;
+                    }
+                    used = used.saturating_add(expiry_weight);
+                    budget -= 1;
+
+                    ConsentExpiries::<T>::remove(bucket, (expires_at, consent_id));
+                    Self::expire_consent(consent_id, now);
+                }
+            }
+
+            NextExpiryBucket::<T>::put(bucket);
+
+            used
+        }
+
+        /// Mark a consent past its expiry as expired and drop it from the owner's and consumer's
+        /// indexes
+        pub(crate) fn expire_consent(consent_id: H256, now: u64) {
+            let Some(mut consent) = Consents::<T>::get(consent_id) else { return };
+
+            // Revoked consents and consents whose expiry was moved are left untouched
+            if !matches!(consent.status, ConsentStatus::Active)
+                || consent.expires_at == 0
+                || consent.expires_at >= now
+            {
+                return;
+            }
+
+            consent.status = ConsentStatus::Expired;
+
+            OwnerConsents::<T>::mutate(&consent.data_owner, |ids| {
+                ids.retain(|id| *id != consent_id)
+            });
+            OwnerPolicyConsents::<T>::mutate(&consent.data_owner, |ids| {
+                ids.retain(|id| *id != consent_id)
+            });
+            ConsumerConsents::<T>::mutate(&consent.data_consumer, |ids| {
+                ids.retain(|id| *id != consent_id)
+            });
+            Consents::<T>::insert(consent_id, consent);
+
+            Self::deposit_event(Event::ConsentExpired { consent_id });
+        }
+
+        /// Append an entry to the consent's access log, starting a new page when it is full
+        fn append_log(consent_id: H256, entry: AccessLog<T>) {
+            AccessLogIndex::<T>::mutate(consent_id, |index| {
+                let mut page = AccessLogs::<T>::get(consent_id, index.current_page);
+                if let Err(entry) = page.try_push(entry) {
+                    index.current_page = index.current_page.saturating_add(1);
+                    page = BoundedVec::truncate_from(vec![entry]);
+                }
+
+                AccessLogs::<T>::insert(consent_id, index.current_page, page);
+                index.total = index.total.saturating_add(1);
+            });
+        }
+
+        /// Get one page of a consent's access log (empty if archived or not written yet)
+        pub fn access_log_page(consent_id: &H256, page: u32) -> Vec<AccessLog<T>> {
+            AccessLogs::<T>::get(consent_id, page).into_inner()
+        }
+
+        /// Get up to `count` of the latest access log entries still on-chain, newest first
+        pub fn recent_access_logs(consent_id: &H256, count: u32) -> Vec<AccessLog<T>> {
+            let index = AccessLogIndex::<T>::get(consent_id);
+            let mut logs = Vec::new();
+
+            for page in (index.first_page..=index.current_page).rev() {
+                for entry in AccessLogs::<T>::get(consent_id, page).into_iter().rev() {
+                    if logs.len() as u32 >= count {
+                        return logs;
+                    }
+                    logs.push(entry);
+                }
+            }
+
+            logs
+        }
+
+        /// Generate unique template ID
+        fn generate_template_id(publisher: &T::AccountId, nonce: u64) -> H256 {
+            let mut data = b"template".to_vec();
+            data.extend_from_slice(&publisher.encode());
+            data.extend_from_slice(&nonce.encode());
+            T::Hashing::hash(&data)
+        }
+
+        /// Generate unique policy ID
+        fn generate_policy_id(owner: &T::AccountId, nonce: u64) -> H256 {
+            let mut data = b"policy".to_vec();
+            data.extend_from_slice(&owner.encode());
+            data.extend_from_slice(&nonce.encode());
+            T::Hashing::hash(&data)
+        }
+
+        /// Mark a template as retired
+        fn do_retire_template(template_id: H256) -> DispatchResult {
+            ConsentTemplates::<T>::try_mutate(template_id, |maybe_template| -> DispatchResult {
+                let template = maybe_template.as_mut().ok_or(Error::<T>::TemplateNotFound)?;
+                ensure!(template.status != TemplateStatus::Retired, Error::<T>::TemplateRetired);
+
+                template.status = TemplateStatus::Retired;
+
+                Self::deposit_event(Event::TemplateRetired { template_id });
+
+                Ok(())
+            })
+        }
+
+        /// Get the template ID and version a consent follows (if any)
+        pub fn consent_template(consent_id: &H256) -> Option<(H256, u32)> {
+            let template_id = Consents::<T>::get(consent_id)?.template_id?;
+            let template = ConsentTemplates::<T>::get(template_id)?;
+            Some((template_id, template.version))
+        }
+
         /// Get all active consents for a data owner
         pub fn get_active_consents_for_owner(owner: &T::AccountId) -> Vec<Consent<T>> {
-            let consent_ids = OwnerConsents::<T>::get(owner);
-            consent_ids
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let owned = OwnerConsents::<T>::get(owner);
+            let obtained = OwnerPolicyConsents::<T>::get(owner);
+            owned
                 .iter()
+                .chain(obtained.iter())
                 .filter_map(|id| Consents::<T>::get(id))
-                .filter(|c| matches!(c.status, ConsentStatus::Active))
+                .filter(|c| Self::is_active(c, now))
                 .collect()
         }
 
         /// Get all active consents for a consumer
         pub fn get_active_consents_for_consumer(consumer: &T::AccountId) -> Vec<Consent<T>> {
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
             let consent_ids = ConsumerConsents::<T>::get(consumer);
             consent_ids
                 .iter()
                 .filter_map(|id| Consents::<T>::get(id))
-                .filter(|c| matches!(c.status, ConsentStatus::Active))
+                .filter(|c| Self::is_active(c, now))
                 .collect()
         }
 
-        /// Check if consent is valid (public helper)
-        pub fn is_consent_valid(consent_id: &H256, accessor: &T::AccountId, now: u64) -> bool {
+        /// Whether a consent is active and not past its expiry (even if not yet processed)
+        fn is_active(consent: &Consent<T>, now: u64) -> bool {
+            matches!(consent.status, ConsentStatus::Active)
+                && (consent.expires_at == 0 || consent.expires_at > now)
+        }
+
+        /// Whether `accessor` is the consent's consumer or a member acting for it
+        fn is_covered(consent: &Consent<T>, accessor: &T::AccountId) -> bool {
+            consent.data_consumer == *accessor
+                || T::Organizations::can_access_data(&consent.data_consumer, accessor)
+        }
+
+        /// Check an access against the consent's scope and access cap
+        fn ensure_in_scope(consent: &Consent<T>, record: Option<&RecordContext>) -> DispatchResult {
+            ensure!(consent.scope.covers(record), Error::<T>::OutOfScope);
+            ensure!(
+                consent.scope.max_accesses.map_or(true, |max| consent.access_count < max),
+                Error::<T>::AccessLimitReached
+            );
+            Ok(())
+        }
+
+        /// Check if consent is valid for an access to `record` (public helper)
+        ///
+        /// Like `verify_consent`, but without consuming an access.
+        pub fn is_consent_valid(
+            consent_id: &H256,
+            accessor: &T::AccountId,
+            record: Option<&RecordContext>,
+            now: u64,
+        ) -> bool {
             if let Some(consent) = Consents::<T>::get(consent_id) {
-                matches!(consent.status, ConsentStatus::Active)
-                    && (consent.expires_at == 0 || consent.expires_at > now)
-                    && consent.data_consumer == *accessor
+                Self::is_active(&consent, now)
+                    && Self::is_covered(&consent, accessor)
+                    && Self::ensure_in_scope(&consent, record).is_ok()
             } else {
                 false
             }
@@ -0,0 +1,128 @@
+//! Storage migrations for the consent-manager pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, storage_alias,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Storage layout of version 0
+pub mod v0 {
+    use super::*;
+
+    /// Consent of version 0, before templates and record-level scopes
+    #[derive(Encode, Decode)]
+    pub struct OldConsent<T: Config> {
+        pub consent_id: H256,
+        pub data_owner: T::AccountId,
+        pub data_consumer: T::AccountId,
+        pub purpose: DataPurpose,
+        pub data_types: BoundedVec<DataType, ConstU32<10>>,
+        pub created_at: u64,
+        pub expires_at: u64,
+        pub status: ConsentStatus,
+        pub revoked_at: Option<u64>,
+        pub access_count: u32,
+        pub last_accessed: Option<u64>,
+        pub terms_hash: H256,
+    }
+
+    /// Access log entry of version 0, before organization members could access data
+    #[derive(Encode, Decode)]
+    pub struct OldAccessLog<T: Config> {
+        pub consent_id: H256,
+        pub accessor: T::AccountId,
+        pub accessed_at: u64,
+        pub data_hash: H256,
+        pub approved: bool,
+    }
+
+    /// Access logs of version 0, one unpaged list per consent
+    #[storage_alias]
+    pub type AccessLogs<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, H256, Vec<OldAccessLog<T>>, ValueQuery>;
+}
+
+/// Version 1 gives consents a template and a scope, and stores access logs in pages of
+/// `AccessLogPageSize` entries
+pub mod v1 {
+    use super::*;
+
+    /// Give existing consents no template and an unrestricted scope, then move each consent's
+    /// access log into pages and index them in `AccessLogIndex`
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut consents = 0u64;
+            Consents::<T>::translate::<v0::OldConsent<T>, _>(|_, old| {
+                consents += 1;
+                Some(Consent {
+                    consent_id: old.consent_id,
+                    data_owner: old.data_owner,
+                    data_consumer: old.data_consumer,
+                    purpose: old.purpose,
+                    data_types: old.data_types,
+                    created_at: old.created_at,
+                    expires_at: old.expires_at,
+                    status: old.status,
+                    revoked_at: old.revoked_at,
+                    access_count: old.access_count,
+                    last_accessed: old.last_accessed,
+                    terms_hash: old.terms_hash,
+                    template_id: None,
+                    scope: ConsentScope::default(),
+                })
+            });
+
+            // The pages share the old logs' storage prefix, so the old logs are taken out first
+            let old_logs: Vec<(H256, Vec<v0::OldAccessLog<T>>)> =
+                v0::AccessLogs::<T>::drain().collect();
+            let mut reads = consents + old_logs.len() as u64;
+            let mut writes = reads;
+
+            let page_size = T::AccessLogPageSize::get().max(1) as usize;
+            for (consent_id, old_entries) in old_logs {
+                let entries: Vec<AccessLog<T>> = old_entries
+                    .into_iter()
+                    .map(|old| AccessLog {
+                        consent_id: old.consent_id,
+                        accessor: old.accessor,
+                        organization: None,
+                        accessed_at: old.accessed_at,
+                        data_hash: old.data_hash,
+                        approved: old.approved,
+                    })
+                    .collect();
+
+                let mut index = LogIndex { total: entries.len() as u64, ..Default::default() };
+                for (page, chunk) in entries.chunks(page_size).enumerate() {
+                    index.current_page = page as u32;
+                    AccessLogs::<T>::insert(
+                        consent_id,
+                        index.current_page,
+                        BoundedVec::truncate_from(chunk.to_vec()),
+                    );
+                    writes += 1;
+                }
+
+                AccessLogIndex::<T>::insert(consent_id, index);
+                reads += 1;
+                writes += 1;
+            }
+
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// Migrate `Consents` and `AccessLogs` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
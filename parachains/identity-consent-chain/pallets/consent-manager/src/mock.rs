@@ -3,7 +3,7 @@
 use crate as pallet_consent_manager;
 use frame_support::{
     parameter_types,
-    traits::{ConstU16, ConstU32, ConstU64},
+    traits::{ConstU16, ConstU32, ConstU64, Contains},
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -52,6 +52,14 @@ impl frame_system::Config for Test {
 
 parameter_types! {
     pub const MinimumPeriod: u64 = 5;
+    /// Valid credentials known to `TestCredentials` (holder, schema_id)
+    pub static HeldCredentials: Vec<(u64, H256)> = vec![];
+    /// Members allowed to access data for an organization (organization, member)
+    pub static OrgMembers: Vec<(u64, u64)> = vec![];
+    /// Owners whose data `ErasureHandler` was asked to erase on the other chains
+    pub static Erased: Vec<u64> = vec![];
+    /// Whether `ErasureHandler` fails, e.g. when the XCM messages cannot be sent
+    pub static ErasureHandlerFails: bool = false;
 }
 
 impl pallet_timestamp::Config for Test {
@@ -65,13 +73,88 @@ impl pallet_identity_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxIdentitiesPerAccount = ConstU32<1>;
+    type VerifierOrigin = frame_system::EnsureRoot<u64>;
+    type MaxVerifiers = ConstU32<4>;
+    type MaxDidControllers = ConstU32<2>;
+    type MaxVerificationMethods = ConstU32<2>;
+    type MaxServices = ConstU32<2>;
+    type MaxGuardians = ConstU32<3>;
+    type MaxGuardianships = ConstU32<2>;
+    type RecoveryHandler = ();
+    type GuardianshipOrigin = frame_system::EnsureRoot<u64>;
+    type GuardianshipHandler = ();
+    type WeightInfo = ();
 }
 
 impl pallet_consent_manager::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxDataTypes = ConstU32<10>;
-    type MaxAccessLogs = ConstU32<1000>;
+    type AccessLogPageSize = ConstU32<3>;
+    type ExpiryBucketDuration = ConstU64<1_000>;
+    type MaxExpiriesPerBlock = ConstU32<2>;
+    type MaxPoliciesPerOwner = ConstU32<2>;
+    type MaxPolicyConsentsPerOwner = ConstU32<3>;
+    type ConsentQueryOrigin = frame_system::EnsureRoot<u64>;
+    type TemplateApprovalOrigin = frame_system::EnsureRoot<u64>;
+    type Credentials = TestCredentials;
+    type Organizations = TestOrganizations;
+    type ErasureHandler = TestErasureHandler;
+    type MigrationOrigin = frame_system::EnsureRoot<u64>;
+    type MaxMigrationBatch = ConstU32<2>;
+    type WardCalls = TestWardCalls;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = TestBenchmarkHelper;
+}
+
+/// Calls a guardian may make for their ward: all but template publication
+pub struct TestWardCalls;
+impl Contains<pallet_consent_manager::Call<Test>> for TestWardCalls {
+    fn contains(call: &pallet_consent_manager::Call<Test>) -> bool {
+        !matches!(call, pallet_consent_manager::Call::publish_template { .. })
+    }
+}
+
+/// Credential check backed by the `HeldCredentials` list
+pub struct TestCredentials;
+impl pallet_consent_manager::CredentialInspect<u64> for TestCredentials {
+    fn has_credential(holder: &u64, schema_id: &H256) -> bool {
+        HeldCredentials::get().contains(&(*holder, *schema_id))
+    }
+}
+
+/// Membership check backed by the `OrgMembers` list
+pub struct TestOrganizations;
+impl pallet_consent_manager::OrganizationInspect<u64> for TestOrganizations {
+    fn can_access_data(organization: &u64, member: &u64) -> bool {
+        OrgMembers::get().contains(&(*organization, *member))
+    }
+}
+
+/// Records owners whose data was erased
+pub struct TestErasureHandler;
+impl pallet_consent_manager::OnDataErased<u64> for TestErasureHandler {
+    fn on_data_erased(owner: &u64) -> sp_runtime::DispatchResult {
+        if ErasureHandlerFails::get() {
+            return Err(sp_runtime::DispatchError::Other("erasure not sent"));
+        }
+        Erased::mutate(|erased| erased.push(*owner));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_consent_manager::BenchmarkHelper<u64> for TestBenchmarkHelper {
+    fn grant_credential(holder: &u64, schema_id: &H256) {
+        grant_credential(*holder, *schema_id);
+    }
+
+    fn add_member(organization: &u64, member: &u64) {
+        add_member(*organization, *member);
+    }
 }
 
 // Build genesis storage according to the mock runtime.
@@ -136,6 +219,7 @@ pub fn register_researcher(account: u64) {
         create_name("Researcher"),
         create_email_hash(&format!("researcher{}@example.com", account)),
     );
+    hold_role(account, pallet_identity_registry::UserRole::Researcher);
 }
 
 // Helper function to register an institution
@@ -147,4 +231,112 @@ pub fn register_institution(account: u64) {
         create_name("Institution"),
         create_email_hash(&format!("institution{}@example.com", account)),
     );
+    hold_role(account, pallet_identity_registry::UserRole::Institution);
+}
+
+// Helper function to hold a requested privileged role, as if its request had been verified
+pub fn hold_role(account: u64, role: pallet_identity_registry::UserRole) {
+    if pallet_identity_registry::RoleRequests::<Test>::take(account, &role).is_some() {
+        pallet_identity_registry::Identities::<Test>::mutate(account, |identity| {
+            if let Some(identity) = identity {
+                identity.roles = sp_runtime::BoundedVec::truncate_from(vec![role]);
+            }
+        });
+    }
+}
+
+// Helper function to verify an identity through an accredited verifier
+pub fn verify(account: u64) {
+    let verifier = 100u64;
+    if !IdentityRegistry::is_verifier(&verifier) {
+        frame_support::assert_ok!(IdentityRegistry::add_verifier(RuntimeOrigin::root(), verifier));
+    }
+    frame_support::assert_ok!(IdentityRegistry::verify_identity(
+        RuntimeOrigin::signed(verifier),
+        account,
+        pallet_identity_registry::AttestationKind::InstitutionalAccreditation,
+        H256::repeat_byte(1),
+        None,
+    ));
+}
+
+// Helper function to publish a template from an institution, returning its ID
+pub fn publish_template(institution: u64, max_duration: u64, supersedes: Option<H256>) -> H256 {
+    let data_types = sp_runtime::BoundedVec::try_from(vec![crate::DataType::LabResults]).unwrap();
+
+    frame_support::assert_ok!(ConsentManager::publish_template(
+        RuntimeOrigin::signed(institution),
+        crate::DataPurpose::ClinicalTrial,
+        data_types,
+        max_duration,
+        H256::repeat_byte(7),
+        supersedes,
+    ));
+
+    match System::events().last().unwrap().event {
+        RuntimeEvent::ConsentManager(crate::Event::TemplatePublished { template_id, .. }) => {
+            template_id
+        },
+        _ => panic!("Expected TemplatePublished event"),
+    }
+}
+
+// Helper function to create a research consent expiring at `expires_at`, returning its ID
+pub fn create_consent(patient: u64, researcher: u64, expires_at: u64) -> H256 {
+    let data_types = sp_runtime::BoundedVec::try_from(vec![crate::DataType::LabResults]).unwrap();
+
+    frame_support::assert_ok!(ConsentManager::create_consent(
+        RuntimeOrigin::signed(patient),
+        researcher,
+        crate::DataPurpose::Research,
+        data_types,
+        expires_at,
+        H256::zero(),
+    ));
+
+    *ConsentManager::owner_consents(patient).last().unwrap()
+}
+
+// Helper function to describe a record created at `created_at`, as the HealthData chain would
+pub fn record(
+    id: u64,
+    category: crate::RecordCategory,
+    created_at: u64,
+) -> Option<crate::RecordContext> {
+    Some(crate::RecordContext {
+        record_id: H256::from_low_u64_be(id),
+        category,
+        created_at,
+        de_identified: false,
+        version: None,
+    })
+}
+
+// Helper function to publish a vitals policy open to researchers, returning its ID
+pub fn publish_policy(patient: u64, verified_only: bool, consent_duration: u64) -> H256 {
+    let data_types = sp_runtime::BoundedVec::try_from(vec![crate::DataType::Vitals]).unwrap();
+
+    frame_support::assert_ok!(ConsentManager::publish_policy(
+        RuntimeOrigin::signed(patient),
+        pallet_identity_registry::UserRole::Researcher,
+        verified_only,
+        None,
+        crate::DataPurpose::PublicHealth,
+        data_types,
+        consent_duration,
+        crate::ConsentScope { de_identified_only: true, ..Default::default() },
+        H256::repeat_byte(5),
+    ));
+
+    *ConsentManager::owner_policies(patient).last().unwrap()
+}
+
+// Helper function to give a holder a valid credential of a schema
+pub fn grant_credential(holder: u64, schema_id: H256) {
+    HeldCredentials::mutate(|credentials| credentials.push((holder, schema_id)));
+}
+
+// Helper function to let a member access data for an organization
+pub fn add_member(organization: u64, member: u64) {
+    OrgMembers::mutate(|members| members.push((organization, member)));
 }
@@ -1,7 +1,19 @@
 //! Tests for consent-manager pallet
 
-use crate::{mock::*, ConsentStatus, DataPurpose, DataType, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations::{v0, v1::MigrateToV1},
+    mock::*,
+    ConsentScope, ConsentStatus, Consents, DataPurpose, DataType, Error, Event, LogIndex,
+    RecordCategory, RecordContext, TemplateStatus,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::unhashed,
+    traits::{Contains, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+    BoundedVec,
+};
+use pallet_identity_registry::UserRole;
 use sp_core::H256;
 
 #[test]
@@ -183,6 +195,97 @@ fn create_consent_works_with_institution() {
     });
 }
 
+#[test]
+fn create_consent_works_with_multi_role_identities() {
+    new_test_ext().execute_with(|| {
+        let clinician = 1u64;
+        let researcher = 2u64;
+
+        // A clinician-researcher is both data owner and consumer
+        register_researcher(clinician);
+        assert_ok!(IdentityRegistry::request_role(
+            RuntimeOrigin::signed(clinician),
+            UserRole::Patient
+        ));
+        register_researcher(researcher);
+
+        let data_types = sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+        let create = |consumer| {
+            ConsentManager::create_consent(
+                RuntimeOrigin::signed(clinician),
+                consumer,
+                DataPurpose::Research,
+                data_types.clone(),
+                0,
+                H256::zero(),
+            )
+        };
+
+        assert_ok!(create(researcher));
+        assert_noop!(create(clinician), Error::<Test>::InvalidConsumer);
+    });
+}
+
+#[test]
+fn organization_consent_covers_members() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let hospital = 2u64;
+        let analyst = 3u64;
+        let outsider = 4u64;
+
+        register_patient(patient);
+        register_institution(hospital);
+        register_researcher(analyst);
+        register_researcher(outsider);
+        add_member(hospital, analyst);
+
+        let consent_id = create_consent(patient, hospital, 0);
+
+        assert_ok!(ConsentManager::check_consent(
+            RuntimeOrigin::signed(hospital),
+            consent_id,
+            analyst,
+            None,
+        ));
+        assert_ok!(ConsentManager::verify_consent(
+            RuntimeOrigin::root(),
+            consent_id,
+            patient,
+            analyst,
+            None,
+        ));
+        assert!(ConsentManager::is_consent_valid(&consent_id, &analyst, None, 0));
+        assert_noop!(
+            ConsentManager::check_consent(
+                RuntimeOrigin::signed(outsider),
+                consent_id,
+                outsider,
+                None,
+            ),
+            Error::<Test>::NotAuthorized
+        );
+
+        // Member accesses are attributed to both the member and the organization
+        assert_ok!(ConsentManager::log_access(
+            RuntimeOrigin::signed(analyst),
+            consent_id,
+            H256::repeat_byte(1),
+            None,
+        ));
+        assert_ok!(ConsentManager::log_access(
+            RuntimeOrigin::signed(hospital),
+            consent_id,
+            H256::repeat_byte(2),
+            None,
+        ));
+
+        let logs = ConsentManager::access_log_page(&consent_id, 0);
+        assert_eq!((logs[0].accessor, logs[0].organization), (analyst, Some(hospital)));
+        assert_eq!((logs[1].accessor, logs[1].organization), (hospital, None));
+    });
+}
+
 #[test]
 fn revoke_consent_works() {
     new_test_ext().execute_with(|| {
@@ -466,10 +569,11 @@ fn log_access_works() {
             RuntimeOrigin::signed(researcher),
             consent_id,
             data_hash,
+            None,
         ));
 
         // Verify access log
-        let logs = ConsentManager::access_logs(consent_id);
+        let logs = ConsentManager::access_log_page(&consent_id, 0);
         assert_eq!(logs.len(), 1);
         assert_eq!(logs[0].accessor, researcher);
         assert_eq!(logs[0].data_hash, data_hash);
@@ -523,6 +627,7 @@ fn log_access_fails_when_expired() {
                 RuntimeOrigin::signed(researcher),
                 consent_id,
                 H256::zero(),
+                None,
             ),
             Error::<Test>::ConsentExpired
         );
@@ -565,6 +670,7 @@ fn check_consent_works() {
             RuntimeOrigin::signed(researcher),
             consent_id,
             researcher,
+            None,
         ));
     });
 }
@@ -604,9 +710,131 @@ fn check_consent_fails_with_wrong_accessor() {
 
         // Try to check as different researcher
         assert_noop!(
-            ConsentManager::check_consent(RuntimeOrigin::signed(other), consent_id, other),
+            ConsentManager::check_consent(RuntimeOrigin::signed(other), consent_id, other, None),
+            Error::<Test>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn verify_consent_works() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let data_types = sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+
+        assert_ok!(ConsentManager::create_consent(
+            RuntimeOrigin::signed(patient),
+            researcher,
+            DataPurpose::Research,
+            data_types,
+            1000000,
+            H256::zero(),
+        ));
+
+        let events = System::events();
+        let consent_id = if let RuntimeEvent::ConsentManager(Event::ConsentCreated {
+            consent_id,
+            ..
+        }) = events.last().unwrap().event
+        {
+            consent_id
+        } else {
+            panic!("Expected ConsentCreated event");
+        };
+
+        assert_ok!(ConsentManager::verify_consent(
+            RuntimeOrigin::root(),
+            consent_id,
+            patient,
+            researcher,
+            None,
+        ));
+
+        System::assert_last_event(
+            Event::ConsentVerified {
+                consent_id,
+                data_owner: patient,
+                accessor: researcher,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn verify_consent_fails_with_wrong_owner_or_origin() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let other_patient = 3u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        register_patient(other_patient);
+
+        let data_types = sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+
+        assert_ok!(ConsentManager::create_consent(
+            RuntimeOrigin::signed(patient),
+            researcher,
+            DataPurpose::Research,
+            data_types,
+            1000000,
+            H256::zero(),
+        ));
+
+        let events = System::events();
+        let consent_id = if let RuntimeEvent::ConsentManager(Event::ConsentCreated {
+            consent_id,
+            ..
+        }) = events.last().unwrap().event
+        {
+            consent_id
+        } else {
+            panic!("Expected ConsentCreated event");
+        };
+
+        // Only the configured query origin may verify
+        assert_noop!(
+            ConsentManager::verify_consent(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                patient,
+                researcher,
+                None,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // Consent belongs to a different patient than the record owner
+        assert_noop!(
+            ConsentManager::verify_consent(
+                RuntimeOrigin::root(),
+                consent_id,
+                other_patient,
+                researcher,
+                None,
+            ),
             Error::<Test>::NotAuthorized
         );
+
+        // Revoked consent is rejected
+        assert_ok!(ConsentManager::revoke_consent(RuntimeOrigin::signed(patient), consent_id));
+        assert_noop!(
+            ConsentManager::verify_consent(
+                RuntimeOrigin::root(),
+                consent_id,
+                patient,
+                researcher,
+                None,
+            ),
+            Error::<Test>::ConsentExpired
+        );
     });
 }
 
@@ -759,6 +987,1502 @@ fn consent_with_no_expiry_works() {
             RuntimeOrigin::signed(researcher),
             consent_id,
             researcher,
+            None,
+        ));
+    });
+}
+
+#[test]
+fn access_logs_continue_on_a_new_page() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let data_types = sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+
+        assert_ok!(ConsentManager::create_consent(
+            RuntimeOrigin::signed(patient),
+            researcher,
+            DataPurpose::Research,
+            data_types,
+            0,
+            H256::zero(),
+        ));
+
+        let consent_id = ConsentManager::owner_consents(patient)[0];
+
+        // Pages hold three entries in the mock, so the log never fills up
+        for i in 0..7u64 {
+            assert_ok!(ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::from_low_u64_be(i),
+                None,
+            ));
+        }
+
+        let index = ConsentManager::access_log_index(consent_id);
+        assert_eq!(index.current_page, 2);
+        assert_eq!(index.total, 7);
+        assert_eq!(ConsentManager::access_log_page(&consent_id, 1).len(), 3);
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().access_count, 7);
+
+        // Newest entries first
+        let recent = ConsentManager::recent_access_logs(&consent_id, 2);
+        assert_eq!(recent[0].data_hash, H256::from_low_u64_be(6));
+        assert_eq!(recent[1].data_hash, H256::from_low_u64_be(5));
+    });
+}
+
+#[test]
+fn archive_access_logs_works() {
+    new_test_ext().execute_with(|| {
+        use sp_runtime::traits::{BlakeTwo256, Hash};
+
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let data_types = sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+
+        assert_ok!(ConsentManager::create_consent(
+            RuntimeOrigin::signed(patient),
+            researcher,
+            DataPurpose::Research,
+            data_types,
+            0,
+            H256::zero(),
+        ));
+
+        let consent_id = ConsentManager::owner_consents(patient)[0];
+
+        for i in 0..3u64 {
+            assert_ok!(ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::from_low_u64_be(i),
+                None,
+            ));
+        }
+
+        // The only page is still the one being written to
+        assert_noop!(
+            ConsentManager::archive_access_logs(RuntimeOrigin::signed(patient), consent_id),
+            Error::<Test>::NothingToArchive
+        );
+
+        assert_ok!(ConsentManager::log_access(
+            RuntimeOrigin::signed(researcher),
+            consent_id,
+            H256::from_low_u64_be(3),
+            None,
         ));
+
+        // Only the data owner can archive
+        assert_noop!(
+            ConsentManager::archive_access_logs(RuntimeOrigin::signed(researcher), consent_id),
+            Error::<Test>::NotAuthorized
+        );
+
+        let page_hash = BlakeTwo256::hash_of(&ConsentManager::access_logs(consent_id, 0));
+        assert_ok!(ConsentManager::archive_access_logs(RuntimeOrigin::signed(patient), consent_id));
+
+        assert!(ConsentManager::access_log_page(&consent_id, 0).is_empty());
+        assert_eq!(ConsentManager::archived_access_logs(consent_id, 0), Some(page_hash));
+        assert_eq!(ConsentManager::access_log_index(consent_id).first_page, 1);
+        assert_eq!(ConsentManager::recent_access_logs(&consent_id, 10).len(), 1);
+    });
+}
+
+#[test]
+fn publish_template_requires_verified_institution() {
+    new_test_ext().execute_with(|| {
+        let institution = 3u64;
+        let researcher = 2u64;
+        let data_types = sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+
+        register_institution(institution);
+        register_researcher(researcher);
+        verify(researcher);
+
+        // Not verified yet
+        assert_noop!(
+            ConsentManager::publish_template(
+                RuntimeOrigin::signed(institution),
+                DataPurpose::ClinicalTrial,
+                data_types.clone(),
+                0,
+                H256::zero(),
+                None,
+            ),
+            Error::<Test>::NotVerifiedInstitution
+        );
+
+        // Verified, but not an institution
+        assert_noop!(
+            ConsentManager::publish_template(
+                RuntimeOrigin::signed(researcher),
+                DataPurpose::ClinicalTrial,
+                data_types,
+                0,
+                H256::zero(),
+                None,
+            ),
+            Error::<Test>::NotVerifiedInstitution
+        );
+    });
+}
+
+#[test]
+fn template_approval_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let institution = 3u64;
+
+        register_institution(institution);
+        verify(institution);
+
+        let template_id = publish_template(institution, 0, None);
+        let template = ConsentManager::consent_templates(template_id).unwrap();
+        assert_eq!(template.publisher, institution);
+        assert_eq!(template.version, 1);
+        assert_eq!(template.status, TemplateStatus::Proposed);
+
+        // Only the governance origin can approve
+        assert_noop!(
+            ConsentManager::approve_template(RuntimeOrigin::signed(institution), template_id),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(ConsentManager::approve_template(RuntimeOrigin::root(), template_id));
+        assert_eq!(
+            ConsentManager::consent_templates(template_id).unwrap().status,
+            TemplateStatus::Approved
+        );
+
+        assert_noop!(
+            ConsentManager::approve_template(RuntimeOrigin::root(), template_id),
+            Error::<Test>::TemplateNotProposed
+        );
+    });
+}
+
+#[test]
+fn create_consent_from_template_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let institution = 3u64;
+
+        register_patient(patient);
+        register_institution(institution);
+        verify(institution);
+
+        let template_id = publish_template(institution, 5000, None);
+
+        // Not usable before approval
+        assert_noop!(
+            ConsentManager::create_consent_from_template(
+                RuntimeOrigin::signed(patient),
+                template_id,
+                institution,
+                4000,
+            ),
+            Error::<Test>::TemplateNotApproved
+        );
+
+        assert_ok!(ConsentManager::approve_template(RuntimeOrigin::root(), template_id));
+
+        // Expiry must stay within the template's maximum duration
+        assert_noop!(
+            ConsentManager::create_consent_from_template(
+                RuntimeOrigin::signed(patient),
+                template_id,
+                institution,
+                0,
+            ),
+            Error::<Test>::ExceedsTemplateDuration
+        );
+        assert_noop!(
+            ConsentManager::create_consent_from_template(
+                RuntimeOrigin::signed(patient),
+                template_id,
+                institution,
+                5001,
+            ),
+            Error::<Test>::ExceedsTemplateDuration
+        );
+
+        assert_ok!(ConsentManager::create_consent_from_template(
+            RuntimeOrigin::signed(patient),
+            template_id,
+            institution,
+            5000,
+        ));
+
+        // Terms come from the template
+        let consent_id = ConsentManager::owner_consents(patient)[0];
+        let consent = ConsentManager::consents(consent_id).unwrap();
+        assert_eq!(consent.purpose, DataPurpose::ClinicalTrial);
+        assert_eq!(consent.data_types.to_vec(), vec![DataType::LabResults]);
+        assert_eq!(consent.terms_hash, H256::repeat_byte(7));
+        assert_eq!(consent.template_id, Some(template_id));
+        assert_eq!(ConsentManager::consent_template(&consent_id), Some((template_id, 1)));
+
+        System::assert_last_event(
+            Event::ConsentCreatedFromTemplate { consent_id, template_id, version: 1 }.into(),
+        );
+    });
+}
+
+#[test]
+fn new_template_version_retires_previous_on_approval() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let institution = 3u64;
+        let other_institution = 4u64;
+
+        register_patient(patient);
+        register_institution(institution);
+        register_institution(other_institution);
+        verify(institution);
+        verify(other_institution);
+
+        let first = publish_template(institution, 0, None);
+        assert_ok!(ConsentManager::approve_template(RuntimeOrigin::root(), first));
+        assert_ok!(ConsentManager::create_consent_from_template(
+            RuntimeOrigin::signed(patient),
+            first,
+            institution,
+            0,
+        ));
+        let consent_id = ConsentManager::owner_consents(patient)[0];
+
+        // Only the publisher can supersede its template
+        assert_noop!(
+            ConsentManager::publish_template(
+                RuntimeOrigin::signed(other_institution),
+                DataPurpose::ClinicalTrial,
+                sp_runtime::BoundedVec::try_from(vec![DataType::LabResults]).unwrap(),
+                0,
+                H256::zero(),
+                Some(first),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+
+        let second = publish_template(institution, 0, Some(first));
+        assert_eq!(ConsentManager::consent_templates(second).unwrap().version, 2);
+
+        // The first version stays usable until the new one is approved
+        assert_eq!(
+            ConsentManager::consent_templates(first).unwrap().status,
+            TemplateStatus::Approved
+        );
+
+        assert_ok!(ConsentManager::approve_template(RuntimeOrigin::root(), second));
+        assert_eq!(
+            ConsentManager::consent_templates(first).unwrap().status,
+            TemplateStatus::Retired
+        );
+
+        assert_noop!(
+            ConsentManager::create_consent_from_template(
+                RuntimeOrigin::signed(patient),
+                first,
+                institution,
+                0,
+            ),
+            Error::<Test>::TemplateNotApproved
+        );
+
+        // Existing consents still point to the version they followed
+        assert_eq!(ConsentManager::consent_template(&consent_id), Some((first, 1)));
+    });
+}
+
+#[test]
+fn retire_template_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let institution = 3u64;
+        let stranger = 5u64;
+
+        register_institution(institution);
+        verify(institution);
+
+        let template_id = publish_template(institution, 0, None);
+        assert_ok!(ConsentManager::approve_template(RuntimeOrigin::root(), template_id));
+
+        assert_noop!(
+            ConsentManager::retire_template(RuntimeOrigin::signed(stranger), template_id),
+            Error::<Test>::NotAuthorized
+        );
+
+        // The publisher can withdraw its own template
+        assert_ok!(ConsentManager::retire_template(
+            RuntimeOrigin::signed(institution),
+            template_id,
+        ));
+        System::assert_last_event(Event::TemplateRetired { template_id }.into());
+
+        assert_noop!(
+            ConsentManager::retire_template(RuntimeOrigin::root(), template_id),
+            Error::<Test>::TemplateRetired
+        );
+    });
+}
+
+#[test]
+fn expired_consents_are_processed_on_idle() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let expiring = create_consent(patient, researcher, 1_500);
+        let later = create_consent(patient, researcher, 2_500);
+        let open_ended = create_consent(patient, researcher, 0);
+
+        // Past its expiry but not processed yet: no longer reported as active
+        Timestamp::set_timestamp(1_600);
+        assert_eq!(ConsentManager::get_active_consents_for_owner(&patient).len(), 2);
+        assert_eq!(ConsentManager::get_active_consents_for_consumer(&researcher).len(), 2);
+        assert_eq!(ConsentManager::consents(expiring).unwrap().status, ConsentStatus::Active);
+
+        // Buckets are one second long in the mock; `later` is in the current one
+        Timestamp::set_timestamp(2_000);
+        ConsentManager::on_idle(1, Weight::MAX);
+
+        assert_eq!(ConsentManager::consents(expiring).unwrap().status, ConsentStatus::Expired);
+        assert_eq!(ConsentManager::consents(later).unwrap().status, ConsentStatus::Active);
+        assert_eq!(ConsentManager::owner_consents(patient).to_vec(), vec![later, open_ended]);
+        assert_eq!(ConsentManager::consumer_consents(researcher).to_vec(), vec![later, open_ended]);
+        assert_eq!(ConsentManager::next_expiry_bucket(), Some(2));
+        System::assert_last_event(Event::ConsentExpired { consent_id: expiring }.into());
+
+        Timestamp::set_timestamp(3_000);
+        ConsentManager::on_idle(2, Weight::MAX);
+
+        assert_eq!(ConsentManager::consents(later).unwrap().status, ConsentStatus::Expired);
+        assert_eq!(ConsentManager::owner_consents(patient).to_vec(), vec![open_ended]);
+        assert_eq!(ConsentManager::consents(open_ended).unwrap().status, ConsentStatus::Active);
+    });
+}
+
+#[test]
+fn consents_expire_at_their_expiry_within_a_bucket() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let later = create_consent(patient, researcher, 1_800);
+        let earlier = create_consent(patient, researcher, 1_200);
+
+        // A consent is valid up to and including its expiry
+        Timestamp::set_timestamp(1_200);
+        ConsentManager::on_idle(1, Weight::MAX);
+        assert_eq!(ConsentManager::consents(earlier).unwrap().status, ConsentStatus::Active);
+
+        Timestamp::set_timestamp(1_201);
+        ConsentManager::on_idle(2, Weight::MAX);
+        assert_eq!(ConsentManager::consents(earlier).unwrap().status, ConsentStatus::Expired);
+        assert_eq!(ConsentManager::consents(later).unwrap().status, ConsentStatus::Active);
+        assert_eq!(ConsentManager::next_expiry_bucket(), Some(1));
+
+        Timestamp::set_timestamp(1_801);
+        ConsentManager::on_idle(3, Weight::MAX);
+        assert_eq!(ConsentManager::consents(later).unwrap().status, ConsentStatus::Expired);
+        assert!(ConsentManager::owner_consents(patient).is_empty());
+    });
+}
+
+#[test]
+fn consent_expiry_is_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        for expires_at in [1_100, 1_200, 1_300] {
+            create_consent(patient, researcher, expires_at);
+        }
+
+        // At most two expiries per block in the mock
+        Timestamp::set_timestamp(5_000);
+        ConsentManager::on_idle(1, Weight::MAX);
+        assert_eq!(ConsentManager::owner_consents(patient).len(), 1);
+        assert_eq!(ConsentManager::next_expiry_bucket(), Some(1));
+
+        ConsentManager::on_idle(2, Weight::MAX);
+        assert!(ConsentManager::owner_consents(patient).is_empty());
+        assert!(ConsentManager::get_active_consents_for_consumer(&researcher).is_empty());
+    });
+}
+
+#[test]
+fn revoked_or_extended_consents_are_not_expired() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let revoked = create_consent(patient, researcher, 1_500);
+        let extended = create_consent(patient, researcher, 1_500);
+
+        assert_ok!(ConsentManager::revoke_consent(RuntimeOrigin::signed(patient), revoked));
+        assert_ok!(ConsentManager::update_consent(
+            RuntimeOrigin::signed(patient),
+            extended,
+            Some(2_500),
+            None,
+        ));
+
+        Timestamp::set_timestamp(2_000);
+        ConsentManager::on_idle(1, Weight::MAX);
+
+        assert_eq!(ConsentManager::consents(revoked).unwrap().status, ConsentStatus::Revoked);
+        assert_eq!(ConsentManager::consents(extended).unwrap().status, ConsentStatus::Active);
+        assert_eq!(ConsentManager::owner_consents(patient).len(), 2);
+
+        // The new expiry is still scheduled
+        assert_eq!(
+            ConsentManager::consent_expiries(2, (2_500u64.to_be_bytes(), extended)),
+            Some(())
+        );
+        Timestamp::set_timestamp(3_000);
+        ConsentManager::on_idle(2, Weight::MAX);
+        assert_eq!(ConsentManager::consents(extended).unwrap().status, ConsentStatus::Expired);
+    });
+}
+
+#[test]
+fn set_consent_scope_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        let scope = ConsentScope {
+            excluded_categories: BoundedVec::try_from(vec![RecordCategory::Genomic]).unwrap(),
+            no_resharing: true,
+            ..Default::default()
+        };
+
+        assert_noop!(
+            ConsentManager::set_consent_scope(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                scope.clone(),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            ConsentManager::set_consent_scope(
+                RuntimeOrigin::signed(patient),
+                consent_id,
+                ConsentScope { records_from: Some(10), records_until: Some(5), ..scope.clone() },
+            ),
+            Error::<Test>::InvalidScope
+        );
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            scope.clone(),
+        ));
+
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().scope, scope);
+        System::assert_last_event(Event::ConsentScopeUpdated { consent_id }.into());
+    });
+}
+
+#[test]
+fn log_access_is_limited_to_scope() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope {
+                record_ids: BoundedVec::try_from(vec![
+                    H256::from_low_u64_be(1),
+                    H256::from_low_u64_be(2),
+                ])
+                .unwrap(),
+                records_from: Some(100),
+                records_until: Some(200),
+                excluded_categories: BoundedVec::try_from(vec![RecordCategory::Genomic]).unwrap(),
+                ..Default::default()
+            },
+        ));
+
+        let log = |record| {
+            ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::zero(),
+                record,
+            )
+        };
+
+        assert_ok!(log(record(1, RecordCategory::LabResults, 150)));
+
+        // Unknown record, record not listed, outside the date range, excluded category
+        assert_noop!(log(None), Error::<Test>::OutOfScope);
+        assert_noop!(log(record(3, RecordCategory::LabResults, 150)), Error::<Test>::OutOfScope);
+        assert_noop!(log(record(2, RecordCategory::LabResults, 201)), Error::<Test>::OutOfScope);
+        assert_noop!(log(record(2, RecordCategory::Genomic, 150)), Error::<Test>::OutOfScope);
+
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().access_count, 1);
+    });
+}
+
+#[test]
+fn pinned_record_versions_limit_the_scope() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope {
+                record_versions: BoundedVec::try_from(vec![(H256::from_low_u64_be(1), 2)])
+                    .unwrap(),
+                ..Default::default()
+            },
+        ));
+
+        let log = |id, version| {
+            ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::zero(),
+                record(id, RecordCategory::LabResults, 0)
+                    .map(|record| RecordContext { version, ..record }),
+            )
+        };
+
+        // The pinned record only at its version, other records at any version
+        assert_ok!(log(1, Some(2)));
+        assert_ok!(log(2, None));
+        assert_ok!(log(2, Some(1)));
+        assert_noop!(log(1, Some(3)), Error::<Test>::OutOfScope);
+        assert_noop!(log(1, None), Error::<Test>::OutOfScope);
+        assert_noop!(
+            ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::zero(),
+                None,
+            ),
+            Error::<Test>::OutOfScope
+        );
+
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().access_count, 3);
+    });
+}
+
+#[test]
+fn de_identified_only_scope_requires_de_identified_access() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope { de_identified_only: true, ..Default::default() },
+        ));
+
+        let identified = record(1, RecordCategory::Vitals, 0);
+        assert_noop!(
+            ConsentManager::check_consent(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                researcher,
+                identified.clone(),
+            ),
+            Error::<Test>::OutOfScope
+        );
+
+        let de_identified =
+            identified.map(|record| RecordContext { de_identified: true, ..record });
+        assert_ok!(ConsentManager::check_consent(
+            RuntimeOrigin::signed(researcher),
+            consent_id,
+            researcher,
+            de_identified,
+        ));
+    });
+}
+
+#[test]
+fn log_access_requires_the_consumer() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let stranger = 3u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope { max_accesses: Some(1), ..Default::default() },
+        ));
+
+        assert_noop!(
+            ConsentManager::log_access(
+                RuntimeOrigin::signed(stranger),
+                consent_id,
+                H256::zero(),
+                None,
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            ConsentManager::log_access(
+                RuntimeOrigin::signed(patient),
+                consent_id,
+                H256::zero(),
+                None,
+            ),
+            Error::<Test>::NotAuthorized
+        );
+
+        // The consumer's access is still available
+        assert_ok!(ConsentManager::log_access(
+            RuntimeOrigin::signed(researcher),
+            consent_id,
+            H256::zero(),
+            None,
+        ));
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().access_count, 1);
+    });
+}
+
+#[test]
+fn access_cap_is_enforced() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope { max_accesses: Some(2), ..Default::default() },
+        ));
+
+        for _ in 0..2 {
+            assert_ok!(ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::zero(),
+                None,
+            ));
+        }
+
+        assert_noop!(
+            ConsentManager::log_access(
+                RuntimeOrigin::signed(researcher),
+                consent_id,
+                H256::zero(),
+                None,
+            ),
+            Error::<Test>::AccessLimitReached
+        );
+        assert_noop!(
+            ConsentManager::verify_consent(
+                RuntimeOrigin::root(),
+                consent_id,
+                patient,
+                researcher,
+                None,
+            ),
+            Error::<Test>::AccessLimitReached
+        );
+    });
+}
+
+#[test]
+fn verification_consumes_an_access() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope { max_accesses: Some(2), ..Default::default() },
+        ));
+        let verify = || {
+            ConsentManager::verify_consent(
+                RuntimeOrigin::root(),
+                consent_id,
+                patient,
+                researcher,
+                None,
+            )
+        };
+
+        assert_ok!(verify());
+        assert!(ConsentManager::is_consent_valid(&consent_id, &researcher, None, 0));
+        assert_ok!(ConsentManager::log_access(
+            RuntimeOrigin::signed(researcher),
+            consent_id,
+            H256::zero(),
+            None,
+        ));
+
+        let consent = ConsentManager::consents(consent_id).unwrap();
+        assert_eq!(consent.access_count, 2);
+        assert!(consent.last_accessed.is_some());
+        assert!(!ConsentManager::is_consent_valid(&consent_id, &researcher, None, 0));
+        assert_noop!(verify(), Error::<Test>::AccessLimitReached);
+    });
+}
+
+#[test]
+fn consent_validity_checks_scope() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope {
+                excluded_categories: BoundedVec::try_from(vec![RecordCategory::Genomic]).unwrap(),
+                ..Default::default()
+            },
+        ));
+        let record = |category| RecordContext {
+            record_id: H256::repeat_byte(1),
+            created_at: 0,
+            category,
+            de_identified: false,
+            version: None,
+        };
+
+        assert!(ConsentManager::is_consent_valid(
+            &consent_id,
+            &researcher,
+            Some(&record(RecordCategory::LabResults)),
+            0,
+        ));
+        assert!(!ConsentManager::is_consent_valid(
+            &consent_id,
+            &researcher,
+            Some(&record(RecordCategory::Genomic)),
+            0,
+        ));
+        // The scope only covers some records
+        assert!(!ConsentManager::is_consent_valid(&consent_id, &researcher, None, 0));
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().access_count, 0);
+    });
+}
+
+#[test]
+fn verify_consent_checks_record_category() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::set_consent_scope(
+            RuntimeOrigin::signed(patient),
+            consent_id,
+            ConsentScope {
+                excluded_categories: BoundedVec::try_from(vec![RecordCategory::Genomic]).unwrap(),
+                ..Default::default()
+            },
+        ));
+
+        assert_noop!(
+            ConsentManager::verify_consent(
+                RuntimeOrigin::root(),
+                consent_id,
+                patient,
+                researcher,
+                record(1, RecordCategory::Genomic, 0),
+            ),
+            Error::<Test>::OutOfScope
+        );
+        assert_ok!(ConsentManager::verify_consent(
+            RuntimeOrigin::root(),
+            consent_id,
+            patient,
+            researcher,
+            record(1, RecordCategory::Imaging, 0),
+        ));
+    });
+}
+
+#[test]
+fn publish_policy_validates_owner_and_role() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let publish = |who, role| {
+            ConsentManager::publish_policy(
+                RuntimeOrigin::signed(who),
+                role,
+                false,
+                None,
+                DataPurpose::PublicHealth,
+                BoundedVec::try_from(vec![DataType::Vitals]).unwrap(),
+                0,
+                ConsentScope::default(),
+                H256::zero(),
+            )
+        };
+
+        assert_noop!(publish(researcher, UserRole::Researcher), Error::<Test>::InvalidIdentity);
+        assert_noop!(publish(patient, UserRole::Patient), Error::<Test>::InvalidConsumerRole);
+
+        // At most two active policies per patient in the mock
+        assert_ok!(publish(patient, UserRole::Researcher));
+        assert_ok!(publish(patient, UserRole::Institution));
+        assert_noop!(publish(patient, UserRole::Researcher), Error::<Test>::TooManyPolicies);
+    });
+}
+
+#[test]
+fn request_policy_consent_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000);
+        let patient = 1u64;
+        let researcher = 2u64;
+        let institution = 3u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        register_institution(institution);
+
+        let policy_id = publish_policy(patient, false, 5_000);
+
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+
+        // The consent follows the policy terms on the patient's behalf
+        let consent_id = ConsentManager::policy_consents(policy_id, researcher).unwrap();
+        let consent = ConsentManager::consents(consent_id).unwrap();
+        assert_eq!(consent.data_owner, patient);
+        assert_eq!(consent.data_consumer, researcher);
+        assert_eq!(consent.purpose, DataPurpose::PublicHealth);
+        assert_eq!(consent.expires_at, 6_000);
+        assert!(consent.scope.de_identified_only);
+        assert_eq!(consent.terms_hash, H256::repeat_byte(5));
+        System::assert_last_event(
+            Event::ConsentCreatedFromPolicy { consent_id, policy_id, consumer: researcher }.into(),
+        );
+
+        assert_noop!(
+            ConsentManager::request_policy_consent(RuntimeOrigin::signed(researcher), policy_id),
+            Error::<Test>::AlreadyConsented
+        );
+        assert_noop!(
+            ConsentManager::request_policy_consent(RuntimeOrigin::signed(institution), policy_id),
+            Error::<Test>::NotEligible
+        );
+
+        // A new consent can be obtained once the previous one has lapsed
+        Timestamp::set_timestamp(7_000);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+        assert_ne!(ConsentManager::policy_consents(policy_id, researcher), Some(consent_id));
+    });
+}
+
+#[test]
+fn verified_only_policy_requires_verified_consumer() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let policy_id = publish_policy(patient, true, 0);
+
+        assert_noop!(
+            ConsentManager::request_policy_consent(RuntimeOrigin::signed(researcher), policy_id),
+            Error::<Test>::NotEligible
+        );
+
+        verify(researcher);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+    });
+}
+
+#[test]
+fn credential_policy_requires_credential() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let irb_member = H256::repeat_byte(7);
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        assert_ok!(ConsentManager::publish_policy(
+            RuntimeOrigin::signed(patient),
+            UserRole::Researcher,
+            false,
+            Some(irb_member),
+            DataPurpose::Research,
+            BoundedVec::try_from(vec![DataType::Genomic]).unwrap(),
+            0,
+            ConsentScope::default(),
+            H256::zero(),
+        ));
+        let policy_id = *ConsentManager::owner_policies(patient).last().unwrap();
+        assert_eq!(
+            ConsentManager::consent_policies(policy_id).unwrap().required_credential,
+            Some(irb_member)
+        );
+
+        // A credential of another schema does not qualify
+        grant_credential(researcher, H256::repeat_byte(8));
+        assert_noop!(
+            ConsentManager::request_policy_consent(RuntimeOrigin::signed(researcher), policy_id),
+            Error::<Test>::NotEligible
+        );
+
+        grant_credential(researcher, irb_member);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+    });
+}
+
+#[test]
+fn excluded_consumer_loses_policy_consent() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let policy_id = publish_policy(patient, false, 0);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+        let consent_id = ConsentManager::policy_consents(policy_id, researcher).unwrap();
+
+        assert_noop!(
+            ConsentManager::exclude_consumer(
+                RuntimeOrigin::signed(researcher),
+                policy_id,
+                researcher,
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(ConsentManager::exclude_consumer(
+            RuntimeOrigin::signed(patient),
+            policy_id,
+            researcher,
+        ));
+
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().status, ConsentStatus::Revoked);
+        assert_noop!(
+            ConsentManager::request_policy_consent(RuntimeOrigin::signed(researcher), policy_id),
+            Error::<Test>::ConsumerExcluded
+        );
+
+        assert_ok!(ConsentManager::readmit_consumer(
+            RuntimeOrigin::signed(patient),
+            policy_id,
+            researcher,
+        ));
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+    });
+}
+
+#[test]
+fn policy_consents_do_not_fill_owner_consents() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researchers = [2u64, 4, 5, 6];
+
+        register_patient(patient);
+        for researcher in researchers {
+            register_researcher(researcher);
+        }
+
+        // The mock bounds a patient's policy consents at three
+        let policy_id = publish_policy(patient, false, 0);
+        for researcher in &researchers[..3] {
+            assert_ok!(ConsentManager::request_policy_consent(
+                RuntimeOrigin::signed(*researcher),
+                policy_id,
+            ));
+        }
+        assert_eq!(ConsentManager::owner_policy_consents(patient).len(), 3);
+        assert_noop!(
+            ConsentManager::request_policy_consent(
+                RuntimeOrigin::signed(researchers[3]),
+                policy_id,
+            ),
+            Error::<Test>::MaxConsentsReached
+        );
+
+        // The patient's own consents are indexed apart
+        assert!(ConsentManager::owner_consents(patient).is_empty());
+        let consent_id = create_consent(patient, researchers[3], 0);
+        assert_eq!(ConsentManager::owner_consents(patient).into_inner(), vec![consent_id]);
+        assert_eq!(ConsentManager::get_active_consents_for_owner(&patient).len(), 4);
+
+        // Excluding a consumer makes room for another
+        assert_ok!(ConsentManager::exclude_consumer(
+            RuntimeOrigin::signed(patient),
+            policy_id,
+            researchers[0],
+        ));
+        assert_eq!(ConsentManager::owner_policy_consents(patient).len(), 2);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researchers[3]),
+            policy_id,
+        ));
+
+        // So does revoking a policy consent
+        let consent_id = ConsentManager::policy_consents(policy_id, researchers[1]).unwrap();
+        assert_ok!(ConsentManager::revoke_consent(RuntimeOrigin::signed(patient), consent_id));
+        assert!(!ConsentManager::owner_policy_consents(patient).contains(&consent_id));
+    });
+}
+
+#[test]
+fn withdrawn_policy_keeps_existing_consents() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+        let other = 4u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        register_researcher(other);
+
+        let policy_id = publish_policy(patient, false, 0);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+
+        assert_ok!(ConsentManager::withdraw_policy(RuntimeOrigin::signed(patient), policy_id));
+
+        assert!(!ConsentManager::consent_policies(policy_id).unwrap().active);
+        assert!(ConsentManager::owner_policies(patient).is_empty());
+        assert_noop!(
+            ConsentManager::request_policy_consent(RuntimeOrigin::signed(other), policy_id),
+            Error::<Test>::PolicyInactive
+        );
+
+        let consent_id = ConsentManager::policy_consents(policy_id, researcher).unwrap();
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().status, ConsentStatus::Active);
+    });
+}
+
+#[test]
+fn request_erasure_revokes_consents_and_tombstones_identity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let consent_id = create_consent(patient, researcher, 0);
+        let policy_id = publish_policy(patient, false, 0);
+        assert_ok!(ConsentManager::request_policy_consent(
+            RuntimeOrigin::signed(researcher),
+            policy_id,
+        ));
+        let policy_consent_id = ConsentManager::policy_consents(policy_id, researcher).unwrap();
+
+        assert_ok!(ConsentManager::request_erasure(RuntimeOrigin::signed(patient)));
+
+        // Consents revoked and policy withdrawn
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().status, ConsentStatus::Revoked);
+        assert_eq!(
+            ConsentManager::consents(policy_consent_id).unwrap().status,
+            ConsentStatus::Revoked
+        );
+        assert!(!ConsentManager::consent_policies(policy_id).unwrap().active);
+        assert!(ConsentManager::owner_policies(patient).is_empty());
+
+        // Identity tombstoned and the other chains asked to erase the rest
+        let identity = IdentityRegistry::identities(patient).unwrap();
+        assert!(identity.name.is_empty());
+        assert!(!identity.active);
+        assert_eq!(Erased::get(), vec![patient]);
+
+        let receipt = ConsentManager::erasure_receipts(patient).unwrap();
+        assert_eq!(receipt.consents_revoked, 2);
+        assert_eq!(receipt.policies_withdrawn, 1);
+        System::assert_last_event(
+            Event::DataErased { owner: patient, identity_hash: receipt.identity_hash }.into(),
+        );
+
+        assert_noop!(
+            ConsentManager::request_erasure(RuntimeOrigin::signed(patient)),
+            Error::<Test>::AlreadyErased
+        );
+        assert_noop!(
+            ConsentManager::create_consent(
+                RuntimeOrigin::signed(patient),
+                researcher,
+                DataPurpose::Research,
+                BoundedVec::try_from(vec![DataType::LabResults]).unwrap(),
+                0,
+                H256::zero(),
+            ),
+            Error::<Test>::InvalidIdentity
+        );
+    });
+}
+
+#[test]
+fn request_erasure_fails_when_handler_fails() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        let researcher = 2u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        ErasureHandlerFails::set(true);
+        assert_noop!(
+            ConsentManager::request_erasure(RuntimeOrigin::signed(patient)),
+            sp_runtime::DispatchError::Other("erasure not sent")
+        );
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().status, ConsentStatus::Active);
+        assert!(IdentityRegistry::is_active_identity(&patient));
+
+        // Only registered identities can be erased
+        assert_noop!(
+            ConsentManager::request_erasure(RuntimeOrigin::signed(5)),
+            pallet_identity_registry::Error::<Test>::IdentityNotFound
+        );
+    });
+}
+
+#[test]
+fn act_for_ward_dispatches_with_ward_origin() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let ward = 1u64;
+        let researcher = 2u64;
+        let guardian = 3u64;
+
+        register_patient(ward);
+        register_researcher(researcher);
+        register_patient(guardian);
+        let create = || {
+            Box::new(crate::Call::<Test>::create_consent {
+                consumer: researcher,
+                purpose: DataPurpose::Research,
+                data_types: BoundedVec::try_from(vec![DataType::LabResults]).unwrap(),
+                expires_at: 0,
+                terms_hash: H256::zero(),
+            })
+        };
+
+        assert_noop!(
+            ConsentManager::act_for_ward(RuntimeOrigin::signed(guardian), ward, create()),
+            Error::<Test>::NotLegalGuardian
+        );
+
+        assert_ok!(IdentityRegistry::register_guardianship(
+            RuntimeOrigin::root(),
+            ward,
+            guardian,
+            pallet_identity_registry::GuardianshipKind::Parent,
+            H256::repeat_byte(7),
+            None,
+        ));
+        assert_ok!(ConsentManager::act_for_ward(RuntimeOrigin::signed(guardian), ward, create()));
+
+        // The consent belongs to the ward
+        let consent_id = *ConsentManager::owner_consents(ward).last().unwrap();
+        assert_eq!(ConsentManager::consents(consent_id).unwrap().data_owner, ward);
+        assert!(ConsentManager::owner_consents(guardian).is_empty());
+        System::assert_last_event(Event::ActedForWard { ward, guardian }.into());
+
+        // Errors of the inner call are passed on
+        let revoke = || Box::new(crate::Call::<Test>::revoke_consent { consent_id });
+        assert_ok!(ConsentManager::act_for_ward(RuntimeOrigin::signed(guardian), ward, revoke()));
+        assert_noop!(
+            ConsentManager::act_for_ward(RuntimeOrigin::signed(guardian), ward, revoke()),
+            Error::<Test>::AlreadyRevoked
+        );
+    });
+}
+
+#[test]
+fn act_for_ward_only_dispatches_allowed_calls() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let ward = 1u64;
+        let guardian = 3u64;
+
+        register_patient(ward);
+        register_patient(guardian);
+        assert_ok!(IdentityRegistry::register_guardianship(
+            RuntimeOrigin::root(),
+            ward,
+            guardian,
+            pallet_identity_registry::GuardianshipKind::Parent,
+            H256::repeat_byte(7),
+            None,
+        ));
+
+        // Only calls admitted by `WardCalls`
+        let publish = Box::new(crate::Call::<Test>::publish_template {
+            purpose: DataPurpose::Research,
+            data_types: BoundedVec::try_from(vec![DataType::LabResults]).unwrap(),
+            max_duration: 1_000,
+            terms_hash: H256::repeat_byte(1),
+            supersedes: None,
+        });
+        assert_noop!(
+            ConsentManager::act_for_ward(RuntimeOrigin::signed(guardian), ward, publish),
+            Error::<Test>::CallNotAllowed
+        );
+
+        // Erasure is never requested for the ward, even when admitted
+        let erase = Box::new(crate::Call::<Test>::request_erasure {});
+        assert!(TestWardCalls::contains(&erase));
+        assert_noop!(
+            ConsentManager::act_for_ward(RuntimeOrigin::signed(guardian), ward, erase),
+            Error::<Test>::CallNotAllowed
+        );
+        assert!(IdentityRegistry::is_active_identity(&ward));
+    });
+}
+
+#[test]
+fn migrate_account_moves_consents_in_batches() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let researcher = 2u64;
+        let recovered = 5u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+
+        let first = create_consent(patient, researcher, 0);
+        let second = create_consent(patient, researcher, 0);
+        let third = create_consent(patient, researcher, 0);
+        let policy_id = publish_policy(patient, false, 0);
+
+        assert_noop!(
+            ConsentManager::migrate_account(RuntimeOrigin::signed(patient), patient, recovered),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            ConsentManager::migrate_account(RuntimeOrigin::root(), patient, patient),
+            Error::<Test>::InvalidMigration
+        );
+
+        // Policies and the first batch of two consents move at once
+        assert_ok!(ConsentManager::migrate_account(RuntimeOrigin::root(), patient, recovered));
+        assert_eq!(ConsentManager::owner_consents(recovered).into_inner(), vec![first, second]);
+        assert_eq!(ConsentManager::owner_consents(patient).into_inner(), vec![third]);
+        assert_eq!(ConsentManager::consents(first).unwrap().data_owner, recovered);
+        assert_eq!(ConsentManager::consents(third).unwrap().data_owner, patient);
+        assert_eq!(ConsentManager::owner_policies(recovered).into_inner(), vec![policy_id]);
+        assert!(ConsentManager::owner_policies(patient).is_empty());
+        assert_eq!(ConsentManager::consent_policies(policy_id).unwrap().owner, recovered);
+        assert_eq!(ConsentManager::account_migrations(patient), Some(recovered));
+        System::assert_last_event(
+            Event::ConsentsMigrated { old: patient, new: recovered, consents: 2 }.into(),
+        );
+
+        assert_noop!(
+            ConsentManager::migrate_account(RuntimeOrigin::root(), patient, 6),
+            Error::<Test>::MigrationInProgress
+        );
+
+        // Anyone can move the rest
+        assert_ok!(ConsentManager::continue_migration(RuntimeOrigin::signed(researcher), patient));
+        assert_eq!(
+            ConsentManager::owner_consents(recovered).into_inner(),
+            vec![first, second, third]
+        );
+        assert!(ConsentManager::owner_consents(patient).is_empty());
+        assert_eq!(ConsentManager::consents(third).unwrap().data_owner, recovered);
+        assert_eq!(ConsentManager::account_migrations(patient), None);
+        System::assert_last_event(Event::AccountMigrated { old: patient, new: recovered }.into());
+
+        assert_noop!(
+            ConsentManager::continue_migration(RuntimeOrigin::signed(researcher), patient),
+            Error::<Test>::NoMigrationPending
+        );
+    });
+}
+
+#[test]
+fn migrate_account_moves_held_consents() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        let researcher = 2u64;
+        let recovered = 5u64;
+
+        register_patient(patient);
+        register_researcher(researcher);
+        let consent_id = create_consent(patient, researcher, 0);
+
+        assert_ok!(ConsentManager::migrate_account(RuntimeOrigin::root(), researcher, recovered));
+
+        assert_eq!(ConsentManager::consumer_consents(recovered).into_inner(), vec![consent_id]);
+        assert!(ConsentManager::consumer_consents(researcher).is_empty());
+        let consent = ConsentManager::consents(consent_id).unwrap();
+        assert_eq!(consent.data_consumer, recovered);
+        assert_eq!(consent.data_owner, patient);
+        assert_eq!(ConsentManager::account_migrations(researcher), None);
+        System::assert_last_event(
+            Event::AccountMigrated { old: researcher, new: recovered }.into(),
+        );
+    });
+}
+
+#[test]
+fn migration_to_v1_moves_access_logs_into_pages() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<ConsentManager>();
+        let consent_id = H256::repeat_byte(1);
+        let logs: Vec<v0::OldAccessLog<Test>> = (0..4u64)
+            .map(|i| v0::OldAccessLog {
+                consent_id,
+                accessor: 2,
+                accessed_at: 1000 + i,
+                data_hash: H256::repeat_byte(i as u8),
+                approved: true,
+            })
+            .collect();
+        v0::AccessLogs::<Test>::insert(consent_id, logs);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(ConsentManager::on_chain_storage_version(), 1);
+        assert_eq!(
+            ConsentManager::access_log_index(consent_id),
+            LogIndex { first_page: 0, current_page: 1, total: 4 }
+        );
+        assert_eq!(ConsentManager::access_log_page(&consent_id, 0).len(), 3);
+
+        let last = &ConsentManager::access_log_page(&consent_id, 1)[0];
+        assert_eq!(last.accessor, 2);
+        assert_eq!(last.organization, None);
+        assert_eq!(last.accessed_at, 1003);
+        assert_eq!(last.data_hash, H256::repeat_byte(3));
+    });
+}
+
+#[test]
+fn migration_to_v1_gives_existing_consents_an_unrestricted_scope() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<ConsentManager>();
+        let consent_id = H256::repeat_byte(1);
+        let old = v0::OldConsent::<Test> {
+            consent_id,
+            data_owner: 1,
+            data_consumer: 2,
+            purpose: DataPurpose::Research,
+            data_types: BoundedVec::try_from(vec![DataType::LabResults]).unwrap(),
+            created_at: 1000,
+            expires_at: 0,
+            status: ConsentStatus::Active,
+            revoked_at: None,
+            access_count: 3,
+            last_accessed: Some(2000),
+            terms_hash: H256::repeat_byte(7),
+        };
+        unhashed::put(&Consents::<Test>::hashed_key_for(consent_id), &old);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let consent = ConsentManager::consents(consent_id).unwrap();
+        assert_eq!(consent.data_owner, 1);
+        assert_eq!(consent.data_consumer, 2);
+        assert_eq!(consent.access_count, 3);
+        assert_eq!(consent.last_accessed, Some(2000));
+        assert_eq!(consent.terms_hash, H256::repeat_byte(7));
+        assert_eq!(consent.template_id, None);
+        assert_eq!(consent.scope, ConsentScope::default());
     });
 }
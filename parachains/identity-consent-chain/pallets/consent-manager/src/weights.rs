@@ -0,0 +1,414 @@
+//! Estimated weights for `pallet_consent_manager`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/identity-consent-node benchmark pallet \
+//!     --chain dev --pallet pallet_consent_manager --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/consent-manager/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_consent_manager`.
+pub trait WeightInfo {
+    fn create_consent() -> Weight;
+    fn revoke_consent() -> Weight;
+    fn update_consent() -> Weight;
+    fn log_access() -> Weight;
+    fn check_consent() -> Weight;
+    fn verify_consent() -> Weight;
+    fn archive_access_logs() -> Weight;
+    fn publish_template() -> Weight;
+    fn approve_template() -> Weight;
+    fn retire_template() -> Weight;
+    fn create_consent_from_template() -> Weight;
+    fn set_consent_scope() -> Weight;
+    fn publish_policy() -> Weight;
+    fn withdraw_policy() -> Weight;
+    fn exclude_consumer() -> Weight;
+    fn readmit_consumer() -> Weight;
+    fn request_policy_consent() -> Weight;
+    fn request_erasure() -> Weight;
+    fn act_for_ward() -> Weight;
+    fn migrate_account() -> Weight;
+    fn continue_migration() -> Weight;
+    fn expire_consent() -> Weight;
+}
+
+/// Estimated weights for `pallet_consent_manager`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `IdentityRegistry::Identities` x2, `Timestamp::Now`, `ConsentManager::ConsentCount`,
+    //     `ConsentManager::OwnerConsents`, `ConsentManager::ConsumerConsents`,
+    //     `ConsentManager::NextExpiryBucket`
+    // Writes: `ConsentManager::ConsentCount`, `ConsentManager::OwnerConsents`,
+    //     `ConsentManager::ConsumerConsents`, `ConsentManager::Consents`,
+    //     `ConsentManager::NextExpiryBucket`, `ConsentManager::ConsentExpiries`
+    fn create_consent() -> Weight {
+        Weight::from_parts(52_000_000, 35_085)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `Timestamp::Now`, `ConsentManager::OwnerPolicyConsents`
+    // Writes: `ConsentManager::Consents`, `ConsentManager::OwnerPolicyConsents`
+    fn revoke_consent() -> Weight {
+        Weight::from_parts(27_000_000, 38_019)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `Timestamp::Now`, `ConsentManager::NextExpiryBucket`
+    // Writes: `ConsentManager::Consents`, `ConsentManager::NextExpiryBucket`,
+    //     `ConsentManager::ConsentExpiries`
+    fn update_consent() -> Weight {
+        Weight::from_parts(23_000_000, 7_483)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `Organizations::Members`, `IdentityRegistry::Identities`,
+    //     `Timestamp::Now`, `ConsentManager::AccessLogIndex`, `ConsentManager::AccessLogs`
+    // Writes: `ConsentManager::Consents`, `ConsentManager::AccessLogIndex`,
+    //     `ConsentManager::AccessLogs`
+    fn log_access() -> Weight {
+        Weight::from_parts(46_000_000, 18_543)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `Organizations::Members`, `IdentityRegistry::Identities`,
+    //     `Timestamp::Now`
+    fn check_consent() -> Weight {
+        Weight::from_parts(20_000_000, 5_989)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `Organizations::Members`, `IdentityRegistry::Identities`,
+    //     `Timestamp::Now`
+    // Writes: `ConsentManager::Consents`
+    fn verify_consent() -> Weight {
+        Weight::from_parts(27_000_000, 5_989)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `ConsentManager::AccessLogIndex`,
+    //     `ConsentManager::AccessLogs`
+    // Writes: `ConsentManager::AccessLogIndex`, `ConsentManager::AccessLogs`,
+    //     `ConsentManager::ArchivedAccessLogs`
+    fn archive_access_logs() -> Weight {
+        Weight::from_parts(55_000_000, 18_543)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `ConsentManager::ConsentTemplates`, `Timestamp::Now`,
+    //     `ConsentManager::TemplateCount`, `IdentityRegistry::Verifiers`,
+    //     `IdentityRegistry::VerificationThresholds` x4, `IdentityRegistry::Attestations` x16
+    // Writes: `ConsentManager::ConsentTemplates`, `ConsentManager::TemplateCount`
+    fn publish_template() -> Weight {
+        Weight::from_parts(56_000_000, 42_379)
+            .saturating_add(T::DbWeight::get().reads(25_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `ConsentManager::ConsentTemplates` x2
+    // Writes: `ConsentManager::ConsentTemplates` x2
+    fn approve_template() -> Weight {
+        Weight::from_parts(27_000_000, 7_128)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `ConsentManager::ConsentTemplates`
+    // Writes: `ConsentManager::ConsentTemplates`
+    fn retire_template() -> Weight {
+        Weight::from_parts(19_000_000, 3_564)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `ConsentManager::ConsentTemplates`, `Timestamp::Now`,
+    //     `IdentityRegistry::Identities` x2, `ConsentManager::ConsentCount`,
+    //     `ConsentManager::OwnerConsents`, `ConsentManager::ConsumerConsents`,
+    //     `ConsentManager::NextExpiryBucket`
+    // Writes: `ConsentManager::ConsentCount`, `ConsentManager::OwnerConsents`,
+    //     `ConsentManager::ConsumerConsents`, `ConsentManager::Consents`,
+    //     `ConsentManager::NextExpiryBucket`, `ConsentManager::ConsentExpiries`
+    fn create_consent_from_template() -> Weight {
+        Weight::from_parts(57_000_000, 35_085)
+            .saturating_add(T::DbWeight::get().reads(8_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `Timestamp::Now`
+    // Writes: `ConsentManager::Consents`
+    fn set_consent_scope() -> Weight {
+        Weight::from_parts(21_000_000, 5_989)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `Timestamp::Now`, `ConsentManager::PolicyCount`,
+    //     `ConsentManager::OwnerPolicies`
+    // Writes: `ConsentManager::PolicyCount`, `ConsentManager::OwnerPolicies`,
+    //     `ConsentManager::ConsentPolicies`
+    fn publish_policy() -> Weight {
+        Weight::from_parts(34_000_000, 3_643)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `ConsentManager::ConsentPolicies`, `ConsentManager::OwnerPolicies`
+    // Writes: `ConsentManager::ConsentPolicies`, `ConsentManager::OwnerPolicies`
+    fn withdraw_policy() -> Weight {
+        Weight::from_parts(24_000_000, 4_795)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `ConsentManager::ConsentPolicies`, `ConsentManager::PolicyConsents`, `Timestamp::Now`,
+    //     `ConsentManager::Consents`, `ConsentManager::OwnerPolicyConsents`
+    // Writes: `ConsentManager::Consents`, `ConsentManager::OwnerPolicyConsents`,
+    //     `ConsentManager::PolicyOptOuts`
+    fn exclude_consumer() -> Weight {
+        Weight::from_parts(38_000_000, 39_171)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `ConsentManager::ConsentPolicies`
+    // Writes: `ConsentManager::PolicyOptOuts`
+    fn readmit_consumer() -> Weight {
+        Weight::from_parts(17_000_000, 3_769)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `ConsentManager::ConsentPolicies`, `ConsentManager::PolicyOptOuts`,
+    //     `IdentityRegistry::Identities` x3, `Timestamp::Now`, `ConsentManager::PolicyConsents`,
+    //     `ConsentManager::Consents`, `ConsentManager::ConsentCount`,
+    //     `ConsentManager::OwnerPolicyConsents`, `ConsentManager::ConsumerConsents`,
+    //     `ConsentManager::NextExpiryBucket`, `IdentityRegistry::Verifiers` x2,
+    //     `IdentityRegistry::VerificationThresholds` x8, `IdentityRegistry::Attestations` x32,
+    //     `Credentials::HolderCredentials`, `Credentials::Credentials`, `Credentials::StatusLists`
+    // Writes: `ConsentManager::PolicyConsents`, `ConsentManager::Consents` x2,
+    //     `ConsentManager::ConsentCount`, `ConsentManager::OwnerPolicyConsents`,
+    //     `ConsentManager::ConsumerConsents`, `ConsentManager::NextExpiryBucket`,
+    //     `ConsentManager::ConsentExpiries`
+    fn request_policy_consent() -> Weight {
+        Weight::from_parts(122_000_000, 115_046)
+            .saturating_add(T::DbWeight::get().reads(57_u64))
+            .saturating_add(T::DbWeight::get().writes(8_u64))
+    }
+
+    // Reads: `ConsentManager::ErasureReceipts`, `IdentityRegistry::Identities`, `Timestamp::Now`,
+    //     `IdentityRegistry::Attestations` x17, `IdentityRegistry::RoleRequests`,
+    //     `ConsentManager::OwnerConsents`, `ConsentManager::OwnerPolicyConsents`,
+    //     `ConsentManager::Consents` x2000, `ConsentManager::OwnerPolicies`,
+    //     `ConsentManager::ConsentPolicies` x20, `IdentityRegistry::Guardianships` x5,
+    //     `IdentityRegistry::Wards` x5, `ParachainSystem::HostConfiguration`,
+    //     `ParachainSystem::RelevantMessagingState`, `XcmpQueue::DeliveryFeeFactor` x12,
+    //     `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `ConsentManager::ErasureReceipts`, `IdentityRegistry::Identities`,
+    //     `IdentityRegistry::DidDocuments`, `IdentityRegistry::Attestations` x16,
+    //     `IdentityRegistry::VerificationQueue`, `IdentityRegistry::RecoveryConfigs`,
+    //     `IdentityRegistry::ActiveRecoveries`, `ConsentManager::Consents` x2000,
+    //     `ConsentManager::OwnerPolicies`, `ConsentManager::ConsentPolicies` x20,
+    //     `IdentityRegistry::Guardianships` x8, `IdentityRegistry::Wards` x8,
+    //     `XcmpQueue::OutboundXcmpStatus`, `XcmpQueue::OutboundXcmpMessages` x12
+    fn request_erasure() -> Weight {
+        Weight::from_parts(48_490_000_000, 4_485_422)
+            .saturating_add(T::DbWeight::get().reads(2069_u64))
+            .saturating_add(T::DbWeight::get().writes(2072_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `IdentityRegistry::Guardianships`, `IdentityRegistry::Identities`,
+    //     `ConsentManager::Consents`
+    // Writes: `ConsentManager::Consents`
+    fn act_for_ward() -> Weight {
+        Weight::from_parts(40_000_000, 5_989)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `ConsentManager::AccountMigrations`, `ConsentManager::OwnerPolicies` x2,
+    //     `ConsentManager::ConsentPolicies` x20, `ConsentManager::OwnerConsents` x2,
+    //     `ConsentManager::OwnerPolicyConsents` x2, `ConsentManager::ConsumerConsents` x2,
+    //     `ConsentManager::Consents` x100
+    // Writes: `ConsentManager::AccountMigrations`, `ConsentManager::OwnerPolicies` x2,
+    //     `ConsentManager::ConsentPolicies` x20, `ConsentManager::OwnerConsents` x2,
+    //     `ConsentManager::OwnerPolicyConsents` x2, `ConsentManager::ConsumerConsents` x2,
+    //     `ConsentManager::Consents` x100
+    fn migrate_account() -> Weight {
+        Weight::from_parts(2_498_000_000, 851_570)
+            .saturating_add(T::DbWeight::get().reads(129_u64))
+            .saturating_add(T::DbWeight::get().writes(129_u64))
+    }
+
+    // Reads: `ConsentManager::AccountMigrations`, `ConsentManager::OwnerConsents` x2,
+    //     `ConsentManager::OwnerPolicyConsents` x2, `ConsentManager::ConsumerConsents` x2,
+    //     `ConsentManager::Consents` x100
+    // Writes: `ConsentManager::OwnerConsents` x2, `ConsentManager::OwnerPolicyConsents` x2,
+    //     `ConsentManager::ConsumerConsents` x2, `ConsentManager::Consents` x100
+    fn continue_migration() -> Weight {
+        Weight::from_parts(2_308_000_000, 791_710)
+            .saturating_add(T::DbWeight::get().reads(107_u64))
+            .saturating_add(T::DbWeight::get().writes(106_u64))
+    }
+
+    // Reads: `ConsentManager::Consents`, `ConsentManager::OwnerConsents`,
+    //     `ConsentManager::OwnerPolicyConsents`, `ConsentManager::ConsumerConsents`
+    // Writes: `ConsentManager::Consents`, `ConsentManager::OwnerConsents`,
+    //     `ConsentManager::OwnerPolicyConsents`, `ConsentManager::ConsumerConsents`
+    fn expire_consent() -> Weight {
+        Weight::from_parts(38_000_000, 102_079)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn create_consent() -> Weight {
+        Weight::from_parts(52_000_000, 35_085)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn revoke_consent() -> Weight {
+        Weight::from_parts(27_000_000, 38_019)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn update_consent() -> Weight {
+        Weight::from_parts(23_000_000, 7_483)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn log_access() -> Weight {
+        Weight::from_parts(46_000_000, 18_543)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn check_consent() -> Weight {
+        Weight::from_parts(20_000_000, 5_989)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+    }
+
+    fn verify_consent() -> Weight {
+        Weight::from_parts(27_000_000, 5_989)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn archive_access_logs() -> Weight {
+        Weight::from_parts(55_000_000, 18_543)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn publish_template() -> Weight {
+        Weight::from_parts(56_000_000, 42_379)
+            .saturating_add(RocksDbWeight::get().reads(25_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn approve_template() -> Weight {
+        Weight::from_parts(27_000_000, 7_128)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn retire_template() -> Weight {
+        Weight::from_parts(19_000_000, 3_564)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn create_consent_from_template() -> Weight {
+        Weight::from_parts(57_000_000, 35_085)
+            .saturating_add(RocksDbWeight::get().reads(8_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn set_consent_scope() -> Weight {
+        Weight::from_parts(21_000_000, 5_989)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn publish_policy() -> Weight {
+        Weight::from_parts(34_000_000, 3_643)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn withdraw_policy() -> Weight {
+        Weight::from_parts(24_000_000, 4_795)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn exclude_consumer() -> Weight {
+        Weight::from_parts(38_000_000, 39_171)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn readmit_consumer() -> Weight {
+        Weight::from_parts(17_000_000, 3_769)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn request_policy_consent() -> Weight {
+        Weight::from_parts(122_000_000, 115_046)
+            .saturating_add(RocksDbWeight::get().reads(57_u64))
+            .saturating_add(RocksDbWeight::get().writes(8_u64))
+    }
+
+    fn request_erasure() -> Weight {
+        Weight::from_parts(48_490_000_000, 4_485_422)
+            .saturating_add(RocksDbWeight::get().reads(2069_u64))
+            .saturating_add(RocksDbWeight::get().writes(2072_u64))
+    }
+
+    fn act_for_ward() -> Weight {
+        Weight::from_parts(40_000_000, 5_989)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn migrate_account() -> Weight {
+        Weight::from_parts(2_498_000_000, 851_570)
+            .saturating_add(RocksDbWeight::get().reads(129_u64))
+            .saturating_add(RocksDbWeight::get().writes(129_u64))
+    }
+
+    fn continue_migration() -> Weight {
+        Weight::from_parts(2_308_000_000, 791_710)
+            .saturating_add(RocksDbWeight::get().reads(107_u64))
+            .saturating_add(RocksDbWeight::get().writes(106_u64))
+    }
+
+    fn expire_consent() -> Weight {
+        Weight::from_parts(38_000_000, 102_079)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+}
@@ -0,0 +1,24 @@
+//! Runtime API for verifying presented credentials
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+pub use pallet_credentials::CredentialStatus;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    /// Verification of credentials anchored by the credentials pallet
+    pub trait CredentialsApi<AccountId> where AccountId: Codec {
+        /// Check a presented credential's issuer, holder, schema, expiry and revocation status
+        fn verify_credential(
+            credential_hash: H256,
+            issuer: AccountId,
+            holder: AccountId,
+            schema_id: H256,
+        ) -> CredentialStatus;
+
+        /// Revocation bitstring of an issuer's status list
+        fn status_list(issuer: AccountId, list_id: u32) -> Vec<u8>;
+    }
+}
@@ -0,0 +1,162 @@
+//! Benchmarking setup for pallet-credentials
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Credentials;
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::EnsureOrigin, BoundedVec};
+use frame_system::RawOrigin;
+use pallet_identity_registry::{Attestation, AttestationKind, UserRole, VerificationStatus};
+use sp_core::{Get, H256};
+use sp_std::prelude::*;
+
+/// Hex digits used to build DIDs from account IDs
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Register an identity with the given role
+fn register<T: Config>(who: &T::AccountId, role: UserRole) {
+    let mut did = b"did:patientx:".to_vec();
+    for byte in who.encode() {
+        did.extend_from_slice(&[HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]);
+    }
+
+    pallet_identity_registry::Pallet::<T>::register_identity(
+        RawOrigin::Signed(who.clone()).into(),
+        BoundedVec::truncate_from(did),
+        role.clone(),
+        BoundedVec::truncate_from(b"Benchmark".to_vec()),
+        H256::repeat_byte(1),
+    )
+    .expect("identity registration must succeed");
+
+    // Hold the requested role as if it had been verified
+    pallet_identity_registry::RoleRequests::<T>::remove(who, &role);
+    pallet_identity_registry::Identities::<T>::mutate(who, |identity| {
+        if let Some(identity) = identity {
+            identity.roles = BoundedVec::truncate_from(vec![role]);
+        }
+    });
+}
+
+/// Register an institution attested by an accredited verifier
+fn verified_institution<T: Config>() -> T::AccountId {
+    let institution: T::AccountId = whitelisted_caller();
+    register::<T>(&institution, UserRole::Institution);
+    let verifier: T::AccountId = account("verifier", 0, 0);
+    pallet_identity_registry::Verifiers::<T>::put(BoundedVec::truncate_from(vec![
+        verifier.clone(),
+    ]));
+    pallet_identity_registry::Attestations::<T>::insert(
+        &institution,
+        &verifier,
+        Attestation {
+            kind: AttestationKind::InstitutionalAccreditation,
+            evidence_hash: H256::repeat_byte(1),
+            attested_at: 0,
+            expires_at: None,
+        },
+    );
+    pallet_identity_registry::Identities::<T>::mutate(&institution, |identity| {
+        if let Some(identity) = identity {
+            identity.verification_status = VerificationStatus::Verified;
+        }
+    });
+    institution
+}
+
+/// Insert an active schema, returning its ID
+fn insert_schema<T: Config>() -> H256 {
+    let name = BoundedVec::truncate_from(b"Benchmark".to_vec());
+    let schema_id = Pallet::<T>::schema_id(&name, &H256::repeat_byte(2));
+    Schemas::<T>::insert(
+        schema_id,
+        CredentialSchema { name, schema_hash: H256::repeat_byte(2), created_at: 0, active: true },
+    );
+    schema_id
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register_schema() -> Result<(), BenchmarkError> {
+        let origin = <T as Config>::SchemaOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let name = BoundedVec::truncate_from(vec![b'a'; 64]);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, name.clone(), H256::repeat_byte(2));
+
+        assert!(Schemas::<T>::contains_key(Pallet::<T>::schema_id(&name, &H256::repeat_byte(2))));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn deprecate_schema() -> Result<(), BenchmarkError> {
+        let origin = <T as Config>::SchemaOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let schema_id = insert_schema::<T>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, schema_id);
+
+        assert!(!Schemas::<T>::get(schema_id).unwrap().active);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn issue_credential() {
+        let issuer = verified_institution::<T>();
+        let holder: T::AccountId = account("holder", 0, 0);
+        register::<T>(&holder, UserRole::Researcher);
+        let schema_id = insert_schema::<T>();
+        T::CredentialHandler::ensure_successful(&schema_id);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(issuer),
+            H256::repeat_byte(3),
+            holder.clone(),
+            schema_id,
+            Some(u64::MAX),
+        );
+
+        assert_eq!(HolderCredentials::<T>::get(&holder, schema_id), Some(H256::repeat_byte(3)));
+    }
+
+    #[benchmark]
+    fn revoke_credential() {
+        let issuer = verified_institution::<T>();
+        let holder: T::AccountId = account("holder", 0, 0);
+        register::<T>(&holder, UserRole::Researcher);
+        let schema_id = insert_schema::<T>();
+
+        // Last bit of an empty list, which grows the list to its full length
+        let last = <T as Config>::StatusListLength::get().saturating_mul(8).saturating_sub(1);
+        NextStatusEntry::<T>::insert(&issuer, StatusListEntry { list_id: 0, index: last });
+        Pallet::<T>::issue_credential(
+            RawOrigin::Signed(issuer.clone()).into(),
+            H256::repeat_byte(3),
+            holder,
+            schema_id,
+            None,
+        )
+        .expect("credential issuance must succeed");
+        T::CredentialHandler::ensure_successful(&schema_id);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(issuer.clone()), H256::repeat_byte(3));
+
+        assert_eq!(
+            StatusLists::<T>::get(&issuer, 0).len() as u32,
+            <T as Config>::StatusListLength::get()
+        );
+    }
+
+    impl_benchmark_test_suite!(Credentials, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,503 @@
+//! # Credentials Pallet
+//!
+//! ## Overview
+//!
+//! The Credentials pallet anchors verifiable credentials for the Patient X platform, such as
+//! "licensed physician", "IRB member" or "ethics training completed". It provides:
+//! - Credential schemas registered by governance
+//! - Credential issuance by verified `Institution` identities, anchored by hash
+//! - Status-list revocation
+//! - Verification of presented credentials (also exposed through the `CredentialsApi` runtime API)
+//!
+//! ## Issuance
+//!
+//! The credential document itself (e.g. a W3C verifiable credential) stays off-chain. The issuer
+//! anchors its hash with `issue_credential`, together with the holder, schema and an optional
+//! expiry. A holder's latest credential for each schema is indexed so other pallets can check
+//! that an account holds a valid credential of a schema.
+//!
+//! ## Revocation
+//!
+//! Each credential is assigned an entry in one of its issuer's status lists: bitstrings of
+//! `StatusListLength` bytes, in the spirit of the W3C Bitstring Status List. Revoking a credential
+//! sets its bit, so verifiers holding a copy of the list can check it off-chain as well.
+//!
+//! Issuing a credential, and revoking the credential a holder currently holds for a schema, is
+//! passed to `CredentialHandler`, through which the runtime mirrors clinician and auditor
+//! credentials to the HealthData chain.
+//!
+//! ## Verification
+//!
+//! `verify_credential` checks a presented credential hash against its claimed issuer, holder and
+//! schema, its expiry, its revocation bit and whether the issuer is still a verified
+//! Institution, returning a `CredentialStatus`.
+//!
+//! ## Architecture Reference
+//! See parachain.md Section: "IdentityConsent Chain - Identity Registry"
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::{pallet_prelude::*, traits::Time};
+    use frame_system::pallet_prelude::*;
+    use sp_std::prelude::*;
+    use sp_core::H256;
+    use sp_runtime::traits::Hash;
+    use pallet_identity_registry::{Pallet as IdentityRegistry, UserRole};
+    use crate::weights::WeightInfo;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Credential schema
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct CredentialSchema {
+        /// Human-readable name (e.g. "IRB member")
+        pub name: BoundedVec<u8, ConstU32<64>>,
+        /// Hash of the schema document describing the credential's claims
+        pub schema_hash: H256,
+        /// Registration timestamp
+        pub created_at: u64,
+        /// Whether new credentials can be issued
+        pub active: bool,
+    }
+
+    /// Position of a credential in its issuer's status lists
+    #[derive(
+        Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct StatusListEntry {
+        /// Status list of the issuer
+        pub list_id: u32,
+        /// Bit index within the list
+        pub index: u32,
+    }
+
+    /// Credential anchored on-chain
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Credential<T: Config> {
+        /// Issuing institution
+        pub issuer: T::AccountId,
+        /// Credential holder
+        pub holder: T::AccountId,
+        /// Schema of the credential
+        pub schema_id: H256,
+        /// Issuance timestamp
+        pub issued_at: u64,
+        /// Expiry timestamp (`None` = no expiry)
+        pub expires_at: Option<u64>,
+        /// Revocation status entry
+        pub status: StatusListEntry,
+    }
+
+    /// Result of verifying a presented credential
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum CredentialStatus {
+        /// The credential is valid
+        Valid,
+        /// No credential is anchored with this hash
+        Unknown,
+        /// The credential was issued by another account
+        IssuerMismatch,
+        /// The credential was issued to another account
+        HolderMismatch,
+        /// The credential has another schema
+        SchemaMismatch,
+        /// The credential has expired
+        Expired,
+        /// The credential has been revoked
+        Revoked,
+        /// The issuer is no longer a verified Institution
+        IssuerNotVerified,
+    }
+
+    /// Handler of credentials issued or revoked on this chain
+    pub trait OnCredentialChanged<AccountId> {
+        /// Called once `holder` holds a credential of `schema_id` valid until `valid_until`, or
+        /// no longer (`None`); an error aborts the change
+        fn on_credential_changed(
+            holder: &AccountId,
+            schema_id: &H256,
+            valid_until: Option<u64>,
+        ) -> DispatchResult;
+
+        /// Make `on_credential_changed` succeed, doing its full work for credentials of
+        /// `schema_id`, in benchmarks (e.g. open the channels it uses)
+        #[cfg(feature = "runtime-benchmarks")]
+        fn ensure_successful(_schema_id: &H256) {}
+    }
+
+    impl<AccountId> OnCredentialChanged<AccountId> for () {
+        fn on_credential_changed(
+            _holder: &AccountId,
+            _schema_id: &H256,
+            _valid_until: Option<u64>,
+        ) -> DispatchResult {
+            Ok(())
+        }
+    }
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_identity_registry::Config {
+        /// The overarching event type
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Time provider for timestamps
+        type TimeProvider: Time;
+
+        /// Governance origin registering and deprecating credential schemas
+        type SchemaOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Length of a status list in bytes (each byte covers eight credentials)
+        #[pallet::constant]
+        type StatusListLength: Get<u32>;
+
+        /// Handler of issued and revoked credentials
+        type CredentialHandler: OnCredentialChanged<Self::AccountId>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Credential schemas by ID
+    #[pallet::storage]
+    #[pallet::getter(fn schemas)]
+    pub type Schemas<T: Config> = StorageMap<_, Blake2_128Concat, H256, CredentialSchema>;
+
+    /// Credentials by hash of the credential document
+    #[pallet::storage]
+    #[pallet::getter(fn credentials)]
+    pub type Credentials<T: Config> = StorageMap<_, Blake2_128Concat, H256, Credential<T>>;
+
+    /// Latest credential of each schema issued to a holder
+    #[pallet::storage]
+    #[pallet::getter(fn holder_credentials)]
+    pub type HolderCredentials<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, H256, H256>;
+
+    /// Revocation bitstrings by issuer and list ID
+    ///
+    /// Lists grow as credentials are revoked; missing bytes are unrevoked.
+    #[pallet::storage]
+    #[pallet::getter(fn status_lists)]
+    pub type StatusLists<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u32,
+        BoundedVec<u8, T::StatusListLength>,
+        ValueQuery,
+    >;
+
+    /// Next free status list entry of each issuer
+    #[pallet::storage]
+    #[pallet::getter(fn next_status_entry)]
+    pub type NextStatusEntry<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, StatusListEntry, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Credential schema registered [schema_id]
+        SchemaRegistered { schema_id: H256 },
+        /// Credential schema deprecated [schema_id]
+        SchemaDeprecated { schema_id: H256 },
+        /// Credential issued [credential_hash, issuer, holder, schema_id]
+        CredentialIssued {
+            credential_hash: H256,
+            issuer: T::AccountId,
+            holder: T::AccountId,
+            schema_id: H256,
+        },
+        /// Credential revoked [credential_hash, status]
+        CredentialRevoked { credential_hash: H256, status: StatusListEntry },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Schema already registered
+        SchemaAlreadyExists,
+        /// Schema not found
+        SchemaNotFound,
+        /// Schema deprecated
+        SchemaDeprecated,
+        /// Schema name must not be empty
+        InvalidSchema,
+        /// Issuer is not a verified Institution
+        NotAuthorizedIssuer,
+        /// Holder has no active identity
+        InvalidHolder,
+        /// A credential with this hash is already anchored
+        CredentialAlreadyExists,
+        /// Credential not found
+        CredentialNotFound,
+        /// Credential already revoked
+        AlreadyRevoked,
+        /// Expiry is in the past
+        InvalidExpiry,
+        /// Caller is not the issuer of the credential
+        NotIssuer,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register a credential schema
+        ///
+        /// The schema ID is the hash of its name and schema document hash.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_schema())]
+        pub fn register_schema(
+            origin: OriginFor<T>,
+            name: BoundedVec<u8, ConstU32<64>>,
+            schema_hash: H256,
+        ) -> DispatchResult {
+            <T as Config>::SchemaOrigin::ensure_origin(origin)?;
+            ensure!(!name.is_empty(), Error::<T>::InvalidSchema);
+
+            let schema_id = Self::schema_id(&name, &schema_hash);
+            ensure!(!Schemas::<T>::contains_key(schema_id), Error::<T>::SchemaAlreadyExists);
+
+            let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+            Schemas::<T>::insert(
+                schema_id,
+                CredentialSchema { name, schema_hash, created_at: now, active: true },
+            );
+
+            Self::deposit_event(Event::SchemaRegistered { schema_id });
+
+            Ok(())
+        }
+
+        /// Deprecate a credential schema
+        ///
+        /// No new credentials can be issued; issued ones stay valid.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::deprecate_schema())]
+        pub fn deprecate_schema(origin: OriginFor<T>, schema_id: H256) -> DispatchResult {
+            <T as Config>::SchemaOrigin::ensure_origin(origin)?;
+
+            Schemas::<T>::try_mutate(schema_id, |maybe_schema| -> DispatchResult {
+                let schema = maybe_schema.as_mut().ok_or(Error::<T>::SchemaNotFound)?;
+                ensure!(schema.active, Error::<T>::SchemaDeprecated);
+                schema.active = false;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::SchemaDeprecated { schema_id });
+
+            Ok(())
+        }
+
+        /// Issue a credential (verified Institutions only)
+        ///
+        /// Parameters:
+        /// - `origin`: Issuing institution
+        /// - `credential_hash`: Hash of the off-chain credential document
+        /// - `holder`: Account the credential is issued to
+        /// - `schema_id`: Schema of the credential
+        /// - `expires_at`: Expiry timestamp (`None` for no expiry)
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::issue_credential())]
+        pub fn issue_credential(
+            origin: OriginFor<T>,
+            credential_hash: H256,
+            holder: T::AccountId,
+            schema_id: H256,
+            expires_at: Option<u64>,
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            ensure!(Self::is_authorized_issuer(&issuer), Error::<T>::NotAuthorizedIssuer);
+            ensure!(IdentityRegistry::<T>::is_active_identity(&holder), Error::<T>::InvalidHolder);
+
+            let schema = Schemas::<T>::get(schema_id).ok_or(Error::<T>::SchemaNotFound)?;
+            ensure!(schema.active, Error::<T>::SchemaDeprecated);
+            ensure!(
+                !Credentials::<T>::contains_key(credential_hash),
+                Error::<T>::CredentialAlreadyExists
+            );
+
+            let now: u64 = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(
+                expires_at.map_or(true, |expires_at| expires_at > now),
+                Error::<T>::InvalidExpiry
+            );
+
+            let status = Self::allocate_status_entry(&issuer);
+
+            Credentials::<T>::insert(
+                credential_hash,
+                Credential {
+                    issuer: issuer.clone(),
+                    holder: holder.clone(),
+                    schema_id,
+                    issued_at: now,
+                    expires_at,
+                    status,
+                },
+            );
+            HolderCredentials::<T>::insert(&holder, schema_id, credential_hash);
+            T::CredentialHandler::on_credential_changed(
+                &holder,
+                &schema_id,
+                Some(expires_at.unwrap_or(u64::MAX)),
+            )?;
+
+            Self::deposit_event(Event::CredentialIssued {
+                credential_hash,
+                issuer,
+                holder,
+                schema_id,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a credential by setting its status list bit (issuer only)
+        ///
+        /// `CredentialHandler` is notified when the credential is the one its holder currently
+        /// holds for its schema.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_credential())]
+        pub fn revoke_credential(origin: OriginFor<T>, credential_hash: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let credential =
+                Credentials::<T>::get(credential_hash).ok_or(Error::<T>::CredentialNotFound)?;
+            ensure!(credential.issuer == who, Error::<T>::NotIssuer);
+
+            let status = credential.status;
+            StatusLists::<T>::try_mutate(&who, status.list_id, |list| -> DispatchResult {
+                let (byte, mask) = Self::status_bit(&status);
+                if list.len() <= byte {
+                    let mut bytes = list.to_vec();
+                    bytes.resize(byte + 1, 0);
+                    // The index was allocated within `StatusListLength` bytes
+                    *list = BoundedVec::truncate_from(bytes);
+                }
+                ensure!(list[byte] & mask == 0, Error::<T>::AlreadyRevoked);
+                list[byte] |= mask;
+                Ok(())
+            })?;
+            if HolderCredentials::<T>::get(&credential.holder, credential.schema_id)
+                == Some(credential_hash)
+            {
+                T::CredentialHandler::on_credential_changed(
+                    &credential.holder,
+                    &credential.schema_id,
+                    None,
+                )?;
+            }
+
+            Self::deposit_event(Event::CredentialRevoked { credential_hash, status });
+
+            Ok(())
+        }
+    }
+
+    // Helper functions
+    impl<T: Config> Pallet<T> {
+        /// Verify a presented credential against its claimed issuer, holder and schema
+        pub fn verify_credential(
+            credential_hash: H256,
+            issuer: &T::AccountId,
+            holder: &T::AccountId,
+            schema_id: H256,
+        ) -> CredentialStatus {
+            match Credentials::<T>::get(credential_hash) {
+                None => CredentialStatus::Unknown,
+                Some(credential) if credential.issuer != *issuer => {
+                    CredentialStatus::IssuerMismatch
+                },
+                Some(credential) if credential.holder != *holder => {
+                    CredentialStatus::HolderMismatch
+                },
+                Some(credential) if credential.schema_id != schema_id => {
+                    CredentialStatus::SchemaMismatch
+                },
+                Some(credential) => Self::credential_status(&credential),
+            }
+        }
+
+        /// Check if `holder` holds a valid credential of `schema_id`
+        pub fn has_valid_credential(holder: &T::AccountId, schema_id: H256) -> bool {
+            HolderCredentials::<T>::get(holder, schema_id)
+                .and_then(Credentials::<T>::get)
+                .map_or(false, |credential| {
+                    Self::credential_status(&credential) == CredentialStatus::Valid
+                })
+        }
+
+        /// Expiry, revocation and issuer status of a credential
+        fn credential_status(credential: &Credential<T>) -> CredentialStatus {
+            let now: u64 = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            if credential.expires_at.map_or(false, |expires_at| expires_at <= now) {
+                CredentialStatus::Expired
+            } else if Self::is_revoked(credential) {
+                CredentialStatus::Revoked
+            } else if !Self::is_authorized_issuer(&credential.issuer) {
+                CredentialStatus::IssuerNotVerified
+            } else {
+                CredentialStatus::Valid
+            }
+        }
+
+        /// Check if the status list bit of a credential is set
+        pub fn is_revoked(credential: &Credential<T>) -> bool {
+            let (byte, mask) = Self::status_bit(&credential.status);
+            StatusLists::<T>::get(&credential.issuer, credential.status.list_id)
+                .get(byte)
+                .map_or(false, |bits| bits & mask != 0)
+        }
+
+        /// Check if account is a verified Institution
+        pub fn is_authorized_issuer(account: &T::AccountId) -> bool {
+            IdentityRegistry::<T>::has_role(account, UserRole::Institution)
+                && IdentityRegistry::<T>::is_verified(account)
+        }
+
+        /// Schema ID derived from the schema name and document hash
+        pub fn schema_id(name: &[u8], schema_hash: &H256) -> H256 {
+            let mut data = b"schema".to_vec();
+            data.extend_from_slice(&name.encode());
+            data.extend_from_slice(schema_hash.as_bytes());
+            T::Hashing::hash(&data)
+        }
+
+        /// Take the next free status list entry of an issuer
+        fn allocate_status_entry(issuer: &T::AccountId) -> StatusListEntry {
+            NextStatusEntry::<T>::mutate(issuer, |next| {
+                let entry = *next;
+                let bits = <T as Config>::StatusListLength::get().saturating_mul(8);
+                *next = if entry.index.saturating_add(1) >= bits {
+                    StatusListEntry { list_id: entry.list_id.saturating_add(1), index: 0 }
+                } else {
+                    StatusListEntry { list_id: entry.list_id, index: entry.index + 1 }
+                };
+                entry
+            })
+        }
+
+        /// Byte offset and bit mask of a status list entry
+        fn status_bit(status: &StatusListEntry) -> (usize, u8) {
+            ((status.index / 8) as usize, 1 << (status.index % 8))
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Tests for credentials pallet
+
+use crate::{mock::*, CredentialStatus, Error, Event, StatusListEntry};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_identity_registry::UserRole;
+use sp_core::H256;
+
+#[test]
+fn register_schema_works() {
+    new_test_ext().execute_with(|| {
+        let schema_id = schema("IRB member");
+
+        let schema = Credentials::schemas(schema_id).unwrap();
+        assert_eq!(schema.name.to_vec(), b"IRB member".to_vec());
+        assert!(schema.active);
+        System::assert_last_event(Event::SchemaRegistered { schema_id }.into());
+
+        // Same name and document
+        assert_noop!(
+            Credentials::register_schema(
+                RuntimeOrigin::root(),
+                BoundedVec::try_from(b"IRB member".to_vec()).unwrap(),
+                H256::repeat_byte(3),
+            ),
+            Error::<Test>::SchemaAlreadyExists
+        );
+
+        // Governance only
+        assert_noop!(
+            Credentials::register_schema(
+                RuntimeOrigin::signed(1),
+                BoundedVec::try_from(b"Physician".to_vec()).unwrap(),
+                H256::repeat_byte(3),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn issue_credential_works() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+        let credential_hash = H256::repeat_byte(9);
+
+        issue(issuer, credential_hash, holder, schema_id);
+
+        let credential = Credentials::credentials(credential_hash).unwrap();
+        assert_eq!(credential.issuer, issuer);
+        assert_eq!(credential.holder, holder);
+        assert_eq!(credential.status, StatusListEntry { list_id: 0, index: 0 });
+        assert_eq!(Credentials::holder_credentials(holder, schema_id), Some(credential_hash));
+        assert!(Credentials::has_valid_credential(&holder, schema_id));
+        System::assert_last_event(
+            Event::CredentialIssued { credential_hash, issuer, holder, schema_id }.into(),
+        );
+
+        // Hashes are unique
+        assert_noop!(
+            Credentials::issue_credential(
+                RuntimeOrigin::signed(issuer),
+                credential_hash,
+                holder,
+                schema_id,
+                None,
+            ),
+            Error::<Test>::CredentialAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn issue_credential_requires_verified_institution() {
+    new_test_ext().execute_with(|| {
+        let (institution, researcher, holder) = (1u64, 2u64, 3u64);
+        register(institution, UserRole::Institution);
+        verified_institution(researcher + 10);
+        register(researcher, UserRole::Researcher);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+
+        // Unverified institution
+        assert_noop!(
+            Credentials::issue_credential(
+                RuntimeOrigin::signed(institution),
+                H256::repeat_byte(9),
+                holder,
+                schema_id,
+                None,
+            ),
+            Error::<Test>::NotAuthorizedIssuer
+        );
+
+        // Researchers cannot issue
+        assert_noop!(
+            Credentials::issue_credential(
+                RuntimeOrigin::signed(researcher),
+                H256::repeat_byte(9),
+                holder,
+                schema_id,
+                None,
+            ),
+            Error::<Test>::NotAuthorizedIssuer
+        );
+
+        // Holder must have an identity
+        assert_noop!(
+            Credentials::issue_credential(
+                RuntimeOrigin::signed(researcher + 10),
+                H256::repeat_byte(9),
+                99,
+                schema_id,
+                None,
+            ),
+            Error::<Test>::InvalidHolder
+        );
+    });
+}
+
+#[test]
+fn deprecated_schema_stops_issuance() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+        issue(issuer, H256::repeat_byte(9), holder, schema_id);
+
+        assert_ok!(Credentials::deprecate_schema(RuntimeOrigin::root(), schema_id));
+        assert!(!Credentials::schemas(schema_id).unwrap().active);
+
+        assert_noop!(
+            Credentials::issue_credential(
+                RuntimeOrigin::signed(issuer),
+                H256::repeat_byte(10),
+                holder,
+                schema_id,
+                None,
+            ),
+            Error::<Test>::SchemaDeprecated
+        );
+        assert_noop!(
+            Credentials::deprecate_schema(RuntimeOrigin::root(), schema_id),
+            Error::<Test>::SchemaDeprecated
+        );
+
+        // Issued credentials stay valid
+        assert!(Credentials::has_valid_credential(&holder, schema_id));
+    });
+}
+
+#[test]
+fn revoke_credential_sets_status_bit() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+        for i in 1..=3u64 {
+            issue(issuer, H256::from_low_u64_be(i), holder, schema_id);
+        }
+
+        // Only the issuer can revoke
+        assert_noop!(
+            Credentials::revoke_credential(RuntimeOrigin::signed(holder), H256::from_low_u64_be(3)),
+            Error::<Test>::NotIssuer
+        );
+
+        assert_ok!(Credentials::revoke_credential(
+            RuntimeOrigin::signed(issuer),
+            H256::from_low_u64_be(3)
+        ));
+        assert_eq!(Credentials::status_lists(issuer, 0).to_vec(), vec![0b0000_0100]);
+        System::assert_last_event(
+            Event::CredentialRevoked {
+                credential_hash: H256::from_low_u64_be(3),
+                status: StatusListEntry { list_id: 0, index: 2 },
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Credentials::revoke_credential(RuntimeOrigin::signed(issuer), H256::from_low_u64_be(3)),
+            Error::<Test>::AlreadyRevoked
+        );
+
+        // The latest credential of the schema is revoked
+        assert!(!Credentials::has_valid_credential(&holder, schema_id));
+        assert_eq!(
+            Credentials::verify_credential(H256::from_low_u64_be(3), &issuer, &holder, schema_id),
+            CredentialStatus::Revoked
+        );
+        assert_eq!(
+            Credentials::verify_credential(H256::from_low_u64_be(1), &issuer, &holder, schema_id),
+            CredentialStatus::Valid
+        );
+    });
+}
+
+#[test]
+fn credential_handler_follows_held_credential() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("Clinician");
+
+        issue(issuer, H256::from_low_u64_be(1), holder, schema_id);
+        assert_ok!(Credentials::issue_credential(
+            RuntimeOrigin::signed(issuer),
+            H256::from_low_u64_be(2),
+            holder,
+            schema_id,
+            Some(5_000),
+        ));
+        assert_eq!(
+            CredentialChanges::get(),
+            vec![(holder, schema_id, Some(u64::MAX)), (holder, schema_id, Some(5_000))]
+        );
+
+        // Revoking a superseded credential changes nothing
+        assert_ok!(Credentials::revoke_credential(
+            RuntimeOrigin::signed(issuer),
+            H256::from_low_u64_be(1)
+        ));
+        assert_eq!(CredentialChanges::get().len(), 2);
+
+        assert_ok!(Credentials::revoke_credential(
+            RuntimeOrigin::signed(issuer),
+            H256::from_low_u64_be(2)
+        ));
+        assert_eq!(CredentialChanges::get().last(), Some(&(holder, schema_id, None)));
+    });
+}
+
+#[test]
+fn status_lists_roll_over() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+
+        // One-byte lists hold eight credentials
+        for i in 1..=9u64 {
+            issue(issuer, H256::from_low_u64_be(i), holder, schema_id);
+        }
+        let ninth = Credentials::credentials(H256::from_low_u64_be(9)).unwrap();
+        assert_eq!(ninth.status, StatusListEntry { list_id: 1, index: 0 });
+
+        assert_ok!(Credentials::revoke_credential(
+            RuntimeOrigin::signed(issuer),
+            H256::from_low_u64_be(9)
+        ));
+        assert_eq!(Credentials::status_lists(issuer, 0).to_vec(), Vec::<u8>::new());
+        assert_eq!(Credentials::status_lists(issuer, 1).to_vec(), vec![0b0000_0001]);
+    });
+}
+
+#[test]
+fn verify_credential_checks_presentation() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder, other) = (1u64, 2u64, 3u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+        let credential_hash = H256::repeat_byte(9);
+        issue(issuer, credential_hash, holder, schema_id);
+
+        let verify = |hash, issuer, holder, schema_id| {
+            Credentials::verify_credential(hash, &issuer, &holder, schema_id)
+        };
+        assert_eq!(verify(credential_hash, issuer, holder, schema_id), CredentialStatus::Valid);
+        assert_eq!(
+            verify(H256::repeat_byte(8), issuer, holder, schema_id),
+            CredentialStatus::Unknown
+        );
+        assert_eq!(
+            verify(credential_hash, other, holder, schema_id),
+            CredentialStatus::IssuerMismatch
+        );
+        assert_eq!(
+            verify(credential_hash, issuer, other, schema_id),
+            CredentialStatus::HolderMismatch
+        );
+        assert_eq!(
+            verify(credential_hash, issuer, holder, H256::repeat_byte(8)),
+            CredentialStatus::SchemaMismatch
+        );
+    });
+}
+
+#[test]
+fn credentials_expire() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+        let credential_hash = H256::repeat_byte(9);
+
+        assert_noop!(
+            Credentials::issue_credential(
+                RuntimeOrigin::signed(issuer),
+                credential_hash,
+                holder,
+                schema_id,
+                Some(1_000),
+            ),
+            Error::<Test>::InvalidExpiry
+        );
+        assert_ok!(Credentials::issue_credential(
+            RuntimeOrigin::signed(issuer),
+            credential_hash,
+            holder,
+            schema_id,
+            Some(2_000),
+        ));
+        assert!(Credentials::has_valid_credential(&holder, schema_id));
+
+        Timestamp::set_timestamp(2_000);
+        assert!(!Credentials::has_valid_credential(&holder, schema_id));
+        assert_eq!(
+            Credentials::verify_credential(credential_hash, &issuer, &holder, schema_id),
+            CredentialStatus::Expired
+        );
+    });
+}
+
+#[test]
+fn credentials_lapse_with_issuer_verification() {
+    new_test_ext().execute_with(|| {
+        let (issuer, holder) = (1u64, 2u64);
+        verified_institution(issuer);
+        register(holder, UserRole::Researcher);
+        let schema_id = schema("IRB member");
+        let credential_hash = H256::repeat_byte(9);
+        issue(issuer, credential_hash, holder, schema_id);
+
+        assert_ok!(IdentityRegistry::revoke_attestation(
+            RuntimeOrigin::signed(VERIFIER),
+            issuer
+        ));
+
+        assert!(!Credentials::has_valid_credential(&holder, schema_id));
+        assert_eq!(
+            Credentials::verify_credential(credential_hash, &issuer, &holder, schema_id),
+            CredentialStatus::IssuerNotVerified
+        );
+    });
+}
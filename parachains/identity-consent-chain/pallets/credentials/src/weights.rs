@@ -0,0 +1,102 @@
+//! Estimated weights for `pallet_credentials`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/identity-consent-node benchmark pallet \
+//!     --chain dev --pallet pallet_credentials --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/credentials/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_credentials`.
+pub trait WeightInfo {
+    fn register_schema() -> Weight;
+    fn deprecate_schema() -> Weight;
+    fn issue_credential() -> Weight;
+    fn revoke_credential() -> Weight;
+}
+
+/// Estimated weights for `pallet_credentials`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Credentials::Schemas`, `Timestamp::Now`
+    // Writes: `Credentials::Schemas`
+    fn register_schema() -> Weight {
+        Weight::from_parts(16_000_000, 3_599)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Credentials::Schemas`
+    // Writes: `Credentials::Schemas`
+    fn deprecate_schema() -> Weight {
+        Weight::from_parts(14_000_000, 3_599)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities` x2, `IdentityRegistry::Verifiers`,
+    //     `IdentityRegistry::VerificationThresholds` x4, `IdentityRegistry::Attestations` x16,
+    //     `Timestamp::Now`, `Credentials::Schemas`, `Credentials::Credentials`,
+    //     `Credentials::NextStatusEntry`, `ParachainSystem::HostConfiguration`,
+    //     `ParachainSystem::RelevantMessagingState`, `XcmpQueue::DeliveryFeeFactor`,
+    //     `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `Credentials::Credentials`, `Credentials::NextStatusEntry`,
+    //     `Credentials::HolderCredentials`, `XcmpQueue::OutboundXcmpStatus`,
+    //     `XcmpQueue::OutboundXcmpMessages`
+    fn issue_credential() -> Weight {
+        Weight::from_parts(81_000_000, 43_864)
+            .saturating_add(T::DbWeight::get().reads(31_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Credentials::Credentials`, `Credentials::StatusLists`,
+    //     `Credentials::HolderCredentials`, `ParachainSystem::HostConfiguration`,
+    //     `ParachainSystem::RelevantMessagingState`, `XcmpQueue::DeliveryFeeFactor`,
+    //     `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `Credentials::StatusLists`, `XcmpQueue::OutboundXcmpStatus`,
+    //     `XcmpQueue::OutboundXcmpMessages`
+    fn revoke_credential() -> Weight {
+        Weight::from_parts(43_000_000, 5_531)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn register_schema() -> Weight {
+        Weight::from_parts(16_000_000, 3_599)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn deprecate_schema() -> Weight {
+        Weight::from_parts(14_000_000, 3_599)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn issue_credential() -> Weight {
+        Weight::from_parts(81_000_000, 43_864)
+            .saturating_add(RocksDbWeight::get().reads(31_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn revoke_credential() -> Weight {
+        Weight::from_parts(43_000_000, 5_531)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+}
@@ -0,0 +1,230 @@
+//! RPC interface resolving `did:patientx:` DIDs
+//!
+//! `did_resolve` returns a W3C DID resolution result: the DID document, with Multikey
+//! verification methods and service endpoints, along with its creation, update and deactivation
+//! metadata.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject};
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+use pallet_identity_registry_runtime_api::{KeyType, VerificationMethod, VerificationRelationship};
+pub use pallet_identity_registry_runtime_api::{DidApi as DidRuntimeApi, ResolvedDid};
+
+/// JSON-LD context of DID documents
+const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+/// JSON-LD context of Multikey verification methods
+const MULTIKEY_CONTEXT: &str = "https://w3id.org/security/multikey/v1";
+/// Error code for runtime API failures
+const RUNTIME_ERROR: i32 = 1;
+
+/// DID resolution RPC methods
+#[rpc(client, server)]
+pub trait DidApi<BlockHash> {
+    /// Resolve a DID to a W3C DID resolution result, `null` if it is malformed or unknown
+    #[method(name = "did_resolve")]
+    fn resolve(&self, did: String, at: Option<BlockHash>) -> RpcResult<Option<Value>>;
+}
+
+/// Provides DID resolution through the runtime API
+pub struct Did<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Did<C, Block> {
+    /// Create a new instance of the DID resolution RPC
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+impl<C, Block> DidApiServer<<Block as BlockT>::Hash> for Did<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: DidRuntimeApi<Block>,
+{
+    fn resolve(&self, did: String, at: Option<Block::Hash>) -> RpcResult<Option<Value>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let resolved = api.resolve_did(at, did.into_bytes()).map_err(|e| {
+            ErrorObject::owned(RUNTIME_ERROR, "Unable to resolve DID.", Some(e.to_string()))
+        })?;
+
+        Ok(resolved.as_ref().map(did_resolution))
+    }
+}
+
+/// Render a resolved DID as a W3C DID resolution result
+pub fn did_resolution(resolved: &ResolvedDid) -> Value {
+    let did = String::from_utf8_lossy(&resolved.did).into_owned();
+    let reference = |fragment: &[u8]| format!("{did}#{}", String::from_utf8_lossy(fragment));
+
+    let mut document = json!({
+        "@context": [DID_CONTEXT, MULTIKEY_CONTEXT],
+        "id": did,
+        "controller": resolved
+            .controllers
+            .iter()
+            .map(|controller| String::from_utf8_lossy(controller).into_owned())
+            .collect::<Vec<_>>(),
+        "verificationMethod": resolved
+            .verification_methods
+            .iter()
+            .map(|method| {
+                json!({
+                    "id": reference(&method.id),
+                    "type": "Multikey",
+                    "controller": did,
+                    "publicKeyMultibase": public_key_multibase(method),
+                })
+            })
+            .collect::<Vec<_>>(),
+        "service": resolved
+            .services
+            .iter()
+            .map(|service| {
+                json!({
+                    "id": reference(&service.id),
+                    "type": String::from_utf8_lossy(&service.service_type),
+                    "serviceEndpoint": String::from_utf8_lossy(&service.endpoint),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+
+    for (relationship, property) in [
+        (VerificationRelationship::Authentication, "authentication"),
+        (VerificationRelationship::AssertionMethod, "assertionMethod"),
+        (VerificationRelationship::KeyAgreement, "keyAgreement"),
+        (VerificationRelationship::CapabilityInvocation, "capabilityInvocation"),
+        (VerificationRelationship::CapabilityDelegation, "capabilityDelegation"),
+    ] {
+        let methods: Vec<String> = resolved
+            .verification_methods
+            .iter()
+            .filter(|method| method.relationships.contains(&relationship))
+            .map(|method| reference(&method.id))
+            .collect();
+        if !methods.is_empty() {
+            document[property] = json!(methods);
+        }
+    }
+
+    json!({
+        "didDocument": document,
+        "didDocumentMetadata": {
+            "created": xml_datetime(resolved.created),
+            "updated": xml_datetime(resolved.updated),
+            "deactivated": resolved.deactivated,
+        },
+        "didResolutionMetadata": { "contentType": "application/did+ld+json" },
+    })
+}
+
+/// Multibase (base58btc) encoding of a multicodec-prefixed public key
+fn public_key_multibase(method: &VerificationMethod) -> String {
+    let codec: [u8; 2] = match method.key_type {
+        KeyType::Ed25519 => [0xed, 0x01],
+        KeyType::Sr25519 => [0xef, 0x01],
+        KeyType::EcdsaSecp256k1 => [0xe7, 0x01],
+        KeyType::X25519 => [0xec, 0x01],
+    };
+    let bytes: Vec<u8> = codec.iter().chain(method.public_key.iter()).copied().collect();
+    format!("z{}", bs58::encode(bytes).into_string())
+}
+
+/// Format a millisecond timestamp as an XML datetime (`YYYY-MM-DDTHH:MM:SSZ`)
+fn xml_datetime(ms: u64) -> String {
+    let secs = ms / 1_000;
+    let (days, time) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the Unix epoch
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(
+        key_type: KeyType,
+        relationships: Vec<VerificationRelationship>,
+    ) -> VerificationMethod {
+        VerificationMethod {
+            id: b"key-1".to_vec().try_into().unwrap(),
+            key_type,
+            public_key: vec![7u8; key_type.public_key_len()].try_into().unwrap(),
+            relationships: relationships.try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn timestamps_are_xml_datetimes() {
+        assert_eq!(xml_datetime(0), "1970-01-01T00:00:00Z");
+        assert_eq!(xml_datetime(951_782_400_000), "2000-02-29T00:00:00Z");
+        assert_eq!(xml_datetime(1_700_000_000_123), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn keys_are_multicodec_multibase() {
+        let ed25519 = method(KeyType::Ed25519, vec![VerificationRelationship::Authentication]);
+        let x25519 = method(KeyType::X25519, vec![VerificationRelationship::KeyAgreement]);
+
+        assert!(public_key_multibase(&ed25519).starts_with("z6Mk"));
+        assert!(public_key_multibase(&x25519).starts_with("z6LS"));
+    }
+
+    #[test]
+    fn document_lists_methods_by_relationship() {
+        let resolved = ResolvedDid {
+            did: b"did:patientx:alice".to_vec(),
+            controllers: vec![b"did:patientx:alice".to_vec()],
+            verification_methods: vec![method(
+                KeyType::Sr25519,
+                vec![
+                    VerificationRelationship::Authentication,
+                    VerificationRelationship::AssertionMethod,
+                ],
+            )],
+            services: vec![],
+            created: 0,
+            updated: 1_000,
+            deactivated: false,
+        };
+
+        let resolution = did_resolution(&resolved);
+        let document = &resolution["didDocument"];
+
+        assert_eq!(document["id"], "did:patientx:alice");
+        assert_eq!(document["controller"], json!(["did:patientx:alice"]));
+        assert_eq!(document["verificationMethod"][0]["id"], "did:patientx:alice#key-1");
+        assert_eq!(document["verificationMethod"][0]["type"], "Multikey");
+        assert_eq!(document["authentication"], json!(["did:patientx:alice#key-1"]));
+        assert_eq!(document["assertionMethod"], json!(["did:patientx:alice#key-1"]));
+        assert!(document.get("keyAgreement").is_none());
+        assert_eq!(resolution["didDocumentMetadata"]["updated"], "1970-01-01T00:00:01Z");
+        assert_eq!(resolution["didDocumentMetadata"]["deactivated"], false);
+    }
+}
@@ -0,0 +1,16 @@
+//! Runtime API for resolving `did:patientx:` DIDs
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet_identity_registry::did::{
+    KeyType, ResolvedDid, Service, VerificationMethod, VerificationRelationship,
+};
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    /// Resolution of `did:patientx:` DIDs to their documents
+    pub trait DidApi {
+        /// Resolve a DID, `None` if it is malformed or not registered
+        fn resolve_did(did: Vec<u8>) -> Option<ResolvedDid>;
+    }
+}
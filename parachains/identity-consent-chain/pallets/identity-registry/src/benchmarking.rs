@@ -0,0 +1,639 @@
+//! Benchmarking setup for pallet-identity-registry
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as IdentityRegistry;
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::ConstU32, BoundedVec};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Hex digits used to build DIDs from account IDs
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Build a unique DID for an account
+fn did_for<T: Config>(who: &T::AccountId) -> BoundedVec<u8, ConstU32<100>> {
+    let mut did = b"did:patientx:".to_vec();
+    for byte in who.encode() {
+        did.extend_from_slice(&[HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]);
+    }
+    BoundedVec::truncate_from(did)
+}
+
+/// Register an identity with the given role
+fn register<T: Config>(who: &T::AccountId, role: UserRole) {
+    Pallet::<T>::register_identity(
+        RawOrigin::Signed(who.clone()).into(),
+        did_for::<T>(who),
+        role,
+        BoundedVec::truncate_from(vec![b'n'; 64]),
+        H256::repeat_byte(1),
+    )
+    .expect("identity registration must succeed");
+}
+
+/// DID fragment `<prefix>-<i>`
+fn fragment(prefix: &[u8], i: u32) -> Fragment {
+    let mut id = prefix.to_vec();
+    id.extend_from_slice(&[b'-', HEX[(i as usize >> 4) & 0xf], HEX[i as usize & 0xf]]);
+    BoundedVec::truncate_from(id)
+}
+
+/// Ed25519 verification method used for authentication and assertions
+fn method(i: u32) -> VerificationMethod {
+    VerificationMethod {
+        id: fragment(b"key", i),
+        key_type: KeyType::Ed25519,
+        public_key: BoundedVec::truncate_from(vec![i as u8; 32]),
+        relationships: BoundedVec::truncate_from(vec![
+            VerificationRelationship::Authentication,
+            VerificationRelationship::AssertionMethod,
+        ]),
+    }
+}
+
+/// Service endpoint with a maximum length URI
+fn service(i: u32) -> Service {
+    Service {
+        id: fragment(b"service", i),
+        service_type: BoundedVec::truncate_from(b"LinkedDomains".to_vec()),
+        endpoint: BoundedVec::truncate_from(vec![b'e'; 256]),
+    }
+}
+
+/// Fill the DID document of `who` with `methods` verification methods and `services` services
+fn fill_document<T: Config>(who: &T::AccountId, methods: u32, services: u32) {
+    DidDocuments::<T>::insert(
+        who,
+        DidDocument {
+            controllers: BoundedVec::truncate_from(vec![who.clone()]),
+            verification_methods: BoundedVec::truncate_from((0..methods).map(method).collect()),
+            services: BoundedVec::truncate_from((0..services).map(service).collect()),
+            updated_at: 0,
+        },
+    );
+}
+
+/// Accredit `n` verifiers
+fn accredit<T: Config>(n: u32) -> Vec<T::AccountId> {
+    let verifiers: Vec<T::AccountId> = (0..n).map(|i| account("verifier", i, 0)).collect();
+    Verifiers::<T>::put(BoundedVec::truncate_from(verifiers.clone()));
+    verifiers
+}
+
+/// Every role, privileged ones last
+fn all_roles() -> [UserRole; 4] {
+    [UserRole::Patient, UserRole::Researcher, UserRole::Institution, UserRole::Auditor]
+}
+
+/// Attest `target` by each of `verifiers`
+fn attest<T: Config>(target: &T::AccountId, verifiers: &[T::AccountId]) {
+    for verifier in verifiers {
+        Attestations::<T>::insert(
+            target,
+            verifier,
+            Attestation {
+                kind: AttestationKind::LegalIdentity,
+                evidence_hash: H256::repeat_byte(3),
+                attested_at: 0,
+                expires_at: None,
+            },
+        );
+    }
+}
+
+/// Name the maximum number of guardians for `who`, all required to approve a recovery
+fn guardians<T: Config>(who: &T::AccountId) -> Vec<T::AccountId> {
+    let guardians: Vec<T::AccountId> =
+        (0..T::MaxGuardians::get()).map(|i| account("guardian", i, 0)).collect();
+    RecoveryConfigs::<T>::insert(
+        who,
+        RecoveryConfig {
+            guardians: BoundedVec::truncate_from(guardians.clone()),
+            threshold: T::MaxGuardians::get(),
+            delay: 0,
+        },
+    );
+    guardians
+}
+
+/// Make `who` the ward of `T::MaxGuardianships` guardians and the guardian of as many wards,
+/// less `spare` on each side
+fn fill_guardianships<T: Config>(who: &T::AccountId, spare: u32) {
+    let guardianship = Guardianship {
+        kind: GuardianshipKind::CourtAppointed,
+        evidence_hash: H256::repeat_byte(7),
+        handover_at: None,
+        registered_at: 0,
+    };
+    for i in 0..T::MaxGuardianships::get().saturating_sub(spare) {
+        let other: T::AccountId = account("guardianship", i, 0);
+        Guardianships::<T>::insert(who, &other, guardianship.clone());
+        Wards::<T>::insert(&other, who, ());
+        Guardianships::<T>::insert(&other, who, guardianship.clone());
+        Wards::<T>::insert(who, &other, ());
+    }
+}
+
+/// Start a recovery of `who` to `new_account`, approved by `approvals`
+fn start_recovery<T: Config>(
+    who: &T::AccountId,
+    new_account: &T::AccountId,
+    approvals: &[T::AccountId],
+) {
+    ActiveRecoveries::<T>::insert(
+        who,
+        ActiveRecovery {
+            new_account: new_account.clone(),
+            initiated_at: 0,
+            approvals: BoundedVec::truncate_from(approvals.to_vec()),
+        },
+    );
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register_identity() {
+        let caller: T::AccountId = whitelisted_caller();
+        let did = did_for::<T>(&caller);
+        let name = BoundedVec::truncate_from(vec![b'n'; 64]);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            did,
+            UserRole::Institution,
+            name,
+            H256::repeat_byte(1),
+        );
+
+        assert!(RoleRequests::<T>::contains_key(&caller, UserRole::Institution));
+    }
+
+    #[benchmark]
+    fn update_identity() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let name = BoundedVec::truncate_from(vec![b'u'; 64]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), Some(name.clone()), Some(H256::repeat_byte(2)));
+
+        assert_eq!(Identities::<T>::get(&caller).unwrap().name, name);
+    }
+
+    #[benchmark]
+    fn request_verification() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Researcher);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(VerificationQueue::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn verify_identity() {
+        let max = T::MaxVerifiers::get();
+        let verifiers = accredit::<T>(max);
+        let target: T::AccountId = whitelisted_caller();
+        register::<T>(&target, UserRole::Patient);
+        Pallet::<T>::request_verification(RawOrigin::Signed(target.clone()).into())
+            .expect("verification request must succeed");
+
+        // The last attestation reaches the threshold of the identity and of every privileged
+        // role it requested
+        for role in all_roles() {
+            VerificationThresholds::<T>::insert(&role, max);
+            if role.is_privileged() {
+                RoleRequests::<T>::insert(&target, &role, 0);
+            }
+        }
+        let (last, others) = verifiers.split_last().expect("at least one verifier");
+        attest::<T>(&target, others);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(last.clone()),
+            target.clone(),
+            AttestationKind::ProfessionalLicense,
+            H256::repeat_byte(3),
+            Some(u64::MAX),
+        );
+
+        assert!(Pallet::<T>::is_verified(&target));
+        assert!(Pallet::<T>::has_role(&target, UserRole::Auditor));
+    }
+
+    #[benchmark]
+    fn reject_verification() {
+        let auditor = accredit::<T>(1).remove(0);
+        let target: T::AccountId = whitelisted_caller();
+        register::<T>(&target, UserRole::Researcher);
+        Pallet::<T>::request_verification(RawOrigin::Signed(target.clone()).into())
+            .expect("verification request must succeed");
+        let reason = BoundedVec::truncate_from(vec![b'r'; 128]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(auditor), target.clone(), reason);
+
+        assert!(!VerificationQueue::<T>::contains_key(&target));
+    }
+
+    #[benchmark]
+    fn deactivate_identity() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        fill_guardianships::<T>(&caller, 0);
+        T::GuardianshipHandler::ensure_successful();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!Pallet::<T>::is_active_identity(&caller));
+        assert_eq!(Wards::<T>::iter_key_prefix(&caller).count(), 0);
+    }
+
+    #[benchmark]
+    fn add_verifier() -> Result<(), BenchmarkError> {
+        let origin =
+            T::VerifierOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        accredit::<T>(T::MaxVerifiers::get() - 1);
+        let verifier: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, verifier.clone());
+
+        assert!(Pallet::<T>::is_verifier(&verifier));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_verifier() -> Result<(), BenchmarkError> {
+        let origin =
+            T::VerifierOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let verifier = accredit::<T>(T::MaxVerifiers::get()).pop().expect("at least one verifier");
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, verifier.clone());
+
+        assert!(!Pallet::<T>::is_verifier(&verifier));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_verification_threshold() -> Result<(), BenchmarkError> {
+        let origin =
+            T::VerifierOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let threshold = T::MaxVerifiers::get();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, UserRole::Institution, threshold);
+
+        assert_eq!(VerificationThresholds::<T>::get(UserRole::Institution), threshold);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn revoke_attestation() {
+        let max = T::MaxVerifiers::get();
+        let verifiers = accredit::<T>(max);
+        let target: T::AccountId = whitelisted_caller();
+        register::<T>(&target, UserRole::Institution);
+
+        // Revoking any attestation drops the identity below the threshold
+        VerificationThresholds::<T>::insert(UserRole::Institution, max);
+        attest::<T>(&target, &verifiers);
+        Identities::<T>::mutate(&target, |identity| {
+            if let Some(identity) = identity {
+                identity.verification_status = VerificationStatus::Verified;
+            }
+        });
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(verifiers[0].clone()), target.clone());
+
+        assert!(!Pallet::<T>::is_verified(&target));
+    }
+
+    #[benchmark]
+    fn revoke_verification() -> Result<(), BenchmarkError> {
+        let origin =
+            T::VerifierOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let verifiers = accredit::<T>(T::MaxVerifiers::get());
+        let target: T::AccountId = whitelisted_caller();
+        register::<T>(&target, UserRole::Institution);
+        attest::<T>(&target, &verifiers);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, target.clone());
+
+        assert_eq!(
+            Identities::<T>::get(&target).unwrap().verification_status,
+            VerificationStatus::Revoked
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn add_verification_method() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let max = T::MaxVerificationMethods::get();
+        fill_document::<T>(&caller, max - 1, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone(), method(max - 1));
+
+        assert_eq!(DidDocuments::<T>::get(&caller).unwrap().verification_methods.len() as u32, max);
+    }
+
+    #[benchmark]
+    fn remove_verification_method() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let max = T::MaxVerificationMethods::get();
+        fill_document::<T>(&caller, max, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone(), fragment(b"key", max - 1));
+
+        assert_eq!(
+            DidDocuments::<T>::get(&caller).unwrap().verification_methods.len() as u32,
+            max - 1
+        );
+    }
+
+    #[benchmark]
+    fn rotate_key() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let max = T::MaxVerificationMethods::get();
+        fill_document::<T>(&caller, max, 0);
+        let public_key = BoundedVec::truncate_from(vec![0xff; 32]);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            caller.clone(),
+            fragment(b"key", max - 1),
+            public_key.clone(),
+        );
+
+        let document = DidDocuments::<T>::get(&caller).unwrap();
+        assert_eq!(document.verification_methods[max as usize - 1].public_key, public_key);
+    }
+
+    #[benchmark]
+    fn add_service() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let max = T::MaxServices::get();
+        fill_document::<T>(&caller, 0, max - 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone(), service(max - 1));
+
+        assert_eq!(DidDocuments::<T>::get(&caller).unwrap().services.len() as u32, max);
+    }
+
+    #[benchmark]
+    fn remove_service() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let max = T::MaxServices::get();
+        fill_document::<T>(&caller, 0, max);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone(), fragment(b"service", max - 1));
+
+        assert_eq!(DidDocuments::<T>::get(&caller).unwrap().services.len() as u32, max - 1);
+    }
+
+    #[benchmark]
+    fn set_did_controllers() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let controllers: Vec<T::AccountId> = (0..T::MaxDidControllers::get())
+            .map(|i| {
+                let controller: T::AccountId = account("controller", i, 0);
+                register::<T>(&controller, UserRole::Patient);
+                controller
+            })
+            .collect();
+        let controllers = BoundedVec::truncate_from(controllers);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone(), controllers.clone());
+
+        assert_eq!(DidDocuments::<T>::get(&caller).unwrap().controllers, controllers);
+    }
+
+    #[benchmark]
+    fn deactivate_did() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        fill_document::<T>(&caller, T::MaxVerificationMethods::get(), T::MaxServices::get());
+        fill_guardianships::<T>(&caller, 0);
+        T::GuardianshipHandler::ensure_successful();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone());
+
+        assert!(Pallet::<T>::resolve_did(&did_for::<T>(&caller)).unwrap().deactivated);
+    }
+
+    #[benchmark]
+    fn request_role() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), UserRole::Institution);
+
+        assert!(RoleRequests::<T>::contains_key(&caller, UserRole::Institution));
+    }
+
+    #[benchmark]
+    fn revoke_role() -> Result<(), BenchmarkError> {
+        let origin =
+            T::VerifierOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let target: T::AccountId = whitelisted_caller();
+        register::<T>(&target, UserRole::Patient);
+        Identities::<T>::mutate(&target, |identity| {
+            if let Some(identity) = identity {
+                identity.roles = BoundedVec::truncate_from(all_roles().to_vec());
+            }
+        });
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, target.clone(), UserRole::Auditor);
+
+        assert!(!Pallet::<T>::has_role(&target, UserRole::Auditor));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_recovery_config() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let guardians: Vec<T::AccountId> =
+            (0..T::MaxGuardians::get()).map(|i| account("guardian", i, 0)).collect();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            BoundedVec::truncate_from(guardians),
+            T::MaxGuardians::get(),
+            0,
+        );
+
+        assert!(RecoveryConfigs::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn remove_recovery_config() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let guardians = guardians::<T>(&caller);
+        start_recovery::<T>(&caller, &account("new", 0, 0), &guardians[..1]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!RecoveryConfigs::<T>::contains_key(&caller));
+        assert!(!ActiveRecoveries::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn initiate_recovery() {
+        let lost: T::AccountId = account("lost", 0, 0);
+        register::<T>(&lost, UserRole::Patient);
+        // The last guardian, the most expensive to look up
+        let guardian = guardians::<T>(&lost).pop().unwrap();
+        let new_account: T::AccountId = account("new", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(guardian), lost.clone(), new_account);
+
+        assert!(ActiveRecoveries::<T>::contains_key(&lost));
+    }
+
+    #[benchmark]
+    fn approve_recovery() {
+        let lost: T::AccountId = account("lost", 0, 0);
+        register::<T>(&lost, UserRole::Patient);
+        let mut guardians = guardians::<T>(&lost);
+        let guardian = guardians.pop().unwrap();
+        start_recovery::<T>(&lost, &account("new", 0, 0), &guardians);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(guardian.clone()), lost.clone());
+
+        assert!(ActiveRecoveries::<T>::get(&lost).unwrap().approvals.contains(&guardian));
+    }
+
+    #[benchmark]
+    fn cancel_recovery() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Patient);
+        let guardians = guardians::<T>(&caller);
+        start_recovery::<T>(&caller, &account("new", 0, 0), &guardians);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!ActiveRecoveries::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn complete_recovery() {
+        let lost: T::AccountId = account("lost", 0, 0);
+        register::<T>(&lost, UserRole::Patient);
+        fill_document::<T>(&lost, T::MaxVerificationMethods::get(), T::MaxServices::get());
+        attest::<T>(&lost, &accredit::<T>(T::MaxVerifiers::get()));
+        for role in all_roles().into_iter().filter(UserRole::is_privileged) {
+            RoleRequests::<T>::insert(&lost, role, 0);
+        }
+        VerificationQueue::<T>::insert(&lost, 0);
+        fill_guardianships::<T>(&lost, 0);
+        let guardians = guardians::<T>(&lost);
+        let new_account: T::AccountId = account("new", 0, 0);
+        start_recovery::<T>(&lost, &new_account, &guardians);
+        T::RecoveryHandler::ensure_successful();
+        T::GuardianshipHandler::ensure_successful();
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), lost.clone());
+
+        assert!(!Identities::<T>::contains_key(&lost));
+        assert_eq!(Identities::<T>::get(&new_account).unwrap().owner, new_account);
+        assert_eq!(
+            Wards::<T>::iter_key_prefix(&new_account).count() as u32,
+            T::MaxGuardianships::get()
+        );
+    }
+
+    #[benchmark]
+    fn register_guardianship() -> Result<(), BenchmarkError> {
+        let origin = T::GuardianshipOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let ward: T::AccountId = account("ward", 0, 0);
+        let guardian: T::AccountId = account("guardian", 0, 0);
+        register::<T>(&ward, UserRole::Patient);
+        register::<T>(&guardian, UserRole::Patient);
+        fill_guardianships::<T>(&ward, 1);
+        fill_guardianships::<T>(&guardian, 1);
+        T::GuardianshipHandler::ensure_successful();
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            ward.clone(),
+            guardian.clone(),
+            GuardianshipKind::CourtAppointed,
+            H256::repeat_byte(7),
+            Some(u64::MAX),
+        );
+
+        assert!(Guardianships::<T>::contains_key(&ward, &guardian));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn end_guardianship() {
+        let ward: T::AccountId = account("ward", 0, 0);
+        let guardian: T::AccountId = whitelisted_caller();
+        Guardianships::<T>::insert(
+            &ward,
+            &guardian,
+            Guardianship {
+                kind: GuardianshipKind::CourtAppointed,
+                evidence_hash: H256::repeat_byte(7),
+                handover_at: None,
+                registered_at: 0,
+            },
+        );
+        Wards::<T>::insert(&guardian, &ward, ());
+        T::GuardianshipHandler::ensure_successful();
+
+        // The guardian steps down
+        #[extrinsic_call]
+        _(RawOrigin::Signed(guardian.clone()), ward.clone(), guardian.clone());
+
+        assert!(!Guardianships::<T>::contains_key(&ward, &guardian));
+        assert!(!Wards::<T>::contains_key(&guardian, &ward));
+    }
+
+    impl_benchmark_test_suite!(IdentityRegistry, crate::mock::new_test_ext(), crate::mock::Test);
+}
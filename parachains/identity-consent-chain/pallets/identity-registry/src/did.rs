@@ -0,0 +1,145 @@
+//! `did:patientx:` DID method
+//!
+//! DIDs of this method have the form `did:patientx:<method-specific-id>`, where the
+//! method-specific identifier follows the W3C DID syntax: one or more segments of `idchar`
+//! (`ALPHA / DIGIT / "." / "-" / "_" / pct-encoded`) separated by `:`.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Prefix of every DID of this method
+pub const DID_PREFIX: &[u8] = b"did:patientx:";
+
+/// Fragment identifying a verification method or service within a DID document (`#key-1`)
+pub type Fragment = BoundedVec<u8, ConstU32<32>>;
+
+/// Parse a `did:patientx:` DID, returning its method-specific identifier
+pub fn parse_did(did: &[u8]) -> Option<&[u8]> {
+    let id = did.strip_prefix(DID_PREFIX)?;
+    id.split(|c| *c == b':').all(is_id).then_some(id)
+}
+
+/// Check that `id` is a non-empty sequence of `idchar`
+fn is_id(id: &[u8]) -> bool {
+    let mut i = 0;
+    while i < id.len() {
+        match id[i] {
+            c if c.is_ascii_alphanumeric() || matches!(c, b'.' | b'-' | b'_') => i += 1,
+            b'%' if id.len() > i + 2
+                && id[i + 1].is_ascii_hexdigit()
+                && id[i + 2].is_ascii_hexdigit() =>
+            {
+                i += 3
+            },
+            _ => return false,
+        }
+    }
+    !id.is_empty()
+}
+
+/// Public key types of verification methods
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KeyType {
+    /// Ed25519 signing key
+    Ed25519,
+    /// Sr25519 signing key
+    Sr25519,
+    /// Compressed secp256k1 signing key
+    EcdsaSecp256k1,
+    /// X25519 key agreement key
+    X25519,
+}
+
+impl KeyType {
+    /// Length of a public key of this type in bytes
+    pub fn public_key_len(&self) -> usize {
+        match self {
+            KeyType::EcdsaSecp256k1 => 33,
+            _ => 32,
+        }
+    }
+}
+
+/// Purposes a verification method can be used for
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum VerificationRelationship {
+    /// Authenticating as the DID subject
+    Authentication,
+    /// Issuing verifiable credentials
+    AssertionMethod,
+    /// Establishing encrypted channels
+    KeyAgreement,
+    /// Invoking capabilities
+    CapabilityInvocation,
+    /// Delegating capabilities
+    CapabilityDelegation,
+}
+
+/// Verification method of a DID document
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VerificationMethod {
+    /// Fragment identifying the method
+    pub id: Fragment,
+    /// Public key type
+    pub key_type: KeyType,
+    /// Public key bytes
+    pub public_key: BoundedVec<u8, ConstU32<33>>,
+    /// Purposes the key can be used for
+    pub relationships: BoundedVec<VerificationRelationship, ConstU32<5>>,
+}
+
+impl VerificationMethod {
+    /// Check the fragment, key length and relationships
+    ///
+    /// X25519 keys can only be used for key agreement, and only they can.
+    pub fn is_valid(&self) -> bool {
+        let key_agreement = self.key_type == KeyType::X25519;
+        is_id(&self.id)
+            && self.public_key.len() == self.key_type.public_key_len()
+            && !self.relationships.is_empty()
+            && self.relationships.iter().enumerate().all(|(i, relationship)| {
+                !self.relationships[..i].contains(relationship)
+                    && key_agreement == (*relationship == VerificationRelationship::KeyAgreement)
+            })
+    }
+}
+
+/// Service endpoint of a DID document
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Service {
+    /// Fragment identifying the service
+    pub id: Fragment,
+    /// Service type (e.g. `LinkedDomains`, `DIDCommMessaging`)
+    pub service_type: BoundedVec<u8, ConstU32<64>>,
+    /// Service endpoint URI
+    pub endpoint: BoundedVec<u8, ConstU32<256>>,
+}
+
+impl Service {
+    /// Check the fragment and that type and endpoint are set
+    pub fn is_valid(&self) -> bool {
+        is_id(&self.id) && !self.service_type.is_empty() && !self.endpoint.is_empty()
+    }
+}
+
+/// Resolved DID document, as returned by the runtime API
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ResolvedDid {
+    /// The DID
+    pub did: Vec<u8>,
+    /// DIDs of the controllers
+    pub controllers: Vec<Vec<u8>>,
+    /// Verification methods
+    pub verification_methods: Vec<VerificationMethod>,
+    /// Service endpoints
+    pub services: Vec<Service>,
+    /// Creation timestamp (milliseconds)
+    pub created: u64,
+    /// Last update timestamp (milliseconds)
+    pub updated: u64,
+    /// Whether the DID has been deactivated
+    pub deactivated: bool,
+}
@@ -5,10 +5,71 @@
 //! The Identity Registry pallet manages user identities for the Patient X medical data marketplace.
 //! It provides functionality for:
 //! - User registration with role-based identity (Patient, Researcher, Institution, Auditor)
+//! - Multi-role identities and role changes
 //! - DID (Decentralized Identifier) management
 //! - Identity verification and attestation
 //! - Profile management
 //!
+//! ## Verification
+//!
+//! Identities are verified by accredited verifiers, a set managed by `VerifierOrigin`. Each
+//! verifier submits a typed attestation (legal identity, professional license, ...) with an
+//! evidence hash and an optional expiry through `verify_identity`. An identity is verified once
+//! it holds valid attestations from at least the threshold of verifiers set for its role with
+//! `set_verification_threshold` (one by default), so Institutions can be required to have more
+//! attesters than Patients. Attestations stop counting when they expire or their verifier loses
+//! accreditation. A verifier can revoke its own attestation, and `VerifierOrigin` can revoke a
+//! verification altogether.
+//!
+//! ## Roles
+//!
+//! An identity holds a set of roles, so a clinician-researcher can be both `Patient` and
+//! `Researcher`. Roles are requested on registration or with `request_role`: `Patient` is granted
+//! immediately, while privileged roles (`Researcher`, `Institution`, `Auditor`) need fresh
+//! verification, and are granted once attestations made after the request meet the role's
+//! verification threshold. `VerifierOrigin` can revoke a role. An identity's verification
+//! threshold is the highest among its roles, or among its requested roles while it holds none.
+//!
+//! ## DID documents
+//!
+//! Identities use the `did:patientx:` DID method (see [`did`]), and DIDs are validated on
+//! registration. Each DID has a document listing its controllers, verification methods and
+//! service endpoints, managed by its controllers (initially the identity owner). Keys are rotated
+//! in place with `rotate_key`, keeping the method's fragment and relationships. Deactivating the
+//! DID, or the identity, removes its keys and services. The `DidApi` runtime API resolves a DID to
+//! its document, which the node's `did_resolve` RPC renders as W3C DID document JSON.
+//!
+//! ## Recovery
+//!
+//! An identity can name guardians with `set_recovery_config`, along with the number of guardian
+//! approvals a recovery needs and a delay. If its keys are lost, a guardian starts moving the
+//! identity to a new account with `initiate_recovery` and other guardians approve it. Once the
+//! threshold is met and the delay has passed, anyone can complete the recovery; until then the
+//! identity's own account can cancel it. Completion moves the identity, its DID and DID document,
+//! attestations, pending requests, recovery config and guardianships to the new account, then
+//! notifies `RecoveryHandler`, through which the runtime moves the account's consents and asks
+//! the HealthData and Marketplace chains to re-key the patient's records, keys, access requests
+//! and listings over XCM.
+//!
+//! ## Guardianship
+//!
+//! Minors and incapacitated patients act through legal guardians. `GuardianshipOrigin` registers
+//! a typed guardianship (parent, court-appointed guardian, power of attorney) between two
+//! identities with `register_guardianship`, along with the hash of its evidence (e.g. a court
+//! order) and, for minors, the age-of-majority date at which the ward takes over. The guardian
+//! may step down, the ward may end the guardianship once that date has passed, and
+//! `GuardianshipOrigin` may end it at any time. Guardianships follow a recovered identity to its
+//! new account, and end when either side is deactivated or erased. Other pallets check
+//! guardianships with `is_legal_guardian`, and every change is passed to `GuardianshipHandler`,
+//! through which the runtime mirrors it to the HealthData chain over XCM.
+//!
+//! ## Erasure
+//!
+//! `erase_identity` tombstones an identity for a right-to-erasure request: its name and email
+//! hash are wiped, its DID document, attestations, pending requests and recovery state are
+//! removed, and its guardianships end. The DID stays reserved so it is never reissued. It is
+//! called by the Consent Manager pallet, which runs the rest of the erasure.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "IdentityConsent Chain - Identity Registry"
 
@@ -16,20 +77,36 @@
 
 pub use pallet::*;
 
+pub mod did;
+pub use did::*;
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
+    use crate::did::{self, ResolvedDid, Service, VerificationMethod};
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// User role types in the Patient X ecosystem
@@ -45,6 +122,13 @@ pub mod pallet {
         Auditor,
     }
 
+    impl UserRole {
+        /// Whether the role needs fresh verification to be added to an identity
+        pub fn is_privileged(&self) -> bool {
+            !matches!(self, UserRole::Patient)
+        }
+    }
+
     /// Identity verification status
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub enum VerificationStatus {
@@ -56,6 +140,34 @@ pub mod pallet {
         Verified,
         /// Verification rejected
         Rejected,
+        /// Verification revoked by governance
+        Revoked,
+    }
+
+    /// What a verifier checked before attesting an identity
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum AttestationKind {
+        /// Legal identity checked against official documents
+        LegalIdentity,
+        /// Professional license (e.g. medical license) checked with the issuing body
+        ProfessionalLicense,
+        /// Accreditation of a healthcare or research institution
+        InstitutionalAccreditation,
+        /// Research ethics approval or training
+        EthicsApproval,
+    }
+
+    /// Attestation of an identity by an accredited verifier
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Attestation {
+        /// What was checked
+        pub kind: AttestationKind,
+        /// Hash of the evidence kept off-chain
+        pub evidence_hash: H256,
+        /// Attestation timestamp
+        pub attested_at: u64,
+        /// Expiry timestamp (`None` = no expiry)
+        pub expires_at: Option<u64>,
     }
 
     /// User identity information
@@ -64,8 +176,8 @@ pub mod pallet {
     pub struct Identity<T: Config> {
         /// Unique DID (Decentralized Identifier)
         pub did: BoundedVec<u8, ConstU32<100>>,
-        /// User role
-        pub role: UserRole,
+        /// Roles held by the identity
+        pub roles: BoundedVec<UserRole, ConstU32<4>>,
         /// Display name
         pub name: BoundedVec<u8, ConstU32<64>>,
         /// Email hash for privacy
@@ -82,6 +194,121 @@ pub mod pallet {
         pub active: bool,
     }
 
+    /// DID document of an identity
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct DidDocument<T: Config> {
+        /// Accounts allowed to update the document
+        pub controllers: BoundedVec<T::AccountId, T::MaxDidControllers>,
+        /// Verification methods
+        pub verification_methods: BoundedVec<VerificationMethod, T::MaxVerificationMethods>,
+        /// Service endpoints
+        pub services: BoundedVec<Service, T::MaxServices>,
+        /// Last update timestamp
+        pub updated_at: u64,
+    }
+
+    /// Guardians able to recover an identity to a new account
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct RecoveryConfig<T: Config> {
+        /// Accounts that may initiate and approve a recovery
+        pub guardians: BoundedVec<T::AccountId, T::MaxGuardians>,
+        /// Number of guardian approvals required
+        pub threshold: u32,
+        /// Minimum time between initiation and completion (milliseconds)
+        pub delay: u64,
+    }
+
+    /// Recovery of an identity in progress
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ActiveRecovery<T: Config> {
+        /// Account the identity moves to
+        pub new_account: T::AccountId,
+        /// Initiation timestamp
+        pub initiated_at: u64,
+        /// Guardians who approved the recovery, starting with its initiator
+        pub approvals: BoundedVec<T::AccountId, T::MaxGuardians>,
+    }
+
+    /// Legal basis of a guardianship
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum GuardianshipKind {
+        /// Parent of a minor
+        Parent,
+        /// Guardian appointed by a court
+        CourtAppointed,
+        /// Holder of a (lasting) power of attorney for health decisions
+        PowerOfAttorney,
+    }
+
+    /// Guardian acting for a minor or incapacitated patient
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Guardianship {
+        /// Legal basis
+        pub kind: GuardianshipKind,
+        /// Hash of the evidence (e.g. court order) kept off-chain
+        pub evidence_hash: H256,
+        /// Age-of-majority date at which the ward takes over (`None` = until ended)
+        pub handover_at: Option<u64>,
+        /// Registration timestamp
+        pub registered_at: u64,
+    }
+
+    impl Guardianship {
+        /// Timestamp until which the guardian may act for the ward
+        pub fn valid_until(&self) -> u64 {
+            self.handover_at.unwrap_or(u64::MAX)
+        }
+    }
+
+    /// Handler of guardianships registered or ended on this chain
+    pub trait OnGuardianshipChanged<AccountId> {
+        /// Called once `guardian` may act for `ward` until `valid_until`, or no longer (`None`);
+        /// an error aborts the change
+        fn on_guardianship_changed(
+            ward: &AccountId,
+            guardian: &AccountId,
+            valid_until: Option<u64>,
+        ) -> DispatchResult;
+
+        /// Make `on_guardianship_changed` succeed in benchmarks (e.g. open the channels it uses)
+        #[cfg(feature = "runtime-benchmarks")]
+        fn ensure_successful() {}
+    }
+
+    impl<AccountId> OnGuardianshipChanged<AccountId> for () {
+        fn on_guardianship_changed(
+            _ward: &AccountId,
+            _guardian: &AccountId,
+            _valid_until: Option<u64>,
+        ) -> DispatchResult {
+            Ok(())
+        }
+    }
+
+    /// Handler of identities moved to a new account by a recovery
+    pub trait OnIdentityRecovered<AccountId> {
+        /// Called once the identity of `lost` has moved to `new`; an error aborts the recovery
+        fn on_identity_recovered(lost: &AccountId, new: &AccountId) -> DispatchResult;
+
+        /// Weight of the work `on_identity_recovered` does on this chain
+        fn weight() -> Weight {
+            Weight::zero()
+        }
+
+        /// Make `on_identity_recovered` succeed in benchmarks (e.g. open the channels it uses)
+        #[cfg(feature = "runtime-benchmarks")]
+        fn ensure_successful() {}
+    }
+
+    impl<AccountId> OnIdentityRecovered<AccountId> for () {
+        fn on_identity_recovered(_lost: &AccountId, _new: &AccountId) -> DispatchResult {
+            Ok(())
+        }
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The overarching event type
@@ -93,6 +320,46 @@ pub mod pallet {
         /// Maximum number of identities per account
         #[pallet::constant]
         type MaxIdentitiesPerAccount: Get<u32>;
+
+        /// Governance origin managing verifiers, thresholds and revocations
+        type VerifierOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of accredited verifiers
+        #[pallet::constant]
+        type MaxVerifiers: Get<u32>;
+
+        /// Maximum number of controllers of a DID
+        #[pallet::constant]
+        type MaxDidControllers: Get<u32>;
+
+        /// Maximum number of verification methods in a DID document
+        #[pallet::constant]
+        type MaxVerificationMethods: Get<u32>;
+
+        /// Maximum number of service endpoints in a DID document
+        #[pallet::constant]
+        type MaxServices: Get<u32>;
+
+        /// Maximum number of recovery guardians of an identity
+        #[pallet::constant]
+        type MaxGuardians: Get<u32>;
+
+        /// Maximum number of legal guardianships an identity takes part in, as ward or as
+        /// guardian
+        #[pallet::constant]
+        type MaxGuardianships: Get<u32>;
+
+        /// Notified when a recovery moves an identity to a new account
+        type RecoveryHandler: OnIdentityRecovered<Self::AccountId>;
+
+        /// Origin registering guardianships (e.g. governance acting on court orders)
+        type GuardianshipOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Notified when a guardianship is registered or ended
+        type GuardianshipHandler: OnGuardianshipChanged<Self::AccountId>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     /// Storage for user identities by account ID
@@ -110,6 +377,78 @@ pub mod pallet {
     #[pallet::getter(fn verification_queue)]
     pub type VerificationQueue<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64>;
 
+    /// Accredited verifiers
+    #[pallet::storage]
+    #[pallet::getter(fn verifiers)]
+    pub type Verifiers<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxVerifiers>, ValueQuery>;
+
+    /// Attestations by subject and verifier
+    #[pallet::storage]
+    #[pallet::getter(fn attestations)]
+    pub type Attestations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        Attestation,
+        OptionQuery,
+    >;
+
+    /// DID documents by identity account
+    #[pallet::storage]
+    #[pallet::getter(fn did_documents)]
+    pub type DidDocuments<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, DidDocument<T>>;
+
+    /// Number of verifier attestations required per role (one by default)
+    #[pallet::storage]
+    #[pallet::getter(fn verification_threshold)]
+    pub type VerificationThresholds<T: Config> =
+        StorageMap<_, Twox64Concat, UserRole, u32, ValueQuery, ConstU32<1>>;
+
+    /// Pending requests for privileged roles (account -> role -> request timestamp)
+    #[pallet::storage]
+    #[pallet::getter(fn role_requests)]
+    pub type RoleRequests<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, UserRole, u64>;
+
+    /// Recovery guardians by identity account
+    #[pallet::storage]
+    #[pallet::getter(fn recovery_configs)]
+    pub type RecoveryConfigs<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, RecoveryConfig<T>>;
+
+    /// Recoveries in progress by the account being recovered
+    #[pallet::storage]
+    #[pallet::getter(fn active_recoveries)]
+    pub type ActiveRecoveries<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ActiveRecovery<T>>;
+
+    /// Legal guardianships (ward -> guardian -> guardianship)
+    #[pallet::storage]
+    #[pallet::getter(fn guardianships)]
+    pub type Guardianships<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // ward
+        Blake2_128Concat,
+        T::AccountId, // guardian
+        Guardianship,
+    >;
+
+    /// Wards of each legal guardian (guardian -> ward), the reverse index of `Guardianships`
+    #[pallet::storage]
+    pub type Wards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // guardian
+        Blake2_128Concat,
+        T::AccountId, // ward
+        (),
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -135,6 +474,74 @@ pub mod pallet {
             account: T::AccountId,
             reason: BoundedVec<u8, ConstU32<128>>
         },
+        /// Identity attested by a verifier [account_id, verifier, kind]
+        IdentityAttested {
+            account: T::AccountId,
+            verifier: T::AccountId,
+            kind: AttestationKind
+        },
+        /// Attestation revoked by its verifier [account_id, verifier]
+        AttestationRevoked {
+            account: T::AccountId,
+            verifier: T::AccountId
+        },
+        /// Verification revoked [account_id]
+        VerificationRevoked { account: T::AccountId },
+        /// Verifier accredited [verifier]
+        VerifierAdded { verifier: T::AccountId },
+        /// Verifier accreditation withdrawn [verifier]
+        VerifierRemoved { verifier: T::AccountId },
+        /// Verification threshold changed [role, threshold]
+        VerificationThresholdSet { role: UserRole, threshold: u32 },
+        /// Verification method added to a DID document [account_id, method_id]
+        VerificationMethodAdded { account: T::AccountId, method_id: did::Fragment },
+        /// Verification method removed from a DID document [account_id, method_id]
+        VerificationMethodRemoved { account: T::AccountId, method_id: did::Fragment },
+        /// Key of a verification method rotated [account_id, method_id]
+        KeyRotated { account: T::AccountId, method_id: did::Fragment },
+        /// Service endpoint added to a DID document [account_id, service_id]
+        ServiceAdded { account: T::AccountId, service_id: did::Fragment },
+        /// Service endpoint removed from a DID document [account_id, service_id]
+        ServiceRemoved { account: T::AccountId, service_id: did::Fragment },
+        /// DID controllers replaced [account_id]
+        DidControllersSet { account: T::AccountId },
+        /// DID deactivated [account_id, did]
+        DidDeactivated {
+            account: T::AccountId,
+            did: BoundedVec<u8, ConstU32<100>>
+        },
+        /// Privileged role requested, pending verification [account_id, role]
+        RoleRequested { account: T::AccountId, role: UserRole },
+        /// Role added to an identity [account_id, role]
+        RoleGranted { account: T::AccountId, role: UserRole },
+        /// Role revoked by governance [account_id, role]
+        RoleRevoked { account: T::AccountId, role: UserRole },
+        /// Recovery guardians set [account_id]
+        RecoveryConfigured { account: T::AccountId },
+        /// Recovery guardians removed [account_id]
+        RecoveryConfigRemoved { account: T::AccountId },
+        /// Recovery to a new account started by a guardian [account_id, new_account, guardian]
+        RecoveryInitiated {
+            account: T::AccountId,
+            new_account: T::AccountId,
+            guardian: T::AccountId
+        },
+        /// Recovery approved by a guardian [account_id, guardian]
+        RecoveryApproved { account: T::AccountId, guardian: T::AccountId },
+        /// Recovery cancelled by the account being recovered [account_id]
+        RecoveryCancelled { account: T::AccountId },
+        /// Identity moved to a new account [account_id, new_account]
+        IdentityRecovered { account: T::AccountId, new_account: T::AccountId },
+        /// Personal data of an identity erased [account_id]
+        IdentityErased { account: T::AccountId },
+        /// Guardianship registered [ward, guardian, kind]
+        GuardianshipRegistered {
+            ward: T::AccountId,
+            guardian: T::AccountId,
+            kind: GuardianshipKind
+        },
+        /// Guardianship ended [ward, guardian]
+        GuardianshipEnded { ward: T::AccountId, guardian: T::AccountId },
     }
 
     #[pallet::error]
@@ -157,6 +564,66 @@ pub mod pallet {
         InvalidName,
         /// Maximum identities per account reached
         MaxIdentitiesReached,
+        /// Account is not an accredited verifier
+        NotVerifier,
+        /// Account is already an accredited verifier
+        AlreadyVerifier,
+        /// Maximum number of verifiers reached
+        TooManyVerifiers,
+        /// Threshold must be between one and the maximum number of verifiers
+        InvalidThreshold,
+        /// Attestation expiry is in the past
+        InvalidExpiry,
+        /// Attestation not found
+        AttestationNotFound,
+        /// Caller is not a controller of the DID
+        NotController,
+        /// Controllers must be registered identities, without duplicates
+        InvalidControllers,
+        /// Malformed verification method
+        InvalidVerificationMethod,
+        /// Malformed service endpoint
+        InvalidService,
+        /// A verification method or service with this ID already exists
+        DuplicateDidEntry,
+        /// Too many verification methods
+        TooManyVerificationMethods,
+        /// Too many service endpoints
+        TooManyServices,
+        /// Verification method not found
+        VerificationMethodNotFound,
+        /// Service endpoint not found
+        ServiceNotFound,
+        /// Identity already holds this role
+        RoleAlreadyHeld,
+        /// A request for this role is already pending
+        RoleRequestPending,
+        /// Identity does not hold this role
+        RoleNotHeld,
+        /// Guardians must be other accounts, without duplicates, and the threshold at most
+        /// their number
+        InvalidRecoveryConfig,
+        /// Identity has no recovery guardians
+        RecoveryNotConfigured,
+        /// Caller is not a guardian of the identity
+        NotGuardian,
+        /// A recovery of the identity is already in progress
+        RecoveryInProgress,
+        /// No recovery of the identity is in progress
+        NoActiveRecovery,
+        /// Guardian already approved the recovery
+        AlreadyApproved,
+        /// Recovery lacks approvals or its delay has not passed
+        RecoveryNotReady,
+        /// New account must differ from the recovered one and have no identity
+        InvalidRecoveryAccount,
+        /// Ward and guardian must be distinct active identities, with evidence and a future
+        /// handover date
+        InvalidGuardianship,
+        /// Guardianship not found
+        GuardianshipNotFound,
+        /// Ward or guardian takes part in too many guardianships
+        TooManyGuardianships,
     }
 
     #[pallet::call]
@@ -166,11 +633,11 @@ pub mod pallet {
         /// Parameters:
         /// - `origin`: The account registering the identity
         /// - `did`: Decentralized identifier (unique)
-        /// - `role`: User role in the ecosystem
+        /// - `role`: Initial user role in the ecosystem, requested until verified if privileged
         /// - `name`: Display name
         /// - `email_hash`: Hashed email for privacy
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::register_identity())]
         pub fn register_identity(
             origin: OriginFor<T>,
             did: BoundedVec<u8, ConstU32<100>>,
@@ -186,17 +653,25 @@ pub mod pallet {
             // Ensure DID is unique
             ensure!(!DidToAccount::<T>::contains_key(&did), Error::<T>::DIDAlreadyExists);
 
-            // Validate DID format (basic check)
-            ensure!(did.len() >= 10, Error::<T>::InvalidDID);
+            // Validate DID format
+            ensure!(did::parse_did(&did).is_some(), Error::<T>::InvalidDID);
 
             // Validate name
             ensure!(!name.is_empty(), Error::<T>::InvalidName);
 
             let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
 
+            // Privileged roles are requested, as with `request_role`, until verified
+            let roles = if role.is_privileged() {
+                RoleRequests::<T>::insert(&who, &role, now);
+                BoundedVec::new()
+            } else {
+                BoundedVec::truncate_from(sp_std::vec![role.clone()])
+            };
+
             let identity = Identity {
                 did: did.clone(),
-                role: role.clone(),
+                roles,
                 name,
                 email_hash,
                 verification_status: VerificationStatus::Unverified,
@@ -209,19 +684,23 @@ pub mod pallet {
             // Store identity
             Identities::<T>::insert(&who, identity);
             DidToAccount::<T>::insert(&did, &who);
+            DidDocuments::<T>::insert(&who, Self::new_did_document(&who, now));
 
             Self::deposit_event(Event::IdentityRegistered {
-                account: who,
+                account: who.clone(),
                 did,
-                role,
+                role: role.clone(),
             });
+            if role.is_privileged() {
+                Self::deposit_event(Event::RoleRequested { account: who, role });
+            }
 
             Ok(())
         }
 
         /// Update identity information
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_identity())]
         pub fn update_identity(
             origin: OriginFor<T>,
             name: Option<BoundedVec<u8, ConstU32<64>>>,
@@ -253,7 +732,7 @@ pub mod pallet {
 
         /// Request identity verification
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::request_verification())]
         pub fn request_verification(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -279,43 +758,78 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Verify an identity (restricted to Auditor role or sudo)
+        /// Attest an identity (restricted to accredited verifiers)
+        ///
+        /// Replaces the verifier's previous attestation of `target`. The identity becomes
+        /// `Verified` once enough verifiers have attested it for its role.
+        ///
+        /// Parameters:
+        /// - `origin`: Accredited verifier
+        /// - `target`: Account to attest
+        /// - `kind`: What was checked
+        /// - `evidence_hash`: Hash of the evidence kept off-chain
+        /// - `expires_at`: Attestation expiry timestamp (`None` for no expiry)
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::verify_identity())]
         pub fn verify_identity(
             origin: OriginFor<T>,
             target: T::AccountId,
+            kind: AttestationKind,
+            evidence_hash: H256,
+            expires_at: Option<u64>,
         ) -> DispatchResult {
             let verifier = ensure_signed(origin)?;
+            ensure!(Self::is_verifier(&verifier), Error::<T>::NotAuthorized);
+            // A verifier cannot attest its own identity
+            ensure!(verifier != target, Error::<T>::NotAuthorized);
 
-            // Check verifier is authorized (Auditor role)
-            let verifier_identity = Identities::<T>::get(&verifier).ok_or(Error::<T>::NotAuthorized)?;
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
             ensure!(
-                matches!(verifier_identity.role, UserRole::Auditor),
-                Error::<T>::NotAuthorized
+                expires_at.map_or(true, |expires_at| expires_at > now),
+                Error::<T>::InvalidExpiry
             );
 
-            Identities::<T>::try_mutate(&target, |maybe_identity| -> DispatchResult {
-                let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
-                identity.verification_status = VerificationStatus::Verified;
-                identity.updated_at = T::TimeProvider::now().try_into().ok().unwrap_or(0);
-                Ok(())
-            })?;
+            let identity = Identities::<T>::get(&target).ok_or(Error::<T>::IdentityNotFound)?;
 
-            // Remove from verification queue
-            VerificationQueue::<T>::remove(&target);
+            Attestations::<T>::insert(
+                &target,
+                &verifier,
+                Attestation { kind: kind.clone(), evidence_hash, attested_at: now, expires_at },
+            );
 
-            Self::deposit_event(Event::IdentityVerified {
-                account: target,
-                verifier,
+            Self::deposit_event(Event::IdentityAttested {
+                account: target.clone(),
+                verifier: verifier.clone(),
+                kind,
             });
 
+            let threshold = Self::threshold(&identity);
+            let verified = matches!(identity.verification_status, VerificationStatus::Verified);
+            if !verified && Self::valid_attestations(&target, now) >= threshold {
+                Identities::<T>::mutate(&target, |maybe_identity| {
+                    if let Some(identity) = maybe_identity {
+                        identity.verification_status = VerificationStatus::Verified;
+                        identity.updated_at = now;
+                    }
+                });
+
+                // Remove from verification queue
+                VerificationQueue::<T>::remove(&target);
+
+                Self::deposit_event(Event::IdentityVerified {
+                    account: target.clone(),
+                    verifier,
+                });
+            }
+
+            Self::grant_verified_roles(&target, now);
+
             Ok(())
         }
 
         /// Reject identity verification
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::reject_verification())]
         pub fn reject_verification(
             origin: OriginFor<T>,
             target: T::AccountId,
@@ -323,12 +837,8 @@ pub mod pallet {
         ) -> DispatchResult {
             let verifier = ensure_signed(origin)?;
 
-            // Check verifier is authorized
-            let verifier_identity = Identities::<T>::get(&verifier).ok_or(Error::<T>::NotAuthorized)?;
-            ensure!(
-                matches!(verifier_identity.role, UserRole::Auditor),
-                Error::<T>::NotAuthorized
-            );
+            // Check verifier is accredited
+            ensure!(Self::is_verifier(&verifier), Error::<T>::NotAuthorized);
 
             Identities::<T>::try_mutate(&target, |maybe_identity| -> DispatchResult {
                 let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
@@ -349,21 +859,625 @@ pub mod pallet {
 
         /// Deactivate identity
         #[pallet::call_index(5)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::deactivate_identity())]
         pub fn deactivate_identity(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            Identities::<T>::try_mutate(&who, |maybe_identity| -> DispatchResult {
+            Self::do_deactivate(&who)
+        }
+
+        /// Accredit a verifier
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::add_verifier())]
+        pub fn add_verifier(origin: OriginFor<T>, verifier: T::AccountId) -> DispatchResult {
+            T::VerifierOrigin::ensure_origin(origin)?;
+
+            Verifiers::<T>::try_mutate(|verifiers| -> DispatchResult {
+                ensure!(!verifiers.contains(&verifier), Error::<T>::AlreadyVerifier);
+                verifiers.try_push(verifier.clone()).map_err(|_| Error::<T>::TooManyVerifiers)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerifierAdded { verifier });
+
+            Ok(())
+        }
+
+        /// Withdraw a verifier's accreditation
+        ///
+        /// Attestations it made no longer count towards verification.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::remove_verifier())]
+        pub fn remove_verifier(origin: OriginFor<T>, verifier: T::AccountId) -> DispatchResult {
+            T::VerifierOrigin::ensure_origin(origin)?;
+
+            Verifiers::<T>::try_mutate(|verifiers| -> DispatchResult {
+                let index = verifiers
+                    .iter()
+                    .position(|v| *v == verifier)
+                    .ok_or(Error::<T>::NotVerifier)?;
+                verifiers.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerifierRemoved { verifier });
+
+            Ok(())
+        }
+
+        /// Set the number of verifier attestations required for a role
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::set_verification_threshold())]
+        pub fn set_verification_threshold(
+            origin: OriginFor<T>,
+            role: UserRole,
+            threshold: u32,
+        ) -> DispatchResult {
+            T::VerifierOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                threshold >= 1 && threshold <= T::MaxVerifiers::get(),
+                Error::<T>::InvalidThreshold
+            );
+
+            VerificationThresholds::<T>::insert(&role, threshold);
+
+            Self::deposit_event(Event::VerificationThresholdSet { role, threshold });
+
+            Ok(())
+        }
+
+        /// Revoke the caller's attestation of an identity
+        ///
+        /// The identity loses its verification if it no longer meets the threshold.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::revoke_attestation())]
+        pub fn revoke_attestation(origin: OriginFor<T>, target: T::AccountId) -> DispatchResult {
+            let verifier = ensure_signed(origin)?;
+
+            ensure!(
+                Attestations::<T>::contains_key(&target, &verifier),
+                Error::<T>::AttestationNotFound
+            );
+            Attestations::<T>::remove(&target, &verifier);
+
+            Self::deposit_event(Event::AttestationRevoked {
+                account: target.clone(),
+                verifier,
+            });
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            if let Some(identity) = Identities::<T>::get(&target) {
+                let threshold = Self::threshold(&identity);
+                let verified = matches!(identity.verification_status, VerificationStatus::Verified);
+                if verified && Self::valid_attestations(&target, now) < threshold {
+                    Self::set_status(&target, VerificationStatus::Unverified, now);
+                    Self::deposit_event(Event::VerificationRevoked { account: target });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Revoke an identity's verification and all its attestations
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::revoke_verification())]
+        pub fn revoke_verification(origin: OriginFor<T>, target: T::AccountId) -> DispatchResult {
+            T::VerifierOrigin::ensure_origin(origin)?;
+
+            ensure!(Identities::<T>::contains_key(&target), Error::<T>::IdentityNotFound);
+
+            let _ = Attestations::<T>::clear_prefix(&target, T::MaxVerifiers::get(), None);
+            VerificationQueue::<T>::remove(&target);
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            Self::set_status(&target, VerificationStatus::Revoked, now);
+
+            Self::deposit_event(Event::VerificationRevoked { account: target });
+
+            Ok(())
+        }
+
+        /// Add a verification method to a DID document (controllers only)
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::add_verification_method())]
+        pub fn add_verification_method(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            method: VerificationMethod,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(method.is_valid(), Error::<T>::InvalidVerificationMethod);
+
+            let method_id = method.id.clone();
+            Self::mutate_did_document(&who, &subject, |document| {
+                ensure!(
+                    !document.verification_methods.iter().any(|m| m.id == method.id),
+                    Error::<T>::DuplicateDidEntry
+                );
+                document
+                    .verification_methods
+                    .try_push(method)
+                    .map_err(|_| Error::<T>::TooManyVerificationMethods)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerificationMethodAdded { account: subject, method_id });
+
+            Ok(())
+        }
+
+        /// Remove a verification method from a DID document (controllers only)
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::remove_verification_method())]
+        pub fn remove_verification_method(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            method_id: did::Fragment,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::mutate_did_document(&who, &subject, |document| {
+                let index = document
+                    .verification_methods
+                    .iter()
+                    .position(|m| m.id == method_id)
+                    .ok_or(Error::<T>::VerificationMethodNotFound)?;
+                document.verification_methods.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerificationMethodRemoved { account: subject, method_id });
+
+            Ok(())
+        }
+
+        /// Replace the public key of a verification method (controllers only)
+        ///
+        /// The method keeps its ID, key type and relationships.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::rotate_key())]
+        pub fn rotate_key(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            method_id: did::Fragment,
+            public_key: BoundedVec<u8, ConstU32<33>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::mutate_did_document(&who, &subject, |document| {
+                let method = document
+                    .verification_methods
+                    .iter_mut()
+                    .find(|m| m.id == method_id)
+                    .ok_or(Error::<T>::VerificationMethodNotFound)?;
+                ensure!(
+                    public_key.len() == method.key_type.public_key_len()
+                        && public_key != method.public_key,
+                    Error::<T>::InvalidVerificationMethod
+                );
+                method.public_key = public_key;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::KeyRotated { account: subject, method_id });
+
+            Ok(())
+        }
+
+        /// Add a service endpoint to a DID document (controllers only)
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::add_service())]
+        pub fn add_service(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            service: Service,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(service.is_valid(), Error::<T>::InvalidService);
+
+            let service_id = service.id.clone();
+            Self::mutate_did_document(&who, &subject, |document| {
+                ensure!(
+                    !document.services.iter().any(|s| s.id == service.id),
+                    Error::<T>::DuplicateDidEntry
+                );
+                document.services.try_push(service).map_err(|_| Error::<T>::TooManyServices)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ServiceAdded { account: subject, service_id });
+
+            Ok(())
+        }
+
+        /// Remove a service endpoint from a DID document (controllers only)
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::remove_service())]
+        pub fn remove_service(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            service_id: did::Fragment,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::mutate_did_document(&who, &subject, |document| {
+                let index = document
+                    .services
+                    .iter()
+                    .position(|s| s.id == service_id)
+                    .ok_or(Error::<T>::ServiceNotFound)?;
+                document.services.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::ServiceRemoved { account: subject, service_id });
+
+            Ok(())
+        }
+
+        /// Replace the controllers of a DID (controllers only)
+        ///
+        /// Controllers must be active identities so they can be referenced by their DIDs.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::set_did_controllers())]
+        pub fn set_did_controllers(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            controllers: BoundedVec<T::AccountId, T::MaxDidControllers>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                !controllers.is_empty()
+                    && controllers.iter().enumerate().all(|(i, controller)| {
+                        !controllers[..i].contains(controller)
+                            && Self::is_active_identity(controller)
+                    }),
+                Error::<T>::InvalidControllers
+            );
+
+            Self::mutate_did_document(&who, &subject, |document| {
+                document.controllers = controllers;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::DidControllersSet { account: subject });
+
+            Ok(())
+        }
+
+        /// Deactivate a DID and its identity (controllers only)
+        ///
+        /// Removes all verification methods and services. Deactivation is permanent.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::deactivate_did())]
+        pub fn deactivate_did(origin: OriginFor<T>, subject: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let identity = Identities::<T>::get(&subject).ok_or(Error::<T>::IdentityNotFound)?;
+            ensure!(identity.active, Error::<T>::IdentityNotActive);
+            ensure!(
+                Self::did_document(&subject, identity.registered_at).controllers.contains(&who),
+                Error::<T>::NotController
+            );
+
+            Self::do_deactivate(&subject)
+        }
+
+        /// Request an additional role
+        ///
+        /// `Patient` is granted immediately. Privileged roles are granted once attestations made
+        /// after the request meet the role's verification threshold.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::request_role())]
+        pub fn request_role(origin: OriginFor<T>, role: UserRole) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let identity = Identities::<T>::get(&who).ok_or(Error::<T>::IdentityNotFound)?;
+            ensure!(identity.active, Error::<T>::IdentityNotActive);
+            ensure!(!identity.roles.contains(&role), Error::<T>::RoleAlreadyHeld);
+            ensure!(!RoleRequests::<T>::contains_key(&who, &role), Error::<T>::RoleRequestPending);
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            if role.is_privileged() {
+                RoleRequests::<T>::insert(&who, &role, now);
+                Self::deposit_event(Event::RoleRequested { account: who, role });
+            } else {
+                Self::add_role(&who, role, now);
+            }
+
+            Ok(())
+        }
+
+        /// Revoke a role from an identity, or cancel a pending request for it
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::revoke_role())]
+        pub fn revoke_role(
+            origin: OriginFor<T>,
+            target: T::AccountId,
+            role: UserRole,
+        ) -> DispatchResult {
+            T::VerifierOrigin::ensure_origin(origin)?;
+
+            let requested = RoleRequests::<T>::take(&target, &role).is_some();
+            Identities::<T>::try_mutate(&target, |maybe_identity| -> DispatchResult {
                 let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
-                identity.active = false;
-                identity.updated_at = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                let held = identity.roles.iter().position(|r| *r == role);
+                ensure!(held.is_some() || requested, Error::<T>::RoleNotHeld);
+                if let Some(index) = held {
+                    identity.roles.remove(index);
+                    identity.updated_at = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                }
                 Ok(())
             })?;
 
-            Self::deposit_event(Event::IdentityDeactivated { account: who });
+            Self::deposit_event(Event::RoleRevoked { account: target, role });
+
+            Ok(())
+        }
+
+        /// Set the guardians able to recover the caller's identity
+        ///
+        /// Parameters:
+        /// - `origin`: Identity owner
+        /// - `guardians`: Accounts that may initiate and approve a recovery
+        /// - `threshold`: Number of guardian approvals required
+        /// - `delay`: Minimum time between initiation and completion (milliseconds)
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::set_recovery_config())]
+        pub fn set_recovery_config(
+            origin: OriginFor<T>,
+            guardians: BoundedVec<T::AccountId, T::MaxGuardians>,
+            threshold: u32,
+            delay: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Self::is_active_identity(&who), Error::<T>::IdentityNotActive);
+            ensure!(
+                !ActiveRecoveries::<T>::contains_key(&who),
+                Error::<T>::RecoveryInProgress
+            );
+            ensure!(
+                threshold >= 1
+                    && threshold as usize <= guardians.len()
+                    && guardians.iter().enumerate().all(|(i, guardian)| {
+                        *guardian != who && !guardians[..i].contains(guardian)
+                    }),
+                Error::<T>::InvalidRecoveryConfig
+            );
+
+            RecoveryConfigs::<T>::insert(&who, RecoveryConfig { guardians, threshold, delay });
+
+            Self::deposit_event(Event::RecoveryConfigured { account: who });
+
+            Ok(())
+        }
+
+        /// Remove the caller's recovery guardians, cancelling any recovery in progress
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::remove_recovery_config())]
+        pub fn remove_recovery_config(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                RecoveryConfigs::<T>::take(&who).is_some(),
+                Error::<T>::RecoveryNotConfigured
+            );
+
+            if ActiveRecoveries::<T>::take(&who).is_some() {
+                Self::deposit_event(Event::RecoveryCancelled { account: who.clone() });
+            }
+            Self::deposit_event(Event::RecoveryConfigRemoved { account: who });
+
+            Ok(())
+        }
+
+        /// Start recovering an identity to a new account (guardians only)
+        ///
+        /// The initiating guardian's approval is counted.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::initiate_recovery())]
+        pub fn initiate_recovery(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            new_account: T::AccountId,
+        ) -> DispatchResult {
+            let guardian = ensure_signed(origin)?;
+
+            let config = RecoveryConfigs::<T>::get(&account)
+                .ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(config.guardians.contains(&guardian), Error::<T>::NotGuardian);
+            ensure!(Self::is_active_identity(&account), Error::<T>::IdentityNotActive);
+            ensure!(
+                !ActiveRecoveries::<T>::contains_key(&account),
+                Error::<T>::RecoveryInProgress
+            );
+            ensure!(
+                new_account != account && !Identities::<T>::contains_key(&new_account),
+                Error::<T>::InvalidRecoveryAccount
+            );
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ActiveRecoveries::<T>::insert(
+                &account,
+                ActiveRecovery {
+                    new_account: new_account.clone(),
+                    initiated_at: now,
+                    approvals: BoundedVec::truncate_from(sp_std::vec![guardian.clone()]),
+                },
+            );
+
+            Self::deposit_event(Event::RecoveryInitiated { account, new_account, guardian });
+
+            Ok(())
+        }
+
+        /// Approve a recovery in progress (guardians only)
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::approve_recovery())]
+        pub fn approve_recovery(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            let guardian = ensure_signed(origin)?;
+
+            let config = RecoveryConfigs::<T>::get(&account)
+                .ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(config.guardians.contains(&guardian), Error::<T>::NotGuardian);
+
+            ActiveRecoveries::<T>::try_mutate(&account, |maybe_recovery| -> DispatchResult {
+                let recovery = maybe_recovery.as_mut().ok_or(Error::<T>::NoActiveRecovery)?;
+                ensure!(!recovery.approvals.contains(&guardian), Error::<T>::AlreadyApproved);
+                // At most one approval per guardian
+                recovery
+                    .approvals
+                    .try_push(guardian.clone())
+                    .map_err(|_| Error::<T>::AlreadyApproved)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::RecoveryApproved { account, guardian });
+
+            Ok(())
+        }
+
+        /// Cancel a recovery of the caller's identity
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::cancel_recovery())]
+        pub fn cancel_recovery(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(ActiveRecoveries::<T>::take(&who).is_some(), Error::<T>::NoActiveRecovery);
+
+            Self::deposit_event(Event::RecoveryCancelled { account: who });
+
+            Ok(())
+        }
+
+        /// Complete a recovery approved by enough guardians once its delay has passed
+        ///
+        /// Moves the identity to the new account and notifies `RecoveryHandler`.
+        #[pallet::call_index(25)]
+        #[pallet::weight(
+            T::WeightInfo::complete_recovery().saturating_add(T::RecoveryHandler::weight())
+        )]
+        pub fn complete_recovery(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let recovery =
+                ActiveRecoveries::<T>::get(&account).ok_or(Error::<T>::NoActiveRecovery)?;
+            let config = RecoveryConfigs::<T>::get(&account)
+                .ok_or(Error::<T>::RecoveryNotConfigured)?;
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let approvals = recovery
+                .approvals
+                .iter()
+                .filter(|guardian| config.guardians.contains(guardian))
+                .count() as u32;
+            ensure!(
+                approvals >= config.threshold
+                    && now >= recovery.initiated_at.saturating_add(config.delay),
+                Error::<T>::RecoveryNotReady
+            );
+            ensure!(
+                !Identities::<T>::contains_key(&recovery.new_account),
+                Error::<T>::InvalidRecoveryAccount
+            );
+
+            ActiveRecoveries::<T>::remove(&account);
+            Self::move_identity(&account, &recovery.new_account, now)?;
+            T::RecoveryHandler::on_identity_recovered(&account, &recovery.new_account)?;
+
+            Self::deposit_event(Event::IdentityRecovered {
+                account,
+                new_account: recovery.new_account,
+            });
+
+            Ok(())
+        }
+
+        /// Register a guardian acting for a minor or incapacitated patient
+        ///
+        /// Replaces any existing guardianship between the two accounts.
+        ///
+        /// Parameters:
+        /// - `ward`: Patient the guardian acts for
+        /// - `guardian`: Account acting for the ward
+        /// - `kind`: Legal basis of the guardianship
+        /// - `evidence_hash`: Hash of the evidence (e.g. court order) kept off-chain
+        /// - `handover_at`: Age-of-majority date at which the ward takes over
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::register_guardianship())]
+        pub fn register_guardianship(
+            origin: OriginFor<T>,
+            ward: T::AccountId,
+            guardian: T::AccountId,
+            kind: GuardianshipKind,
+            evidence_hash: H256,
+            handover_at: Option<u64>,
+        ) -> DispatchResult {
+            T::GuardianshipOrigin::ensure_origin(origin)?;
+
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(
+                ward != guardian
+                    && Self::is_active_identity(&ward)
+                    && Self::is_active_identity(&guardian)
+                    && evidence_hash != H256::zero()
+                    && handover_at.map_or(true, |handover_at| handover_at > now),
+                Error::<T>::InvalidGuardianship
+            );
+
+            if !Guardianships::<T>::contains_key(&ward, &guardian) {
+                let max = T::MaxGuardianships::get() as usize;
+                ensure!(
+                    Guardianships::<T>::iter_key_prefix(&ward).count() < max
+                        && Wards::<T>::iter_key_prefix(&guardian).count() < max,
+                    Error::<T>::TooManyGuardianships
+                );
+            }
+
+            let guardianship =
+                Guardianship { kind: kind.clone(), evidence_hash, handover_at, registered_at: now };
+            let valid_until = guardianship.valid_until();
+            Guardianships::<T>::insert(&ward, &guardian, guardianship);
+            Wards::<T>::insert(&guardian, &ward, ());
+            T::GuardianshipHandler::on_guardianship_changed(&ward, &guardian, Some(valid_until))?;
+
+            Self::deposit_event(Event::GuardianshipRegistered { ward, guardian, kind });
 
             Ok(())
         }
+
+        /// End a guardianship
+        ///
+        /// Callable by `GuardianshipOrigin`, by the guardian stepping down, or by the ward once
+        /// its handover date has passed.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::end_guardianship())]
+        pub fn end_guardianship(
+            origin: OriginFor<T>,
+            ward: T::AccountId,
+            guardian: T::AccountId,
+        ) -> DispatchResult {
+            let who = match T::GuardianshipOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let guardianship = Guardianships::<T>::get(&ward, &guardian)
+                .ok_or(Error::<T>::GuardianshipNotFound)?;
+
+            if let Some(who) = who {
+                let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(
+                    who == guardian || (who == ward && now >= guardianship.valid_until()),
+                    Error::<T>::NotAuthorized
+                );
+            }
+
+            Self::remove_guardianship(&ward, &guardian)
+        }
     }
 
     // Helper functions
@@ -377,6 +1491,13 @@ pub mod pallet {
             }
         }
 
+        /// Check if `guardian` may act for `ward` at `now`
+        pub fn is_legal_guardian(ward: &T::AccountId, guardian: &T::AccountId, now: u64) -> bool {
+            Guardianships::<T>::get(ward, guardian)
+                .map_or(false, |guardianship| now < guardianship.valid_until())
+                && Self::is_active_identity(guardian)
+        }
+
         /// Get identity by DID
         pub fn get_identity_by_did(did: &BoundedVec<u8, ConstU32<100>>) -> Option<Identity<T>> {
             if let Some(account) = DidToAccount::<T>::get(did) {
@@ -386,22 +1507,321 @@ pub mod pallet {
             }
         }
 
-        /// Check if account has specific role
+        /// Check if account has an active identity holding a specific role
         pub fn has_role(account: &T::AccountId, role: UserRole) -> bool {
+            Self::has_any_role(account, &[role])
+        }
+
+        /// Check if account has an active identity holding any of `roles`
+        pub fn has_any_role(account: &T::AccountId, roles: &[UserRole]) -> bool {
             if let Some(identity) = Identities::<T>::get(account) {
-                identity.role == role && identity.active
+                identity.active && identity.roles.iter().any(|role| roles.contains(role))
             } else {
                 false
             }
         }
 
-        /// Check if identity is verified
+        /// Check if identity is verified and still holds enough valid attestations
         pub fn is_verified(account: &T::AccountId) -> bool {
             if let Some(identity) = Identities::<T>::get(account) {
-                matches!(identity.verification_status, VerificationStatus::Verified) && identity.active
+                let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                matches!(identity.verification_status, VerificationStatus::Verified)
+                    && identity.active
+                    && Self::valid_attestations(account, now) >= Self::threshold(&identity)
             } else {
                 false
             }
         }
+
+        /// Check if account is an accredited verifier
+        pub fn is_verifier(account: &T::AccountId) -> bool {
+            Verifiers::<T>::get().contains(account)
+        }
+
+        /// Number of unexpired attestations of an account by accredited verifiers
+        pub fn valid_attestations(account: &T::AccountId, now: u64) -> u32 {
+            Self::attestations_since(account, 0, now)
+        }
+
+        /// Number of valid attestations of an account made at or after `since`
+        fn attestations_since(account: &T::AccountId, since: u64, now: u64) -> u32 {
+            let verifiers = Verifiers::<T>::get();
+            Attestations::<T>::iter_prefix(account)
+                .filter(|(verifier, attestation)| {
+                    verifiers.contains(verifier)
+                        && attestation.attested_at >= since
+                        && attestation.expires_at.map_or(true, |expires_at| expires_at > now)
+                })
+                .count() as u32
+        }
+
+        /// Verification threshold of an identity, the highest among its roles, or among its
+        /// requested roles while it holds none
+        pub fn threshold(identity: &Identity<T>) -> u32 {
+            let threshold = if identity.roles.is_empty() {
+                RoleRequests::<T>::iter_key_prefix(&identity.owner)
+                    .map(|role| VerificationThresholds::<T>::get(&role))
+                    .max()
+            } else {
+                identity.roles.iter().map(VerificationThresholds::<T>::get).max()
+            };
+            threshold.unwrap_or_else(|| VerificationThresholds::<T>::get(UserRole::Patient))
+        }
+
+        /// Grant the requested roles whose threshold is met by attestations made since the request
+        fn grant_verified_roles(account: &T::AccountId, now: u64) {
+            let requests: Vec<(UserRole, u64)> = RoleRequests::<T>::iter_prefix(account).collect();
+            for (role, requested_at) in requests {
+                let threshold = VerificationThresholds::<T>::get(&role);
+                if Self::attestations_since(account, requested_at, now) >= threshold {
+                    RoleRequests::<T>::remove(account, &role);
+                    Self::add_role(account, role, now);
+                }
+            }
+        }
+
+        /// Add a role to an identity
+        fn add_role(account: &T::AccountId, role: UserRole, now: u64) {
+            let added = Identities::<T>::mutate(account, |maybe_identity| {
+                let Some(identity) = maybe_identity else { return false };
+                // At most one of each of the four roles
+                let added = !identity.roles.contains(&role)
+                    && identity.roles.try_push(role.clone()).is_ok();
+                identity.updated_at = now;
+                added
+            });
+
+            if added {
+                Self::deposit_event(Event::RoleGranted { account: account.clone(), role });
+            }
+        }
+
+        /// Resolve a `did:patientx:` DID to its document
+        pub fn resolve_did(did: &[u8]) -> Option<ResolvedDid> {
+            did::parse_did(did)?;
+            let bounded: BoundedVec<u8, ConstU32<100>> = did.to_vec().try_into().ok()?;
+            let account = DidToAccount::<T>::get(&bounded)?;
+            let identity = Identities::<T>::get(&account)?;
+            let document = Self::did_document(&account, identity.registered_at);
+
+            Some(ResolvedDid {
+                did: did.to_vec(),
+                controllers: document
+                    .controllers
+                    .iter()
+                    .filter_map(Identities::<T>::get)
+                    .map(|controller| controller.did.into_inner())
+                    .collect(),
+                verification_methods: document.verification_methods.into_inner(),
+                services: document.services.into_inner(),
+                created: identity.registered_at,
+                updated: document.updated_at,
+                deactivated: !identity.active,
+            })
+        }
+
+        /// DID document of an account, defaulting to one controlled by the account itself
+        ///
+        /// Identities registered before DID documents were introduced have none stored.
+        pub fn did_document(account: &T::AccountId, registered_at: u64) -> DidDocument<T> {
+            DidDocuments::<T>::get(account)
+                .unwrap_or_else(|| Self::new_did_document(account, registered_at))
+        }
+
+        /// Empty DID document controlled by `account`
+        fn new_did_document(account: &T::AccountId, now: u64) -> DidDocument<T> {
+            DidDocument {
+                controllers: BoundedVec::truncate_from(sp_std::vec![account.clone()]),
+                verification_methods: BoundedVec::new(),
+                services: BoundedVec::new(),
+                updated_at: now,
+            }
+        }
+
+        /// Update the DID document of an active identity on behalf of one of its controllers
+        fn mutate_did_document(
+            who: &T::AccountId,
+            subject: &T::AccountId,
+            f: impl FnOnce(&mut DidDocument<T>) -> DispatchResult,
+        ) -> DispatchResult {
+            let identity = Identities::<T>::get(subject).ok_or(Error::<T>::IdentityNotFound)?;
+            ensure!(identity.active, Error::<T>::IdentityNotActive);
+
+            let mut document = Self::did_document(subject, identity.registered_at);
+            ensure!(document.controllers.contains(who), Error::<T>::NotController);
+
+            f(&mut document)?;
+            document.updated_at = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            DidDocuments::<T>::insert(subject, document);
+
+            Ok(())
+        }
+
+        /// Deactivate an identity and clear the keys and services of its DID document
+        fn do_deactivate(account: &T::AccountId) -> DispatchResult {
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            let did = Identities::<T>::try_mutate(account, |maybe_identity| {
+                let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
+                identity.active = false;
+                identity.updated_at = now;
+                Ok::<_, DispatchError>(identity.did.clone())
+            })?;
+
+            DidDocuments::<T>::mutate(account, |maybe_document| {
+                if let Some(document) = maybe_document {
+                    document.verification_methods = BoundedVec::new();
+                    document.services = BoundedVec::new();
+                    document.updated_at = now;
+                }
+            });
+
+            Self::end_guardianships(account)?;
+
+            Self::deposit_event(Event::DidDeactivated { account: account.clone(), did });
+            Self::deposit_event(Event::IdentityDeactivated { account: account.clone() });
+
+            Ok(())
+        }
+
+        /// Tombstone an identity, wiping its personal data, and return the hash of the identity
+        /// as it was before erasure
+        ///
+        /// The identity is kept, deactivated, so its DID cannot be registered again.
+        pub fn erase_identity(account: &T::AccountId) -> Result<H256, DispatchError> {
+            let now: u64 = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            let (identity_hash, did) = Identities::<T>::try_mutate(account, |maybe_identity| {
+                let identity = maybe_identity.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
+                let identity_hash = H256(sp_io::hashing::blake2_256(&identity.encode()));
+
+                identity.name = BoundedVec::new();
+                identity.email_hash = H256::zero();
+                identity.verification_status = VerificationStatus::Unverified;
+                identity.active = false;
+                identity.updated_at = now;
+
+                Ok::<_, DispatchError>((identity_hash, identity.did.clone()))
+            })?;
+
+            DidDocuments::<T>::remove(account);
+            let _ = Attestations::<T>::clear_prefix(account, u32::MAX, None);
+            let _ = RoleRequests::<T>::clear_prefix(account, u32::MAX, None);
+            VerificationQueue::<T>::remove(account);
+            RecoveryConfigs::<T>::remove(account);
+            ActiveRecoveries::<T>::remove(account);
+            Self::end_guardianships(account)?;
+
+            Self::deposit_event(Event::DidDeactivated { account: account.clone(), did });
+            Self::deposit_event(Event::IdentityErased { account: account.clone() });
+
+            Ok(identity_hash)
+        }
+
+        /// Move an identity and the state keyed by its account to `new`
+        fn move_identity(lost: &T::AccountId, new: &T::AccountId, now: u64) -> DispatchResult {
+            let mut identity = Identities::<T>::take(lost).ok_or(Error::<T>::IdentityNotFound)?;
+            identity.owner = new.clone();
+            identity.updated_at = now;
+            DidToAccount::<T>::insert(&identity.did, new);
+            Identities::<T>::insert(new, identity);
+
+            if let Some(mut document) = DidDocuments::<T>::take(lost) {
+                for controller in document.controllers.iter_mut().filter(|c| *c == lost) {
+                    *controller = new.clone();
+                }
+                document.updated_at = now;
+                DidDocuments::<T>::insert(new, document);
+            }
+
+            let attestations: Vec<_> = Attestations::<T>::drain_prefix(lost).collect();
+            for (verifier, attestation) in attestations {
+                Attestations::<T>::insert(new, verifier, attestation);
+            }
+
+            let requests: Vec<_> = RoleRequests::<T>::drain_prefix(lost).collect();
+            for (role, requested_at) in requests {
+                RoleRequests::<T>::insert(new, role, requested_at);
+            }
+
+            if let Some(requested_at) = VerificationQueue::<T>::take(lost) {
+                VerificationQueue::<T>::insert(new, requested_at);
+            }
+
+            if let Some(config) = RecoveryConfigs::<T>::take(lost) {
+                RecoveryConfigs::<T>::insert(new, config);
+            }
+
+            // Guardianships follow the identity on both sides
+            let guardians: Vec<_> = Guardianships::<T>::iter_key_prefix(lost).collect();
+            for guardian in guardians {
+                Self::move_guardianship((lost, &guardian), (new, &guardian))?;
+            }
+            let wards: Vec<_> = Wards::<T>::iter_key_prefix(lost).collect();
+            for ward in wards {
+                Self::move_guardianship((&ward, lost), (&ward, new))?;
+            }
+
+            Ok(())
+        }
+
+        /// Remove the guardianship between `ward` and `guardian`
+        fn remove_guardianship(ward: &T::AccountId, guardian: &T::AccountId) -> DispatchResult {
+            Guardianships::<T>::remove(ward, guardian);
+            Wards::<T>::remove(guardian, ward);
+            T::GuardianshipHandler::on_guardianship_changed(ward, guardian, None)?;
+
+            Self::deposit_event(Event::GuardianshipEnded {
+                ward: ward.clone(),
+                guardian: guardian.clone(),
+            });
+
+            Ok(())
+        }
+
+        /// End the guardianships of a deactivated or erased identity, as ward and as guardian
+        fn end_guardianships(account: &T::AccountId) -> DispatchResult {
+            let guardians: Vec<_> = Guardianships::<T>::iter_key_prefix(account).collect();
+            for guardian in guardians {
+                Self::remove_guardianship(account, &guardian)?;
+            }
+            let wards: Vec<_> = Wards::<T>::iter_key_prefix(account).collect();
+            for ward in wards {
+                Self::remove_guardianship(&ward, account)?;
+            }
+
+            Ok(())
+        }
+
+        /// Re-key a guardianship from `(ward, guardian)` to `(new_ward, new_guardian)`
+        fn move_guardianship(
+            (ward, guardian): (&T::AccountId, &T::AccountId),
+            (new_ward, new_guardian): (&T::AccountId, &T::AccountId),
+        ) -> DispatchResult {
+            let Some(guardianship) = Guardianships::<T>::take(ward, guardian) else {
+                return Ok(());
+            };
+            let valid_until = guardianship.valid_until();
+            Wards::<T>::remove(guardian, ward);
+            Guardianships::<T>::insert(new_ward, new_guardian, guardianship);
+            Wards::<T>::insert(new_guardian, new_ward, ());
+
+            T::GuardianshipHandler::on_guardianship_changed(ward, guardian, None)?;
+            T::GuardianshipHandler::on_guardianship_changed(
+                new_ward,
+                new_guardian,
+                Some(valid_until),
+            )
+        }
+
+        /// Update the verification status of an identity
+        fn set_status(account: &T::AccountId, status: VerificationStatus, now: u64) {
+            Identities::<T>::mutate(account, |maybe_identity| {
+                if let Some(identity) = maybe_identity {
+                    identity.verification_status = status;
+                    identity.updated_at = now;
+                }
+            });
+        }
     }
 }
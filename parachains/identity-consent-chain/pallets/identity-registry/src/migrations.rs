@@ -0,0 +1,80 @@
+//! Storage migrations for the identity-registry pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Version 1 lets an identity hold several roles
+pub mod v1 {
+    use super::*;
+
+    /// Identity of version 0, holding a single role
+    #[derive(Encode, Decode)]
+    pub struct OldIdentity<T: Config> {
+        pub did: BoundedVec<u8, ConstU32<100>>,
+        pub role: UserRole,
+        pub name: BoundedVec<u8, ConstU32<64>>,
+        pub email_hash: H256,
+        pub verification_status: VerificationStatus,
+        pub registered_at: u64,
+        pub updated_at: u64,
+        pub owner: T::AccountId,
+        pub active: bool,
+    }
+
+    /// Give each identity its old role as a role set
+    ///
+    /// A privileged role of an identity that is not verified becomes a role request, as it
+    /// would be on registration today.
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut identities = 0u64;
+            let mut requests = Vec::new();
+
+            Identities::<T>::translate::<OldIdentity<T>, _>(|account, old| {
+                identities += 1;
+
+                let verified = matches!(old.verification_status, VerificationStatus::Verified);
+                let roles = if old.role.is_privileged() && !verified {
+                    requests.push((account, old.role, old.registered_at));
+                    BoundedVec::new()
+                } else {
+                    BoundedVec::truncate_from(vec![old.role])
+                };
+
+                Some(Identity {
+                    did: old.did,
+                    roles,
+                    name: old.name,
+                    email_hash: old.email_hash,
+                    verification_status: old.verification_status,
+                    registered_at: old.registered_at,
+                    updated_at: old.updated_at,
+                    owner: old.owner,
+                    active: old.active,
+                })
+            });
+
+            let writes = identities + requests.len() as u64;
+            for (account, role, requested_at) in requests {
+                RoleRequests::<T>::insert(account, role, requested_at);
+            }
+
+            T::DbWeight::get().reads_writes(identities, writes)
+        }
+    }
+
+    /// Migrate `Identities` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
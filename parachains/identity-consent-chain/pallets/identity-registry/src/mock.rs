@@ -64,6 +64,56 @@ impl pallet_identity_registry::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxIdentitiesPerAccount = ConstU32<1>;
+    type VerifierOrigin = frame_system::EnsureRoot<u64>;
+    type MaxVerifiers = ConstU32<4>;
+    type MaxDidControllers = ConstU32<2>;
+    type MaxVerificationMethods = ConstU32<2>;
+    type MaxServices = ConstU32<2>;
+    type MaxGuardians = ConstU32<3>;
+    type MaxGuardianships = ConstU32<2>;
+    type RecoveryHandler = TestRecoveryHandler;
+    type GuardianshipOrigin = frame_system::EnsureRoot<u64>;
+    type GuardianshipHandler = TestGuardianshipHandler;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    /// Recoveries notified to `RecoveryHandler` (lost account, new account)
+    pub static Recovered: Vec<(u64, u64)> = vec![];
+    /// Whether `RecoveryHandler` fails, e.g. when the XCM notification cannot be sent
+    pub static RecoveryHandlerFails: bool = false;
+}
+
+/// Records notified recoveries
+pub struct TestRecoveryHandler;
+
+impl pallet_identity_registry::OnIdentityRecovered<u64> for TestRecoveryHandler {
+    fn on_identity_recovered(lost: &u64, new: &u64) -> sp_runtime::DispatchResult {
+        if RecoveryHandlerFails::get() {
+            return Err(sp_runtime::DispatchError::Other("notification failed"));
+        }
+        Recovered::mutate(|recovered| recovered.push((*lost, *new)));
+        Ok(())
+    }
+}
+
+parameter_types! {
+    /// Guardianship changes notified to `GuardianshipHandler` (ward, guardian, valid until)
+    pub static GuardianshipChanges: Vec<(u64, u64, Option<u64>)> = vec![];
+}
+
+/// Records notified guardianship changes
+pub struct TestGuardianshipHandler;
+
+impl pallet_identity_registry::OnGuardianshipChanged<u64> for TestGuardianshipHandler {
+    fn on_guardianship_changed(
+        ward: &u64,
+        guardian: &u64,
+        valid_until: Option<u64>,
+    ) -> sp_runtime::DispatchResult {
+        GuardianshipChanges::mutate(|changes| changes.push((*ward, *guardian, valid_until)));
+        Ok(())
+    }
 }
 
 // Build genesis storage according to the mock runtime.
@@ -107,3 +157,50 @@ pub fn create_email_hash(email: &str) -> H256 {
 pub fn create_name(name: &str) -> sp_runtime::BoundedVec<u8, ConstU32<64>> {
     sp_runtime::BoundedVec::try_from(name.as_bytes().to_vec()).unwrap()
 }
+
+// Helper function to register an identity with the given role
+pub fn register(account: u64, role: pallet_identity_registry::UserRole) {
+    frame_support::assert_ok!(IdentityRegistry::register_identity(
+        RuntimeOrigin::signed(account),
+        create_did(&account.to_string()),
+        role,
+        create_name("User"),
+        create_email_hash("user@example.com"),
+    ));
+}
+
+// Helper function to register an identity and grant its privileged role through an attestation
+pub fn register_verified(account: u64, role: pallet_identity_registry::UserRole) {
+    let verifier = 100u64;
+    register(account, role);
+    if !IdentityRegistry::is_verifier(&verifier) {
+        accredit(verifier);
+    }
+    attest(verifier, account, None);
+}
+
+// Helper function to name recovery guardians
+pub fn set_guardians(account: u64, guardians: &[u64], threshold: u32, delay: u64) {
+    frame_support::assert_ok!(IdentityRegistry::set_recovery_config(
+        RuntimeOrigin::signed(account),
+        sp_runtime::BoundedVec::try_from(guardians.to_vec()).unwrap(),
+        threshold,
+        delay,
+    ));
+}
+
+// Helper function to accredit a verifier
+pub fn accredit(verifier: u64) {
+    frame_support::assert_ok!(IdentityRegistry::add_verifier(RuntimeOrigin::root(), verifier));
+}
+
+// Helper function to attest an identity's legal identity
+pub fn attest(verifier: u64, account: u64, expires_at: Option<u64>) {
+    frame_support::assert_ok!(IdentityRegistry::verify_identity(
+        RuntimeOrigin::signed(verifier),
+        account,
+        pallet_identity_registry::AttestationKind::LegalIdentity,
+        create_email_hash("passport"),
+        expires_at,
+    ));
+}
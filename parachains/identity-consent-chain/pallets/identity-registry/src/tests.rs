@@ -1,7 +1,19 @@
 //! Tests for identity-registry pallet
 
-use crate::{mock::*, Error, Event, UserRole, VerificationStatus};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations::v1::{MigrateToV1, OldIdentity},
+    mock::*,
+    AttestationKind, Error, Event, GuardianshipKind, Identities, KeyType, Service, UserRole,
+    VerificationMethod, VerificationRelationship, VerificationStatus, Wards,
+};
+use codec::Encode;
+use sp_core::H256;
+use sp_runtime::BoundedVec;
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::unhashed,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
 
 #[test]
 fn register_identity_works() {
@@ -23,7 +35,7 @@ fn register_identity_works() {
         // Check identity was stored
         let identity = IdentityRegistry::identities(account).unwrap();
         assert_eq!(identity.did, did);
-        assert_eq!(identity.role, UserRole::Patient);
+        assert_eq!(identity.roles.to_vec(), vec![UserRole::Patient]);
         assert_eq!(identity.name, name);
         assert_eq!(identity.email_hash, email_hash);
         assert_eq!(identity.verification_status, VerificationStatus::Unverified);
@@ -343,9 +355,13 @@ fn verify_identity_works() {
         )));
 
         // Verify identity
+        accredit(auditor);
         assert_ok!(IdentityRegistry::verify_identity(
             RuntimeOrigin::signed(auditor),
             patient,
+            AttestationKind::LegalIdentity,
+            create_email_hash("passport"),
+            None,
         ));
 
         // Check status changed
@@ -397,9 +413,15 @@ fn verify_identity_fails_when_not_auditor() {
             patient
         )));
 
-        // Try to verify as non-auditor
+        // Try to verify as a non-accredited account
         assert_noop!(
-            IdentityRegistry::verify_identity(RuntimeOrigin::signed(researcher), patient),
+            IdentityRegistry::verify_identity(
+                RuntimeOrigin::signed(researcher),
+                patient,
+                AttestationKind::LegalIdentity,
+                email_hash,
+                None,
+            ),
             Error::<Test>::NotAuthorized
         );
     });
@@ -437,6 +459,7 @@ fn reject_verification_works() {
         )));
 
         // Reject verification
+        accredit(auditor);
         let reason = sp_runtime::BoundedVec::try_from(b"Invalid documents".to_vec()).unwrap();
         assert_ok!(IdentityRegistry::reject_verification(
             RuntimeOrigin::signed(auditor),
@@ -586,9 +609,13 @@ fn helper_is_verified_works() {
             patient
         )));
 
+        accredit(auditor);
         assert_ok!(IdentityRegistry::verify_identity(
             RuntimeOrigin::signed(auditor),
             patient,
+            AttestationKind::LegalIdentity,
+            create_email_hash("passport"),
+            None,
         ));
 
         // Check verified
@@ -599,51 +626,1093 @@ fn helper_is_verified_works() {
 #[test]
 fn all_user_roles_work() {
     new_test_ext().execute_with(|| {
-        let email_hash = create_email_hash("test@example.com");
-        let name = create_name("Test User");
+        // Patient role
+        let patient = 1u64;
+        register(patient, UserRole::Patient);
+        assert!(IdentityRegistry::has_role(&patient, UserRole::Patient));
+
+        // Privileged roles are granted once verified
+        for (account, role) in
+            [(2u64, UserRole::Researcher), (3, UserRole::Institution), (4, UserRole::Auditor)]
+        {
+            register_verified(account, role.clone());
+            assert!(IdentityRegistry::has_role(&account, role));
+        }
+    });
+}
+
+#[test]
+fn registration_with_privileged_role_requires_verification() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = 1u64;
+        register(account, UserRole::Institution);
+
+        // The role is requested as with `request_role`
+        assert!(IdentityRegistry::identities(account).unwrap().roles.is_empty());
+        assert!(!IdentityRegistry::has_role(&account, UserRole::Institution));
+        assert!(IdentityRegistry::role_requests(account, UserRole::Institution).is_some());
+        System::assert_last_event(
+            Event::RoleRequested { account, role: UserRole::Institution }.into(),
+        );
+        assert_noop!(
+            IdentityRegistry::request_role(RuntimeOrigin::signed(account), UserRole::Institution),
+            Error::<Test>::RoleRequestPending
+        );
+
+        accredit(10);
+        attest(10, account, None);
+
+        assert!(IdentityRegistry::has_role(&account, UserRole::Institution));
+        assert!(IdentityRegistry::is_verified(&account));
+        assert_eq!(IdentityRegistry::role_requests(account, UserRole::Institution), None);
+    });
+}
+
+#[test]
+fn verifier_cannot_attest_itself() {
+    new_test_ext().execute_with(|| {
+        let verifier = 5u64;
+        register(verifier, UserRole::Researcher);
+        accredit(verifier);
 
-        // Test Patient role
+        assert_noop!(
+            IdentityRegistry::verify_identity(
+                RuntimeOrigin::signed(verifier),
+                verifier,
+                AttestationKind::LegalIdentity,
+                create_email_hash("passport"),
+                None,
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert!(IdentityRegistry::attestations(verifier, verifier).is_none());
+    });
+}
+
+#[test]
+fn add_and_remove_verifier_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let verifier = 5u64;
+
+        assert_noop!(
+            IdentityRegistry::add_verifier(RuntimeOrigin::signed(verifier), verifier),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(IdentityRegistry::add_verifier(RuntimeOrigin::root(), verifier));
+        assert!(IdentityRegistry::is_verifier(&verifier));
+        System::assert_last_event(Event::VerifierAdded { verifier }.into());
+
+        assert_noop!(
+            IdentityRegistry::add_verifier(RuntimeOrigin::root(), verifier),
+            Error::<Test>::AlreadyVerifier
+        );
+
+        // The mock allows four verifiers
+        for other in 6..9 {
+            accredit(other);
+        }
+        assert_noop!(
+            IdentityRegistry::add_verifier(RuntimeOrigin::root(), 9),
+            Error::<Test>::TooManyVerifiers
+        );
+
+        assert_ok!(IdentityRegistry::remove_verifier(RuntimeOrigin::root(), verifier));
+        assert!(!IdentityRegistry::is_verifier(&verifier));
+        assert_noop!(
+            IdentityRegistry::remove_verifier(RuntimeOrigin::root(), verifier),
+            Error::<Test>::NotVerifier
+        );
+    });
+}
+
+#[test]
+fn verification_threshold_is_per_role() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
         let patient = 1u64;
-        assert_ok!(IdentityRegistry::register_identity(
-            RuntimeOrigin::signed(patient),
-            create_did("patient1"),
-            UserRole::Patient,
-            name.clone(),
-            email_hash,
+        let institution = 2u64;
+        register(patient, UserRole::Patient);
+        register(institution, UserRole::Institution);
+        accredit(5);
+        accredit(6);
+
+        assert_noop!(
+            IdentityRegistry::set_verification_threshold(
+                RuntimeOrigin::root(),
+                UserRole::Institution,
+                5,
+            ),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_ok!(IdentityRegistry::set_verification_threshold(
+            RuntimeOrigin::root(),
+            UserRole::Institution,
+            2,
         ));
-        assert!(IdentityRegistry::has_role(&patient, UserRole::Patient));
+        System::assert_last_event(
+            Event::VerificationThresholdSet { role: UserRole::Institution, threshold: 2 }.into(),
+        );
 
-        // Test Researcher role
-        let researcher = 2u64;
-        assert_ok!(IdentityRegistry::register_identity(
-            RuntimeOrigin::signed(researcher),
-            create_did("researcher1"),
-            UserRole::Researcher,
-            name.clone(),
-            email_hash,
+        // One attestation is enough for a patient
+        attest(5, patient, None);
+        assert!(IdentityRegistry::is_verified(&patient));
+
+        // An institution needs two
+        attest(5, institution, None);
+        assert!(!IdentityRegistry::is_verified(&institution));
+        System::assert_last_event(
+            Event::IdentityAttested {
+                account: institution,
+                verifier: 5,
+                kind: AttestationKind::LegalIdentity,
+            }
+            .into(),
+        );
+
+        attest(6, institution, None);
+        assert!(IdentityRegistry::is_verified(&institution));
+        assert!(IdentityRegistry::has_role(&institution, UserRole::Institution));
+        System::assert_has_event(
+            Event::IdentityVerified { account: institution, verifier: 6 }.into(),
+        );
+    });
+}
+
+#[test]
+fn expired_or_unaccredited_attestations_do_not_count() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        register(patient, UserRole::Patient);
+        accredit(5);
+        Timestamp::set_timestamp(1_000);
+
+        assert_noop!(
+            IdentityRegistry::verify_identity(
+                RuntimeOrigin::signed(5),
+                patient,
+                AttestationKind::LegalIdentity,
+                create_email_hash("passport"),
+                Some(1_000),
+            ),
+            Error::<Test>::InvalidExpiry
+        );
+
+        attest(5, patient, Some(2_000));
+        assert!(IdentityRegistry::is_verified(&patient));
+
+        Timestamp::set_timestamp(2_000);
+        assert!(!IdentityRegistry::is_verified(&patient));
+
+        // A fresh attestation counts until its verifier loses accreditation
+        attest(5, patient, None);
+        assert!(IdentityRegistry::is_verified(&patient));
+        assert_ok!(IdentityRegistry::remove_verifier(RuntimeOrigin::root(), 5));
+        assert!(!IdentityRegistry::is_verified(&patient));
+    });
+}
+
+#[test]
+fn revoke_attestation_drops_verification_below_threshold() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let institution = 2u64;
+        register(institution, UserRole::Institution);
+        accredit(5);
+        accredit(6);
+        assert_ok!(IdentityRegistry::set_verification_threshold(
+            RuntimeOrigin::root(),
+            UserRole::Institution,
+            2,
         ));
-        assert!(IdentityRegistry::has_role(&researcher, UserRole::Researcher));
+        attest(5, institution, None);
+        attest(6, institution, None);
+
+        assert_noop!(
+            IdentityRegistry::revoke_attestation(RuntimeOrigin::signed(7), institution),
+            Error::<Test>::AttestationNotFound
+        );
+
+        assert_ok!(IdentityRegistry::revoke_attestation(RuntimeOrigin::signed(5), institution));
+
+        assert!(IdentityRegistry::attestations(institution, 5).is_none());
+        assert_eq!(
+            IdentityRegistry::identities(institution).unwrap().verification_status,
+            VerificationStatus::Unverified
+        );
+        System::assert_last_event(Event::VerificationRevoked { account: institution }.into());
+    });
+}
+
+#[test]
+fn revoke_verification_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        register(patient, UserRole::Patient);
+        accredit(5);
+        attest(5, patient, None);
+
+        assert_noop!(
+            IdentityRegistry::revoke_verification(RuntimeOrigin::signed(5), patient),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(IdentityRegistry::revoke_verification(RuntimeOrigin::root(), patient));
+
+        assert!(!IdentityRegistry::is_verified(&patient));
+        assert!(IdentityRegistry::attestations(patient, 5).is_none());
+        assert_eq!(
+            IdentityRegistry::identities(patient).unwrap().verification_status,
+            VerificationStatus::Revoked
+        );
+        System::assert_last_event(Event::VerificationRevoked { account: patient }.into());
+    });
+}
+
+#[test]
+fn register_identity_validates_did_syntax() {
+    new_test_ext().execute_with(|| {
+        for (account, did) in [
+            "did:other:patient1",
+            "did:patientx:",
+            "did:patientx:pat ient",
+            "did:patientx:clinic::1",
+            "did:patientx:clinic:",
+            "did:patientx:bad%2",
+        ]
+        .iter()
+        .enumerate()
+        {
+            assert_noop!(
+                IdentityRegistry::register_identity(
+                    RuntimeOrigin::signed(account as u64),
+                    BoundedVec::try_from(did.as_bytes().to_vec()).unwrap(),
+                    UserRole::Patient,
+                    create_name("User"),
+                    create_email_hash("user@example.com"),
+                ),
+                Error::<Test>::InvalidDID
+            );
+        }
 
-        // Test Institution role
-        let institution = 3u64;
         assert_ok!(IdentityRegistry::register_identity(
-            RuntimeOrigin::signed(institution),
-            create_did("institution1"),
+            RuntimeOrigin::signed(1),
+            create_did("org:clinic-1.%C3%A9"),
             UserRole::Institution,
-            name.clone(),
-            email_hash,
+            create_name("Clinic"),
+            create_email_hash("clinic@example.com"),
         ));
-        assert!(IdentityRegistry::has_role(&institution, UserRole::Institution));
+    });
+}
 
-        // Test Auditor role
-        let auditor = 4u64;
-        assert_ok!(IdentityRegistry::register_identity(
-            RuntimeOrigin::signed(auditor),
-            create_did("auditor1"),
-            UserRole::Auditor,
-            name,
-            email_hash,
+#[test]
+fn verification_methods_can_be_added_rotated_and_removed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = 1u64;
+        register(account, UserRole::Patient);
+        let auth = key("key-1", KeyType::Ed25519, &[VerificationRelationship::Authentication]);
+
+        assert_ok!(IdentityRegistry::add_verification_method(
+            RuntimeOrigin::signed(account),
+            account,
+            auth.clone(),
+        ));
+        System::assert_last_event(
+            Event::VerificationMethodAdded { account, method_id: auth.id.clone() }.into(),
+        );
+
+        assert_noop!(
+            IdentityRegistry::add_verification_method(
+                RuntimeOrigin::signed(account),
+                account,
+                auth.clone(),
+            ),
+            Error::<Test>::DuplicateDidEntry
+        );
+
+        // X25519 keys are only for key agreement
+        assert_noop!(
+            IdentityRegistry::add_verification_method(
+                RuntimeOrigin::signed(account),
+                account,
+                key("key-2", KeyType::X25519, &[VerificationRelationship::Authentication]),
+            ),
+            Error::<Test>::InvalidVerificationMethod
+        );
+
+        let rotated = BoundedVec::try_from(vec![9u8; 32]).unwrap();
+        assert_ok!(IdentityRegistry::rotate_key(
+            RuntimeOrigin::signed(account),
+            account,
+            auth.id.clone(),
+            rotated.clone(),
+        ));
+        System::assert_last_event(Event::KeyRotated { account, method_id: auth.id.clone() }.into());
+
+        let resolved = IdentityRegistry::resolve_did(&create_did("1")).unwrap();
+        assert_eq!(resolved.verification_methods.len(), 1);
+        assert_eq!(resolved.verification_methods[0].public_key, rotated);
+        assert_eq!(resolved.verification_methods[0].relationships, auth.relationships);
+
+        assert_noop!(
+            IdentityRegistry::rotate_key(
+                RuntimeOrigin::signed(account),
+                account,
+                auth.id.clone(),
+                BoundedVec::try_from(vec![9u8; 33]).unwrap(),
+            ),
+            Error::<Test>::InvalidVerificationMethod
+        );
+
+        assert_ok!(IdentityRegistry::remove_verification_method(
+            RuntimeOrigin::signed(account),
+            account,
+            auth.id.clone(),
+        ));
+        assert!(IdentityRegistry::resolve_did(&create_did("1"))
+            .unwrap()
+            .verification_methods
+            .is_empty());
+        assert_noop!(
+            IdentityRegistry::remove_verification_method(
+                RuntimeOrigin::signed(account),
+                account,
+                auth.id,
+            ),
+            Error::<Test>::VerificationMethodNotFound
+        );
+    });
+}
+
+#[test]
+fn did_controllers_manage_the_document() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        let controller = 2u64;
+        register(account, UserRole::Patient);
+        register(controller, UserRole::Patient);
+        let service = Service {
+            id: BoundedVec::try_from(b"records".to_vec()).unwrap(),
+            service_type: BoundedVec::try_from(b"LinkedDomains".to_vec()).unwrap(),
+            endpoint: BoundedVec::try_from(b"https://clinic.example".to_vec()).unwrap(),
+        };
+
+        // Only the owner controls the DID at first
+        assert_noop!(
+            IdentityRegistry::add_service(
+                RuntimeOrigin::signed(controller),
+                account,
+                service.clone(),
+            ),
+            Error::<Test>::NotController
+        );
+
+        // Controllers must be registered identities
+        assert_noop!(
+            IdentityRegistry::set_did_controllers(
+                RuntimeOrigin::signed(account),
+                account,
+                BoundedVec::try_from(vec![3]).unwrap(),
+            ),
+            Error::<Test>::InvalidControllers
+        );
+
+        assert_ok!(IdentityRegistry::set_did_controllers(
+            RuntimeOrigin::signed(account),
+            account,
+            BoundedVec::try_from(vec![controller]).unwrap(),
+        ));
+
+        assert_ok!(IdentityRegistry::add_service(
+            RuntimeOrigin::signed(controller),
+            account,
+            service.clone(),
+        ));
+        assert_noop!(
+            IdentityRegistry::remove_service(RuntimeOrigin::signed(account), account, service.id),
+            Error::<Test>::NotController
+        );
+
+        let resolved = IdentityRegistry::resolve_did(&create_did("1")).unwrap();
+        assert_eq!(resolved.controllers, vec![create_did("2").into_inner()]);
+        assert_eq!(resolved.services, vec![service]);
+    });
+}
+
+#[test]
+fn deactivate_did_clears_the_document() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = 1u64;
+        register(account, UserRole::Patient);
+        let auth = key("key-1", KeyType::Sr25519, &[VerificationRelationship::Authentication]);
+        assert_ok!(IdentityRegistry::add_verification_method(
+            RuntimeOrigin::signed(account),
+            account,
+            auth.clone(),
+        ));
+
+        assert_noop!(
+            IdentityRegistry::deactivate_did(RuntimeOrigin::signed(2), account),
+            Error::<Test>::NotController
+        );
+
+        assert_ok!(IdentityRegistry::deactivate_did(RuntimeOrigin::signed(account), account));
+
+        let resolved = IdentityRegistry::resolve_did(&create_did("1")).unwrap();
+        assert!(resolved.deactivated);
+        assert!(resolved.verification_methods.is_empty());
+        assert!(!IdentityRegistry::is_active_identity(&account));
+        System::assert_has_event(
+            Event::DidDeactivated { account, did: create_did("1") }.into(),
+        );
+
+        assert_noop!(
+            IdentityRegistry::add_verification_method(
+                RuntimeOrigin::signed(account),
+                account,
+                auth,
+            ),
+            Error::<Test>::IdentityNotActive
+        );
+    });
+}
+
+#[test]
+fn resolve_did_fails_for_unknown_or_malformed_did() {
+    new_test_ext().execute_with(|| {
+        register(1, UserRole::Patient);
+
+        assert!(IdentityRegistry::resolve_did(b"did:patientx:2").is_none());
+        assert!(IdentityRegistry::resolve_did(b"did:patientx:1#key-1").is_none());
+        assert!(IdentityRegistry::resolve_did(b"did:patientx:1").is_some());
+    });
+}
+
+#[test]
+fn request_patient_role_is_granted_immediately() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = 1u64;
+        register_verified(account, UserRole::Researcher);
+
+        assert_ok!(IdentityRegistry::request_role(
+            RuntimeOrigin::signed(account),
+            UserRole::Patient
+        ));
+
+        assert!(IdentityRegistry::has_role(&account, UserRole::Patient));
+        assert!(IdentityRegistry::has_role(&account, UserRole::Researcher));
+        System::assert_last_event(Event::RoleGranted { account, role: UserRole::Patient }.into());
+
+        assert_noop!(
+            IdentityRegistry::request_role(RuntimeOrigin::signed(account), UserRole::Researcher),
+            Error::<Test>::RoleAlreadyHeld
+        );
+    });
+}
+
+#[test]
+fn privileged_role_requires_fresh_verification() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = 1u64;
+        let verifier = 10u64;
+        accredit(verifier);
+        register(account, UserRole::Patient);
+
+        Timestamp::set_timestamp(1_000);
+        attest(verifier, account, None);
+        assert!(IdentityRegistry::is_verified(&account));
+
+        Timestamp::set_timestamp(2_000);
+        assert_ok!(IdentityRegistry::request_role(
+            RuntimeOrigin::signed(account),
+            UserRole::Researcher
+        ));
+        System::assert_last_event(
+            Event::RoleRequested { account, role: UserRole::Researcher }.into(),
+        );
+        assert_eq!(IdentityRegistry::role_requests(account, UserRole::Researcher), Some(2_000));
+        assert_noop!(
+            IdentityRegistry::request_role(RuntimeOrigin::signed(account), UserRole::Researcher),
+            Error::<Test>::RoleRequestPending
+        );
+
+        // The earlier attestation does not count
+        assert!(!IdentityRegistry::has_role(&account, UserRole::Researcher));
+
+        attest(verifier, account, None);
+
+        assert!(IdentityRegistry::has_role(&account, UserRole::Researcher));
+        assert!(IdentityRegistry::has_role(&account, UserRole::Patient));
+        assert_eq!(IdentityRegistry::role_requests(account, UserRole::Researcher), None);
+        System::assert_last_event(
+            Event::RoleGranted { account, role: UserRole::Researcher }.into(),
+        );
+    });
+}
+
+#[test]
+fn role_request_uses_role_threshold() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        accredit(10);
+        accredit(11);
+        register(account, UserRole::Patient);
+        assert_ok!(IdentityRegistry::set_verification_threshold(
+            RuntimeOrigin::root(),
+            UserRole::Institution,
+            2
+        ));
+
+        assert_ok!(IdentityRegistry::request_role(
+            RuntimeOrigin::signed(account),
+            UserRole::Institution
+        ));
+
+        attest(10, account, None);
+        assert!(IdentityRegistry::is_verified(&account));
+        assert!(!IdentityRegistry::has_role(&account, UserRole::Institution));
+
+        attest(11, account, None);
+        assert!(IdentityRegistry::has_role(&account, UserRole::Institution));
+
+        // The identity now needs both attestations to stay verified
+        assert_ok!(IdentityRegistry::revoke_attestation(RuntimeOrigin::signed(11), account));
+        assert!(!IdentityRegistry::is_verified(&account));
+    });
+}
+
+#[test]
+fn revoke_role_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = 1u64;
+        register_verified(account, UserRole::Researcher);
+        assert_ok!(IdentityRegistry::request_role(
+            RuntimeOrigin::signed(account),
+            UserRole::Patient
+        ));
+        assert_ok!(IdentityRegistry::request_role(
+            RuntimeOrigin::signed(account),
+            UserRole::Auditor
+        ));
+
+        // Governance only
+        assert_noop!(
+            IdentityRegistry::revoke_role(
+                RuntimeOrigin::signed(account),
+                account,
+                UserRole::Researcher
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(IdentityRegistry::revoke_role(
+            RuntimeOrigin::root(),
+            account,
+            UserRole::Researcher
+        ));
+        assert!(!IdentityRegistry::has_role(&account, UserRole::Researcher));
+        assert!(IdentityRegistry::has_role(&account, UserRole::Patient));
+        System::assert_last_event(
+            Event::RoleRevoked { account, role: UserRole::Researcher }.into(),
+        );
+
+        // Pending requests are cancelled
+        assert_ok!(IdentityRegistry::revoke_role(
+            RuntimeOrigin::root(),
+            account,
+            UserRole::Auditor
+        ));
+        assert_eq!(IdentityRegistry::role_requests(account, UserRole::Auditor), None);
+
+        assert_noop!(
+            IdentityRegistry::revoke_role(RuntimeOrigin::root(), account, UserRole::Researcher),
+            Error::<Test>::RoleNotHeld
+        );
+    });
+}
+
+#[test]
+fn recovery_config_is_validated() {
+    new_test_ext().execute_with(|| {
+        let account = 1u64;
+        let guardians = |g: Vec<u64>| BoundedVec::try_from(g).unwrap();
+
+        // Identity required
+        assert_noop!(
+            IdentityRegistry::set_recovery_config(
+                RuntimeOrigin::signed(account),
+                guardians(vec![2, 3]),
+                1,
+                0
+            ),
+            Error::<Test>::IdentityNotActive
+        );
+
+        register(account, UserRole::Patient);
+        // Threshold out of range, self as guardian, duplicate guardian
+        let invalid_configs = [(vec![2, 3], 0), (vec![2, 3], 3), (vec![1, 2], 1), (vec![2, 2], 1)];
+        for (invalid, threshold) in invalid_configs {
+            assert_noop!(
+                IdentityRegistry::set_recovery_config(
+                    RuntimeOrigin::signed(account),
+                    guardians(invalid),
+                    threshold,
+                    0
+                ),
+                Error::<Test>::InvalidRecoveryConfig
+            );
+        }
+
+        set_guardians(account, &[2, 3], 2, 1000);
+        let config = IdentityRegistry::recovery_configs(account).unwrap();
+        assert_eq!(config.guardians.to_vec(), vec![2, 3]);
+        assert_eq!((config.threshold, config.delay), (2, 1000));
+
+        assert_ok!(IdentityRegistry::remove_recovery_config(RuntimeOrigin::signed(account)));
+        assert!(IdentityRegistry::recovery_configs(account).is_none());
+        assert_noop!(
+            IdentityRegistry::remove_recovery_config(RuntimeOrigin::signed(account)),
+            Error::<Test>::RecoveryNotConfigured
+        );
+    });
+}
+
+#[test]
+fn guardians_recover_identity_to_new_account() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, new_account) = (1u64, 9u64);
+        register(patient, UserRole::Patient);
+        accredit(10);
+        attest(10, patient, None);
+        set_guardians(patient, &[2, 3, 4], 2, 20_000);
+
+        assert_ok!(IdentityRegistry::initiate_recovery(
+            RuntimeOrigin::signed(2),
+            patient,
+            new_account
+        ));
+        System::assert_last_event(
+            Event::RecoveryInitiated { account: patient, new_account, guardian: 2 }.into(),
+        );
+
+        // One approval out of two
+        assert_noop!(
+            IdentityRegistry::complete_recovery(RuntimeOrigin::signed(new_account), patient),
+            Error::<Test>::RecoveryNotReady
+        );
+
+        assert_ok!(IdentityRegistry::approve_recovery(RuntimeOrigin::signed(3), patient));
+        System::assert_last_event(Event::RecoveryApproved { account: patient, guardian: 3 }.into());
+
+        // Delay not passed
+        assert_noop!(
+            IdentityRegistry::complete_recovery(RuntimeOrigin::signed(new_account), patient),
+            Error::<Test>::RecoveryNotReady
+        );
+
+        run_to_block(3);
+        assert_ok!(IdentityRegistry::complete_recovery(
+            RuntimeOrigin::signed(new_account),
+            patient
+        ));
+        System::assert_last_event(
+            Event::IdentityRecovered { account: patient, new_account }.into(),
+        );
+
+        // Identity, DID and attestations moved
+        assert!(IdentityRegistry::identities(patient).is_none());
+        let identity = IdentityRegistry::identities(new_account).unwrap();
+        assert_eq!(identity.owner, new_account);
+        assert_eq!(IdentityRegistry::did_to_account(create_did("1")), Some(new_account));
+        assert!(IdentityRegistry::is_verified(&new_account));
+        assert_eq!(
+            IdentityRegistry::did_documents(new_account).unwrap().controllers.to_vec(),
+            vec![new_account]
+        );
+        assert!(IdentityRegistry::recovery_configs(new_account).is_some());
+        assert!(IdentityRegistry::active_recoveries(patient).is_none());
+
+        // Other chains are asked to re-key the patient's data
+        assert_eq!(Recovered::get(), vec![(patient, new_account)]);
+    });
+}
+
+#[test]
+fn recovery_can_be_cancelled_by_the_account() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        register(patient, UserRole::Patient);
+        register(8, UserRole::Patient);
+        set_guardians(patient, &[2, 3], 1, 0);
+
+        assert_noop!(
+            IdentityRegistry::initiate_recovery(RuntimeOrigin::signed(5), patient, 9),
+            Error::<Test>::NotGuardian
+        );
+        // The new account must be unused
+        assert_noop!(
+            IdentityRegistry::initiate_recovery(RuntimeOrigin::signed(2), patient, 8),
+            Error::<Test>::InvalidRecoveryAccount
+        );
+
+        assert_ok!(IdentityRegistry::initiate_recovery(RuntimeOrigin::signed(2), patient, 9));
+        assert_noop!(
+            IdentityRegistry::initiate_recovery(RuntimeOrigin::signed(3), patient, 7),
+            Error::<Test>::RecoveryInProgress
+        );
+        assert_noop!(
+            IdentityRegistry::approve_recovery(RuntimeOrigin::signed(2), patient),
+            Error::<Test>::AlreadyApproved
+        );
+
+        assert_ok!(IdentityRegistry::cancel_recovery(RuntimeOrigin::signed(patient)));
+        System::assert_last_event(Event::RecoveryCancelled { account: patient }.into());
+
+        assert_noop!(
+            IdentityRegistry::complete_recovery(RuntimeOrigin::signed(9), patient),
+            Error::<Test>::NoActiveRecovery
+        );
+        assert!(IdentityRegistry::is_active_identity(&patient));
+        assert!(Recovered::get().is_empty());
+    });
+}
+
+#[test]
+fn recovery_fails_when_handler_fails() {
+    new_test_ext().execute_with(|| {
+        let patient = 1u64;
+        register(patient, UserRole::Patient);
+        set_guardians(patient, &[2], 1, 0);
+        assert_ok!(IdentityRegistry::initiate_recovery(RuntimeOrigin::signed(2), patient, 9));
+
+        RecoveryHandlerFails::set(true);
+        assert_noop!(
+            IdentityRegistry::complete_recovery(RuntimeOrigin::signed(9), patient),
+            sp_runtime::DispatchError::Other("notification failed")
+        );
+        assert_eq!(IdentityRegistry::did_to_account(create_did("1")), Some(patient));
+    });
+}
+
+#[test]
+fn erase_identity_wipes_personal_data() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let patient = 1u64;
+        register(patient, UserRole::Patient);
+        accredit(10);
+        attest(10, patient, None);
+        set_guardians(patient, &[2], 1, 0);
+        let before = IdentityRegistry::identities(patient).unwrap();
+
+        let identity_hash = IdentityRegistry::erase_identity(&patient).unwrap();
+        assert_eq!(identity_hash, H256(sp_io::hashing::blake2_256(&before.encode())));
+        System::assert_last_event(Event::IdentityErased { account: patient }.into());
+
+        // Tombstone kept, personal fields wiped
+        let identity = IdentityRegistry::identities(patient).unwrap();
+        assert!(identity.name.is_empty());
+        assert_eq!(identity.email_hash, H256::zero());
+        assert!(!identity.active);
+        assert!(!IdentityRegistry::is_verified(&patient));
+        assert!(IdentityRegistry::did_documents(patient).is_none());
+        assert!(IdentityRegistry::attestations(patient, 10).is_none());
+        assert!(IdentityRegistry::recovery_configs(patient).is_none());
+
+        // The DID stays reserved
+        assert_eq!(IdentityRegistry::did_to_account(create_did("1")), Some(patient));
+        assert_noop!(IdentityRegistry::erase_identity(&2), Error::<Test>::IdentityNotFound);
+    });
+}
+
+#[test]
+fn register_guardianship_works_until_handover() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (ward, guardian) = (1u64, 2u64);
+        register(ward, UserRole::Patient);
+        register(guardian, UserRole::Patient);
+        let evidence = H256::repeat_byte(7);
+
+        assert_noop!(
+            IdentityRegistry::register_guardianship(
+                RuntimeOrigin::signed(guardian),
+                ward,
+                guardian,
+                GuardianshipKind::Parent,
+                evidence,
+                Some(1_000),
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // Evidence is required, and the guardian must have an identity
+        for (guardian, evidence) in [(guardian, H256::zero()), (3, evidence)] {
+            assert_noop!(
+                IdentityRegistry::register_guardianship(
+                    RuntimeOrigin::root(),
+                    ward,
+                    guardian,
+                    GuardianshipKind::Parent,
+                    evidence,
+                    Some(1_000),
+                ),
+                Error::<Test>::InvalidGuardianship
+            );
+        }
+
+        assert_ok!(IdentityRegistry::register_guardianship(
+            RuntimeOrigin::root(),
+            ward,
+            guardian,
+            GuardianshipKind::Parent,
+            evidence,
+            Some(1_000),
+        ));
+
+        let guardianship = IdentityRegistry::guardianships(ward, guardian).unwrap();
+        assert_eq!(guardianship.evidence_hash, evidence);
+        assert_eq!(GuardianshipChanges::get(), vec![(ward, guardian, Some(1_000))]);
+        System::assert_last_event(
+            Event::GuardianshipRegistered { ward, guardian, kind: GuardianshipKind::Parent }.into(),
+        );
+
+        // The ward takes over at the age of majority
+        assert!(IdentityRegistry::is_legal_guardian(&ward, &guardian, 999));
+        assert!(!IdentityRegistry::is_legal_guardian(&ward, &guardian, 1_000));
+        assert!(!IdentityRegistry::is_legal_guardian(&guardian, &ward, 0));
+    });
+}
+
+#[test]
+fn end_guardianship_by_guardian_or_ward_after_handover() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (ward, guardian) = (1u64, 2u64);
+        register(ward, UserRole::Patient);
+        register(guardian, UserRole::Patient);
+        let register_guardianship = || {
+            assert_ok!(IdentityRegistry::register_guardianship(
+                RuntimeOrigin::root(),
+                ward,
+                guardian,
+                GuardianshipKind::CourtAppointed,
+                H256::repeat_byte(7),
+                Some(1_000),
+            ));
+        };
+        register_guardianship();
+
+        // The ward cannot end the guardianship before the handover date
+        for who in [ward, 3] {
+            assert_noop!(
+                IdentityRegistry::end_guardianship(RuntimeOrigin::signed(who), ward, guardian),
+                Error::<Test>::NotAuthorized
+            );
+        }
+
+        // The guardian can step down
+        assert_ok!(IdentityRegistry::end_guardianship(
+            RuntimeOrigin::signed(guardian),
+            ward,
+            guardian,
         ));
-        assert!(IdentityRegistry::has_role(&auditor, UserRole::Auditor));
+        assert!(IdentityRegistry::guardianships(ward, guardian).is_none());
+        assert_eq!(GuardianshipChanges::get().last(), Some(&(ward, guardian, None)));
+
+        register_guardianship();
+        Timestamp::set_timestamp(1_000);
+        assert_ok!(IdentityRegistry::end_guardianship(RuntimeOrigin::signed(ward), ward, guardian));
+        System::assert_last_event(Event::GuardianshipEnded { ward, guardian }.into());
+
+        assert_noop!(
+            IdentityRegistry::end_guardianship(RuntimeOrigin::root(), ward, guardian),
+            Error::<Test>::GuardianshipNotFound
+        );
+    });
+}
+
+#[test]
+fn guardianships_are_bounded_on_both_sides() {
+    new_test_ext().execute_with(|| {
+        for account in 1..=5 {
+            register(account, UserRole::Patient);
+        }
+        let guardianship = |ward, guardian| {
+            IdentityRegistry::register_guardianship(
+                RuntimeOrigin::root(),
+                ward,
+                guardian,
+                GuardianshipKind::Parent,
+                H256::repeat_byte(7),
+                None,
+            )
+        };
+
+        // Two guardians per ward in the mock
+        assert_ok!(guardianship(1, 2));
+        assert_ok!(guardianship(1, 3));
+        assert_noop!(guardianship(1, 4), Error::<Test>::TooManyGuardianships);
+        // Replacing an existing guardianship is not limited
+        assert_ok!(guardianship(1, 2));
+
+        // And two wards per guardian
+        assert_ok!(guardianship(4, 2));
+        assert_noop!(guardianship(5, 2), Error::<Test>::TooManyGuardianships);
+    });
+}
+
+#[test]
+fn guardianships_follow_recovered_identity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (patient, new_account, guardian, ward) = (1u64, 9u64, 2u64, 3u64);
+        for account in [patient, guardian, ward, 4] {
+            register(account, UserRole::Patient);
+        }
+        // The patient is both a ward and a guardian
+        for (ward, guardian) in [(patient, guardian), (ward, patient)] {
+            assert_ok!(IdentityRegistry::register_guardianship(
+                RuntimeOrigin::root(),
+                ward,
+                guardian,
+                GuardianshipKind::CourtAppointed,
+                H256::repeat_byte(7),
+                Some(1_000),
+            ));
+        }
+        GuardianshipChanges::set(vec![]);
+
+        set_guardians(patient, &[4], 1, 0);
+        assert_ok!(IdentityRegistry::initiate_recovery(
+            RuntimeOrigin::signed(4),
+            patient,
+            new_account
+        ));
+        assert_ok!(IdentityRegistry::complete_recovery(RuntimeOrigin::signed(4), patient));
+
+        assert!(IdentityRegistry::guardianships(patient, guardian).is_none());
+        assert!(IdentityRegistry::guardianships(ward, patient).is_none());
+        assert!(IdentityRegistry::is_legal_guardian(&new_account, &guardian, 0));
+        assert!(IdentityRegistry::is_legal_guardian(&ward, &new_account, 0));
+        assert!(Wards::<Test>::contains_key(guardian, new_account));
+        assert!(!Wards::<Test>::contains_key(guardian, patient));
+        assert!(Wards::<Test>::contains_key(new_account, ward));
+        assert!(!Wards::<Test>::contains_key(patient, ward));
+        assert_eq!(
+            GuardianshipChanges::get(),
+            vec![
+                (patient, guardian, None),
+                (new_account, guardian, Some(1_000)),
+                (ward, patient, None),
+                (ward, new_account, Some(1_000)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn guardianships_end_with_deactivation_or_erasure() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for account in 1..=4 {
+            register(account, UserRole::Patient);
+        }
+        for (ward, guardian) in [(1, 2), (3, 1), (4, 2)] {
+            assert_ok!(IdentityRegistry::register_guardianship(
+                RuntimeOrigin::root(),
+                ward,
+                guardian,
+                GuardianshipKind::Parent,
+                H256::repeat_byte(7),
+                None,
+            ));
+        }
+        GuardianshipChanges::set(vec![]);
+
+        assert_ok!(IdentityRegistry::deactivate_identity(RuntimeOrigin::signed(1)));
+
+        assert!(IdentityRegistry::guardianships(1, 2).is_none());
+        assert!(IdentityRegistry::guardianships(3, 1).is_none());
+        assert!(!Wards::<Test>::contains_key(2, 1));
+        assert!(!Wards::<Test>::contains_key(1, 3));
+        assert_eq!(GuardianshipChanges::get(), vec![(1, 2, None), (3, 1, None)]);
+        System::assert_has_event(Event::GuardianshipEnded { ward: 3, guardian: 1 }.into());
+
+        // Other guardianships of the guardian are kept until it is erased
+        assert!(IdentityRegistry::is_legal_guardian(&4, &2, 0));
+        assert_ok!(IdentityRegistry::erase_identity(&2));
+        assert!(IdentityRegistry::guardianships(4, 2).is_none());
+        assert_eq!(Wards::<Test>::iter_key_prefix(2).count(), 0);
+        assert_eq!(GuardianshipChanges::get().last(), Some(&(4, 2, None)));
+    });
+}
+
+// Helper function to build a verification method
+fn key(
+    id: &str,
+    key_type: KeyType,
+    relationships: &[VerificationRelationship],
+) -> VerificationMethod {
+    VerificationMethod {
+        id: BoundedVec::try_from(id.as_bytes().to_vec()).unwrap(),
+        key_type,
+        public_key: BoundedVec::try_from(vec![1u8; key_type.public_key_len()]).unwrap(),
+        relationships: BoundedVec::try_from(relationships.to_vec()).unwrap(),
+    }
+}
+
+#[test]
+fn migration_to_v1_turns_roles_into_role_sets() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<IdentityRegistry>();
+        let old = |account: u64, role, verification_status| OldIdentity::<Test> {
+            did: create_did(&account.to_string()),
+            role,
+            name: create_name("User"),
+            email_hash: create_email_hash("user@example.com"),
+            verification_status,
+            registered_at: 1000,
+            updated_at: 1000,
+            owner: account,
+            active: true,
+        };
+        let identities = [
+            (1u64, old(1, UserRole::Patient, VerificationStatus::Unverified)),
+            (2u64, old(2, UserRole::Researcher, VerificationStatus::Verified)),
+            (3u64, old(3, UserRole::Auditor, VerificationStatus::Pending)),
+        ];
+        for (account, identity) in &identities {
+            unhashed::put(&Identities::<Test>::hashed_key_for(account), identity);
+        }
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(IdentityRegistry::on_chain_storage_version(), 1);
+        assert_eq!(
+            IdentityRegistry::identities(1).unwrap().roles.to_vec(),
+            vec![UserRole::Patient]
+        );
+        assert_eq!(
+            IdentityRegistry::identities(2).unwrap().roles.to_vec(),
+            vec![UserRole::Researcher]
+        );
+        assert_eq!(IdentityRegistry::role_requests(2, UserRole::Researcher), None);
+
+        // An unverified privileged role waits for verification, as on registration
+        let auditor = IdentityRegistry::identities(3).unwrap();
+        assert!(auditor.roles.is_empty());
+        assert_eq!(auditor.did, create_did("3"));
+        assert_eq!(auditor.verification_status, VerificationStatus::Pending);
+        assert_eq!(IdentityRegistry::role_requests(3, UserRole::Auditor), Some(1000));
     });
 }
@@ -0,0 +1,485 @@
+//! Estimated weights for `pallet_identity_registry`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/identity-consent-node benchmark pallet \
+//!     --chain dev --pallet pallet_identity_registry --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/identity-registry/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_identity_registry`.
+pub trait WeightInfo {
+    fn register_identity() -> Weight;
+    fn update_identity() -> Weight;
+    fn request_verification() -> Weight;
+    fn verify_identity() -> Weight;
+    fn reject_verification() -> Weight;
+    fn deactivate_identity() -> Weight;
+    fn add_verifier() -> Weight;
+    fn remove_verifier() -> Weight;
+    fn set_verification_threshold() -> Weight;
+    fn revoke_attestation() -> Weight;
+    fn revoke_verification() -> Weight;
+    fn add_verification_method() -> Weight;
+    fn remove_verification_method() -> Weight;
+    fn rotate_key() -> Weight;
+    fn add_service() -> Weight;
+    fn remove_service() -> Weight;
+    fn set_did_controllers() -> Weight;
+    fn deactivate_did() -> Weight;
+    fn request_role() -> Weight;
+    fn revoke_role() -> Weight;
+    fn set_recovery_config() -> Weight;
+    fn remove_recovery_config() -> Weight;
+    fn initiate_recovery() -> Weight;
+    fn approve_recovery() -> Weight;
+    fn cancel_recovery() -> Weight;
+    fn complete_recovery() -> Weight;
+    fn register_guardianship() -> Weight;
+    fn end_guardianship() -> Weight;
+}
+
+/// Estimated weights for `pallet_identity_registry`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidToAccount`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::DidToAccount`,
+    //     `IdentityRegistry::DidDocuments`, `IdentityRegistry::RoleRequests`
+    fn register_identity() -> Weight {
+        Weight::from_parts(31_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::Identities`
+    fn update_identity() -> Weight {
+        Weight::from_parts(17_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::VerificationQueue`,
+    //     `Timestamp::Now`
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::VerificationQueue`
+    fn request_verification() -> Weight {
+        Weight::from_parts(19_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `IdentityRegistry::Verifiers`, `Timestamp::Now`, `IdentityRegistry::Identities`,
+    //     `IdentityRegistry::VerificationThresholds` x4, `IdentityRegistry::Attestations` x64,
+    //     `IdentityRegistry::RoleRequests` x4
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::Attestations`,
+    //     `IdentityRegistry::VerificationQueue`, `IdentityRegistry::RoleRequests` x3
+    fn verify_identity() -> Weight {
+        Weight::from_parts(124_000_000, 167_268)
+            .saturating_add(T::DbWeight::get().reads(75_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    // Reads: `IdentityRegistry::Verifiers`, `IdentityRegistry::Identities`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::VerificationQueue`
+    fn reject_verification() -> Weight {
+        Weight::from_parts(20_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `Timestamp::Now`, `IdentityRegistry::DidDocuments`,
+    //     `IdentityRegistry::Guardianships` x5, `IdentityRegistry::Wards` x5,
+    //     `ParachainSystem::HostConfiguration`, `ParachainSystem::RelevantMessagingState`,
+    //     `XcmpQueue::DeliveryFeeFactor` x8, `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`,
+    //     `IdentityRegistry::Guardianships` x4, `IdentityRegistry::Wards` x8,
+    //     `XcmpQueue::OutboundXcmpStatus`, `XcmpQueue::OutboundXcmpMessages` x8
+    fn deactivate_identity() -> Weight {
+        Weight::from_parts(270_000_000, 28_561)
+            .saturating_add(T::DbWeight::get().reads(24_u64))
+            .saturating_add(T::DbWeight::get().writes(23_u64))
+    }
+
+    // Reads: `IdentityRegistry::Verifiers`
+    // Writes: `IdentityRegistry::Verifiers`
+    fn add_verifier() -> Weight {
+        Weight::from_parts(11_000_000, 1_507)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Verifiers`
+    // Writes: `IdentityRegistry::Verifiers`
+    fn remove_verifier() -> Weight {
+        Weight::from_parts(12_000_000, 1_507)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Writes: `IdentityRegistry::VerificationThresholds`
+    fn set_verification_threshold() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Attestations` x16, `Timestamp::Now`,
+    //     `IdentityRegistry::Identities`, `IdentityRegistry::VerificationThresholds` x4,
+    //     `IdentityRegistry::Verifiers`
+    // Writes: `IdentityRegistry::Attestations`, `IdentityRegistry::Identities`
+    fn revoke_attestation() -> Weight {
+        Weight::from_parts(46_000_000, 42_060)
+            .saturating_add(T::DbWeight::get().reads(23_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::Attestations` x16,
+    //     `IdentityRegistry::VerificationQueue`
+    fn revoke_verification() -> Weight {
+        Weight::from_parts(40_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(18_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::DidDocuments`
+    fn add_verification_method() -> Weight {
+        Weight::from_parts(27_000_000, 6_721)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::DidDocuments`
+    fn remove_verification_method() -> Weight {
+        Weight::from_parts(25_000_000, 6_721)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::DidDocuments`
+    fn rotate_key() -> Weight {
+        Weight::from_parts(25_000_000, 6_721)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::DidDocuments`
+    fn add_service() -> Weight {
+        Weight::from_parts(28_000_000, 6_721)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::DidDocuments`
+    fn remove_service() -> Weight {
+        Weight::from_parts(25_000_000, 6_721)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities` x5, `IdentityRegistry::DidDocuments`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::DidDocuments`
+    fn set_did_controllers() -> Weight {
+        Weight::from_parts(38_000_000, 17_700)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`, `Timestamp::Now`,
+    //     `IdentityRegistry::Guardianships` x5, `IdentityRegistry::Wards` x5,
+    //     `ParachainSystem::HostConfiguration`, `ParachainSystem::RelevantMessagingState`,
+    //     `XcmpQueue::DeliveryFeeFactor` x8, `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `IdentityRegistry::Identities`, `IdentityRegistry::DidDocuments`,
+    //     `IdentityRegistry::Guardianships` x4, `IdentityRegistry::Wards` x8,
+    //     `XcmpQueue::OutboundXcmpStatus`, `XcmpQueue::OutboundXcmpMessages` x8
+    fn deactivate_did() -> Weight {
+        Weight::from_parts(282_000_000, 28_561)
+            .saturating_add(T::DbWeight::get().reads(24_u64))
+            .saturating_add(T::DbWeight::get().writes(23_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::RoleRequests`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::RoleRequests`
+    fn request_role() -> Weight {
+        Weight::from_parts(21_000_000, 3_748)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::RoleRequests`, `IdentityRegistry::Identities`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::RoleRequests`, `IdentityRegistry::Identities`
+    fn revoke_role() -> Weight {
+        Weight::from_parts(19_000_000, 3_748)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `IdentityRegistry::ActiveRecoveries`
+    // Writes: `IdentityRegistry::RecoveryConfigs`
+    fn set_recovery_config() -> Weight {
+        Weight::from_parts(24_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::RecoveryConfigs`, `IdentityRegistry::ActiveRecoveries`
+    // Writes: `IdentityRegistry::RecoveryConfigs`, `IdentityRegistry::ActiveRecoveries`
+    fn remove_recovery_config() -> Weight {
+        Weight::from_parts(19_000_000, 3_661)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `IdentityRegistry::RecoveryConfigs`, `IdentityRegistry::Identities` x2,
+    //     `IdentityRegistry::ActiveRecoveries`, `Timestamp::Now`
+    // Writes: `IdentityRegistry::ActiveRecoveries`
+    fn initiate_recovery() -> Weight {
+        Weight::from_parts(31_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::RecoveryConfigs`, `IdentityRegistry::ActiveRecoveries`
+    // Writes: `IdentityRegistry::ActiveRecoveries`
+    fn approve_recovery() -> Weight {
+        Weight::from_parts(24_000_000, 3_661)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::ActiveRecoveries`
+    // Writes: `IdentityRegistry::ActiveRecoveries`
+    fn cancel_recovery() -> Weight {
+        Weight::from_parts(16_000_000, 3_661)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::ActiveRecoveries`, `IdentityRegistry::RecoveryConfigs`,
+    //     `Timestamp::Now`, `IdentityRegistry::Identities` x2, `IdentityRegistry::DidDocuments`,
+    //     `IdentityRegistry::Attestations` x17, `IdentityRegistry::RoleRequests` x4,
+    //     `IdentityRegistry::VerificationQueue`, `IdentityRegistry::Guardianships` x13,
+    //     `IdentityRegistry::Wards` x5, `ParachainSystem::HostConfiguration`,
+    //     `ParachainSystem::RelevantMessagingState`, `XcmpQueue::DeliveryFeeFactor` x20,
+    //     `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `IdentityRegistry::ActiveRecoveries`, `IdentityRegistry::RecoveryConfigs` x2,
+    //     `IdentityRegistry::Identities` x2, `IdentityRegistry::DidToAccount`,
+    //     `IdentityRegistry::DidDocuments` x2, `IdentityRegistry::Attestations` x32,
+    //     `IdentityRegistry::RoleRequests` x6, `IdentityRegistry::VerificationQueue` x2,
+    //     `IdentityRegistry::Guardianships` x16, `IdentityRegistry::Wards` x16,
+    //     `XcmpQueue::OutboundXcmpStatus`, `XcmpQueue::OutboundXcmpMessages` x20
+    fn complete_recovery() -> Weight {
+        Weight::from_parts(744_000_000, 64_457)
+            .saturating_add(T::DbWeight::get().reads(69_u64))
+            .saturating_add(T::DbWeight::get().writes(101_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `IdentityRegistry::Identities` x2,
+    //     `ParachainSystem::HostConfiguration`, `ParachainSystem::RelevantMessagingState`,
+    //     `XcmpQueue::DeliveryFeeFactor`, `XcmpQueue::OutboundXcmpStatus`,
+    //     `IdentityRegistry::Guardianships` x4, `IdentityRegistry::Wards` x4
+    // Writes: `XcmpQueue::OutboundXcmpStatus`, `XcmpQueue::OutboundXcmpMessages`,
+    //     `IdentityRegistry::Guardianships`, `IdentityRegistry::Wards`
+    fn register_guardianship() -> Weight {
+        Weight::from_parts(56_000_000, 18_410)
+            .saturating_add(T::DbWeight::get().reads(15_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    // Reads: `IdentityRegistry::Guardianships`, `Timestamp::Now`,
+    //     `ParachainSystem::HostConfiguration`, `ParachainSystem::RelevantMessagingState`,
+    //     `XcmpQueue::DeliveryFeeFactor`, `XcmpQueue::OutboundXcmpStatus`
+    // Writes: `IdentityRegistry::Guardianships`, `XcmpQueue::OutboundXcmpStatus`,
+    //     `XcmpQueue::OutboundXcmpMessages`, `IdentityRegistry::Wards`
+    fn end_guardianship() -> Weight {
+        Weight::from_parts(41_000_000, 3_728)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn register_identity() -> Weight {
+        Weight::from_parts(31_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn update_identity() -> Weight {
+        Weight::from_parts(17_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn request_verification() -> Weight {
+        Weight::from_parts(19_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn verify_identity() -> Weight {
+        Weight::from_parts(124_000_000, 167_268)
+            .saturating_add(RocksDbWeight::get().reads(75_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn reject_verification() -> Weight {
+        Weight::from_parts(20_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn deactivate_identity() -> Weight {
+        Weight::from_parts(270_000_000, 28_561)
+            .saturating_add(RocksDbWeight::get().reads(24_u64))
+            .saturating_add(RocksDbWeight::get().writes(23_u64))
+    }
+
+    fn add_verifier() -> Weight {
+        Weight::from_parts(11_000_000, 1_507)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_verifier() -> Weight {
+        Weight::from_parts(12_000_000, 1_507)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_verification_threshold() -> Weight {
+        Weight::from_parts(8_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn revoke_attestation() -> Weight {
+        Weight::from_parts(46_000_000, 42_060)
+            .saturating_add(RocksDbWeight::get().reads(23_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn revoke_verification() -> Weight {
+        Weight::from_parts(40_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(18_u64))
+    }
+
+    fn add_verification_method() -> Weight {
+        Weight::from_parts(27_000_000, 6_721)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_verification_method() -> Weight {
+        Weight::from_parts(25_000_000, 6_721)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn rotate_key() -> Weight {
+        Weight::from_parts(25_000_000, 6_721)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn add_service() -> Weight {
+        Weight::from_parts(28_000_000, 6_721)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_service() -> Weight {
+        Weight::from_parts(25_000_000, 6_721)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_did_controllers() -> Weight {
+        Weight::from_parts(38_000_000, 17_700)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn deactivate_did() -> Weight {
+        Weight::from_parts(282_000_000, 28_561)
+            .saturating_add(RocksDbWeight::get().reads(24_u64))
+            .saturating_add(RocksDbWeight::get().writes(23_u64))
+    }
+
+    fn request_role() -> Weight {
+        Weight::from_parts(21_000_000, 3_748)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn revoke_role() -> Weight {
+        Weight::from_parts(19_000_000, 3_748)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn set_recovery_config() -> Weight {
+        Weight::from_parts(24_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_recovery_config() -> Weight {
+        Weight::from_parts(19_000_000, 3_661)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn initiate_recovery() -> Weight {
+        Weight::from_parts(31_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn approve_recovery() -> Weight {
+        Weight::from_parts(24_000_000, 3_661)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn cancel_recovery() -> Weight {
+        Weight::from_parts(16_000_000, 3_661)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn complete_recovery() -> Weight {
+        Weight::from_parts(744_000_000, 64_457)
+            .saturating_add(RocksDbWeight::get().reads(69_u64))
+            .saturating_add(RocksDbWeight::get().writes(101_u64))
+    }
+
+    fn register_guardianship() -> Weight {
+        Weight::from_parts(56_000_000, 18_410)
+            .saturating_add(RocksDbWeight::get().reads(15_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn end_guardianship() -> Weight {
+        Weight::from_parts(41_000_000, 3_728)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+}
@@ -0,0 +1,171 @@
+//! Benchmarking setup for pallet-organizations
+
+use super::*;
+
+use codec::Encode;
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use pallet_identity_registry::UserRole;
+use sp_core::{Get, H256};
+use sp_std::prelude::*;
+
+/// Hex digits used to build DIDs from account IDs
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Register an identity with the given role
+fn register<T: Config>(who: &T::AccountId, role: UserRole) {
+    let mut did = b"did:patientx:".to_vec();
+    for byte in who.encode() {
+        did.extend_from_slice(&[HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]);
+    }
+
+    pallet_identity_registry::Pallet::<T>::register_identity(
+        RawOrigin::Signed(who.clone()).into(),
+        BoundedVec::truncate_from(did),
+        role.clone(),
+        BoundedVec::truncate_from(b"Benchmark".to_vec()),
+        H256::repeat_byte(1),
+    )
+    .expect("identity registration must succeed");
+
+    // Hold the requested role as if it had been verified
+    pallet_identity_registry::RoleRequests::<T>::remove(who, &role);
+    pallet_identity_registry::Identities::<T>::mutate(who, |identity| {
+        if let Some(identity) = identity {
+            identity.roles = BoundedVec::truncate_from(vec![role]);
+        }
+    });
+}
+
+/// Create an organization whose admin list is one short of full
+fn organization<T: Config>() -> T::AccountId {
+    let institution: T::AccountId = whitelisted_caller();
+    register::<T>(&institution, UserRole::Institution);
+    let mut admins = vec![institution.clone()];
+    for i in 1..<T as Config>::MaxAdmins::get().saturating_sub(1) {
+        admins.push(account("admin", i, 0));
+    }
+    Organizations::<T>::insert(
+        &institution,
+        Organization {
+            name: BoundedVec::truncate_from(b"Benchmark".to_vec()),
+            admins: BoundedVec::truncate_from(admins),
+            member_count: 0,
+            created_at: 0,
+        },
+    );
+    institution
+}
+
+/// Add a registered member with the given permissions
+fn member<T: Config>(
+    organization: &T::AccountId,
+    name: &'static str,
+    permissions: Vec<MemberPermission>,
+) -> T::AccountId {
+    let member: T::AccountId = account(name, 0, 0);
+    register::<T>(&member, UserRole::Researcher);
+    Members::<T>::insert(
+        organization,
+        &member,
+        Membership { permissions: BoundedVec::truncate_from(permissions), added_at: 0 },
+    );
+    Organizations::<T>::mutate(organization, |org| {
+        if let Some(org) = org {
+            org.member_count = org.member_count.saturating_add(1);
+        }
+    });
+    member
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_organization() {
+        let caller: T::AccountId = whitelisted_caller();
+        register::<T>(&caller, UserRole::Institution);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), BoundedVec::truncate_from(vec![b'a'; 64]));
+
+        assert!(Organizations::<T>::contains_key(&caller));
+    }
+
+    #[benchmark]
+    fn add_admin() {
+        let organization = organization::<T>();
+        let admin: T::AccountId = account("new-admin", 0, 0);
+        register::<T>(&admin, UserRole::Researcher);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(organization.clone()), organization.clone(), admin.clone());
+
+        assert!(Pallet::<T>::is_admin(&organization, &admin));
+    }
+
+    #[benchmark]
+    fn remove_admin() {
+        let organization = organization::<T>();
+        let admin: T::AccountId = account("new-admin", 0, 0);
+        Organizations::<T>::mutate(&organization, |org| {
+            if let Some(org) = org {
+                let _ = org.admins.try_push(admin.clone());
+            }
+        });
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(organization.clone()), organization.clone(), admin.clone());
+
+        assert!(!Pallet::<T>::is_admin(&organization, &admin));
+    }
+
+    #[benchmark]
+    fn add_member() {
+        let organization = organization::<T>();
+        let manager =
+            member::<T>(&organization, "manager", vec![MemberPermission::ManageMembers]);
+        let analyst: T::AccountId = account("analyst", 0, 0);
+        register::<T>(&analyst, UserRole::Researcher);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(manager),
+            organization.clone(),
+            analyst.clone(),
+            BoundedVec::truncate_from(vec![MemberPermission::AccessData]),
+        );
+
+        assert!(Members::<T>::contains_key(&organization, &analyst));
+    }
+
+    #[benchmark]
+    fn set_member_permissions() {
+        let organization = organization::<T>();
+        let manager =
+            member::<T>(&organization, "manager", vec![MemberPermission::ManageMembers]);
+        let analyst = member::<T>(&organization, "analyst", vec![MemberPermission::AccessData]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(manager), organization.clone(), analyst.clone(), BoundedVec::new());
+
+        assert!(Members::<T>::get(&organization, &analyst).unwrap().permissions.is_empty());
+    }
+
+    #[benchmark]
+    fn remove_member() {
+        let organization = organization::<T>();
+        let manager =
+            member::<T>(&organization, "manager", vec![MemberPermission::ManageMembers]);
+        let analyst = member::<T>(&organization, "analyst", vec![MemberPermission::AccessData]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(manager), organization.clone(), analyst.clone());
+
+        assert!(!Members::<T>::contains_key(&organization, &analyst));
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,411 @@
+//! # Organizations Pallet
+//!
+//! ## Overview
+//!
+//! The Organizations pallet lets an `Institution` identity (a hospital, a pharma company) act
+//! through its staff. It provides:
+//! - Organizations owned by an `Institution` account, managed by a set of admins
+//! - Member accounts with per-member permissions
+//! - Membership checks used by the Consent Manager, so a consent granted to the organization
+//!   covers its members
+//!
+//! ## Membership
+//!
+//! The organization is identified by the `Institution` account that created it, which is its
+//! first admin. Admins add and remove admins and members. Members hold a set of permissions:
+//! `AccessData` lets them access data under consents granted to the organization, and
+//! `ManageMembers` lets them add and remove members (but not grant `ManageMembers` or manage
+//! admins). Members and admins must have an active identity; a member whose identity is
+//! deactivated loses its permissions. Members can leave an organization themselves.
+//!
+//! ## Architecture Reference
+//! See parachain.md Section: "IdentityConsent Chain - Identity Registry"
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::{pallet_prelude::*, traits::Time};
+    use frame_system::pallet_prelude::*;
+    use sp_std::prelude::*;
+    use pallet_identity_registry::{Pallet as IdentityRegistry, UserRole};
+    use crate::weights::WeightInfo;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// What a member may do on behalf of its organization
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum MemberPermission {
+        /// Access data under consents granted to the organization
+        AccessData,
+        /// Add and remove members
+        ManageMembers,
+    }
+
+    /// Permissions of a member, without duplicates
+    pub type Permissions = BoundedVec<MemberPermission, ConstU32<2>>;
+
+    /// Organization owned by an institution
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Organization<T: Config> {
+        /// Display name
+        pub name: BoundedVec<u8, ConstU32<64>>,
+        /// Accounts managing admins and members
+        pub admins: BoundedVec<T::AccountId, T::MaxAdmins>,
+        /// Number of members
+        pub member_count: u32,
+        /// Creation timestamp
+        pub created_at: u64,
+    }
+
+    /// Membership of an account in an organization
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct Membership {
+        /// Permissions held by the member
+        pub permissions: Permissions,
+        /// Timestamp the member joined
+        pub added_at: u64,
+    }
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_identity_registry::Config {
+        /// The overarching event type
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Time provider for timestamps
+        type TimeProvider: Time;
+
+        /// Maximum number of admins per organization
+        #[pallet::constant]
+        type MaxAdmins: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Organizations by institution account
+    #[pallet::storage]
+    #[pallet::getter(fn organizations)]
+    pub type Organizations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Organization<T>>;
+
+    /// Memberships by organization and member
+    #[pallet::storage]
+    #[pallet::getter(fn members)]
+    pub type Members<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        Membership,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Organization created [organization]
+        OrganizationCreated { organization: T::AccountId },
+        /// Admin added [organization, admin]
+        AdminAdded { organization: T::AccountId, admin: T::AccountId },
+        /// Admin removed [organization, admin]
+        AdminRemoved { organization: T::AccountId, admin: T::AccountId },
+        /// Member added [organization, member]
+        MemberAdded { organization: T::AccountId, member: T::AccountId },
+        /// Member permissions replaced [organization, member]
+        MemberPermissionsSet { organization: T::AccountId, member: T::AccountId },
+        /// Member removed [organization, member]
+        MemberRemoved { organization: T::AccountId, member: T::AccountId },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Only Institution identities can create organizations
+        NotInstitution,
+        /// Organization already exists
+        OrganizationAlreadyExists,
+        /// Organization not found
+        OrganizationNotFound,
+        /// Name must not be empty
+        InvalidName,
+        /// Not authorized to perform this action
+        NotAuthorized,
+        /// Account has no active identity
+        InvalidIdentity,
+        /// Account is already an admin
+        AlreadyAdmin,
+        /// Account is not an admin
+        NotAdmin,
+        /// Maximum number of admins reached
+        TooManyAdmins,
+        /// An organization must keep at least one admin
+        LastAdmin,
+        /// Account is already a member
+        AlreadyMember,
+        /// Account is not a member
+        NotMember,
+        /// Permissions contain duplicates
+        InvalidPermissions,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create an organization for the caller's Institution identity
+        ///
+        /// The caller becomes its first admin.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_organization())]
+        pub fn create_organization(
+            origin: OriginFor<T>,
+            name: BoundedVec<u8, ConstU32<64>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                IdentityRegistry::<T>::has_role(&who, UserRole::Institution),
+                Error::<T>::NotInstitution
+            );
+            ensure!(
+                !Organizations::<T>::contains_key(&who),
+                Error::<T>::OrganizationAlreadyExists
+            );
+            ensure!(!name.is_empty(), Error::<T>::InvalidName);
+
+            let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+            Organizations::<T>::insert(
+                &who,
+                Organization {
+                    name,
+                    admins: BoundedVec::truncate_from(sp_std::vec![who.clone()]),
+                    member_count: 0,
+                    created_at: now,
+                },
+            );
+
+            Self::deposit_event(Event::OrganizationCreated { organization: who });
+
+            Ok(())
+        }
+
+        /// Add an admin (admins only)
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_admin())]
+        pub fn add_admin(
+            origin: OriginFor<T>,
+            organization: T::AccountId,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                IdentityRegistry::<T>::is_active_identity(&admin),
+                Error::<T>::InvalidIdentity
+            );
+
+            Self::mutate_organization(&who, &organization, |org| {
+                ensure!(!org.admins.contains(&admin), Error::<T>::AlreadyAdmin);
+                org.admins.try_push(admin.clone()).map_err(|_| Error::<T>::TooManyAdmins)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::AdminAdded { organization, admin });
+
+            Ok(())
+        }
+
+        /// Remove an admin (admins only, keeping at least one)
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_admin())]
+        pub fn remove_admin(
+            origin: OriginFor<T>,
+            organization: T::AccountId,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::mutate_organization(&who, &organization, |org| {
+                let index =
+                    org.admins.iter().position(|a| *a == admin).ok_or(Error::<T>::NotAdmin)?;
+                ensure!(org.admins.len() > 1, Error::<T>::LastAdmin);
+                org.admins.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::AdminRemoved { organization, admin });
+
+            Ok(())
+        }
+
+        /// Add a member with the given permissions
+        ///
+        /// Admins can grant any permission; members with `ManageMembers` can add members
+        /// without it.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::add_member())]
+        pub fn add_member(
+            origin: OriginFor<T>,
+            organization: T::AccountId,
+            member: T::AccountId,
+            permissions: Permissions,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut org =
+                Organizations::<T>::get(&organization).ok_or(Error::<T>::OrganizationNotFound)?;
+            Self::ensure_can_grant(&org, &organization, &who, &permissions)?;
+            ensure!(
+                IdentityRegistry::<T>::is_active_identity(&member),
+                Error::<T>::InvalidIdentity
+            );
+            ensure!(
+                !Members::<T>::contains_key(&organization, &member),
+                Error::<T>::AlreadyMember
+            );
+
+            let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+            Members::<T>::insert(&organization, &member, Membership { permissions, added_at: now });
+            org.member_count = org.member_count.saturating_add(1);
+            Organizations::<T>::insert(&organization, org);
+
+            Self::deposit_event(Event::MemberAdded { organization, member });
+
+            Ok(())
+        }
+
+        /// Replace a member's permissions
+        ///
+        /// Same rules as `add_member`; only admins can change the permissions of a member
+        /// holding `ManageMembers`.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_member_permissions())]
+        pub fn set_member_permissions(
+            origin: OriginFor<T>,
+            organization: T::AccountId,
+            member: T::AccountId,
+            permissions: Permissions,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let org =
+                Organizations::<T>::get(&organization).ok_or(Error::<T>::OrganizationNotFound)?;
+            Self::ensure_can_grant(&org, &organization, &who, &permissions)?;
+
+            Members::<T>::try_mutate(&organization, &member, |maybe_membership| {
+                let membership = maybe_membership.as_mut().ok_or(Error::<T>::NotMember)?;
+                let manager = membership.permissions.contains(&MemberPermission::ManageMembers);
+                ensure!(!manager || org.admins.contains(&who), Error::<T>::NotAuthorized);
+                membership.permissions = permissions;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::MemberPermissionsSet { organization, member });
+
+            Ok(())
+        }
+
+        /// Remove a member
+        ///
+        /// Admins can remove any member, members with `ManageMembers` those without it, and
+        /// members can remove themselves.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_member())]
+        pub fn remove_member(
+            origin: OriginFor<T>,
+            organization: T::AccountId,
+            member: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut org =
+                Organizations::<T>::get(&organization).ok_or(Error::<T>::OrganizationNotFound)?;
+            let membership =
+                Members::<T>::get(&organization, &member).ok_or(Error::<T>::NotMember)?;
+
+            let manager = membership.permissions.contains(&MemberPermission::ManageMembers);
+            let manages_members =
+                Self::has_permission(&organization, &who, MemberPermission::ManageMembers);
+            let authorized =
+                who == member || org.admins.contains(&who) || (!manager && manages_members);
+            ensure!(authorized, Error::<T>::NotAuthorized);
+
+            Members::<T>::remove(&organization, &member);
+            org.member_count = org.member_count.saturating_sub(1);
+            Organizations::<T>::insert(&organization, org);
+
+            Self::deposit_event(Event::MemberRemoved { organization, member });
+
+            Ok(())
+        }
+    }
+
+    // Helper functions
+    impl<T: Config> Pallet<T> {
+        /// Check if `member` holds `permission` in `organization` and has an active identity
+        pub fn has_permission(
+            organization: &T::AccountId,
+            member: &T::AccountId,
+            permission: MemberPermission,
+        ) -> bool {
+            Members::<T>::get(organization, member).map_or(false, |membership| {
+                membership.permissions.contains(&permission)
+                    && IdentityRegistry::<T>::is_active_identity(member)
+            })
+        }
+
+        /// Check if account is an admin of the organization
+        pub fn is_admin(organization: &T::AccountId, account: &T::AccountId) -> bool {
+            Organizations::<T>::get(organization).map_or(false, |org| org.admins.contains(account))
+        }
+
+        /// Update an organization on behalf of one of its admins
+        fn mutate_organization(
+            who: &T::AccountId,
+            organization: &T::AccountId,
+            f: impl FnOnce(&mut Organization<T>) -> DispatchResult,
+        ) -> DispatchResult {
+            Organizations::<T>::try_mutate(organization, |maybe_org| {
+                let org = maybe_org.as_mut().ok_or(Error::<T>::OrganizationNotFound)?;
+                ensure!(org.admins.contains(who), Error::<T>::NotAuthorized);
+                f(org)
+            })
+        }
+
+        /// Check that `who` may grant `permissions` to a member
+        fn ensure_can_grant(
+            org: &Organization<T>,
+            organization: &T::AccountId,
+            who: &T::AccountId,
+            permissions: &Permissions,
+        ) -> DispatchResult {
+            ensure!(
+                permissions.iter().enumerate().all(|(i, p)| !permissions[..i].contains(p)),
+                Error::<T>::InvalidPermissions
+            );
+
+            if org.admins.contains(who) {
+                return Ok(());
+            }
+            ensure!(
+                Self::has_permission(organization, who, MemberPermission::ManageMembers)
+                    && !permissions.contains(&MemberPermission::ManageMembers),
+                Error::<T>::NotAuthorized
+            );
+            Ok(())
+        }
+    }
+}
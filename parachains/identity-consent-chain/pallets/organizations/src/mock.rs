@@ -0,0 +1,139 @@
+//! Mock runtime for organizations pallet tests
+
+use crate as pallet_organizations;
+use frame_support::{
+    parameter_types,
+    traits::{ConstU16, ConstU32, ConstU64},
+};
+use pallet_identity_registry::UserRole;
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BlakeTwo256, IdentityLookup},
+    BoundedVec, BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Timestamp: pallet_timestamp,
+        IdentityRegistry: pallet_identity_registry,
+        Organizations: pallet_organizations,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+impl pallet_identity_registry::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxIdentitiesPerAccount = ConstU32<1>;
+    type VerifierOrigin = frame_system::EnsureRoot<u64>;
+    type MaxVerifiers = ConstU32<4>;
+    type MaxDidControllers = ConstU32<2>;
+    type MaxVerificationMethods = ConstU32<2>;
+    type MaxServices = ConstU32<2>;
+    type MaxGuardians = ConstU32<3>;
+    type MaxGuardianships = ConstU32<2>;
+    type RecoveryHandler = ();
+    type GuardianshipOrigin = frame_system::EnsureRoot<u64>;
+    type GuardianshipHandler = ();
+    type WeightInfo = ();
+}
+
+impl pallet_organizations::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxAdmins = ConstU32<2>;
+    type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_timestamp::GenesisConfig::<Test> { now: 0 }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+// Helper function to register an identity with the given role
+pub fn register(account: u64, role: UserRole) {
+    frame_support::assert_ok!(IdentityRegistry::register_identity(
+        RuntimeOrigin::signed(account),
+        BoundedVec::try_from(format!("did:patientx:{}", account).into_bytes()).unwrap(),
+        role.clone(),
+        BoundedVec::try_from(b"User".to_vec()).unwrap(),
+        H256::from_low_u64_be(account),
+    ));
+    hold_role(account, role);
+}
+
+// Helper function to hold a requested privileged role, as if its request had been verified
+pub fn hold_role(account: u64, role: UserRole) {
+    if pallet_identity_registry::RoleRequests::<Test>::take(account, &role).is_some() {
+        pallet_identity_registry::Identities::<Test>::mutate(account, |identity| {
+            if let Some(identity) = identity {
+                identity.roles = BoundedVec::truncate_from(vec![role]);
+            }
+        });
+    }
+}
+
+// Helper function to create an organization for a new institution
+pub fn create_organization(institution: u64) {
+    register(institution, UserRole::Institution);
+    frame_support::assert_ok!(Organizations::create_organization(
+        RuntimeOrigin::signed(institution),
+        BoundedVec::try_from(b"St Mary's Hospital".to_vec()).unwrap(),
+    ));
+}
+
+// Helper function to build a permission set
+pub fn permissions(permissions: &[crate::MemberPermission]) -> crate::Permissions {
+    BoundedVec::try_from(permissions.to_vec()).unwrap()
+}
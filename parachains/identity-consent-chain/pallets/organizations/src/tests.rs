@@ -0,0 +1,288 @@
+//! Tests for organizations pallet
+
+use crate::{mock::*, Error, Event, MemberPermission};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_identity_registry::UserRole;
+
+#[test]
+fn create_organization_works() {
+    new_test_ext().execute_with(|| {
+        let hospital = 1u64;
+        create_organization(hospital);
+
+        let org = Organizations::organizations(hospital).unwrap();
+        assert_eq!(org.admins.to_vec(), vec![hospital]);
+        assert_eq!(org.member_count, 0);
+        System::assert_last_event(Event::OrganizationCreated { organization: hospital }.into());
+
+        assert_noop!(
+            Organizations::create_organization(
+                RuntimeOrigin::signed(hospital),
+                BoundedVec::try_from(b"Again".to_vec()).unwrap(),
+            ),
+            Error::<Test>::OrganizationAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn create_organization_requires_institution() {
+    new_test_ext().execute_with(|| {
+        let researcher = 1u64;
+        register(researcher, UserRole::Researcher);
+
+        assert_noop!(
+            Organizations::create_organization(
+                RuntimeOrigin::signed(researcher),
+                BoundedVec::try_from(b"Lab".to_vec()).unwrap(),
+            ),
+            Error::<Test>::NotInstitution
+        );
+    });
+}
+
+#[test]
+fn admins_can_be_added_and_removed() {
+    new_test_ext().execute_with(|| {
+        let (hospital, admin, other) = (1u64, 2u64, 3u64);
+        create_organization(hospital);
+        register(admin, UserRole::Researcher);
+        register(other, UserRole::Researcher);
+
+        // Admins need an identity
+        assert_noop!(
+            Organizations::add_admin(RuntimeOrigin::signed(hospital), hospital, 9),
+            Error::<Test>::InvalidIdentity
+        );
+        assert_noop!(
+            Organizations::add_admin(RuntimeOrigin::signed(admin), hospital, admin),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Organizations::add_admin(RuntimeOrigin::signed(hospital), hospital, admin));
+        assert!(Organizations::is_admin(&hospital, &admin));
+        assert_noop!(
+            Organizations::add_admin(RuntimeOrigin::signed(admin), hospital, admin),
+            Error::<Test>::AlreadyAdmin
+        );
+
+        // At most two admins in the mock
+        assert_noop!(
+            Organizations::add_admin(RuntimeOrigin::signed(admin), hospital, other),
+            Error::<Test>::TooManyAdmins
+        );
+
+        // The new admin can remove the institution account, but not the last admin
+        assert_ok!(Organizations::remove_admin(RuntimeOrigin::signed(admin), hospital, hospital));
+        System::assert_last_event(
+            Event::AdminRemoved { organization: hospital, admin: hospital }.into(),
+        );
+        assert_noop!(
+            Organizations::remove_admin(RuntimeOrigin::signed(admin), hospital, admin),
+            Error::<Test>::LastAdmin
+        );
+    });
+}
+
+#[test]
+fn add_member_works() {
+    new_test_ext().execute_with(|| {
+        let (hospital, analyst) = (1u64, 2u64);
+        create_organization(hospital);
+        register(analyst, UserRole::Researcher);
+
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            analyst,
+            permissions(&[MemberPermission::AccessData]),
+        ));
+
+        assert!(Organizations::has_permission(&hospital, &analyst, MemberPermission::AccessData));
+        assert!(!Organizations::has_permission(
+            &hospital,
+            &analyst,
+            MemberPermission::ManageMembers
+        ));
+        assert_eq!(Organizations::organizations(hospital).unwrap().member_count, 1);
+        System::assert_last_event(
+            Event::MemberAdded { organization: hospital, member: analyst }.into(),
+        );
+
+        assert_noop!(
+            Organizations::add_member(
+                RuntimeOrigin::signed(hospital),
+                hospital,
+                analyst,
+                permissions(&[]),
+            ),
+            Error::<Test>::AlreadyMember
+        );
+        assert_noop!(
+            Organizations::add_member(
+                RuntimeOrigin::signed(hospital),
+                hospital,
+                9,
+                permissions(&[MemberPermission::AccessData]),
+            ),
+            Error::<Test>::InvalidIdentity
+        );
+        assert_noop!(
+            Organizations::add_member(
+                RuntimeOrigin::signed(hospital),
+                hospital,
+                3,
+                permissions(&[MemberPermission::AccessData, MemberPermission::AccessData]),
+            ),
+            Error::<Test>::InvalidPermissions
+        );
+    });
+}
+
+#[test]
+fn member_managers_cannot_escalate() {
+    new_test_ext().execute_with(|| {
+        let (hospital, manager, analyst, other) = (1u64, 2u64, 3u64, 4u64);
+        create_organization(hospital);
+        register(manager, UserRole::Researcher);
+        register(analyst, UserRole::Researcher);
+        register(other, UserRole::Researcher);
+
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            manager,
+            permissions(&[MemberPermission::ManageMembers]),
+        ));
+
+        // Managers add members without the manager permission
+        assert_noop!(
+            Organizations::add_member(
+                RuntimeOrigin::signed(manager),
+                hospital,
+                analyst,
+                permissions(&[MemberPermission::ManageMembers]),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(manager),
+            hospital,
+            analyst,
+            permissions(&[MemberPermission::AccessData]),
+        ));
+
+        // Analysts cannot manage members
+        assert_noop!(
+            Organizations::add_member(
+                RuntimeOrigin::signed(analyst),
+                hospital,
+                other,
+                permissions(&[MemberPermission::AccessData]),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+
+        // Managers cannot touch other managers
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            other,
+            permissions(&[MemberPermission::ManageMembers]),
+        ));
+        assert_noop!(
+            Organizations::set_member_permissions(
+                RuntimeOrigin::signed(manager),
+                hospital,
+                other,
+                permissions(&[]),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            Organizations::remove_member(RuntimeOrigin::signed(manager), hospital, other),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Organizations::remove_member(RuntimeOrigin::signed(manager), hospital, analyst));
+        assert_eq!(Organizations::organizations(hospital).unwrap().member_count, 2);
+    });
+}
+
+#[test]
+fn set_member_permissions_works() {
+    new_test_ext().execute_with(|| {
+        let (hospital, analyst) = (1u64, 2u64);
+        create_organization(hospital);
+        register(analyst, UserRole::Researcher);
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            analyst,
+            permissions(&[MemberPermission::AccessData]),
+        ));
+
+        assert_ok!(Organizations::set_member_permissions(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            analyst,
+            permissions(&[]),
+        ));
+
+        assert!(!Organizations::has_permission(&hospital, &analyst, MemberPermission::AccessData));
+        System::assert_last_event(
+            Event::MemberPermissionsSet { organization: hospital, member: analyst }.into(),
+        );
+        assert_noop!(
+            Organizations::set_member_permissions(
+                RuntimeOrigin::signed(hospital),
+                hospital,
+                3,
+                permissions(&[]),
+            ),
+            Error::<Test>::NotMember
+        );
+    });
+}
+
+#[test]
+fn members_can_leave() {
+    new_test_ext().execute_with(|| {
+        let (hospital, analyst) = (1u64, 2u64);
+        create_organization(hospital);
+        register(analyst, UserRole::Researcher);
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            analyst,
+            permissions(&[MemberPermission::AccessData]),
+        ));
+
+        assert_ok!(Organizations::remove_member(RuntimeOrigin::signed(analyst), hospital, analyst));
+
+        assert_eq!(Organizations::members(hospital, analyst), None);
+        assert_eq!(Organizations::organizations(hospital).unwrap().member_count, 0);
+        System::assert_last_event(
+            Event::MemberRemoved { organization: hospital, member: analyst }.into(),
+        );
+    });
+}
+
+#[test]
+fn deactivated_members_lose_permissions() {
+    new_test_ext().execute_with(|| {
+        let (hospital, analyst) = (1u64, 2u64);
+        create_organization(hospital);
+        register(analyst, UserRole::Researcher);
+        assert_ok!(Organizations::add_member(
+            RuntimeOrigin::signed(hospital),
+            hospital,
+            analyst,
+            permissions(&[MemberPermission::AccessData]),
+        ));
+
+        assert_ok!(IdentityRegistry::deactivate_identity(RuntimeOrigin::signed(analyst)));
+
+        assert!(!Organizations::has_permission(&hospital, &analyst, MemberPermission::AccessData));
+    });
+}
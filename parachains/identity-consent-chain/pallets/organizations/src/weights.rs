@@ -0,0 +1,124 @@
+//! Estimated weights for `pallet_organizations`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/identity-consent-node benchmark pallet \
+//!     --chain dev --pallet pallet_organizations --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/organizations/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_organizations`.
+pub trait WeightInfo {
+    fn create_organization() -> Weight;
+    fn add_admin() -> Weight;
+    fn remove_admin() -> Weight;
+    fn add_member() -> Weight;
+    fn set_member_permissions() -> Weight;
+    fn remove_member() -> Weight;
+}
+
+/// Estimated weights for `pallet_organizations`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `IdentityRegistry::Identities`, `Organizations::Organizations`, `Timestamp::Now`
+    // Writes: `Organizations::Organizations`
+    fn create_organization() -> Weight {
+        Weight::from_parts(20_000_000, 3_745)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `IdentityRegistry::Identities`, `Organizations::Organizations`
+    // Writes: `Organizations::Organizations`
+    fn add_admin() -> Weight {
+        Weight::from_parts(21_000_000, 3_894)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Organizations::Organizations`
+    // Writes: `Organizations::Organizations`
+    fn remove_admin() -> Weight {
+        Weight::from_parts(17_000_000, 3_894)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Organizations::Organizations`, `Organizations::Members` x2,
+    //     `IdentityRegistry::Identities` x2, `Timestamp::Now`
+    // Writes: `Organizations::Organizations`, `Organizations::Members`
+    fn add_member() -> Weight {
+        Weight::from_parts(31_000_000, 3_894)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `Organizations::Organizations`, `Organizations::Members` x2,
+    //     `IdentityRegistry::Identities`
+    // Writes: `Organizations::Members`
+    fn set_member_permissions() -> Weight {
+        Weight::from_parts(26_000_000, 3_894)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Organizations::Organizations`, `Organizations::Members` x2,
+    //     `IdentityRegistry::Identities`
+    // Writes: `Organizations::Organizations`, `Organizations::Members`
+    fn remove_member() -> Weight {
+        Weight::from_parts(27_000_000, 3_894)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn create_organization() -> Weight {
+        Weight::from_parts(20_000_000, 3_745)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn add_admin() -> Weight {
+        Weight::from_parts(21_000_000, 3_894)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_admin() -> Weight {
+        Weight::from_parts(17_000_000, 3_894)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn add_member() -> Weight {
+        Weight::from_parts(31_000_000, 3_894)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn set_member_permissions() -> Weight {
+        Weight::from_parts(26_000_000, 3_894)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_member() -> Weight {
+        Weight::from_parts(27_000_000, 3_894)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+}
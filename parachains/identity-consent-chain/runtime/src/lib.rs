@@ -35,11 +35,15 @@ use sp_version::RuntimeVersion;
 
 // XCM imports
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
+use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
+use frame_support::traits::TransformOrigin;
+use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
+use polkadot_runtime_common::xcm_sender::NoPriceForMessageDelivery;
 use xcm::latest::prelude::*;
 use xcm_builder::{
     AccountId32Aliases, AllowUnpaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
     ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SignedAccountId32AsNative,
-    SovereignSignedViaLocation,
+    SovereignSignedViaLocation, XcmPassthrough,
 };
 use xcm_executor::XcmExecutor;
 
@@ -109,6 +113,13 @@ pub type SignedExtra = (
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+    pallet_identity_registry::migrations::v1::MigrateToV1<Runtime>,
+    pallet_consent_manager::migrations::v1::MigrateToV1<Runtime>,
+    pallet_authentication::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -116,6 +127,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
@@ -281,10 +293,10 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnSystemEvent = ();
     type SelfParaId = parachain_info::Pallet<Runtime>;
-    type OutboundXcmpMessageSource = ();
+    type OutboundXcmpMessageSource = XcmpQueue;
     type DmpMessageHandler = ();
     type ReservedDmpWeight = ReservedDmpWeight;
-    type XcmpMessageHandler = ();
+    type XcmpMessageHandler = XcmpQueue;
     type ReservedXcmpWeight = ReservedXcmpWeight;
     type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
     type WeightInfo = ();
@@ -312,14 +324,22 @@ pub type XcmOriginToCallOrigin = (
     RelayChainAsNative<RelayChainOrigin, RuntimeOrigin>,
     SiblingParachainAsNative<cumulus_pallet_xcm::Origin, RuntimeOrigin>,
     SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+    // Keeps the XCM origin of sibling queries (e.g. consent verification) as `pallet_xcm::Origin`
+    XcmPassthrough<RuntimeOrigin>,
 );
 
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
+/// Routes XCM to the relay chain (UMP) and to sibling parachains (XCMP)
+pub type XcmRouter = (
+    cumulus_primitives_utility::ParentAsUmp<ParachainSystem, (), ()>,
+    XcmpQueue,
+);
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
     type RuntimeCall = RuntimeCall;
-    type XcmSender = ();
+    type XcmSender = XcmRouter;
     type AssetTransactor = ();
     type OriginConverter = XcmOriginToCallOrigin;
     type IsReserve = ();
@@ -328,7 +348,7 @@ impl xcm_executor::Config for XcmConfig {
     type Barrier = Barrier;
     type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
     type Trader = ();
-    type ResponseHandler = ();
+    type ResponseHandler = PolkadotXcm;
     type AssetTrap = ();
     type AssetClaims = ();
     type SubscriptionService = ();
@@ -347,7 +367,7 @@ impl xcm_executor::Config for XcmConfig {
 impl pallet_xcm::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, ()>;
-    type XcmRouter = ();
+    type XcmRouter = XcmRouter;
     type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocationToAccountId>;
     type XcmExecuteFilter = Everything;
     type XcmExecutor = XcmExecutor<XcmConfig>;
@@ -375,19 +395,448 @@ impl cumulus_pallet_xcm::Config for Runtime {
     type XcmExecutor = XcmExecutor<XcmConfig>;
 }
 
+impl cumulus_pallet_xcmp_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type ChannelInfo = ParachainSystem;
+    type VersionWrapper = PolkadotXcm;
+    type XcmpQueue = TransformOrigin<MessageQueue, AggregateMessageOrigin, ParaId, ParaIdToSibling>;
+    type MaxInboundSuspended = ConstU32<1_000>;
+    type MaxActiveOutboundChannels = ConstU32<128>;
+    type MaxPageSize = ConstU32<{ 1 << 16 }>;
+    type ControllerOrigin = EnsureRoot<AccountId>;
+    type ControllerOriginConverter = XcmOriginToCallOrigin;
+    type WeightInfo = ();
+    type PriceForSiblingDelivery = NoPriceForMessageDelivery<ParaId>;
+}
+
+parameter_types! {
+    /// A quarter of the block's execution time and proof size for incoming messages
+    pub MessageQueueServiceWeight: Weight = Weight::from_parts(
+        WEIGHT_REF_TIME_PER_SECOND / 4,
+        cumulus_primitives_core::relay_chain::MAX_POV_SIZE as u64 / 4,
+    );
+}
+
+impl pallet_message_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MessageProcessor = xcm_builder::ProcessXcmMessage<
+        AggregateMessageOrigin,
+        XcmExecutor<XcmConfig>,
+        RuntimeCall,
+    >;
+    type Size = u32;
+    type QueueChangeHandler = NarrowOriginToSibling<XcmpQueue>;
+    type QueuePausedQuery = NarrowOriginToSibling<XcmpQueue>;
+    type HeapSize = ConstU32<{ 64 * 1024 }>;
+    type MaxStale = ConstU32<8>;
+    type ServiceWeight = MessageQueueServiceWeight;
+    type IdleMaxServiceWeight = ();
+}
+
 // Custom pallets configuration
 
 impl pallet_identity_registry::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxIdentitiesPerAccount = ConstU32<1>;
+    type VerifierOrigin = EnsureRoot<AccountId>;
+    type MaxVerifiers = ConstU32<16>;
+    type MaxDidControllers = ConstU32<4>;
+    type MaxVerificationMethods = ConstU32<8>;
+    type MaxServices = ConstU32<4>;
+    type MaxGuardians = ConstU32<8>;
+    type MaxGuardianships = ConstU32<4>;
+    type RecoveryHandler = CrossChainNotifier;
+    type GuardianshipOrigin = EnsureRoot<AccountId>;
+    type GuardianshipHandler = CrossChainNotifier;
+    type WeightInfo = pallet_identity_registry::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    /// Marketplace chain (Para ID 2002)
+    pub MarketplaceLocation: Location = Location::new(1, [Parachain(2002)]);
+    /// Indexes of the pallets re-keying, erasing or acting for an account on the other chains
+    pub const HealthRecordsPalletIndex: u8 = 50;
+    pub const IpfsIntegrationPalletIndex: u8 = 51;
+    pub const AccessControlPalletIndex: u8 = 52;
+    pub const EncryptionPalletIndex: u8 = 53;
+    pub const DataListingsPalletIndex: u8 = 50;
+}
+
+/// Weights of the calls sent to the HealthData and Marketplace chains, which share
+/// this runtime's `DbWeight`
+type HealthRecordsWeights = pallet_health_records::weights::SubstrateWeight<Runtime>;
+type IpfsIntegrationWeights = pallet_ipfs_integration::weights::SubstrateWeight<Runtime>;
+type AccessControlWeights = pallet_access_control::weights::SubstrateWeight<Runtime>;
+type EncryptionWeights = pallet_encryption::weights::SubstrateWeight<Runtime>;
+type DataListingsWeights = pallet_data_listings::weights::SubstrateWeight<Runtime>;
+
+/// Encoding of the account migration, erasure, guardianship and credential calls on the
+/// HealthData and Marketplace chains
+///
+/// The variant indexes must match the call indexes in the target pallets.
+#[derive(Encode)]
+enum HealthRecordsCall {
+    /// `pallet_health_records::migrate_patient`
+    #[codec(index = 6)]
+    MigratePatient { old: AccountId, new: AccountId },
+    /// `pallet_health_records::erase_patient`
+    #[codec(index = 8)]
+    ErasePatient { patient: AccountId },
+}
+
+#[derive(Encode)]
+enum IpfsIntegrationCall {
+    /// `pallet_ipfs_integration::erase_owner_content`
+    #[codec(index = 4)]
+    EraseOwnerContent { owner: AccountId },
+}
+
+#[derive(Encode)]
+enum AccessControlCall {
+    /// `pallet_access_control::set_clinician`
+    #[codec(index = 9)]
+    SetClinician { account: AccountId, valid_until: Option<u64> },
+    /// `pallet_access_control::set_auditor`
+    #[codec(index = 10)]
+    SetAuditor { account: AccountId, is_auditor: bool },
+    /// `pallet_access_control::set_guardianship`
+    #[codec(index = 13)]
+    SetGuardianship { ward: AccountId, guardian: AccountId, valid_until: Option<u64> },
+    /// `pallet_access_control::migrate_account`
+    #[codec(index = 16)]
+    MigrateAccount { old: AccountId, new: AccountId },
+}
+
+#[derive(Encode)]
+enum EncryptionCall {
+    /// `pallet_encryption::migrate_keys`
+    #[codec(index = 5)]
+    MigrateKeys { old: AccountId, new: AccountId },
+    /// `pallet_encryption::revoke_account_keys`
+    #[codec(index = 6)]
+    RevokeAccountKeys { account: AccountId },
+}
+
+#[derive(Encode)]
+enum DataListingsCall {
+    /// `pallet_data_listings::migrate_provider`
+    #[codec(index = 5)]
+    MigrateProvider { old: AccountId, new: AccountId },
+    /// `pallet_data_listings::delist_provider`
+    #[codec(index = 6)]
+    DelistProvider { provider: AccountId },
+}
+
+/// Asks the HealthData and Marketplace chains over XCM to re-key the records, keys and
+/// listings of an identity recovered to a new account, or to erase those of an account that
+/// exercised the right to erasure, and mirrors guardianships and clinician and auditor
+/// credentials to the HealthData chain
+pub struct CrossChainNotifier;
+
+impl CrossChainNotifier {
+    /// Send `call` to the pallet at `pallet_index` on `dest` for unpaid execution, bounded by
+    /// its benchmarked `weight` there
+    ///
+    /// Each call goes in its own message, so that every message fits the target chain's
+    /// message queue service weight.
+    fn send<C: Encode>(
+        dest: Location,
+        pallet_index: u8,
+        call: C,
+        weight: Weight,
+    ) -> sp_runtime::DispatchResult {
+        let message = Xcm(sp_std::vec![
+            UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+            Transact {
+                origin_kind: OriginKind::Xcm,
+                require_weight_at_most: weight,
+                call: (pallet_index, call).encode().into(),
+            },
+        ]);
+        send_xcm::<XcmRouter>(dest, message)
+            .map(|_| ())
+            .map_err(|_| sp_runtime::DispatchError::Other("cross-chain call not sent"))
+    }
+}
+
+impl pallet_identity_registry::OnIdentityRecovered<AccountId> for CrossChainNotifier {
+    fn on_identity_recovered(lost: &AccountId, new: &AccountId) -> sp_runtime::DispatchResult {
+        let (old, new) = (lost.clone(), new.clone());
+        ConsentManager::migrate_account(RuntimeOrigin::root(), old.clone(), new.clone())?;
+        Self::send(
+            HealthDataLocation::get(),
+            HealthRecordsPalletIndex::get(),
+            HealthRecordsCall::MigratePatient { old: old.clone(), new: new.clone() },
+            <HealthRecordsWeights as pallet_health_records::WeightInfo>::migrate_patient(),
+        )?;
+        Self::send(
+            HealthDataLocation::get(),
+            AccessControlPalletIndex::get(),
+            AccessControlCall::MigrateAccount { old: old.clone(), new: new.clone() },
+            <AccessControlWeights as pallet_access_control::WeightInfo>::migrate_account(),
+        )?;
+        Self::send(
+            HealthDataLocation::get(),
+            EncryptionPalletIndex::get(),
+            EncryptionCall::MigrateKeys { old: old.clone(), new: new.clone() },
+            <EncryptionWeights as pallet_encryption::WeightInfo>::migrate_keys(),
+        )?;
+        Self::send(
+            MarketplaceLocation::get(),
+            DataListingsPalletIndex::get(),
+            DataListingsCall::MigrateProvider { old, new },
+            <DataListingsWeights as pallet_data_listings::WeightInfo>::migrate_provider(),
+        )
+    }
+
+    fn weight() -> Weight {
+        use pallet_consent_manager::WeightInfo;
+        <Runtime as pallet_consent_manager::Config>::WeightInfo::migrate_account()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn ensure_successful() {
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2001));
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2002));
+    }
+}
+
+impl pallet_identity_registry::OnGuardianshipChanged<AccountId> for CrossChainNotifier {
+    fn on_guardianship_changed(
+        ward: &AccountId,
+        guardian: &AccountId,
+        valid_until: Option<u64>,
+    ) -> sp_runtime::DispatchResult {
+        Self::send(
+            HealthDataLocation::get(),
+            AccessControlPalletIndex::get(),
+            AccessControlCall::SetGuardianship {
+                ward: ward.clone(),
+                guardian: guardian.clone(),
+                valid_until,
+            },
+            <AccessControlWeights as pallet_access_control::WeightInfo>::set_guardianship(),
+        )
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn ensure_successful() {
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2001));
+    }
+}
+
+impl pallet_credentials::OnCredentialChanged<AccountId> for CrossChainNotifier {
+    fn on_credential_changed(
+        holder: &AccountId,
+        schema_id: &sp_core::H256,
+        valid_until: Option<u64>,
+    ) -> sp_runtime::DispatchResult {
+        if *schema_id == ClinicianCredentialSchema::get() {
+            Self::send(
+                HealthDataLocation::get(),
+                AccessControlPalletIndex::get(),
+                AccessControlCall::SetClinician { account: holder.clone(), valid_until },
+                <AccessControlWeights as pallet_access_control::WeightInfo>::set_clinician(),
+            )
+        } else if *schema_id == AuditorCredentialSchema::get() {
+            Self::send(
+                HealthDataLocation::get(),
+                AccessControlPalletIndex::get(),
+                AccessControlCall::SetAuditor {
+                    account: holder.clone(),
+                    is_auditor: valid_until.is_some(),
+                },
+                <AccessControlWeights as pallet_access_control::WeightInfo>::set_auditor(),
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn ensure_successful(schema_id: &sp_core::H256) {
+        ClinicianCredentialSchema::set(schema_id);
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2001));
+    }
+}
+
+impl pallet_consent_manager::OnDataErased<AccountId> for CrossChainNotifier {
+    fn on_data_erased(owner: &AccountId) -> sp_runtime::DispatchResult {
+        Self::send(
+            HealthDataLocation::get(),
+            HealthRecordsPalletIndex::get(),
+            HealthRecordsCall::ErasePatient { patient: owner.clone() },
+            <HealthRecordsWeights as pallet_health_records::WeightInfo>::erase_patient(),
+        )?;
+        Self::send(
+            HealthDataLocation::get(),
+            IpfsIntegrationPalletIndex::get(),
+            IpfsIntegrationCall::EraseOwnerContent { owner: owner.clone() },
+            <IpfsIntegrationWeights as pallet_ipfs_integration::WeightInfo>::erase_owner_content(),
+        )?;
+        Self::send(
+            HealthDataLocation::get(),
+            EncryptionPalletIndex::get(),
+            EncryptionCall::RevokeAccountKeys { account: owner.clone() },
+            <EncryptionWeights as pallet_encryption::WeightInfo>::revoke_account_keys(),
+        )?;
+        Self::send(
+            MarketplaceLocation::get(),
+            DataListingsPalletIndex::get(),
+            DataListingsCall::DelistProvider { provider: owner.clone() },
+            <DataListingsWeights as pallet_data_listings::WeightInfo>::delist_provider(),
+        )
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn ensure_successful() {
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2001));
+        ParachainSystem::open_outbound_hrmp_channel_for_benchmarks_or_tests(ParaId::from(2002));
+    }
+}
+
+parameter_types! {
+    /// HealthData chain (Para ID 2001), the only chain allowed to run consent queries
+    pub HealthDataLocation: Location = Location::new(1, [Parachain(2001)]);
+    pub const ConsentExpiryBucket: u64 = 60 * 60 * 1000; // 1 hour (ms)
+    pub const MaxConsentExpiriesPerBlock: u32 = 50;
+    pub const MaxPoliciesPerOwner: u32 = 20;
+    pub const MaxPolicyConsentsPerOwner: u32 = 1000;
+    pub const MaxConsentMigrationBatch: u32 = 100;
 }
 
 impl pallet_consent_manager::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxDataTypes = ConstU32<10>;
-    type MaxAccessLogs = ConstU32<1000>;
+    type AccessLogPageSize = ConstU32<100>;
+    type ExpiryBucketDuration = ConsentExpiryBucket;
+    type MaxExpiriesPerBlock = MaxConsentExpiriesPerBlock;
+    type MaxPoliciesPerOwner = MaxPoliciesPerOwner;
+    type MaxPolicyConsentsPerOwner = MaxPolicyConsentsPerOwner;
+    type ConsentQueryOrigin = pallet_xcm::EnsureXcm<frame_support::traits::Equals<HealthDataLocation>>;
+    type TemplateApprovalOrigin = EnsureRoot<AccountId>;
+    type Credentials = CredentialsInspect;
+    type Organizations = OrganizationsInspect;
+    type ErasureHandler = CrossChainNotifier;
+    type MigrationOrigin = EnsureRoot<AccountId>;
+    type MaxMigrationBatch = MaxConsentMigrationBatch;
+    type WardCalls = ConsentWardCalls;
+    type WeightInfo = pallet_consent_manager::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ConsentManagerBenchmarkHelper;
+}
+
+/// Calls a legal guardian may dispatch on behalf of their ward: managing the ward's consents
+/// and standing policies
+pub struct ConsentWardCalls;
+
+impl frame_support::traits::Contains<pallet_consent_manager::Call<Runtime>> for ConsentWardCalls {
+    fn contains(call: &pallet_consent_manager::Call<Runtime>) -> bool {
+        use pallet_consent_manager::Call;
+        matches!(
+            call,
+            Call::create_consent { .. }
+                | Call::revoke_consent { .. }
+                | Call::update_consent { .. }
+                | Call::archive_access_logs { .. }
+                | Call::create_consent_from_template { .. }
+                | Call::set_consent_scope { .. }
+                | Call::publish_policy { .. }
+                | Call::withdraw_policy { .. }
+                | Call::exclude_consumer { .. }
+                | Call::readmit_consumer { .. }
+        )
+    }
+}
+
+/// Checks `pallet_credentials` credentials for `pallet_consent_manager` policies
+pub struct CredentialsInspect;
+
+impl pallet_consent_manager::CredentialInspect<AccountId> for CredentialsInspect {
+    fn has_credential(holder: &AccountId, schema_id: &sp_core::H256) -> bool {
+        Credentials::has_valid_credential(holder, *schema_id)
+    }
+}
+
+/// Checks `pallet_organizations` memberships for `pallet_consent_manager` consents
+pub struct OrganizationsInspect;
+
+impl pallet_consent_manager::OrganizationInspect<AccountId> for OrganizationsInspect {
+    fn can_access_data(organization: &AccountId, member: &AccountId) -> bool {
+        Organizations::has_permission(
+            organization,
+            member,
+            pallet_organizations::MemberPermission::AccessData,
+        )
+    }
+}
+
+/// Sets up credentials and memberships for `pallet_consent_manager` benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct ConsentManagerBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_consent_manager::BenchmarkHelper<AccountId> for ConsentManagerBenchmarkHelper {
+    fn grant_credential(holder: &AccountId, schema_id: &sp_core::H256) {
+        // Self-issued by the holder, which the benchmark sets up as a verified institution
+        let credential_hash = sp_core::H256::repeat_byte(0xcc);
+        pallet_credentials::Credentials::<Runtime>::insert(
+            credential_hash,
+            pallet_credentials::Credential {
+                issuer: holder.clone(),
+                holder: holder.clone(),
+                schema_id: *schema_id,
+                issued_at: 0,
+                expires_at: Some(u64::MAX),
+                status: Default::default(),
+            },
+        );
+        pallet_credentials::HolderCredentials::<Runtime>::insert(
+            holder,
+            schema_id,
+            credential_hash,
+        );
+    }
+
+    fn add_member(organization: &AccountId, member: &AccountId) {
+        pallet_organizations::Members::<Runtime>::insert(
+            organization,
+            member,
+            pallet_organizations::Membership {
+                permissions: sp_runtime::BoundedVec::truncate_from(vec![
+                    pallet_organizations::MemberPermission::AccessData,
+                ]),
+                added_at: 0,
+            },
+        );
+    }
+}
+
+parameter_types! {
+    pub const CredentialStatusListLength: u32 = 512; // 4096 credentials per list
+    /// Schemas of the credentials mirrored to the HealthData chain's access control, set by
+    /// governance (`System::set_storage`) once registered
+    pub storage ClinicianCredentialSchema: sp_core::H256 = sp_core::H256::zero();
+    pub storage AuditorCredentialSchema: sp_core::H256 = sp_core::H256::zero();
+}
+
+impl pallet_credentials::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type SchemaOrigin = EnsureRoot<AccountId>;
+    type StatusListLength = CredentialStatusListLength;
+    type CredentialHandler = CrossChainNotifier;
+    type WeightInfo = pallet_credentials::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_organizations::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxAdmins = ConstU32<8>;
+    type WeightInfo = pallet_organizations::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -398,6 +847,7 @@ impl pallet_authentication::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type SessionDuration = SessionDuration;
+    type WeightInfo = pallet_authentication::weights::SubstrateWeight<Runtime>;
 }
 
 // Use trait to avoid cyclic dependency
@@ -425,16 +875,31 @@ construct_runtime!(
         ParachainInfo: parachain_info = 31,
 
         // XCM
-        XcmpQueue: cumulus_pallet_xcm = 40,
+        CumulusXcm: cumulus_pallet_xcm = 40,
         PolkadotXcm: pallet_xcm = 41,
+        XcmpQueue: cumulus_pallet_xcmp_queue = 42,
+        MessageQueue: pallet_message_queue = 43,
 
         // Custom pallets
         IdentityRegistry: pallet_identity_registry = 50,
         ConsentManager: pallet_consent_manager = 51,
         Authentication: pallet_authentication = 52,
+        Credentials: pallet_credentials = 53,
+        Organizations: pallet_organizations = 54,
     }
 );
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benches {
+    frame_benchmarking::define_benchmarks!(
+        [pallet_identity_registry, IdentityRegistry]
+        [pallet_consent_manager, ConsentManager]
+        [pallet_authentication, Authentication]
+        [pallet_credentials, Credentials]
+        [pallet_organizations, Organizations]
+    );
+}
+
 /// The type used to represent the kinds of proxying allowed.
 pub type RuntimeGenesisConfig = frame_support::genesis_config_prelude::RuntimeGenesisConfig;
 
@@ -532,4 +997,66 @@ impl_runtime_apis! {
             ParachainSystem::collect_collation_info(header)
         }
     }
+
+    impl pallet_identity_registry_runtime_api::DidApi<Block> for Runtime {
+        fn resolve_did(did: Vec<u8>) -> Option<pallet_identity_registry::ResolvedDid> {
+            IdentityRegistry::resolve_did(&did)
+        }
+    }
+
+    impl pallet_credentials_runtime_api::CredentialsApi<Block, AccountId> for Runtime {
+        fn verify_credential(
+            credential_hash: sp_core::H256,
+            issuer: AccountId,
+            holder: AccountId,
+            schema_id: sp_core::H256,
+        ) -> pallet_credentials::CredentialStatus {
+            Credentials::verify_credential(credential_hash, &issuer, &holder, schema_id)
+        }
+
+        fn status_list(issuer: AccountId, list_id: u32) -> Vec<u8> {
+            Credentials::status_lists(issuer, list_id).into_inner()
+        }
+    }
+
+    impl pallet_authentication_runtime_api::AuthenticationApi<Block, AccountId> for Runtime {
+        fn verify_api_request(
+            request: pallet_authentication::ApiRequest,
+            signature: sp_core::sr25519::Signature,
+        ) -> Result<AccountId, pallet_authentication::ApiKeyError> {
+            Authentication::verify_api_request(&request, &signature).map(|key| key.account)
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn benchmark_metadata(extra: bool) -> (
+            Vec<frame_benchmarking::BenchmarkList>,
+            Vec<frame_support::traits::StorageInfo>,
+        ) {
+            use frame_benchmarking::{Benchmarking, BenchmarkList};
+            use frame_support::traits::StorageInfoTrait;
+
+            let mut list = Vec::<BenchmarkList>::new();
+            list_benchmarks!(list, extra);
+
+            let storage_info = AllPalletsWithSystem::storage_info();
+            (list, storage_info)
+        }
+
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+            use frame_benchmarking::{BenchmarkBatch, Benchmarking};
+            use frame_support::traits::WhitelistedStorageKeys;
+
+            let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+            add_benchmarks!(params, batches);
+
+            Ok(batches)
+        }
+    }
 }
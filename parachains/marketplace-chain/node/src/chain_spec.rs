@@ -1,7 +1,9 @@
 //! Chain specification for Marketplace Chain
 
 use cumulus_primitives_core::ParaId;
-use marketplace_runtime::{AccountId, AuraId, Signature, EXISTENTIAL_DEPOSIT};
+use marketplace_runtime::{
+    AccountId, AuraId, Balance, MarketplaceTreasuryAccount, Signature, EXISTENTIAL_DEPOSIT,
+};
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
 use sc_service::ChainType;
 use serde::{Deserialize, Serialize};
@@ -127,9 +129,14 @@ fn testnet_genesis(
     root: AccountId,
     id: ParaId,
 ) -> serde_json::Value {
+    let mut balances: Vec<(AccountId, Balance)> =
+        endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect();
+    // Platform fees are paid into the treasury, so it must exist before the first sale
+    balances.push((MarketplaceTreasuryAccount::get(), EXISTENTIAL_DEPOSIT));
+
     serde_json::json!({
         "balances": {
-            "balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
+            "balances": balances,
         },
         "parachainInfo": {
             "parachainId": id,
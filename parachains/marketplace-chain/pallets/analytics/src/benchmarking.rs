@@ -0,0 +1,50 @@
+//! Benchmarking setup for pallet-analytics
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Analytics;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_core::H256;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn record_event() {
+        let caller: T::AccountId = whitelisted_caller();
+        let listing_id = H256::repeat_byte(1);
+
+        // A view updates the listing, the user and the global counters
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            AnalyticsEventType::ListingView,
+            Some(listing_id),
+            Some(H256::repeat_byte(2)),
+        );
+
+        assert_eq!(TotalViews::<T>::get(), 1);
+    }
+
+    #[benchmark]
+    fn record_purchase() {
+        let caller: T::AccountId = whitelisted_caller();
+        let buyer: T::AccountId = account("buyer", 0, 0);
+        let listing_id = H256::repeat_byte(1);
+        Pallet::<T>::record_event(
+            RawOrigin::Signed(buyer.clone()).into(),
+            AnalyticsEventType::ListingView,
+            Some(listing_id),
+            None,
+        )
+        .expect("event recording must succeed");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), listing_id, buyer, 1_000);
+
+        assert_eq!(TotalPurchases::<T>::get(), 1);
+    }
+}
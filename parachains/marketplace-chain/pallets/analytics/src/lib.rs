@@ -23,12 +23,19 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -129,6 +136,9 @@ pub mod pallet {
 
         /// Time provider for timestamps
         type TimeProvider: Time;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     /// Storage for analytics events
@@ -200,7 +210,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Record an analytics event
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::record_event())]
         pub fn record_event(
             origin: OriginFor<T>,
             event_type: AnalyticsEventType,
@@ -240,7 +250,7 @@ pub mod pallet {
 
         /// Record a purchase for analytics (called by marketplace pallet)
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::record_purchase())]
         pub fn record_purchase(
             origin: OriginFor<T>,
             listing_id: H256,
@@ -0,0 +1,65 @@
+//! Estimated weights for `pallet_analytics`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/marketplace-node benchmark pallet \
+//!     --chain dev --pallet pallet_analytics --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/analytics/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_analytics`.
+pub trait WeightInfo {
+    fn record_event() -> Weight;
+    fn record_purchase() -> Weight;
+}
+
+/// Estimated weights for `pallet_analytics`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Timestamp::Now`, `Analytics::EventCount`, `Analytics::ListingStatistics`,
+    //     `Analytics::UserActivities`, `Analytics::TotalViews`
+    // Writes: `Analytics::EventCount`, `Analytics::ListingStatistics`, `Analytics::UserActivities`,
+    //     `Analytics::TotalViews`, `Analytics::Events`
+    fn record_event() -> Weight {
+        Weight::from_parts(31_000_000, 3_634)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `Analytics::DailyStatistics`, `Analytics::ListingStatistics`,
+    //     `Analytics::UserActivities`, `Analytics::TotalPurchases`, `Analytics::TotalRevenue`
+    // Writes: `Analytics::DailyStatistics`, `Analytics::ListingStatistics`,
+    //     `Analytics::UserActivities`, `Analytics::TotalPurchases`, `Analytics::TotalRevenue`
+    fn record_purchase() -> Weight {
+        Weight::from_parts(29_000_000, 3_634)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn record_event() -> Weight {
+        Weight::from_parts(31_000_000, 3_634)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn record_purchase() -> Weight {
+        Weight::from_parts(29_000_000, 3_634)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+}
@@ -0,0 +1,194 @@
+//! Benchmarking setup for pallet-data-listings
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as DataListings;
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Fill the provider's listings up to one below `MaxListingsPerProvider` and the category
+/// index up to one below its bound
+fn fill_indices<T: Config>(provider: &T::AccountId) {
+    let max_listings = T::MaxListingsPerProvider::get().saturating_sub(1) as u64;
+    let provider_listings: Vec<H256> = (0..max_listings).map(H256::from_low_u64_be).collect();
+    ProviderListings::<T>::insert(provider, BoundedVec::truncate_from(provider_listings));
+
+    let category_listings: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+    CategoryListings::<T>::insert(
+        DataCategory::Genomic,
+        BoundedVec::truncate_from(category_listings),
+    );
+}
+
+/// Create a listing from the whitelisted provider
+fn create<T: Config>() -> (T::AccountId, H256) {
+    let provider: T::AccountId = whitelisted_caller();
+
+    Pallet::<T>::create_listing(
+        RawOrigin::Signed(provider.clone()).into(),
+        BoundedVec::truncate_from(vec![b't'; 128]),
+        BoundedVec::truncate_from(vec![b'd'; 512]),
+        DataCategory::Genomic,
+        PricingModel::FixedPrice { amount: 1_000 },
+        100,
+        None,
+    )
+    .expect("listing creation must succeed");
+
+    let listing_id = *ProviderListings::<T>::get(&provider).last().expect("listing was just created");
+    (provider, listing_id)
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_listing() {
+        let provider: T::AccountId = whitelisted_caller();
+        fill_indices::<T>(&provider);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(provider.clone()),
+            BoundedVec::truncate_from(vec![b't'; 128]),
+            BoundedVec::truncate_from(vec![b'd'; 512]),
+            DataCategory::Genomic,
+            PricingModel::Subscription { amount: 1_000, period_days: 30 },
+            100,
+            Some(u64::MAX),
+        );
+
+        assert_eq!(
+            ProviderListings::<T>::get(&provider).len(),
+            T::MaxListingsPerProvider::get() as usize
+        );
+    }
+
+    #[benchmark]
+    fn update_listing() {
+        let (provider, listing_id) = create::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(provider),
+            listing_id,
+            Some(BoundedVec::truncate_from(vec![b'u'; 128])),
+            Some(BoundedVec::truncate_from(vec![b'u'; 512])),
+            Some(PricingModel::Subscription { amount: 2_000, period_days: 30 }),
+        );
+
+        assert_eq!(Listings::<T>::get(listing_id).unwrap().title.len(), 128);
+    }
+
+    #[benchmark]
+    fn set_listing_status() {
+        let (provider, listing_id) = create::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(provider), listing_id, ListingStatus::Paused);
+
+        assert_eq!(Listings::<T>::get(listing_id).unwrap().status, ListingStatus::Paused);
+    }
+
+    #[benchmark]
+    fn update_quality_score() {
+        let (_, listing_id) = create::<T>();
+        let caller: T::AccountId = account("caller", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), listing_id, 100);
+
+        assert_eq!(Listings::<T>::get(listing_id).unwrap().quality_score, 100);
+    }
+
+    #[benchmark]
+    fn remove_listing() {
+        fill_indices::<T>(&whitelisted_caller());
+        let (provider, listing_id) = create::<T>();
+
+        // Both indices are full and scanned to remove the listing
+        #[extrinsic_call]
+        _(RawOrigin::Signed(provider), listing_id);
+
+        assert!(!Listings::<T>::contains_key(listing_id));
+    }
+
+    #[benchmark]
+    fn migrate_provider() -> Result<(), BenchmarkError> {
+        let origin =
+            T::MigrationOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let (_, listing_id) = create::<T>();
+        let listing = Listings::<T>::get(listing_id).expect("listing was just created");
+        let old: T::AccountId = account("old", 0, 0);
+        let new: T::AccountId = account("new", 0, 0);
+
+        // A full listing list
+        let listings: Vec<H256> =
+            (0..T::MaxListingsPerProvider::get() as u64).map(H256::from_low_u64_be).collect();
+        for id in listings.iter() {
+            Listings::<T>::insert(
+                id,
+                DataListing { listing_id: *id, provider: old.clone(), ..listing.clone() },
+            );
+        }
+        ProviderListings::<T>::insert(&old, BoundedVec::truncate_from(listings));
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, old.clone(), new.clone());
+
+        assert_eq!(
+            ProviderListings::<T>::get(&new).len() as u32,
+            T::MaxListingsPerProvider::get()
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn delist_provider() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ErasureOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let (provider, listing_id) = create::<T>();
+        let listing = Listings::<T>::get(listing_id).expect("listing was just created");
+        let categories = [
+            DataCategory::Genomic,
+            DataCategory::Clinical,
+            DataCategory::Laboratory,
+            DataCategory::Imaging,
+            DataCategory::Wearable,
+            DataCategory::Pharmaceutical,
+            DataCategory::Research,
+            DataCategory::Population,
+            DataCategory::Other,
+        ];
+
+        // A full listing list spread over every category, each with a full index
+        let listings: Vec<H256> =
+            (0..T::MaxListingsPerProvider::get() as u64).map(H256::from_low_u64_be).collect();
+        for (i, id) in listings.iter().enumerate() {
+            let category = categories[i % categories.len()].clone();
+            Listings::<T>::insert(id, DataListing { listing_id: *id, category, ..listing.clone() });
+        }
+        ProviderListings::<T>::insert(&provider, BoundedVec::truncate_from(listings.clone()));
+        let category_listings: Vec<H256> = (0..1000u64).map(H256::from_low_u64_be).collect();
+        for category in categories.iter() {
+            CategoryListings::<T>::insert(
+                category,
+                BoundedVec::truncate_from(category_listings.clone()),
+            );
+        }
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, provider.clone());
+
+        assert!(ProviderListings::<T>::get(&provider).is_empty());
+        assert!(!Listings::<T>::contains_key(listings[0]));
+
+        Ok(())
+    }
+}
@@ -9,6 +9,17 @@
 //! - Listing lifecycle management (active, paused, expired)
 //! - Data categorization and metadata
 //!
+//! ## Account migration
+//!
+//! When a provider's identity is recovered to a new account on the IdentityConsent chain, that
+//! chain calls `migrate_provider` over XCM (`MigrationOrigin`) to move the provider's listings
+//! to the new account.
+//!
+//! ## Erasure
+//!
+//! When a provider exercises the right to erasure on the IdentityConsent chain, that chain calls
+//! `delist_provider` over XCM (`ErasureOrigin`), which removes all of the provider's listings.
+//!
 //! ## Architecture Reference
 //! See parachain.md Section: "Marketplace Chain - Data Listings"
 
@@ -22,12 +33,19 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -126,6 +144,15 @@ pub mod pallet {
         /// Maximum listings per provider
         #[pallet::constant]
         type MaxListingsPerProvider: Get<u32>;
+
+        /// Origin moving a provider's listings to a new account (the IdentityConsent chain)
+        type MigrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin removing a provider's listings on erasure (the IdentityConsent chain)
+        type ErasureOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     /// Storage for data listings by listing_id
@@ -183,6 +210,17 @@ pub mod pallet {
         },
         /// Listing removed [listing_id]
         ListingRemoved { listing_id: H256 },
+        /// Listings moved to a new account [old, new, listings]
+        ProviderMigrated {
+            old: T::AccountId,
+            new: T::AccountId,
+            listings: u32,
+        },
+        /// All listings of a provider removed on erasure [provider, listings]
+        ProviderDelisted {
+            provider: T::AccountId,
+            listings: u32,
+        },
     }
 
     #[pallet::error]
@@ -201,13 +239,15 @@ pub mod pallet {
         ListingExpired,
         /// Invalid pricing
         InvalidPricing,
+        /// Cannot migrate an account to itself
+        InvalidMigration,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a new data listing
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::create_listing())]
         pub fn create_listing(
             origin: OriginFor<T>,
             title: BoundedVec<u8, ConstU32<128>>,
@@ -276,7 +316,7 @@ pub mod pallet {
 
         /// Update listing details
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_listing())]
         pub fn update_listing(
             origin: OriginFor<T>,
             listing_id: H256,
@@ -319,7 +359,7 @@ pub mod pallet {
 
         /// Change listing status
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::set_listing_status())]
         pub fn set_listing_status(
             origin: OriginFor<T>,
             listing_id: H256,
@@ -343,7 +383,7 @@ pub mod pallet {
 
         /// Update quality score (can be called by authorized oracles/reviewers)
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_quality_score())]
         pub fn update_quality_score(
             origin: OriginFor<T>,
             listing_id: H256,
@@ -367,7 +407,7 @@ pub mod pallet {
 
         /// Remove a listing
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::remove_listing())]
         pub fn remove_listing(origin: OriginFor<T>, listing_id: H256) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -391,6 +431,79 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Move a provider's listings to a new account after an identity recovery
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::migrate_provider())]
+        pub fn migrate_provider(
+            origin: OriginFor<T>,
+            old: T::AccountId,
+            new: T::AccountId,
+        ) -> DispatchResult {
+            T::MigrationOrigin::ensure_origin(origin)?;
+
+            ensure!(old != new, Error::<T>::InvalidMigration);
+
+            let listings = ProviderListings::<T>::take(&old);
+            ProviderListings::<T>::try_mutate(&new, |provider_listings| -> DispatchResult {
+                for listing_id in listings.iter() {
+                    provider_listings
+                        .try_push(*listing_id)
+                        .map_err(|_| Error::<T>::MaxListingsReached)?;
+                }
+                Ok(())
+            })?;
+
+            for listing_id in listings.iter() {
+                Listings::<T>::mutate(listing_id, |maybe_listing| {
+                    if let Some(listing) = maybe_listing {
+                        listing.provider = new.clone();
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::ProviderMigrated {
+                old,
+                new,
+                listings: listings.len() as u32,
+            });
+
+            Ok(())
+        }
+
+        /// Remove all listings of a provider after a right-to-erasure request
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::delist_provider())]
+        pub fn delist_provider(origin: OriginFor<T>, provider: T::AccountId) -> DispatchResult {
+            T::ErasureOrigin::ensure_origin(origin)?;
+
+            let listings = ProviderListings::<T>::take(&provider);
+
+            // Remove listings, noting the categories they were indexed under
+            let mut categories: Vec<DataCategory> = Vec::new();
+            for listing_id in listings.iter() {
+                if let Some(listing) = Listings::<T>::take(listing_id) {
+                    if !categories.contains(&listing.category) {
+                        categories.push(listing.category);
+                    }
+                    Self::deposit_event(Event::ListingRemoved { listing_id: *listing_id });
+                }
+            }
+
+            // Remove from category listings
+            for category in categories.iter() {
+                CategoryListings::<T>::mutate(category, |category_listings| {
+                    category_listings.retain(|id| !listings.contains(id));
+                });
+            }
+
+            Self::deposit_event(Event::ProviderDelisted {
+                provider,
+                listings: listings.len() as u32,
+            });
+
+            Ok(())
+        }
     }
 
     // Helper functions
@@ -0,0 +1,142 @@
+//! Estimated weights for `pallet_data_listings`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/marketplace-node benchmark pallet \
+//!     --chain dev --pallet pallet_data_listings --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/data-listings/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_data_listings`.
+pub trait WeightInfo {
+    fn create_listing() -> Weight;
+    fn update_listing() -> Weight;
+    fn set_listing_status() -> Weight;
+    fn update_quality_score() -> Weight;
+    fn remove_listing() -> Weight;
+    fn migrate_provider() -> Weight;
+    fn delist_provider() -> Weight;
+}
+
+/// Estimated weights for `pallet_data_listings`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Timestamp::Now`, `DataListings::ProviderListings`, `DataListings::ListingCount`,
+    //     `DataListings::CategoryListings`
+    // Writes: `DataListings::ProviderListings`, `DataListings::ListingCount`,
+    //     `DataListings::CategoryListings`, `DataListings::Listings`
+    fn create_listing() -> Weight {
+        Weight::from_parts(52_000_000, 65_529)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    // Reads: `DataListings::Listings`, `Timestamp::Now`
+    // Writes: `DataListings::Listings`
+    fn update_listing() -> Weight {
+        Weight::from_parts(21_000_000, 4_269)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `DataListings::Listings`, `Timestamp::Now`
+    // Writes: `DataListings::Listings`
+    fn set_listing_status() -> Weight {
+        Weight::from_parts(18_000_000, 4_269)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `DataListings::Listings`, `Timestamp::Now`
+    // Writes: `DataListings::Listings`
+    fn update_quality_score() -> Weight {
+        Weight::from_parts(18_000_000, 4_269)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `DataListings::Listings`, `DataListings::ProviderListings`,
+    //     `DataListings::CategoryListings`
+    // Writes: `DataListings::Listings`, `DataListings::ProviderListings`,
+    //     `DataListings::CategoryListings`
+    fn remove_listing() -> Weight {
+        Weight::from_parts(61_000_000, 65_529)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    // Reads: `DataListings::ProviderListings` x2, `DataListings::Listings` x100
+    // Writes: `DataListings::ProviderListings` x2, `DataListings::Listings` x100
+    fn migrate_provider() -> Weight {
+        Weight::from_parts(402_000_000, 427_090)
+            .saturating_add(T::DbWeight::get().reads(102_u64))
+            .saturating_add(T::DbWeight::get().writes(102_u64))
+    }
+
+    // Reads: `DataListings::ProviderListings`, `DataListings::Listings` x100,
+    //     `DataListings::CategoryListings` x9
+    // Writes: `DataListings::ProviderListings`, `DataListings::Listings` x100,
+    //     `DataListings::CategoryListings` x9
+    fn delist_provider() -> Weight {
+        Weight::from_parts(1_284_000_000, 605_860)
+            .saturating_add(T::DbWeight::get().reads(110_u64))
+            .saturating_add(T::DbWeight::get().writes(110_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn create_listing() -> Weight {
+        Weight::from_parts(52_000_000, 65_529)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn update_listing() -> Weight {
+        Weight::from_parts(21_000_000, 4_269)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn set_listing_status() -> Weight {
+        Weight::from_parts(18_000_000, 4_269)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn update_quality_score() -> Weight {
+        Weight::from_parts(18_000_000, 4_269)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn remove_listing() -> Weight {
+        Weight::from_parts(61_000_000, 65_529)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+
+    fn migrate_provider() -> Weight {
+        Weight::from_parts(402_000_000, 427_090)
+            .saturating_add(RocksDbWeight::get().reads(102_u64))
+            .saturating_add(RocksDbWeight::get().writes(102_u64))
+    }
+
+    fn delist_provider() -> Weight {
+        Weight::from_parts(1_284_000_000, 605_860)
+            .saturating_add(RocksDbWeight::get().reads(110_u64))
+            .saturating_add(RocksDbWeight::get().writes(110_u64))
+    }
+}
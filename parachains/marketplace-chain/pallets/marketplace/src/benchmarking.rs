@@ -0,0 +1,248 @@
+//! Benchmarking setup for pallet-marketplace
+
+use super::*;
+
+use crate::Pallet as Marketplace;
+use frame_benchmarking::v2::*;
+use frame_support::{
+    traits::{Currency, EnsureOrigin},
+    BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_runtime::{traits::Saturating, Percent};
+use sp_std::prelude::*;
+
+/// Price used for purchases and subscriptions
+fn price<T: Config>() -> BalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+/// Give an account enough free balance to pay many times over
+fn fund<T: Config>(who: &T::AccountId) {
+    T::Currency::make_free_balance_be(
+        who,
+        T::Currency::minimum_balance().saturating_mul(1_000_000u32.into()),
+    );
+}
+
+/// Fund the whitelisted buyer, a provider and the treasury account
+fn setup_accounts<T: Config>() -> (T::AccountId, T::AccountId) {
+    let buyer: T::AccountId = whitelisted_caller();
+    let provider: T::AccountId = account("provider", 0, 0);
+    fund::<T>(&buyer);
+    fund::<T>(&provider);
+    fund::<T>(&T::TreasuryAccount::get());
+    (buyer, provider)
+}
+
+/// Create a paid purchase from the whitelisted buyer
+fn paid_purchase<T: Config>() -> (T::AccountId, T::AccountId, H256) {
+    let (buyer, provider) = setup_accounts::<T>();
+
+    Pallet::<T>::create_purchase(
+        RawOrigin::Signed(buyer.clone()).into(),
+        H256::repeat_byte(1),
+        provider.clone(),
+        price::<T>(),
+    )
+    .expect("purchase creation must succeed");
+
+    let purchase_id = *BuyerPurchases::<T>::get(&buyer).last().expect("purchase was just created");
+    (buyer, provider, purchase_id)
+}
+
+/// Create a 30 day subscription from the whitelisted subscriber
+fn subscription<T: Config>() -> (T::AccountId, H256) {
+    let (subscriber, provider) = setup_accounts::<T>();
+
+    Pallet::<T>::create_subscription(
+        RawOrigin::Signed(subscriber.clone()).into(),
+        H256::repeat_byte(1),
+        provider,
+        price::<T>(),
+        30,
+    )
+    .expect("subscription creation must succeed");
+
+    let subscription_id = *SubscriberSubscriptions::<T>::get(&subscriber)
+        .last()
+        .expect("subscription was just created");
+    (subscriber, subscription_id)
+}
+
+/// Create a purchase that is fulfilled but not yet settled
+///
+/// Without a dispute period purchases settle on fulfillment, so there is nothing to measure.
+fn fulfilled_purchase<T: Config>() -> Result<(T::AccountId, T::AccountId, H256), BenchmarkError> {
+    if T::DisputePeriod::get() == 0 {
+        return Err(BenchmarkError::Weightless);
+    }
+
+    T::BenchmarkHelper::set_time(MILLISECS_PER_DAY);
+    let (buyer, provider, purchase_id) = paid_purchase::<T>();
+    Pallet::<T>::fulfill_purchase(RawOrigin::Signed(provider.clone()).into(), purchase_id)
+        .expect("fulfillment must succeed");
+
+    Ok((buyer, provider, purchase_id))
+}
+
+/// Fulfilled purchase with a pending refund request
+fn refund_requested<T: Config>() -> Result<(T::AccountId, T::AccountId, H256), BenchmarkError> {
+    let (buyer, provider, purchase_id) = fulfilled_purchase::<T>()?;
+    Pallet::<T>::request_refund(
+        RawOrigin::Signed(buyer.clone()).into(),
+        purchase_id,
+        H256::repeat_byte(2),
+    )
+    .expect("refund request must succeed");
+
+    Ok((buyer, provider, purchase_id))
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create_purchase() {
+        let (buyer, provider) = setup_accounts::<T>();
+
+        // Fill both indices up to one below their bound
+        let existing: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+        BuyerPurchases::<T>::insert(&buyer, BoundedVec::truncate_from(existing.clone()));
+        ProviderSales::<T>::insert(&provider, BoundedVec::truncate_from(existing));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(buyer.clone()), H256::repeat_byte(1), provider, price::<T>());
+
+        assert_eq!(BuyerPurchases::<T>::get(&buyer).len(), 1000);
+    }
+
+    #[benchmark]
+    fn fulfill_purchase() {
+        let (_, provider, purchase_id) = paid_purchase::<T>();
+
+        // Releases the escrow as well when there is no dispute period
+        #[extrinsic_call]
+        _(RawOrigin::Signed(provider), purchase_id);
+
+        assert_eq!(Purchases::<T>::get(purchase_id).unwrap().status, PurchaseStatus::Fulfilled);
+    }
+
+    #[benchmark]
+    fn cancel_purchase() {
+        let (buyer, _, purchase_id) = paid_purchase::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(buyer), purchase_id);
+
+        assert_eq!(Purchases::<T>::get(purchase_id).unwrap().status, PurchaseStatus::Cancelled);
+    }
+
+    #[benchmark]
+    fn create_subscription() {
+        let (subscriber, provider) = setup_accounts::<T>();
+
+        // Fill the index up to one below its bound
+        let existing: Vec<H256> = (0..99u64).map(H256::from_low_u64_be).collect();
+        SubscriberSubscriptions::<T>::insert(&subscriber, BoundedVec::truncate_from(existing));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(subscriber.clone()), H256::repeat_byte(1), provider, price::<T>(), 30);
+
+        assert_eq!(SubscriberSubscriptions::<T>::get(&subscriber).len(), 100);
+    }
+
+    #[benchmark]
+    fn cancel_subscription() {
+        let (subscriber, subscription_id) = subscription::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(subscriber), subscription_id);
+
+        assert!(!Subscriptions::<T>::get(subscription_id).unwrap().active);
+    }
+
+    #[benchmark]
+    fn renew_subscription() {
+        let (_, subscription_id) = subscription::<T>();
+        let due_at = Subscriptions::<T>::get(subscription_id).unwrap().next_payment_at;
+
+        // A successful payment, which also schedules the next one
+        #[block]
+        {
+            Pallet::<T>::renew_subscription(subscription_id, due_at);
+        }
+
+        assert!(Subscriptions::<T>::get(subscription_id).unwrap().expires_at > due_at);
+    }
+
+    #[benchmark]
+    fn request_refund() -> Result<(), BenchmarkError> {
+        let (buyer, _, purchase_id) = fulfilled_purchase::<T>()?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(buyer), purchase_id, H256::repeat_byte(2));
+
+        assert!(RefundRequests::<T>::contains_key(purchase_id));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn approve_refund() -> Result<(), BenchmarkError> {
+        let (_, provider, purchase_id) = refund_requested::<T>()?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(provider), purchase_id);
+
+        assert_eq!(Purchases::<T>::get(purchase_id).unwrap().status, PurchaseStatus::Refunded);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn open_dispute() -> Result<(), BenchmarkError> {
+        let (buyer, _, purchase_id) = refund_requested::<T>()?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(buyer), purchase_id, H256::repeat_byte(3));
+
+        assert!(Disputes::<T>::contains_key(purchase_id));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn resolve_dispute() -> Result<(), BenchmarkError> {
+        let (buyer, _, purchase_id) = refund_requested::<T>()?;
+        Pallet::<T>::open_dispute(
+            RawOrigin::Signed(buyer).into(),
+            purchase_id,
+            H256::repeat_byte(3),
+        )
+        .expect("dispute must open");
+        let origin =
+            T::ArbiterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        // A split pays out to the buyer, the provider and the treasury
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, purchase_id, Percent::from_percent(50));
+
+        assert!(Purchases::<T>::get(purchase_id).unwrap().settled);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn claim_payment() -> Result<(), BenchmarkError> {
+        let (_, _, purchase_id) = fulfilled_purchase::<T>()?;
+        T::BenchmarkHelper::set_time(MILLISECS_PER_DAY.saturating_add(T::DisputePeriod::get()));
+        let caller: T::AccountId = account("caller", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), purchase_id);
+
+        assert!(Purchases::<T>::get(purchase_id).unwrap().settled);
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(Marketplace, crate::mock::new_bench_ext(), crate::mock::Test);
+}
@@ -5,11 +5,31 @@
 //! The Marketplace pallet handles purchase transactions and payment processing.
 //! It provides functionality for:
 //! - Purchase request and fulfillment
-//! - Payment escrow and settlement
+//! - Payment escrow and settlement (buyer funds are reserved until fulfillment)
 //! - Revenue distribution (provider, platform fee)
-//! - Subscription management
-//! - Refund processing
+//! - Subscription management (automatic renewal and expiry)
+//! - Refund processing and dispute resolution
 //!
+//! ## Purchase lifecycle
+//!
+//! 1. `create_purchase` reserves the amount from the buyer (`Paid`); the buyer may
+//!    `cancel_purchase` until the provider fulfills it.
+//! 2. `fulfill_purchase` grants access (`Fulfilled`) and starts the `DisputePeriod`. The escrow
+//!    is released with `claim_payment` once the period is over (immediately if it is zero).
+//! 3. During the period the buyer may `request_refund`. The provider either `approve_refund`s
+//!    (`Refunded`) or either party escalates with `open_dispute` (`Disputed`), after which the
+//!    `ArbiterOrigin` splits the escrow with `resolve_dispute`.
+//!
+//! Every settled outcome is reported to `pallet-reputation`.
+//!
+//! ## Subscription renewal
+//!
+//! Each active subscription is scheduled in `RenewalSchedule` under the day its next payment
+//! is due, ordered by the exact due time within the day. `on_idle` walks the schedule day by
+//! day (at most `MaxRenewalsPerBlock` entries per block) and charges the subscriber for the
+//! next period, stopping at the first payment that is not due yet. A subscription whose
+//! payment keeps failing is retried a day later and deactivated once `SubscriptionGracePeriod`
+//! has passed since it expired.
 //! ## Architecture Reference
 //! See parachain.md Section: "Marketplace Chain - Marketplace"
 
@@ -23,14 +43,38 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{pallet_prelude::*, traits::Time};
+    use frame_support::{
+        pallet_prelude::*,
+        storage::with_storage_layer,
+        traits::{BalanceStatus, Currency, ReservableCurrency, Time},
+    };
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
+    use sp_runtime::{traits::Zero, PerThing, Percent};
+    use pallet_reputation::TradeOutcome;
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// Milliseconds per day (timestamps come from `TimeProvider` in milliseconds)
+    pub const MILLISECS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Purchase status
@@ -38,7 +82,7 @@ pub mod pallet {
     pub enum PurchaseStatus {
         /// Purchase pending payment
         Pending,
-        /// Payment completed, awaiting fulfillment
+        /// Payment escrowed (reserved from the buyer), awaiting fulfillment
         Paid,
         /// Purchase fulfilled and access granted
         Fulfilled,
@@ -63,11 +107,11 @@ pub mod pallet {
         /// Provider account
         pub provider: T::AccountId,
         /// Purchase amount
-        pub amount: u128,
+        pub amount: BalanceOf<T>,
         /// Platform fee
-        pub platform_fee: u128,
+        pub platform_fee: BalanceOf<T>,
         /// Provider receives
-        pub provider_amount: u128,
+        pub provider_amount: BalanceOf<T>,
         /// Purchase status
         pub status: PurchaseStatus,
         /// Purchase timestamp
@@ -76,6 +120,22 @@ pub mod pallet {
         pub fulfilled_at: Option<u64>,
         /// Access expires at (for subscriptions)
         pub expires_at: Option<u64>,
+        /// Whether the escrowed funds have been paid out or refunded
+        pub settled: bool,
+    }
+
+    /// Purchase dispute
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Dispute<T: Config> {
+        /// Purchase ID
+        pub purchase_id: H256,
+        /// Account that opened the dispute (buyer or provider)
+        pub opened_by: T::AccountId,
+        /// Hash of the off-chain evidence
+        pub evidence_hash: H256,
+        /// Opened timestamp
+        pub opened_at: u64,
     }
 
     /// Subscription
@@ -91,7 +151,7 @@ pub mod pallet {
         /// Provider account
         pub provider: T::AccountId,
         /// Subscription amount per period
-        pub amount: u128,
+        pub amount: BalanceOf<T>,
         /// Period in days
         pub period_days: u32,
         /// Started timestamp
@@ -104,17 +164,56 @@ pub mod pallet {
         pub active: bool,
     }
 
+    /// Runtime-specific setup needed to benchmark escrow settlement
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper {
+        /// Set the time reported by `TimeProvider`, in milliseconds
+        fn set_time(now: u64);
+    }
+
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + pallet_reputation::Config {
         /// The overarching event type
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         /// Time provider for timestamps
         type TimeProvider: Time;
 
+        /// Currency used for purchase payments and escrow
+        type Currency: ReservableCurrency<Self::AccountId>;
+
         /// Platform fee percentage (e.g., 5 = 5%)
         #[pallet::constant]
         type PlatformFeePercent: Get<u8>;
+
+        /// Account receiving platform fees
+        ///
+        /// Must exist (hold at least the existential deposit), as fees below the existential
+        /// deposit cannot create it.
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
+
+        /// Origin allowed to resolve disputes (e.g. council or root)
+        type ArbiterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Time after fulfillment during which the buyer can request a refund
+        #[pallet::constant]
+        type DisputePeriod: Get<u64>;
+
+        /// Time after expiry during which a failed subscription payment is retried
+        #[pallet::constant]
+        type SubscriptionGracePeriod: Get<u64>;
+
+        /// Maximum subscription renewals processed per block
+        #[pallet::constant]
+        type MaxRenewalsPerBlock: Get<u32>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+
+        /// Benchmark setup for time-dependent settlement
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper;
     }
 
     /// Storage for purchases by purchase_id
@@ -160,6 +259,30 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Refund requests by purchase_id (purchase_id -> reason hash)
+    #[pallet::storage]
+    #[pallet::getter(fn refund_requests)]
+    pub type RefundRequests<T: Config> = StorageMap<_, Blake2_128Concat, H256, H256>;
+
+    /// Open disputes by purchase_id
+    #[pallet::storage]
+    #[pallet::getter(fn disputes)]
+    pub type Disputes<T: Config> = StorageMap<_, Blake2_128Concat, H256, Dispute<T>>;
+
+    /// Subscriptions due for payment, by day (day -> (due time, subscription_id))
+    ///
+    /// The due time is stored big-endian under the identity hasher, so that each day's entries
+    /// are iterated in the order they fall due.
+    #[pallet::storage]
+    #[pallet::getter(fn renewal_schedule)]
+    pub type RenewalSchedule<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, u64, Identity, ([u8; 8], H256), (), OptionQuery>;
+
+    /// Next day of `RenewalSchedule` to process
+    #[pallet::storage]
+    #[pallet::getter(fn next_renewal_day)]
+    pub type NextRenewalDay<T: Config> = StorageValue<_, u64, OptionQuery>;
+
     /// Purchase counter
     #[pallet::storage]
     #[pallet::getter(fn purchase_count)]
@@ -170,10 +293,10 @@ pub mod pallet {
     #[pallet::getter(fn subscription_count)]
     pub type SubscriptionCount<T: Config> = StorageValue<_, u64, ValueQuery>;
 
-    /// Platform revenue collected
+    /// Platform revenue collected (transferred to `TreasuryAccount`)
     #[pallet::storage]
     #[pallet::getter(fn platform_revenue)]
-    pub type PlatformRevenue<T: Config> = StorageValue<_, u128, ValueQuery>;
+    pub type PlatformRevenue<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -183,26 +306,63 @@ pub mod pallet {
             purchase_id: H256,
             listing_id: H256,
             buyer: T::AccountId,
-            amount: u128,
+            amount: BalanceOf<T>,
         },
         /// Purchase fulfilled [purchase_id]
         PurchaseFulfilled { purchase_id: H256 },
+        /// Escrowed payment released [purchase_id, provider, provider_amount, platform_fee]
+        PaymentReleased {
+            purchase_id: H256,
+            provider: T::AccountId,
+            provider_amount: BalanceOf<T>,
+            platform_fee: BalanceOf<T>,
+        },
         /// Purchase cancelled [purchase_id]
         PurchaseCancelled { purchase_id: H256 },
+        /// Escrowed payment returned to the buyer [purchase_id, buyer, amount]
+        PaymentRefunded {
+            purchase_id: H256,
+            buyer: T::AccountId,
+            amount: BalanceOf<T>,
+        },
         /// Purchase refunded [purchase_id]
         PurchaseRefunded { purchase_id: H256 },
+        /// Refund requested [purchase_id, reason_hash]
+        RefundRequested {
+            purchase_id: H256,
+            reason_hash: H256,
+        },
+        /// Dispute opened [purchase_id, opened_by, evidence_hash]
+        DisputeOpened {
+            purchase_id: H256,
+            opened_by: T::AccountId,
+            evidence_hash: H256,
+        },
+        /// Dispute resolved [purchase_id, buyer_refund, provider_amount]
+        DisputeResolved {
+            purchase_id: H256,
+            buyer_refund: BalanceOf<T>,
+            provider_amount: BalanceOf<T>,
+        },
         /// Subscription created [subscription_id, listing_id, subscriber]
         SubscriptionCreated {
             subscription_id: H256,
             listing_id: H256,
             subscriber: T::AccountId,
         },
-        /// Subscription renewed [subscription_id]
-        SubscriptionRenewed { subscription_id: H256 },
+        /// Subscription renewed [subscription_id, expires_at]
+        SubscriptionRenewed {
+            subscription_id: H256,
+            expires_at: u64,
+        },
+        /// Subscription payment failed, will be retried [subscription_id]
+        SubscriptionPaymentFailed { subscription_id: H256 },
+        /// Subscription deactivated after the grace period [subscription_id]
+        SubscriptionExpired { subscription_id: H256 },
         /// Subscription cancelled [subscription_id]
         SubscriptionCancelled { subscription_id: H256 },
         /// Platform fee collected [amount]
-        PlatformFeeCollected { amount: u128 },
+        PlatformFeeCollected { amount: BalanceOf<T> },
     }
 
     #[pallet::error]
@@ -225,24 +385,57 @@ pub mod pallet {
         SubscriptionAlreadyExists,
         /// Subscription not active
         SubscriptionNotActive,
+        /// Escrowed funds could not be fully released
+        EscrowReleaseFailed,
+        /// Purchase cannot be cancelled in its current status
+        CannotCancel,
+        /// Purchase is not fulfilled
+        NotFulfilled,
+        /// Escrowed funds were already paid out or refunded
+        AlreadySettled,
+        /// The dispute period for this purchase is over
+        DisputePeriodOver,
+        /// The dispute period for this purchase is still running
+        DisputePeriodNotOver,
+        /// A refund was already requested for this purchase
+        RefundAlreadyRequested,
+        /// No refund was requested for this purchase
+        NoRefundRequest,
+        /// A refund request or dispute is pending
+        RefundPending,
+        /// Purchase is not disputed
+        NotDisputed,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::process_renewals(remaining_weight)
+        }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a purchase
+        ///
+        /// The full `amount` is reserved from the buyer and held in escrow until the
+        /// purchase is fulfilled or cancelled.
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_purchase())]
         pub fn create_purchase(
             origin: OriginFor<T>,
             listing_id: H256,
             provider: T::AccountId,
-            amount: u128,
+            amount: BalanceOf<T>,
         ) -> DispatchResult {
             let buyer = ensure_signed(origin)?;
 
-            ensure!(amount > 0, Error::<T>::InvalidAmount);
+            ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+            // Escrow the payment
+            T::Currency::reserve(&buyer, amount).map_err(|_| Error::<T>::InsufficientBalance)?;
 
-            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
 
             // Calculate platform fee
             let platform_fee = Self::calculate_platform_fee(amount);
@@ -261,10 +454,11 @@ pub mod pallet {
                 amount,
                 platform_fee,
                 provider_amount,
-                status: PurchaseStatus::Pending,
+                status: PurchaseStatus::Paid,
                 purchased_at: now,
                 fulfilled_at: None,
                 expires_at: None,
+                settled: false,
             };
 
             Purchases::<T>::insert(purchase_id, purchase);
@@ -290,8 +484,11 @@ pub mod pallet {
         }
 
         /// Fulfill a purchase (grant access)
+        ///
+        /// Starts the dispute period. Without a dispute period the escrow is released at once:
+        /// `provider_amount` to the provider and `platform_fee` to the treasury account.
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::fulfill_purchase())]
         pub fn fulfill_purchase(
             origin: OriginFor<T>,
             purchase_id: H256,
@@ -304,44 +501,26 @@ pub mod pallet {
                 ensure!(purchase.provider == who, Error::<T>::NotAuthorized);
                 ensure!(purchase.status == PurchaseStatus::Paid, Error::<T>::NotPaid);
 
-                let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+                let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
                 purchase.status = PurchaseStatus::Fulfilled;
                 purchase.fulfilled_at = Some(now);
 
-                // Collect platform fee
-                let current_revenue = PlatformRevenue::<T>::get();
-                PlatformRevenue::<T>::put(current_revenue.saturating_add(purchase.platform_fee));
-
                 Self::deposit_event(Event::PurchaseFulfilled { purchase_id });
-                Self::deposit_event(Event::PlatformFeeCollected {
-                    amount: purchase.platform_fee,
-                });
-
-                Ok(())
-            })
-        }
-
-        /// Mark purchase as paid (simulated payment for now)
-        #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
-        pub fn mark_paid(origin: OriginFor<T>, purchase_id: H256) -> DispatchResult {
-            let who = ensure_signed(origin)?;
 
-            Purchases::<T>::try_mutate(purchase_id, |maybe_purchase| -> DispatchResult {
-                let purchase = maybe_purchase.as_mut().ok_or(Error::<T>::PurchaseNotFound)?;
-
-                ensure!(purchase.buyer == who, Error::<T>::NotAuthorized);
-                ensure!(purchase.status == PurchaseStatus::Pending, Error::<T>::AlreadyFulfilled);
-
-                purchase.status = PurchaseStatus::Paid;
+                // Purchases made before escrow have nothing reserved to release
+                if T::DisputePeriod::get() == 0 && !purchase.settled {
+                    Self::settle_purchase(purchase)?;
+                }
 
                 Ok(())
             })
         }
 
         /// Cancel a purchase
+        ///
+        /// Only unfulfilled purchases can be cancelled; the escrowed amount is returned to the buyer.
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_purchase())]
         pub fn cancel_purchase(origin: OriginFor<T>, purchase_id: H256) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -350,11 +529,23 @@ pub mod pallet {
 
                 ensure!(purchase.buyer == who, Error::<T>::NotAuthorized);
                 ensure!(
-                    purchase.status == PurchaseStatus::Pending,
-                    Error::<T>::AlreadyFulfilled
+                    matches!(purchase.status, PurchaseStatus::Pending | PurchaseStatus::Paid),
+                    Error::<T>::CannotCancel
                 );
 
+                // Return escrowed funds, unless the purchase was made before escrow
+                if purchase.status == PurchaseStatus::Paid && !purchase.settled {
+                    T::Currency::unreserve(&purchase.buyer, purchase.amount);
+
+                    Self::deposit_event(Event::PaymentRefunded {
+                        purchase_id,
+                        buyer: purchase.buyer.clone(),
+                        amount: purchase.amount,
+                    });
+                }
+
                 purchase.status = PurchaseStatus::Cancelled;
+                purchase.settled = true;
 
                 Self::deposit_event(Event::PurchaseCancelled { purchase_id });
 
@@ -363,28 +554,30 @@ pub mod pallet {
         }
 
         /// Create a subscription
+        ///
+        /// The first period is charged immediately; later periods are renewed automatically.
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_subscription())]
         pub fn create_subscription(
             origin: OriginFor<T>,
             listing_id: H256,
             provider: T::AccountId,
-            amount: u128,
+            amount: BalanceOf<T>,
             period_days: u32,
         ) -> DispatchResult {
             let subscriber = ensure_signed(origin)?;
 
-            ensure!(amount > 0, Error::<T>::InvalidAmount);
+            ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
             ensure!(period_days > 0, Error::<T>::InvalidAmount);
 
-            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
 
             // Generate subscription ID
             let count = SubscriptionCount::<T>::get();
             let subscription_id = Self::generate_subscription_id(&subscriber, count);
             SubscriptionCount::<T>::put(count.saturating_add(1));
 
-            let next_payment_at = now + (period_days as u64 * 86400);
+            let next_payment_at = now.saturating_add(Self::period_duration(period_days));
             let expires_at = next_payment_at;
 
             let subscription = Subscription {
@@ -400,7 +593,11 @@ pub mod pallet {
                 active: true,
             };
 
+            // Pay for the first period
+            Self::charge_subscription(&subscription)?;
+
             Subscriptions::<T>::insert(subscription_id, subscription);
+            Self::schedule_renewal(next_payment_at, subscription_id);
 
             // Add to subscriber's subscriptions
             let mut subscriber_subscriptions = SubscriberSubscriptions::<T>::get(&subscriber);
@@ -418,7 +615,7 @@ pub mod pallet {
 
         /// Cancel a subscription
         #[pallet::call_index(5)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_subscription())]
         pub fn cancel_subscription(
             origin: OriginFor<T>,
             subscription_id: H256,
@@ -440,6 +637,213 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        /// Request a refund for a fulfilled purchase (buyer only, during the dispute period)
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::request_refund())]
+        pub fn request_refund(
+            origin: OriginFor<T>,
+            purchase_id: H256,
+            reason_hash: H256,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let purchase = Purchases::<T>::get(purchase_id).ok_or(Error::<T>::PurchaseNotFound)?;
+
+            ensure!(purchase.buyer == who, Error::<T>::NotAuthorized);
+            ensure!(purchase.status == PurchaseStatus::Fulfilled, Error::<T>::NotFulfilled);
+            ensure!(!purchase.settled, Error::<T>::AlreadySettled);
+            ensure!(
+                !RefundRequests::<T>::contains_key(purchase_id),
+                Error::<T>::RefundAlreadyRequested
+            );
+
+            let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+            ensure!(!Self::dispute_period_over(&purchase, now), Error::<T>::DisputePeriodOver);
+
+            RefundRequests::<T>::insert(purchase_id, reason_hash);
+
+            Self::deposit_event(Event::RefundRequested { purchase_id, reason_hash });
+
+            Ok(())
+        }
+
+        /// Approve a refund (provider only); the full escrowed amount is returned to the buyer
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve_refund())]
+        pub fn approve_refund(origin: OriginFor<T>, purchase_id: H256) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Purchases::<T>::try_mutate(purchase_id, |maybe_purchase| -> DispatchResult {
+                let purchase = maybe_purchase.as_mut().ok_or(Error::<T>::PurchaseNotFound)?;
+
+                ensure!(purchase.provider == who, Error::<T>::NotAuthorized);
+                ensure!(
+                    matches!(purchase.status, PurchaseStatus::Fulfilled | PurchaseStatus::Disputed),
+                    Error::<T>::NotFulfilled
+                );
+                ensure!(!purchase.settled, Error::<T>::AlreadySettled);
+
+                T::Currency::unreserve(&purchase.buyer, purchase.amount);
+
+                purchase.status = PurchaseStatus::Refunded;
+                purchase.settled = true;
+
+                RefundRequests::<T>::remove(purchase_id);
+                Disputes::<T>::remove(purchase_id);
+
+                pallet_reputation::Pallet::<T>::record_trade_outcome(
+                    &purchase.provider,
+                    TradeOutcome::Refunded,
+                );
+
+                Self::deposit_event(Event::PaymentRefunded {
+                    purchase_id,
+                    buyer: purchase.buyer.clone(),
+                    amount: purchase.amount,
+                });
+                Self::deposit_event(Event::PurchaseRefunded { purchase_id });
+
+                Ok(())
+            })
+        }
+
+        /// Escalate a refund request to the arbiter (buyer or provider)
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as Config>::WeightInfo::open_dispute())]
+        pub fn open_dispute(
+            origin: OriginFor<T>,
+            purchase_id: H256,
+            evidence_hash: H256,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Purchases::<T>::try_mutate(purchase_id, |maybe_purchase| -> DispatchResult {
+                let purchase = maybe_purchase.as_mut().ok_or(Error::<T>::PurchaseNotFound)?;
+
+                ensure!(
+                    purchase.buyer == who || purchase.provider == who,
+                    Error::<T>::NotAuthorized
+                );
+                ensure!(purchase.status == PurchaseStatus::Fulfilled, Error::<T>::NotFulfilled);
+                ensure!(!purchase.settled, Error::<T>::AlreadySettled);
+                ensure!(
+                    RefundRequests::<T>::contains_key(purchase_id),
+                    Error::<T>::NoRefundRequest
+                );
+
+                let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+                purchase.status = PurchaseStatus::Disputed;
+
+                Disputes::<T>::insert(
+                    purchase_id,
+                    Dispute {
+                        purchase_id,
+                        opened_by: who.clone(),
+                        evidence_hash,
+                        opened_at: now,
+                    },
+                );
+
+                Self::deposit_event(Event::DisputeOpened {
+                    purchase_id,
+                    opened_by: who,
+                    evidence_hash,
+                });
+
+                Ok(())
+            })
+        }
+
+        /// Resolve a dispute by splitting the escrow (arbiter only)
+        ///
+        /// - `buyer_share`: Portion of the purchase amount refunded to the buyer; the rest is
+        ///   paid out to the provider and the treasury as for a regular sale.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::resolve_dispute())]
+        pub fn resolve_dispute(
+            origin: OriginFor<T>,
+            purchase_id: H256,
+            buyer_share: Percent,
+        ) -> DispatchResult {
+            T::ArbiterOrigin::ensure_origin(origin)?;
+
+            Purchases::<T>::try_mutate(purchase_id, |maybe_purchase| -> DispatchResult {
+                let purchase = maybe_purchase.as_mut().ok_or(Error::<T>::PurchaseNotFound)?;
+
+                ensure!(purchase.status == PurchaseStatus::Disputed, Error::<T>::NotDisputed);
+                ensure!(!purchase.settled, Error::<T>::AlreadySettled);
+
+                let buyer_refund = buyer_share.mul_floor(purchase.amount);
+                let remaining = purchase.amount.saturating_sub(buyer_refund);
+                let platform_fee = Self::calculate_platform_fee(remaining);
+                let provider_amount = remaining.saturating_sub(platform_fee);
+
+                T::Currency::unreserve(&purchase.buyer, buyer_refund);
+                Self::release_escrow(&purchase.buyer, &purchase.provider, provider_amount)?;
+                Self::release_escrow(&purchase.buyer, &T::TreasuryAccount::get(), platform_fee)?;
+
+                PlatformRevenue::<T>::mutate(|revenue| {
+                    *revenue = revenue.saturating_add(platform_fee)
+                });
+
+                purchase.platform_fee = platform_fee;
+                purchase.provider_amount = provider_amount;
+                purchase.settled = true;
+                purchase.status = if remaining.is_zero() {
+                    PurchaseStatus::Refunded
+                } else {
+                    PurchaseStatus::Fulfilled
+                };
+
+                RefundRequests::<T>::remove(purchase_id);
+                Disputes::<T>::remove(purchase_id);
+
+                pallet_reputation::Pallet::<T>::record_trade_outcome(
+                    &purchase.provider,
+                    TradeOutcome::Disputed { buyer_share },
+                );
+
+                Self::deposit_event(Event::DisputeResolved {
+                    purchase_id,
+                    buyer_refund,
+                    provider_amount,
+                });
+                if purchase.status == PurchaseStatus::Refunded {
+                    Self::deposit_event(Event::PurchaseRefunded { purchase_id });
+                }
+
+                Ok(())
+            })
+        }
+
+        /// Release the escrow of a fulfilled purchase once the dispute period is over
+        ///
+        /// Callable by anyone; fails while a refund request or dispute is pending.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_payment())]
+        pub fn claim_payment(origin: OriginFor<T>, purchase_id: H256) -> DispatchResult {
+            let _who = ensure_signed(origin)?;
+
+            Purchases::<T>::try_mutate(purchase_id, |maybe_purchase| -> DispatchResult {
+                let purchase = maybe_purchase.as_mut().ok_or(Error::<T>::PurchaseNotFound)?;
+
+                ensure!(purchase.status == PurchaseStatus::Fulfilled, Error::<T>::NotFulfilled);
+                ensure!(!purchase.settled, Error::<T>::AlreadySettled);
+                ensure!(
+                    !RefundRequests::<T>::contains_key(purchase_id),
+                    Error::<T>::RefundPending
+                );
+
+                let now = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+                ensure!(
+                    Self::dispute_period_over(purchase, now),
+                    Error::<T>::DisputePeriodNotOver
+                );
+
+                Self::settle_purchase(purchase)
+            })
+        }
     }
 
     // Helper functions
@@ -463,9 +867,199 @@ pub mod pallet {
         }
 
         /// Calculate platform fee
-        fn calculate_platform_fee(amount: u128) -> u128 {
-            let fee_percent = T::PlatformFeePercent::get() as u128;
-            amount.saturating_mul(fee_percent) / 100
+        fn calculate_platform_fee(amount: BalanceOf<T>) -> BalanceOf<T> {
+            let fee_percent: BalanceOf<T> = (T::PlatformFeePercent::get() as u32).into();
+            amount.saturating_mul(fee_percent) / 100u32.into()
+        }
+
+        /// Length of a subscription period
+        fn period_duration(period_days: u32) -> u64 {
+            (period_days as u64).saturating_mul(MILLISECS_PER_DAY)
+        }
+
+        /// Schedule a subscription for payment at `due_at`
+        pub(crate) fn schedule_renewal(due_at: u64, subscription_id: H256) {
+            let day = due_at / MILLISECS_PER_DAY;
+            RenewalSchedule::<T>::insert(day, (due_at.to_be_bytes(), subscription_id), ());
+            NextRenewalDay::<T>::mutate(|next| {
+                if next.map_or(true, |next| day < next) {
+                    *next = Some(day);
+                }
+            });
+        }
+
+        /// Charge the subscriber for one period, paying the provider and the treasury
+        fn charge_subscription(subscription: &Subscription<T>) -> DispatchResult {
+            T::Currency::reserve(&subscription.subscriber, subscription.amount)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            let platform_fee = Self::calculate_platform_fee(subscription.amount);
+            let provider_amount = subscription.amount.saturating_sub(platform_fee);
+
+            Self::release_escrow(&subscription.subscriber, &subscription.provider, provider_amount)?;
+            Self::release_escrow(&subscription.subscriber, &T::TreasuryAccount::get(), platform_fee)?;
+
+            PlatformRevenue::<T>::mutate(|revenue| *revenue = revenue.saturating_add(platform_fee));
+
+            pallet_reputation::Pallet::<T>::record_trade_outcome(
+                &subscription.provider,
+                TradeOutcome::Completed,
+            );
+
+            Self::deposit_event(Event::PlatformFeeCollected { amount: platform_fee });
+
+            Ok(())
+        }
+
+        /// Process due subscriptions, bounded by `MaxRenewalsPerBlock` and `remaining_weight`
+        fn process_renewals(remaining_weight: Weight) -> Weight {
+            let db_weight = <T as frame_system::Config>::DbWeight::get();
+            let step_weight = db_weight.reads_writes(1, 0);
+            let renewal_weight = <T as Config>::WeightInfo::renew_subscription();
+
+            let mut used = db_weight.reads_writes(1, 1);
+            if remaining_weight.any_lt(used) {
+                return Weight::zero();
+            }
+
+            let Some(mut day) = NextRenewalDay::<T>::get() else {
+                return db_weight.reads(1);
+            };
+
+            let now: u64 = <T as Config>::TimeProvider::now().try_into().ok().unwrap_or(0);
+            let today = now / MILLISECS_PER_DAY;
+            let mut budget = T::MaxRenewalsPerBlock::get();
+
+            'days: while day <= today {
+                if budget == 0 || remaining_weight.any_lt(used.saturating_add(step_weight)) {
+                    break;
+                }
+                used = used.saturating_add(step_weight);
+
+                let due: Vec<([u8; 8], H256)> =
+                    RenewalSchedule::<T>::iter_key_prefix(day).take(budget as usize).collect();
+                if due.is_empty() {
+                    budget -= 1;
+                    day = day.saturating_add(1);
+                    continue;
+                }
+
+                for (due_at, subscription_id) in due {
+                    // Entries are in due order, so the rest of the day is not due either
+                    if u64::from_be_bytes(due_at) > now {
+                        break 'days;
+                    }
+                    if remaining_weight.any_lt(used.saturating_add(renewal_weight)) {
+                        break 'days;
+                    }
+                    used = used.saturating_add(renewal_weight);
+                    budget -= 1;
+
+                    RenewalSchedule::<T>::remove(day, (due_at, subscription_id));
+                    Self::renew_subscription(subscription_id, now);
+                }
+            }
+
+            NextRenewalDay::<T>::put(day);
+
+            used
+        }
+
+        /// Charge a due subscription and extend it, or handle the failed payment
+        pub(crate) fn renew_subscription(subscription_id: H256, now: u64) {
+            Subscriptions::<T>::mutate(subscription_id, |maybe_subscription| {
+                let Some(subscription) = maybe_subscription else { return };
+
+                // Cancelled subscriptions are simply dropped from the schedule
+                if !subscription.active {
+                    return;
+                }
+
+                if with_storage_layer(|| Self::charge_subscription(subscription)).is_ok() {
+                    subscription.expires_at = subscription
+                        .expires_at
+                        .saturating_add(Self::period_duration(subscription.period_days));
+                    subscription.next_payment_at = subscription.expires_at;
+
+                    // A subscription that fell behind is caught up on later blocks
+                    Self::schedule_renewal(subscription.next_payment_at.max(now), subscription_id);
+
+                    Self::deposit_event(Event::SubscriptionRenewed {
+                        subscription_id,
+                        expires_at: subscription.expires_at,
+                    });
+                } else if now
+                    >= subscription.expires_at.saturating_add(T::SubscriptionGracePeriod::get())
+                {
+                    subscription.active = false;
+
+                    Self::deposit_event(Event::SubscriptionExpired { subscription_id });
+                } else {
+                    // Retry a day later
+                    Self::schedule_renewal(now.saturating_add(MILLISECS_PER_DAY), subscription_id);
+
+                    Self::deposit_event(Event::SubscriptionPaymentFailed { subscription_id });
+                }
+            });
+        }
+
+        /// Release the escrow of a fulfilled purchase to the provider and the treasury
+        fn settle_purchase(purchase: &mut Purchase<T>) -> DispatchResult {
+            Self::release_escrow(&purchase.buyer, &purchase.provider, purchase.provider_amount)?;
+            Self::release_escrow(
+                &purchase.buyer,
+                &T::TreasuryAccount::get(),
+                purchase.platform_fee,
+            )?;
+
+            purchase.settled = true;
+
+            // Collect platform fee
+            PlatformRevenue::<T>::mutate(|revenue| {
+                *revenue = revenue.saturating_add(purchase.platform_fee)
+            });
+
+            pallet_reputation::Pallet::<T>::record_trade_outcome(
+                &purchase.provider,
+                TradeOutcome::Completed,
+            );
+
+            Self::deposit_event(Event::PaymentReleased {
+                purchase_id: purchase.purchase_id,
+                provider: purchase.provider.clone(),
+                provider_amount: purchase.provider_amount,
+                platform_fee: purchase.platform_fee,
+            });
+            Self::deposit_event(Event::PlatformFeeCollected {
+                amount: purchase.platform_fee,
+            });
+
+            Ok(())
+        }
+
+        /// Whether the dispute period of a fulfilled purchase has elapsed
+        fn dispute_period_over(purchase: &Purchase<T>, now: u64) -> bool {
+            purchase
+                .fulfilled_at
+                .map(|fulfilled_at| now >= fulfilled_at.saturating_add(T::DisputePeriod::get()))
+                .unwrap_or(false)
+        }
+
+        /// Move reserved funds from the buyer to the free balance of `to`
+        fn release_escrow(
+            buyer: &T::AccountId,
+            to: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            if amount.is_zero() {
+                return Ok(());
+            }
+
+            let remaining =
+                T::Currency::repatriate_reserved(buyer, to, amount, BalanceStatus::Free)?;
+            ensure!(remaining.is_zero(), Error::<T>::EscrowReleaseFailed);
+
+            Ok(())
         }
 
         /// Check if user has active access to listing
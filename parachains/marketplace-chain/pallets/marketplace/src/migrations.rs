@@ -0,0 +1,107 @@
+//! Storage migrations for the marketplace pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_core::H256;
+use sp_runtime::SaturatedConversion;
+
+/// Version 1 records whether the escrow of each purchase has been settled, and renews
+/// subscriptions from `RenewalSchedule`
+pub mod v1 {
+    use super::*;
+
+    /// Purchase record of version 0, before purchases were escrowed
+    #[derive(Encode, Decode)]
+    pub struct OldPurchase<T: Config> {
+        pub purchase_id: H256,
+        pub listing_id: H256,
+        pub buyer: T::AccountId,
+        pub provider: T::AccountId,
+        pub amount: BalanceOf<T>,
+        pub platform_fee: BalanceOf<T>,
+        pub provider_amount: BalanceOf<T>,
+        pub status: PurchaseStatus,
+        pub purchased_at: u64,
+        pub fulfilled_at: Option<u64>,
+        pub expires_at: Option<u64>,
+    }
+
+    /// Subscription of version 0, before amounts were balances of `Currency`
+    #[derive(Encode, Decode)]
+    pub struct OldSubscription<T: Config> {
+        pub subscription_id: H256,
+        pub listing_id: H256,
+        pub subscriber: T::AccountId,
+        pub provider: T::AccountId,
+        pub amount: u128,
+        pub period_days: u32,
+        pub started_at: u64,
+        pub next_payment_at: u64,
+        pub expires_at: u64,
+        pub active: bool,
+    }
+
+    /// Mark every existing purchase as settled, since no funds were reserved before escrow, and
+    /// schedule every active subscription for renewal when its next payment is due
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+
+            Purchases::<T>::translate::<OldPurchase<T>, _>(|_, old| {
+                translated += 1;
+                Some(Purchase {
+                    purchase_id: old.purchase_id,
+                    listing_id: old.listing_id,
+                    buyer: old.buyer,
+                    provider: old.provider,
+                    amount: old.amount,
+                    platform_fee: old.platform_fee,
+                    provider_amount: old.provider_amount,
+                    status: old.status,
+                    purchased_at: old.purchased_at,
+                    fulfilled_at: old.fulfilled_at,
+                    expires_at: old.expires_at,
+                    settled: true,
+                })
+            });
+
+            let mut scheduled = 0u64;
+            Subscriptions::<T>::translate::<OldSubscription<T>, _>(|subscription_id, old| {
+                translated += 1;
+                if old.active {
+                    scheduled += 1;
+                    Pallet::<T>::schedule_renewal(old.next_payment_at, subscription_id);
+                }
+                Some(Subscription {
+                    subscription_id: old.subscription_id,
+                    listing_id: old.listing_id,
+                    subscriber: old.subscriber,
+                    provider: old.provider,
+                    amount: old.amount.saturated_into(),
+                    period_days: old.period_days,
+                    started_at: old.started_at,
+                    next_payment_at: old.next_payment_at,
+                    expires_at: old.expires_at,
+                    active: old.active,
+                })
+            });
+
+            T::DbWeight::get()
+                .reads_writes(translated, translated)
+                .saturating_add(T::DbWeight::get().reads_writes(scheduled, scheduled * 2))
+        }
+    }
+
+    /// Migrate `Purchases` and `Subscriptions` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,199 @@
+//! Mock runtime for marketplace pallet tests
+
+use crate as pallet_marketplace;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU128, ConstU32, ConstU64, ConstU8},
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::BuildStorage;
+
+pub type AccountId = u64;
+pub type Balance = u128;
+
+pub const BUYER: AccountId = 1;
+pub const PROVIDER: AccountId = 2;
+pub const STRANGER: AccountId = 3;
+/// Receives platform fees; endowed with the existential deposit only
+pub const TREASURY: AccountId = 99;
+
+pub const EXISTENTIAL_DEPOSIT: Balance = 10;
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+
+/// Purchase and subscription price; the 5% platform fee is `FEE`
+pub const PRICE: Balance = 1_000;
+pub const FEE: Balance = 50;
+
+/// Time after fulfillment during which refunds can be requested, when enabled
+pub const DISPUTE_PERIOD: u64 = 3 * pallet_marketplace::MILLISECS_PER_DAY;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        Reputation: pallet_reputation,
+        Marketplace: pallet_marketplace,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type Balance = Balance;
+    type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+    type AccountStore = System;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<5>;
+    type WeightInfo = ();
+}
+
+impl pallet_reputation::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxReviewsPerUser = ConstU32<10>;
+    type DisputePenalty = ConstU8<20>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub static DisputePeriod: u64 = 0;
+}
+
+impl pallet_marketplace::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type Currency = Balances;
+    type PlatformFeePercent = ConstU8<5>;
+    type TreasuryAccount = ConstU64<TREASURY>;
+    type ArbiterOrigin = EnsureRoot<AccountId>;
+    type DisputePeriod = DisputePeriod;
+    type SubscriptionGracePeriod = ConstU64<{ 3 * pallet_marketplace::MILLISECS_PER_DAY }>;
+    type MaxRenewalsPerBlock = ConstU32<2>;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MockTime;
+}
+
+/// Sets the mock timestamp for settlement benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockTime;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_marketplace::BenchmarkHelper for MockTime {
+    fn set_time(now: u64) {
+        Timestamp::set_timestamp(now);
+    }
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (BUYER, INITIAL_BALANCE),
+            (PROVIDER, INITIAL_BALANCE),
+            (STRANGER, INITIAL_BALANCE),
+            (TREASURY, EXISTENTIAL_DEPOSIT),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000);
+        DisputePeriod::set(0);
+    });
+    ext
+}
+
+// Build genesis storage with a dispute period, so that the settlement benchmarks are not skipped
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_bench_ext() -> sp_io::TestExternalities {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| DisputePeriod::set(DISPUTE_PERIOD));
+    ext
+}
+
+// Helper function to buy a listing from `PROVIDER` as `BUYER`, returning the purchase ID
+pub fn purchase() -> H256 {
+    frame_support::assert_ok!(Marketplace::create_purchase(
+        RuntimeOrigin::signed(BUYER),
+        H256::repeat_byte(1),
+        PROVIDER,
+        PRICE,
+    ));
+    *Marketplace::buyer_purchases(BUYER).last().unwrap()
+}
+
+// Helper function to create a purchase fulfilled under the dispute period
+pub fn fulfilled_purchase() -> H256 {
+    DisputePeriod::set(DISPUTE_PERIOD);
+    let purchase_id = purchase();
+    frame_support::assert_ok!(Marketplace::fulfill_purchase(
+        RuntimeOrigin::signed(PROVIDER),
+        purchase_id,
+    ));
+    purchase_id
+}
+
+// Helper function to request a refund of a fulfilled purchase
+pub fn refund_requested() -> H256 {
+    let purchase_id = fulfilled_purchase();
+    frame_support::assert_ok!(Marketplace::request_refund(
+        RuntimeOrigin::signed(BUYER),
+        purchase_id,
+        H256::repeat_byte(2),
+    ));
+    purchase_id
+}
+
+// Helper function to escalate a refund request to the arbiter
+pub fn disputed_purchase() -> H256 {
+    let purchase_id = refund_requested();
+    frame_support::assert_ok!(Marketplace::open_dispute(
+        RuntimeOrigin::signed(BUYER),
+        purchase_id,
+        H256::repeat_byte(3),
+    ));
+    purchase_id
+}
+
+// Helper function to subscribe `BUYER` to a daily plan from `PROVIDER`, returning its ID
+pub fn subscribe(amount: Balance) -> H256 {
+    frame_support::assert_ok!(Marketplace::create_subscription(
+        RuntimeOrigin::signed(BUYER),
+        H256::repeat_byte(1),
+        PROVIDER,
+        amount,
+        1,
+    ));
+    *Marketplace::subscriber_subscriptions(BUYER).last().unwrap()
+}
+
+// Helper function to run the idle hook at `now` with unlimited weight
+pub fn renew_at(now: u64) {
+    use frame_support::traits::Hooks;
+
+    Timestamp::set_timestamp(now);
+    Marketplace::on_idle(System::block_number(), frame_support::weights::Weight::MAX);
+}
@@ -0,0 +1,584 @@
+//! Tests for marketplace pallet
+
+use crate::{
+    migrations::v1::{MigrateToV1, OldPurchase, OldSubscription},
+    mock::*,
+    Error, Event, PurchaseStatus, Purchases, RenewalSchedule, Subscriptions, MILLISECS_PER_DAY,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::unhashed,
+    traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use pallet_reputation::{
+    migrations::v1::{MigrateToV1 as MigrateReputationToV1, OldProviderReputation},
+    ProviderReputations,
+};
+use sp_core::H256;
+use sp_runtime::{DispatchError, Percent};
+
+#[test]
+fn create_purchase_escrows_payment() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = purchase();
+
+        let purchase = Marketplace::purchases(purchase_id).unwrap();
+        assert_eq!(purchase.status, PurchaseStatus::Paid);
+        assert_eq!(purchase.platform_fee, FEE);
+        assert_eq!(purchase.provider_amount, PRICE - FEE);
+        assert!(!purchase.settled);
+        assert_eq!(Balances::reserved_balance(BUYER), PRICE);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - PRICE);
+        assert_eq!(Marketplace::provider_sales(PROVIDER).into_inner(), vec![purchase_id]);
+        System::assert_last_event(
+            Event::PurchaseCreated {
+                purchase_id,
+                listing_id: H256::repeat_byte(1),
+                buyer: BUYER,
+                amount: PRICE,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn create_purchase_fails_for_invalid_amount() {
+    new_test_ext().execute_with(|| {
+        let listing_id = H256::repeat_byte(1);
+        assert_noop!(
+            Marketplace::create_purchase(RuntimeOrigin::signed(BUYER), listing_id, PROVIDER, 0),
+            Error::<Test>::InvalidAmount
+        );
+        assert_noop!(
+            Marketplace::create_purchase(
+                RuntimeOrigin::signed(BUYER),
+                listing_id,
+                PROVIDER,
+                INITIAL_BALANCE + 1,
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn cancel_purchase_returns_escrow() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = purchase();
+
+        assert_noop!(
+            Marketplace::cancel_purchase(RuntimeOrigin::signed(STRANGER), purchase_id),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Marketplace::cancel_purchase(RuntimeOrigin::signed(BUYER), purchase_id));
+
+        let purchase = Marketplace::purchases(purchase_id).unwrap();
+        assert_eq!(purchase.status, PurchaseStatus::Cancelled);
+        assert!(purchase.settled);
+        assert_eq!(Balances::reserved_balance(BUYER), 0);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE);
+
+        assert_noop!(
+            Marketplace::cancel_purchase(RuntimeOrigin::signed(BUYER), purchase_id),
+            Error::<Test>::CannotCancel
+        );
+    });
+}
+
+#[test]
+fn fulfillment_without_dispute_period_settles_at_once() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = purchase();
+
+        assert_noop!(
+            Marketplace::fulfill_purchase(RuntimeOrigin::signed(STRANGER), purchase_id),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Marketplace::fulfill_purchase(RuntimeOrigin::signed(PROVIDER), purchase_id));
+
+        let purchase = Marketplace::purchases(purchase_id).unwrap();
+        assert_eq!(purchase.status, PurchaseStatus::Fulfilled);
+        assert!(purchase.settled);
+        assert_eq!(Balances::reserved_balance(BUYER), 0);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - PRICE);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + PRICE - FEE);
+        assert_eq!(Balances::free_balance(TREASURY), EXISTENTIAL_DEPOSIT + FEE);
+        assert_eq!(Marketplace::platform_revenue(), FEE);
+        assert_eq!(Reputation::provider_reputation(PROVIDER).unwrap().total_sales, 1);
+        System::assert_has_event(
+            Event::PaymentReleased {
+                purchase_id,
+                provider: PROVIDER,
+                provider_amount: PRICE - FEE,
+                platform_fee: FEE,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Marketplace::fulfill_purchase(RuntimeOrigin::signed(PROVIDER), purchase_id),
+            Error::<Test>::NotPaid
+        );
+    });
+}
+
+#[test]
+fn fee_below_existential_deposit_reaches_existing_treasury() {
+    new_test_ext().execute_with(|| {
+        // A 5% fee of 100 is below the existential deposit
+        assert_ok!(Marketplace::create_purchase(
+            RuntimeOrigin::signed(BUYER),
+            H256::repeat_byte(1),
+            PROVIDER,
+            100,
+        ));
+        let purchase_id = *Marketplace::buyer_purchases(BUYER).last().unwrap();
+
+        assert_ok!(Marketplace::fulfill_purchase(RuntimeOrigin::signed(PROVIDER), purchase_id));
+
+        assert_eq!(Balances::free_balance(TREASURY), EXISTENTIAL_DEPOSIT + 5);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + 95);
+    });
+}
+
+#[test]
+fn claim_payment_waits_for_dispute_period() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = fulfilled_purchase();
+        assert_eq!(Balances::reserved_balance(BUYER), PRICE);
+
+        assert_noop!(
+            Marketplace::claim_payment(RuntimeOrigin::signed(STRANGER), purchase_id),
+            Error::<Test>::DisputePeriodNotOver
+        );
+
+        Timestamp::set_timestamp(1_000 + DISPUTE_PERIOD);
+        assert_ok!(Marketplace::claim_payment(RuntimeOrigin::signed(STRANGER), purchase_id));
+
+        assert!(Marketplace::purchases(purchase_id).unwrap().settled);
+        assert_eq!(Balances::reserved_balance(BUYER), 0);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + PRICE - FEE);
+        assert_eq!(Balances::free_balance(TREASURY), EXISTENTIAL_DEPOSIT + FEE);
+
+        assert_noop!(
+            Marketplace::claim_payment(RuntimeOrigin::signed(STRANGER), purchase_id),
+            Error::<Test>::AlreadySettled
+        );
+    });
+}
+
+#[test]
+fn refund_request_holds_escrow_until_approved() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = refund_requested();
+
+        assert_noop!(
+            Marketplace::request_refund(
+                RuntimeOrigin::signed(BUYER),
+                purchase_id,
+                H256::repeat_byte(2),
+            ),
+            Error::<Test>::RefundAlreadyRequested
+        );
+
+        // The provider cannot claim around a pending request
+        Timestamp::set_timestamp(1_000 + DISPUTE_PERIOD);
+        assert_noop!(
+            Marketplace::claim_payment(RuntimeOrigin::signed(PROVIDER), purchase_id),
+            Error::<Test>::RefundPending
+        );
+
+        assert_noop!(
+            Marketplace::approve_refund(RuntimeOrigin::signed(BUYER), purchase_id),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Marketplace::approve_refund(RuntimeOrigin::signed(PROVIDER), purchase_id));
+
+        let purchase = Marketplace::purchases(purchase_id).unwrap();
+        assert_eq!(purchase.status, PurchaseStatus::Refunded);
+        assert!(purchase.settled);
+        assert!(Marketplace::refund_requests(purchase_id).is_none());
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE);
+        assert_eq!(Balances::free_balance(TREASURY), EXISTENTIAL_DEPOSIT);
+        assert_eq!(Reputation::provider_reputation(PROVIDER).unwrap().total_refunds, 1);
+        System::assert_last_event(Event::PurchaseRefunded { purchase_id }.into());
+    });
+}
+
+#[test]
+fn refund_cannot_be_requested_after_dispute_period() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = fulfilled_purchase();
+
+        assert_noop!(
+            Marketplace::request_refund(
+                RuntimeOrigin::signed(STRANGER),
+                purchase_id,
+                H256::repeat_byte(2),
+            ),
+            Error::<Test>::NotAuthorized
+        );
+
+        Timestamp::set_timestamp(1_000 + DISPUTE_PERIOD);
+        assert_noop!(
+            Marketplace::request_refund(
+                RuntimeOrigin::signed(BUYER),
+                purchase_id,
+                H256::repeat_byte(2),
+            ),
+            Error::<Test>::DisputePeriodOver
+        );
+    });
+}
+
+#[test]
+fn dispute_requires_refund_request() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = fulfilled_purchase();
+
+        assert_noop!(
+            Marketplace::open_dispute(
+                RuntimeOrigin::signed(BUYER),
+                purchase_id,
+                H256::repeat_byte(3),
+            ),
+            Error::<Test>::NoRefundRequest
+        );
+    });
+}
+
+#[test]
+fn arbiter_splits_disputed_escrow() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = disputed_purchase();
+        assert_eq!(
+            Marketplace::purchases(purchase_id).unwrap().status,
+            PurchaseStatus::Disputed
+        );
+
+        assert_noop!(
+            Marketplace::resolve_dispute(
+                RuntimeOrigin::signed(BUYER),
+                purchase_id,
+                Percent::from_percent(40),
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Marketplace::resolve_dispute(
+            RuntimeOrigin::root(),
+            purchase_id,
+            Percent::from_percent(40),
+        ));
+
+        // 400 back to the buyer, the remaining 600 is settled like a sale
+        let purchase = Marketplace::purchases(purchase_id).unwrap();
+        assert_eq!(purchase.status, PurchaseStatus::Fulfilled);
+        assert_eq!(purchase.platform_fee, 30);
+        assert_eq!(purchase.provider_amount, 570);
+        assert!(purchase.settled);
+        assert!(Marketplace::disputes(purchase_id).is_none());
+        assert_eq!(Balances::reserved_balance(BUYER), 0);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - 600);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + 570);
+        assert_eq!(Balances::free_balance(TREASURY), EXISTENTIAL_DEPOSIT + 30);
+        assert_eq!(Reputation::provider_reputation(PROVIDER).unwrap().disputes_lost, 1);
+
+        assert_noop!(
+            Marketplace::resolve_dispute(
+                RuntimeOrigin::root(),
+                purchase_id,
+                Percent::from_percent(40),
+            ),
+            Error::<Test>::NotDisputed
+        );
+    });
+}
+
+#[test]
+fn dispute_decided_for_provider_counts_as_sale() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = disputed_purchase();
+
+        assert_ok!(Marketplace::resolve_dispute(
+            RuntimeOrigin::root(),
+            purchase_id,
+            Percent::from_percent(0),
+        ));
+
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - PRICE);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + PRICE - FEE);
+        let reputation = Reputation::provider_reputation(PROVIDER).unwrap();
+        assert_eq!(reputation.total_sales, 1);
+        assert_eq!(reputation.disputes_lost, 0);
+    });
+}
+
+#[test]
+fn dispute_decided_for_buyer_refunds_everything() {
+    new_test_ext().execute_with(|| {
+        let purchase_id = disputed_purchase();
+
+        assert_ok!(Marketplace::resolve_dispute(
+            RuntimeOrigin::root(),
+            purchase_id,
+            Percent::from_percent(100),
+        ));
+
+        assert_eq!(
+            Marketplace::purchases(purchase_id).unwrap().status,
+            PurchaseStatus::Refunded
+        );
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE);
+        assert_eq!(Balances::reserved_balance(BUYER), 0);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE);
+        System::assert_last_event(Event::PurchaseRefunded { purchase_id }.into());
+    });
+}
+
+#[test]
+fn subscription_renews_once_due() {
+    new_test_ext().execute_with(|| {
+        let subscription_id = subscribe(PRICE);
+        let due_at = 1_000 + MILLISECS_PER_DAY;
+        assert_eq!(Marketplace::subscriptions(subscription_id).unwrap().next_payment_at, due_at);
+
+        // Later on the day it falls due, but before the due time
+        renew_at(due_at - 1);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - PRICE);
+        assert_eq!(Marketplace::subscriptions(subscription_id).unwrap().expires_at, due_at);
+
+        renew_at(due_at);
+        let subscription = Marketplace::subscriptions(subscription_id).unwrap();
+        assert_eq!(subscription.expires_at, due_at + MILLISECS_PER_DAY);
+        assert_eq!(subscription.next_payment_at, due_at + MILLISECS_PER_DAY);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - 2 * PRICE);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE + 2 * (PRICE - FEE));
+        assert_eq!(Marketplace::platform_revenue(), 2 * FEE);
+        System::assert_has_event(
+            Event::SubscriptionRenewed {
+                subscription_id,
+                expires_at: due_at + MILLISECS_PER_DAY,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn cancelled_subscription_is_not_renewed() {
+    new_test_ext().execute_with(|| {
+        let subscription_id = subscribe(PRICE);
+        assert_ok!(Marketplace::cancel_subscription(RuntimeOrigin::signed(BUYER), subscription_id));
+
+        renew_at(1_000 + MILLISECS_PER_DAY);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - PRICE);
+        assert_eq!(RenewalSchedule::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn failed_renewal_is_retried_until_grace_period_ends() {
+    new_test_ext().execute_with(|| {
+        // The first period leaves too little for a second
+        let amount = INITIAL_BALANCE / 2 + 1;
+        let subscription_id = subscribe(amount);
+        let due_at = 1_000 + MILLISECS_PER_DAY;
+
+        renew_at(due_at);
+        assert!(Marketplace::subscriptions(subscription_id).unwrap().active);
+        System::assert_last_event(Event::SubscriptionPaymentFailed { subscription_id }.into());
+
+        // Retried a day later, not earlier
+        System::reset_events();
+        renew_at(due_at + MILLISECS_PER_DAY - 1);
+        assert!(System::events().is_empty());
+        renew_at(due_at + MILLISECS_PER_DAY);
+        System::assert_last_event(Event::SubscriptionPaymentFailed { subscription_id }.into());
+
+        renew_at(due_at + 2 * MILLISECS_PER_DAY);
+        assert!(Marketplace::subscriptions(subscription_id).unwrap().active);
+
+        renew_at(due_at + 3 * MILLISECS_PER_DAY);
+        assert!(!Marketplace::subscriptions(subscription_id).unwrap().active);
+        System::assert_last_event(Event::SubscriptionExpired { subscription_id }.into());
+    });
+}
+
+#[test]
+fn renewals_are_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        let subscriptions = [subscribe(PRICE), subscribe(PRICE), subscribe(PRICE)];
+        let due_at = 1_000 + MILLISECS_PER_DAY;
+        let renewed = || {
+            subscriptions
+                .iter()
+                .filter(|id| Marketplace::subscriptions(*id).unwrap().expires_at > due_at)
+                .count()
+        };
+
+        // The mock renews at most two subscriptions per block
+        renew_at(due_at);
+        assert_eq!(renewed(), 2);
+        renew_at(due_at);
+        assert_eq!(renewed(), 3);
+    });
+}
+
+#[test]
+fn migration_to_v1_settles_existing_purchases() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Marketplace>();
+        let purchase_id = H256::repeat_byte(7);
+        let old = OldPurchase::<Test> {
+            purchase_id,
+            listing_id: H256::repeat_byte(1),
+            buyer: BUYER,
+            provider: PROVIDER,
+            amount: PRICE,
+            platform_fee: FEE,
+            provider_amount: PRICE - FEE,
+            status: PurchaseStatus::Fulfilled,
+            purchased_at: 1_000,
+            fulfilled_at: Some(1_000),
+            expires_at: None,
+        };
+        unhashed::put(&Purchases::<Test>::hashed_key_for(purchase_id), &old);
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let purchase = Marketplace::purchases(purchase_id).unwrap();
+        assert_eq!(purchase.status, PurchaseStatus::Fulfilled);
+        assert_eq!(purchase.provider_amount, PRICE - FEE);
+        assert!(purchase.settled);
+        assert_eq!(Marketplace::on_chain_storage_version(), 1);
+
+        // Nothing was escrowed for the purchase, so nothing is paid out
+        assert_noop!(
+            Marketplace::claim_payment(RuntimeOrigin::signed(PROVIDER), purchase_id),
+            Error::<Test>::AlreadySettled
+        );
+    });
+}
+
+#[test]
+fn migration_to_v1_schedules_existing_subscriptions() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Marketplace>();
+        let due_at = 1_000 + MILLISECS_PER_DAY;
+        let legacy = |subscription_id: H256, active: bool| OldSubscription::<Test> {
+            subscription_id,
+            listing_id: H256::repeat_byte(1),
+            subscriber: BUYER,
+            provider: PROVIDER,
+            amount: PRICE,
+            period_days: 1,
+            started_at: 1_000,
+            next_payment_at: due_at,
+            expires_at: due_at,
+            active,
+        };
+        let (active, cancelled) = (H256::repeat_byte(7), H256::repeat_byte(8));
+        for (subscription_id, is_active) in [(active, true), (cancelled, false)] {
+            unhashed::put(
+                &Subscriptions::<Test>::hashed_key_for(subscription_id),
+                &legacy(subscription_id, is_active),
+            );
+        }
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let day = due_at / MILLISECS_PER_DAY;
+        assert!(Marketplace::renewal_schedule(day, (due_at.to_be_bytes(), active)).is_some());
+        assert!(Marketplace::renewal_schedule(day, (due_at.to_be_bytes(), cancelled)).is_none());
+        assert_eq!(Marketplace::subscriptions(active).unwrap().amount, PRICE);
+
+        // The active subscription renews when it falls due
+        renew_at(due_at);
+        let subscription = Marketplace::subscriptions(active).unwrap();
+        assert_eq!(subscription.expires_at, due_at + MILLISECS_PER_DAY);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE - PRICE);
+        assert_eq!(Marketplace::subscriptions(cancelled).unwrap().expires_at, due_at);
+    });
+}
+
+#[test]
+fn legacy_paid_purchases_leave_open_escrows_untouched() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Marketplace>();
+        let legacy = |purchase_id: H256| OldPurchase::<Test> {
+            purchase_id,
+            listing_id: H256::repeat_byte(1),
+            buyer: BUYER,
+            provider: PROVIDER,
+            amount: PRICE,
+            platform_fee: FEE,
+            provider_amount: PRICE - FEE,
+            status: PurchaseStatus::Paid,
+            purchased_at: 1_000,
+            fulfilled_at: None,
+            expires_at: None,
+        };
+        let (cancelled, fulfilled) = (H256::repeat_byte(7), H256::repeat_byte(8));
+        for purchase_id in [cancelled, fulfilled] {
+            unhashed::put(&Purchases::<Test>::hashed_key_for(purchase_id), &legacy(purchase_id));
+        }
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        // The buyer has another purchase in escrow
+        let escrowed = purchase();
+        assert_eq!(Balances::reserved_balance(BUYER), PRICE);
+        System::reset_events();
+
+        assert_ok!(Marketplace::cancel_purchase(RuntimeOrigin::signed(BUYER), cancelled));
+        assert_ok!(Marketplace::fulfill_purchase(RuntimeOrigin::signed(PROVIDER), fulfilled));
+
+        assert_eq!(Marketplace::purchases(cancelled).unwrap().status, PurchaseStatus::Cancelled);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::Marketplace(
+                Event::PaymentRefunded { .. } | Event::PaymentReleased { .. }
+            )
+        )));
+        assert_eq!(Balances::reserved_balance(BUYER), PRICE);
+        assert_eq!(Balances::free_balance(PROVIDER), INITIAL_BALANCE);
+
+        // The open escrow is still refunded in full
+        assert_ok!(Marketplace::cancel_purchase(RuntimeOrigin::signed(BUYER), escrowed));
+        assert_eq!(Balances::reserved_balance(BUYER), 0);
+        assert_eq!(Balances::free_balance(BUYER), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn migration_to_v1_starts_reputations_without_refunds_or_disputes() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Reputation>();
+        let old = OldProviderReputation::<Test> {
+            provider: PROVIDER,
+            total_reviews: 4,
+            average_rating: 450,
+            total_sales: 12,
+            response_rate: 90,
+            quality_score: 80,
+            verified: true,
+            updated_at: 1_000,
+        };
+        unhashed::put(&ProviderReputations::<Test>::hashed_key_for(PROVIDER), &old);
+
+        MigrateReputationToV1::<Test>::on_runtime_upgrade();
+
+        let reputation = Reputation::provider_reputation(PROVIDER).unwrap();
+        assert_eq!(reputation.total_sales, 12);
+        assert_eq!(reputation.total_refunds, 0);
+        assert_eq!(reputation.disputes_lost, 0);
+        assert_eq!(reputation.quality_score, 80);
+        assert!(reputation.verified);
+        assert_eq!(Reputation::on_chain_storage_version(), 1);
+
+        // Trade outcomes are counted on top of the migrated record
+        let purchase_id = refund_requested();
+        assert_ok!(Marketplace::approve_refund(RuntimeOrigin::signed(PROVIDER), purchase_id));
+        assert_eq!(Reputation::provider_reputation(PROVIDER).unwrap().total_refunds, 1);
+    });
+}
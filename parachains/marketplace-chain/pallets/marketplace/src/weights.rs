@@ -0,0 +1,213 @@
+//! Estimated weights for `pallet_marketplace`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/marketplace-node benchmark pallet \
+//!     --chain dev --pallet pallet_marketplace --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/marketplace/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_marketplace`.
+pub trait WeightInfo {
+    fn create_purchase() -> Weight;
+    fn fulfill_purchase() -> Weight;
+    fn cancel_purchase() -> Weight;
+    fn create_subscription() -> Weight;
+    fn cancel_subscription() -> Weight;
+    fn request_refund() -> Weight;
+    fn approve_refund() -> Weight;
+    fn open_dispute() -> Weight;
+    fn resolve_dispute() -> Weight;
+    fn claim_payment() -> Weight;
+    fn renew_subscription() -> Weight;
+}
+
+/// Estimated weights for `pallet_marketplace`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `System::Account`, `Timestamp::Now`, `Marketplace::PurchaseCount`,
+    //     `Marketplace::BuyerPurchases`, `Marketplace::ProviderSales`
+    // Writes: `System::Account`, `Marketplace::PurchaseCount`, `Marketplace::BuyerPurchases`,
+    //     `Marketplace::ProviderSales`, `Marketplace::Purchases`
+    fn create_purchase() -> Weight {
+        Weight::from_parts(62_000_000, 64_529)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `Timestamp::Now`, `System::Account` x3,
+    //     `Marketplace::PlatformRevenue`, `Reputation::ProviderReputations`
+    // Writes: `Marketplace::Purchases`, `System::Account` x3, `Marketplace::PlatformRevenue`,
+    //     `Reputation::ProviderReputations`
+    fn fulfill_purchase() -> Weight {
+        Weight::from_parts(71_000_000, 8_799)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `System::Account`
+    // Writes: `Marketplace::Purchases`, `System::Account`
+    fn cancel_purchase() -> Weight {
+        Weight::from_parts(33_000_000, 3_876)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `Timestamp::Now`, `Marketplace::SubscriptionCount`, `System::Account` x3,
+    //     `Marketplace::PlatformRevenue`, `Reputation::ProviderReputations`,
+    //     `Marketplace::NextRenewalDay`, `Marketplace::SubscriberSubscriptions`
+    // Writes: `Marketplace::SubscriptionCount`, `System::Account` x3,
+    //     `Marketplace::PlatformRevenue`, `Reputation::ProviderReputations`,
+    //     `Marketplace::Subscriptions`, `Marketplace::RenewalSchedule`,
+    //     `Marketplace::NextRenewalDay`, `Marketplace::SubscriberSubscriptions`
+    fn create_subscription() -> Weight {
+        Weight::from_parts(88_000_000, 9_729)
+            .saturating_add(T::DbWeight::get().reads(9_u64))
+            .saturating_add(T::DbWeight::get().writes(10_u64))
+    }
+
+    // Reads: `Marketplace::Subscriptions`
+    // Writes: `Marketplace::Subscriptions`
+    fn cancel_subscription() -> Weight {
+        Weight::from_parts(17_000_000, 3_795)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `Marketplace::RefundRequests`, `Timestamp::Now`
+    // Writes: `Marketplace::RefundRequests`
+    fn request_refund() -> Weight {
+        Weight::from_parts(21_000_000, 3_876)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `System::Account`, `Reputation::ProviderReputations`
+    // Writes: `Marketplace::Purchases`, `System::Account`, `Reputation::ProviderReputations`,
+    //     `Marketplace::RefundRequests`, `Marketplace::Disputes`
+    fn approve_refund() -> Weight {
+        Weight::from_parts(46_000_000, 3_876)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `Marketplace::RefundRequests`, `Timestamp::Now`
+    // Writes: `Marketplace::Purchases`, `Marketplace::Disputes`
+    fn open_dispute() -> Weight {
+        Weight::from_parts(24_000_000, 3_876)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `System::Account` x3, `Marketplace::PlatformRevenue`,
+    //     `Reputation::ProviderReputations`, `Timestamp::Now`
+    // Writes: `Marketplace::Purchases`, `System::Account` x3, `Marketplace::PlatformRevenue`,
+    //     `Reputation::ProviderReputations`, `Marketplace::RefundRequests`, `Marketplace::Disputes`
+    fn resolve_dispute() -> Weight {
+        Weight::from_parts(84_000_000, 8_799)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(8_u64))
+    }
+
+    // Reads: `Marketplace::Purchases`, `Marketplace::RefundRequests`, `Timestamp::Now`,
+    //     `System::Account` x3, `Marketplace::PlatformRevenue`, `Reputation::ProviderReputations`
+    // Writes: `Marketplace::Purchases`, `System::Account` x3, `Marketplace::PlatformRevenue`,
+    //     `Reputation::ProviderReputations`
+    fn claim_payment() -> Weight {
+        Weight::from_parts(74_000_000, 8_799)
+            .saturating_add(T::DbWeight::get().reads(8_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+
+    // Reads: `Marketplace::Subscriptions`, `System::Account` x3, `Marketplace::PlatformRevenue`,
+    //     `Reputation::ProviderReputations`, `Marketplace::NextRenewalDay`
+    // Writes: `Marketplace::Subscriptions`, `System::Account` x3, `Marketplace::PlatformRevenue`,
+    //     `Reputation::ProviderReputations`, `Marketplace::RenewalSchedule`,
+    //     `Marketplace::NextRenewalDay`
+    fn renew_subscription() -> Weight {
+        Weight::from_parts(72_000_000, 9_729)
+            .saturating_add(T::DbWeight::get().reads(7_u64))
+            .saturating_add(T::DbWeight::get().writes(8_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn create_purchase() -> Weight {
+        Weight::from_parts(62_000_000, 64_529)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn fulfill_purchase() -> Weight {
+        Weight::from_parts(71_000_000, 8_799)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn cancel_purchase() -> Weight {
+        Weight::from_parts(33_000_000, 3_876)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn create_subscription() -> Weight {
+        Weight::from_parts(88_000_000, 9_729)
+            .saturating_add(RocksDbWeight::get().reads(9_u64))
+            .saturating_add(RocksDbWeight::get().writes(10_u64))
+    }
+
+    fn cancel_subscription() -> Weight {
+        Weight::from_parts(17_000_000, 3_795)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn request_refund() -> Weight {
+        Weight::from_parts(21_000_000, 3_876)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn approve_refund() -> Weight {
+        Weight::from_parts(46_000_000, 3_876)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn open_dispute() -> Weight {
+        Weight::from_parts(24_000_000, 3_876)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn resolve_dispute() -> Weight {
+        Weight::from_parts(84_000_000, 8_799)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(8_u64))
+    }
+
+    fn claim_payment() -> Weight {
+        Weight::from_parts(74_000_000, 8_799)
+            .saturating_add(RocksDbWeight::get().reads(8_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+
+    fn renew_subscription() -> Weight {
+        Weight::from_parts(72_000_000, 9_729)
+            .saturating_add(RocksDbWeight::get().reads(7_u64))
+            .saturating_add(RocksDbWeight::get().writes(8_u64))
+    }
+}
@@ -0,0 +1,132 @@
+//! Benchmarking setup for pallet-reputation
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Reputation;
+use frame_benchmarking::v2::*;
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+/// Submit a review of the provider from a fresh reviewer
+fn submit<T: Config>(provider: &T::AccountId) -> H256 {
+    let reviewer: T::AccountId = account("reviewer", 0, 0);
+
+    Pallet::<T>::submit_review(
+        RawOrigin::Signed(reviewer.clone()).into(),
+        H256::repeat_byte(1),
+        provider.clone(),
+        4,
+        BoundedVec::truncate_from(vec![b'c'; 512]),
+        true,
+    )
+    .expect("review submission must succeed");
+
+    *ReviewerReviews::<T>::get(&reviewer).last().expect("review was just submitted")
+}
+
+/// Give the provider every badge except `Verified`
+fn fill_badges<T: Config>(provider: &T::AccountId) {
+    ProviderBadges::<T>::insert(
+        provider,
+        BoundedVec::truncate_from(vec![
+            BadgeType::TopRated,
+            BadgeType::HighVolume,
+            BadgeType::QualityLeader,
+            BadgeType::FastResponder,
+        ]),
+    );
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn submit_review() {
+        let reviewer: T::AccountId = whitelisted_caller();
+        let provider: T::AccountId = account("provider", 0, 0);
+        let listing_id = H256::repeat_byte(1);
+
+        // Fill both indices up to one below their bound, and update an existing reputation
+        let existing: Vec<H256> = (0..999u64).map(H256::from_low_u64_be).collect();
+        ListingReviews::<T>::insert(listing_id, BoundedVec::truncate_from(existing.clone()));
+        let max_reviews = T::MaxReviewsPerUser::get().saturating_sub(1) as usize;
+        ReviewerReviews::<T>::insert(
+            &reviewer,
+            BoundedVec::truncate_from(existing.into_iter().take(max_reviews).collect::<Vec<_>>()),
+        );
+        submit::<T>(&provider);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(reviewer),
+            listing_id,
+            provider.clone(),
+            5,
+            BoundedVec::truncate_from(vec![b'c'; 512]),
+            true,
+        );
+
+        assert_eq!(ProviderReputations::<T>::get(&provider).unwrap().total_reviews, 2);
+    }
+
+    #[benchmark]
+    fn mark_helpful() {
+        let review_id = submit::<T>(&account("provider", 0, 0));
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), review_id);
+
+        assert_eq!(Reviews::<T>::get(review_id).unwrap().helpful_count, 1);
+    }
+
+    #[benchmark]
+    fn flag_review() {
+        let review_id = submit::<T>(&account("provider", 0, 0));
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), review_id);
+
+        assert!(Reviews::<T>::get(review_id).unwrap().flagged);
+    }
+
+    #[benchmark]
+    fn update_quality_score() {
+        let caller: T::AccountId = whitelisted_caller();
+        let provider: T::AccountId = account("provider", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), provider.clone(), 100);
+
+        assert_eq!(ProviderReputations::<T>::get(&provider).unwrap().quality_score, 100);
+    }
+
+    #[benchmark]
+    fn verify_provider() {
+        let caller: T::AccountId = whitelisted_caller();
+        let provider: T::AccountId = account("provider", 0, 0);
+        fill_badges::<T>(&provider);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), provider.clone());
+
+        assert!(ProviderBadges::<T>::get(&provider).contains(&BadgeType::Verified));
+    }
+
+    #[benchmark]
+    fn award_badge() {
+        let caller: T::AccountId = whitelisted_caller();
+        let provider: T::AccountId = account("provider", 0, 0);
+        fill_badges::<T>(&provider);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), provider.clone(), BadgeType::Verified);
+
+        assert_eq!(ProviderBadges::<T>::get(&provider).len(), 5);
+    }
+}
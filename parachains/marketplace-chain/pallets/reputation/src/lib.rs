@@ -9,6 +9,7 @@
 //! - Calculating reputation scores
 //! - Dispute resolution for ratings
 //! - Provider badges and achievements
+//! - Trade outcomes reported by `pallet-marketplace` (sales, refunds, disputes)
 //!
 //! ## Architecture Reference
 //! See parachain.md Section: "Marketplace Chain - Reputation"
@@ -23,14 +24,27 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Time};
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
     use sp_core::H256;
+    use crate::weights::WeightInfo;
+    use sp_runtime::{PerThing, Percent};
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Rating value (1-5 stars)
@@ -74,6 +88,10 @@ pub mod pallet {
         pub average_rating: u16,
         /// Total sales count
         pub total_sales: u64,
+        /// Purchases refunded by the provider
+        pub total_refunds: u32,
+        /// Disputes decided (partly) in the buyer's favour
+        pub disputes_lost: u32,
         /// Response rate percentage (0-100)
         pub response_rate: u8,
         /// Data quality score (0-100)
@@ -99,6 +117,17 @@ pub mod pallet {
         FastResponder,
     }
 
+    /// Settled outcome of a marketplace purchase
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TradeOutcome {
+        /// Escrow paid out to the provider
+        Completed,
+        /// Provider refunded the buyer
+        Refunded,
+        /// Dispute resolved by the arbiter, refunding `buyer_share` to the buyer
+        Disputed { buyer_share: Percent },
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The overarching event type
@@ -110,6 +139,13 @@ pub mod pallet {
         /// Maximum reviews per user
         #[pallet::constant]
         type MaxReviewsPerUser: Get<u32>;
+
+        /// Quality score deducted for a dispute fully decided in the buyer's favour
+        #[pallet::constant]
+        type DisputePenalty: Get<u8>;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
     }
 
     /// Storage for reviews by review_id
@@ -187,6 +223,11 @@ pub mod pallet {
         },
         /// Provider verified [provider]
         ProviderVerified { provider: T::AccountId },
+        /// Trade outcome recorded [provider, outcome]
+        TradeOutcomeRecorded {
+            provider: T::AccountId,
+            outcome: TradeOutcome,
+        },
     }
 
     #[pallet::error]
@@ -209,7 +250,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Submit a review
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::submit_review())]
         pub fn submit_review(
             origin: OriginFor<T>,
             listing_id: H256,
@@ -280,7 +321,7 @@ pub mod pallet {
 
         /// Mark review as helpful
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::mark_helpful())]
         pub fn mark_helpful(origin: OriginFor<T>, review_id: H256) -> DispatchResult {
             let _who = ensure_signed(origin)?;
 
@@ -297,7 +338,7 @@ pub mod pallet {
 
         /// Flag review as inappropriate
         #[pallet::call_index(2)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::flag_review())]
         pub fn flag_review(origin: OriginFor<T>, review_id: H256) -> DispatchResult {
             let _who = ensure_signed(origin)?;
 
@@ -314,7 +355,7 @@ pub mod pallet {
 
         /// Update provider quality score (admin/oracle)
         #[pallet::call_index(3)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::update_quality_score())]
         pub fn update_quality_score(
             origin: OriginFor<T>,
             provider: T::AccountId,
@@ -335,6 +376,8 @@ pub mod pallet {
                         total_reviews: 0,
                         average_rating: 0,
                         total_sales: 0,
+                        total_refunds: 0,
+                        disputes_lost: 0,
                         response_rate: 0,
                         quality_score: score,
                         verified: false,
@@ -348,7 +391,7 @@ pub mod pallet {
 
         /// Verify provider (admin/oracle)
         #[pallet::call_index(4)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::verify_provider())]
         pub fn verify_provider(origin: OriginFor<T>, provider: T::AccountId) -> DispatchResult {
             let _who = ensure_signed(origin)?;
 
@@ -363,6 +406,8 @@ pub mod pallet {
                         total_reviews: 0,
                         average_rating: 0,
                         total_sales: 0,
+                        total_refunds: 0,
+                        disputes_lost: 0,
                         response_rate: 0,
                         quality_score: 0,
                         verified: true,
@@ -383,7 +428,7 @@ pub mod pallet {
 
         /// Award badge to provider (admin/automated)
         #[pallet::call_index(5)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(T::WeightInfo::award_badge())]
         pub fn award_badge(
             origin: OriginFor<T>,
             provider: T::AccountId,
@@ -428,6 +473,8 @@ pub mod pallet {
                         total_reviews: 1,
                         average_rating: (new_rating as u16) * 100,
                         total_sales: 0,
+                        total_refunds: 0,
+                        disputes_lost: 0,
                         response_rate: 0,
                         quality_score: 0,
                         verified: false,
@@ -444,6 +491,51 @@ pub mod pallet {
             });
         }
 
+        /// Record the settled outcome of a marketplace purchase
+        pub fn record_trade_outcome(provider: &T::AccountId, outcome: TradeOutcome) {
+            let now = T::TimeProvider::now().try_into().ok().unwrap_or(0);
+
+            ProviderReputations::<T>::mutate(provider, |maybe_reputation| {
+                let reputation = maybe_reputation.get_or_insert_with(|| ProviderReputation {
+                    provider: provider.clone(),
+                    total_reviews: 0,
+                    average_rating: 0,
+                    total_sales: 0,
+                    total_refunds: 0,
+                    disputes_lost: 0,
+                    response_rate: 0,
+                    quality_score: 0,
+                    verified: false,
+                    updated_at: now,
+                });
+
+                match &outcome {
+                    TradeOutcome::Completed => {
+                        reputation.total_sales = reputation.total_sales.saturating_add(1);
+                    }
+                    TradeOutcome::Refunded => {
+                        reputation.total_refunds = reputation.total_refunds.saturating_add(1);
+                    }
+                    TradeOutcome::Disputed { buyer_share } => {
+                        if buyer_share.is_zero() {
+                            reputation.total_sales = reputation.total_sales.saturating_add(1);
+                        } else {
+                            reputation.disputes_lost = reputation.disputes_lost.saturating_add(1);
+                            let penalty = buyer_share.mul_ceil(T::DisputePenalty::get());
+                            reputation.quality_score =
+                                reputation.quality_score.saturating_sub(penalty);
+                        }
+                    }
+                }
+                reputation.updated_at = now;
+            });
+
+            Self::deposit_event(Event::TradeOutcomeRecorded {
+                provider: provider.clone(),
+                outcome,
+            });
+        }
+
         /// Award badge to provider
         fn award_badge(provider: &T::AccountId, badge_type: BadgeType) {
             let mut badges = ProviderBadges::<T>::get(provider);
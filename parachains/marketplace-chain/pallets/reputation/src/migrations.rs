@@ -0,0 +1,60 @@
+//! Storage migrations for the reputation pallet
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration, pallet_prelude::*, traits::UncheckedOnRuntimeUpgrade,
+};
+
+/// Version 1 counts the refunds and lost disputes of each provider
+pub mod v1 {
+    use super::*;
+
+    /// Provider reputation of version 0, before trade outcomes were recorded
+    #[derive(Encode, Decode)]
+    pub struct OldProviderReputation<T: Config> {
+        pub provider: T::AccountId,
+        pub total_reviews: u32,
+        pub average_rating: u16,
+        pub total_sales: u64,
+        pub response_rate: u8,
+        pub quality_score: u8,
+        pub verified: bool,
+        pub updated_at: u64,
+    }
+
+    /// Start every existing provider with no refunds and no lost disputes
+    pub struct VersionUncheckedMigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+
+            ProviderReputations::<T>::translate::<OldProviderReputation<T>, _>(|_, old| {
+                translated += 1;
+                Some(ProviderReputation {
+                    provider: old.provider,
+                    total_reviews: old.total_reviews,
+                    average_rating: old.average_rating,
+                    total_sales: old.total_sales,
+                    total_refunds: 0,
+                    disputes_lost: 0,
+                    response_rate: old.response_rate,
+                    quality_score: old.quality_score,
+                    verified: old.verified,
+                    updated_at: old.updated_at,
+                })
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+    }
+
+    /// Migrate `ProviderReputations` from version 0 to 1
+    pub type MigrateToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
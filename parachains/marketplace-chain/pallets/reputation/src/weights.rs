@@ -0,0 +1,123 @@
+//! Estimated weights for `pallet_reputation`
+//!
+//! These are not benchmark results. Each weight is an estimated execution time, the proof size
+//! of the largest values the call may read, and the database reads and writes listed above
+//! it. Replace this file with the output of the pallet's benchmarks on reference hardware
+//! before a production release; `scripts/benchmark-weights.sh` regenerates the weights of
+//! every pallet with:
+//!
+//! ```text
+//! ./target/release/marketplace-node benchmark pallet \
+//!     --chain dev --pallet pallet_reputation --extrinsic '*' --steps 50 --repeat 20 \
+//!     --template ../scripts/frame-weight-template.hbs \
+//!     --output pallets/reputation/src/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_reputation`.
+pub trait WeightInfo {
+    fn submit_review() -> Weight;
+    fn mark_helpful() -> Weight;
+    fn flag_review() -> Weight;
+    fn update_quality_score() -> Weight;
+    fn verify_provider() -> Weight;
+    fn award_badge() -> Weight;
+}
+
+/// Estimated weights for `pallet_reputation`, charged by the runtimes until benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: `Reputation::ReviewerReviews`, `Timestamp::Now`, `Reputation::ReviewCount`,
+    //     `Reputation::ListingReviews`, `Reputation::ProviderReputations`
+    // Writes: `Reputation::ReviewerReviews`, `Reputation::ReviewCount`,
+    //     `Reputation::ListingReviews`, `Reputation::ProviderReputations`, `Reputation::Reviews`
+    fn submit_review() -> Weight {
+        Weight::from_parts(57_000_000, 48_529)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    // Reads: `Reputation::Reviews`
+    // Writes: `Reputation::Reviews`
+    fn mark_helpful() -> Weight {
+        Weight::from_parts(16_000_000, 4_187)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Reputation::Reviews`
+    // Writes: `Reputation::Reviews`
+    fn flag_review() -> Weight {
+        Weight::from_parts(16_000_000, 4_187)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Reputation::ProviderReputations`, `Timestamp::Now`
+    // Writes: `Reputation::ProviderReputations`
+    fn update_quality_score() -> Weight {
+        Weight::from_parts(18_000_000, 3_617)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    // Reads: `Reputation::ProviderReputations`, `Timestamp::Now`, `Reputation::ProviderBadges`
+    // Writes: `Reputation::ProviderReputations`, `Reputation::ProviderBadges`
+    fn verify_provider() -> Weight {
+        Weight::from_parts(23_000_000, 3_617)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    // Reads: `Reputation::ProviderBadges`
+    // Writes: `Reputation::ProviderBadges`
+    fn award_badge() -> Weight {
+        Weight::from_parts(15_000_000, 3_539)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+}
+
+// For tests.
+impl WeightInfo for () {
+    fn submit_review() -> Weight {
+        Weight::from_parts(57_000_000, 48_529)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn mark_helpful() -> Weight {
+        Weight::from_parts(16_000_000, 4_187)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn flag_review() -> Weight {
+        Weight::from_parts(16_000_000, 4_187)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn update_quality_score() -> Weight {
+        Weight::from_parts(18_000_000, 3_617)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+
+    fn verify_provider() -> Weight {
+        Weight::from_parts(23_000_000, 3_617)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+
+    fn award_badge() -> Weight {
+        Weight::from_parts(15_000_000, 3_539)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+}
@@ -16,6 +16,7 @@ use frame_support::{
     construct_runtime, parameter_types,
     traits::{ConstU128, ConstU32, ConstU64, ConstU8, Everything},
     weights::{ConstantMultiplier, Weight},
+    PalletId,
 };
 use frame_system::EnsureRoot;
 use sp_api::impl_runtime_apis;
@@ -23,7 +24,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
     create_runtime_str, generic, impl_opaque_keys,
     traits::{
-        AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify,
+        AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+        Verify,
     },
     transaction_validity::{TransactionSource, TransactionValidity},
     ApplyExtrinsicResult, MultiSignature,
@@ -35,11 +37,15 @@ use sp_version::RuntimeVersion;
 
 // XCM imports
 use cumulus_pallet_parachain_system::RelayNumberStrictlyIncreases;
+use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
+use frame_support::traits::TransformOrigin;
+use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
+use polkadot_runtime_common::xcm_sender::NoPriceForMessageDelivery;
 use xcm::latest::prelude::*;
 use xcm_builder::{
     AccountId32Aliases, AllowUnpaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
     ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SignedAccountId32AsNative,
-    SovereignSignedViaLocation,
+    SovereignSignedViaLocation, XcmPassthrough,
 };
 use xcm_executor::XcmExecutor;
 
@@ -109,6 +115,12 @@ pub type SignedExtra = (
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+    pallet_marketplace::migrations::v1::MigrateToV1<Runtime>,
+    pallet_reputation::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -116,6 +128,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
@@ -281,10 +294,10 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnSystemEvent = ();
     type SelfParaId = parachain_info::Pallet<Runtime>;
-    type OutboundXcmpMessageSource = ();
+    type OutboundXcmpMessageSource = XcmpQueue;
     type DmpMessageHandler = ();
     type ReservedDmpWeight = ReservedDmpWeight;
-    type XcmpMessageHandler = ();
+    type XcmpMessageHandler = XcmpQueue;
     type ReservedXcmpWeight = ReservedXcmpWeight;
     type CheckAssociatedRelayNumber = RelayNumberStrictlyIncreases;
     type WeightInfo = ();
@@ -312,14 +325,22 @@ pub type XcmOriginToCallOrigin = (
     RelayChainAsNative<RelayChainOrigin, RuntimeOrigin>,
     SiblingParachainAsNative<cumulus_pallet_xcm::Origin, RuntimeOrigin>,
     SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+    // Keeps the XCM origin of sibling calls (e.g. account migrations) as `pallet_xcm::Origin`
+    XcmPassthrough<RuntimeOrigin>,
 );
 
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
+/// Routes XCM to the relay chain (UMP) and to sibling parachains (XCMP)
+pub type XcmRouter = (
+    cumulus_primitives_utility::ParentAsUmp<ParachainSystem, (), ()>,
+    XcmpQueue,
+);
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
     type RuntimeCall = RuntimeCall;
-    type XcmSender = ();
+    type XcmSender = XcmRouter;
     type AssetTransactor = ();
     type OriginConverter = XcmOriginToCallOrigin;
     type IsReserve = ();
@@ -328,7 +349,7 @@ impl xcm_executor::Config for XcmConfig {
     type Barrier = Barrier;
     type Weigher = FixedWeightBounds<ConstU64<10>, RuntimeCall, ConstU32<100>>;
     type Trader = ();
-    type ResponseHandler = ();
+    type ResponseHandler = PolkadotXcm;
     type AssetTrap = ();
     type AssetClaims = ();
     type SubscriptionService = ();
@@ -347,7 +368,7 @@ impl xcm_executor::Config for XcmConfig {
 impl pallet_xcm::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, ()>;
-    type XcmRouter = ();
+    type XcmRouter = XcmRouter;
     type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocationToAccountId>;
     type XcmExecuteFilter = Everything;
     type XcmExecutor = XcmExecutor<XcmConfig>;
@@ -375,41 +396,119 @@ impl cumulus_pallet_xcm::Config for Runtime {
     type XcmExecutor = XcmExecutor<XcmConfig>;
 }
 
+impl cumulus_pallet_xcmp_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type ChannelInfo = ParachainSystem;
+    type VersionWrapper = PolkadotXcm;
+    type XcmpQueue = TransformOrigin<MessageQueue, AggregateMessageOrigin, ParaId, ParaIdToSibling>;
+    type MaxInboundSuspended = ConstU32<1_000>;
+    type MaxActiveOutboundChannels = ConstU32<128>;
+    type MaxPageSize = ConstU32<{ 1 << 16 }>;
+    type ControllerOrigin = EnsureRoot<AccountId>;
+    type ControllerOriginConverter = XcmOriginToCallOrigin;
+    type WeightInfo = ();
+    type PriceForSiblingDelivery = NoPriceForMessageDelivery<ParaId>;
+}
+
+parameter_types! {
+    /// A quarter of the block's execution time and proof size for incoming messages
+    pub MessageQueueServiceWeight: Weight = Weight::from_parts(
+        WEIGHT_REF_TIME_PER_SECOND / 4,
+        cumulus_primitives_core::relay_chain::MAX_POV_SIZE as u64 / 4,
+    );
+}
+
+impl pallet_message_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MessageProcessor = xcm_builder::ProcessXcmMessage<
+        AggregateMessageOrigin,
+        XcmExecutor<XcmConfig>,
+        RuntimeCall,
+    >;
+    type Size = u32;
+    type QueueChangeHandler = NarrowOriginToSibling<XcmpQueue>;
+    type QueuePausedQuery = NarrowOriginToSibling<XcmpQueue>;
+    type HeapSize = ConstU32<{ 64 * 1024 }>;
+    type MaxStale = ConstU32<8>;
+    type ServiceWeight = MessageQueueServiceWeight;
+    type IdleMaxServiceWeight = ();
+}
+
 // Custom pallets configuration
 
 parameter_types! {
     pub const MaxListingsPerProvider: u32 = 100;
+    /// IdentityConsent chain (Para ID 2000), requesting account migrations and erasures
+    pub IdentityConsentLocation: Location = Location::new(1, [Parachain(2000)]);
 }
 
+/// Origin of account migrations and erasures requested by the IdentityConsent chain
+pub type IdentityConsentOrigin =
+    pallet_xcm::EnsureXcm<frame_support::traits::Equals<IdentityConsentLocation>>;
+
 impl pallet_data_listings::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxListingsPerProvider = MaxListingsPerProvider;
+    type MigrationOrigin = IdentityConsentOrigin;
+    type ErasureOrigin = IdentityConsentOrigin;
+    type WeightInfo = pallet_data_listings::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
     pub const PlatformFeePercent: u8 = 5; // 5% platform fee
+    pub const MarketplaceTreasuryPalletId: PalletId = PalletId(*b"px/trsry");
+    pub MarketplaceTreasuryAccount: AccountId =
+        MarketplaceTreasuryPalletId::get().into_account_truncating();
+    pub const DisputePeriod: u64 = 3 * 24 * 60 * 60 * 1000; // 3 days (ms)
+    pub const SubscriptionGracePeriod: u64 = 3 * 24 * 60 * 60 * 1000; // 3 days (ms)
+    pub const MaxRenewalsPerBlock: u32 = 50;
 }
 
 impl pallet_marketplace::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
+    type Currency = Balances;
     type PlatformFeePercent = PlatformFeePercent;
+    type TreasuryAccount = MarketplaceTreasuryAccount;
+    type ArbiterOrigin = EnsureRoot<AccountId>;
+    type DisputePeriod = DisputePeriod;
+    type SubscriptionGracePeriod = SubscriptionGracePeriod;
+    type MaxRenewalsPerBlock = MaxRenewalsPerBlock;
+    type WeightInfo = pallet_marketplace::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MarketplaceBenchmarkHelper;
+}
+
+/// Moves the timestamp forward for marketplace settlement benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MarketplaceBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_marketplace::BenchmarkHelper for MarketplaceBenchmarkHelper {
+    fn set_time(now: u64) {
+        Timestamp::set_timestamp(now);
+    }
 }
 
 parameter_types! {
     pub const MaxReviewsPerUser: u32 = 500;
+    pub const DisputePenalty: u8 = 10;
 }
 
 impl pallet_reputation::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
     type MaxReviewsPerUser = MaxReviewsPerUser;
+    type DisputePenalty = DisputePenalty;
+    type WeightInfo = pallet_reputation::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_analytics::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type TimeProvider = Timestamp;
+    type WeightInfo = pallet_analytics::weights::SubstrateWeight<Runtime>;
 }
 
 // Use trait to avoid cyclic dependency
@@ -437,8 +536,10 @@ construct_runtime!(
         ParachainInfo: parachain_info = 31,
 
         // XCM
-        XcmpQueue: cumulus_pallet_xcm = 40,
+        CumulusXcm: cumulus_pallet_xcm = 40,
         PolkadotXcm: pallet_xcm = 41,
+        XcmpQueue: cumulus_pallet_xcmp_queue = 42,
+        MessageQueue: pallet_message_queue = 43,
 
         // Custom pallets
         DataListings: pallet_data_listings = 50,
@@ -448,6 +549,16 @@ construct_runtime!(
     }
 );
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benches {
+    frame_benchmarking::define_benchmarks!(
+        [pallet_data_listings, DataListings]
+        [pallet_marketplace, Marketplace]
+        [pallet_reputation, Reputation]
+        [pallet_analytics, Analytics]
+    );
+}
+
 /// The type used to represent the kinds of proxying allowed.
 pub type RuntimeGenesisConfig = frame_support::genesis_config_prelude::RuntimeGenesisConfig;
 
@@ -545,4 +656,36 @@ impl_runtime_apis! {
             ParachainSystem::collect_collation_info(header)
         }
     }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn benchmark_metadata(extra: bool) -> (
+            Vec<frame_benchmarking::BenchmarkList>,
+            Vec<frame_support::traits::StorageInfo>,
+        ) {
+            use frame_benchmarking::{Benchmarking, BenchmarkList};
+            use frame_support::traits::StorageInfoTrait;
+
+            let mut list = Vec::<BenchmarkList>::new();
+            list_benchmarks!(list, extra);
+
+            let storage_info = AllPalletsWithSystem::storage_info();
+            (list, storage_info)
+        }
+
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+            use frame_benchmarking::{BenchmarkBatch, Benchmarking};
+            use frame_support::traits::WhitelistedStorageKeys;
+
+            let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
+
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+            add_benchmarks!(params, batches);
+
+            Ok(batches)
+        }
+    }
 }
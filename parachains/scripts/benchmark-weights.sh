@@ -0,0 +1,87 @@
+#!/bin/bash
+
+# Patient X Parachains - Benchmark Weights
+# This script builds each node with runtime benchmarks and regenerates the weights.rs file of
+# every custom pallet from its benchmarks. Run it on reference hardware.
+
+set -e
+
+echo "========================================="
+echo "Patient X Parachains - Benchmarking Weights"
+echo "========================================="
+
+# Colors for output
+RED='\033[0;31m'
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+NC='\033[0m' # No Color
+
+TEMPLATE="$(pwd)/scripts/frame-weight-template.hbs"
+
+# Function to benchmark the pallets of a parachain
+# Each pallet is given as <crate name>:<directory under pallets/>
+benchmark_parachain() {
+    local chain_name=$1
+    local chain_dir=$2
+    local node=$3
+    shift 3
+
+    echo ""
+    echo -e "${YELLOW}Benchmarking $chain_name...${NC}"
+    echo "========================================="
+
+    cd "$chain_dir"
+
+    echo "Building node with runtime benchmarks..."
+    cargo build --release --features runtime-benchmarks -p "$node"
+
+    for pallet in "$@"; do
+        local crate_name=${pallet%%:*}
+        local pallet_dir=${pallet##*:}
+
+        echo "Benchmarking $crate_name..."
+        "./target/release/$node" benchmark pallet \
+            --chain dev \
+            --pallet "$crate_name" \
+            --extrinsic '*' \
+            --steps 50 \
+            --repeat 20 \
+            --template "$TEMPLATE" \
+            --output "pallets/$pallet_dir/src/weights.rs"
+    done
+
+    cd - > /dev/null
+
+    echo -e "${GREEN}✓ $chain_name weights generated${NC}"
+}
+
+# Check if we're in the parachains directory
+if [ ! -f "README.md" ]; then
+    echo -e "${RED}Error: Please run this script from the parachains directory${NC}"
+    exit 1
+fi
+
+benchmark_parachain "IdentityConsent Chain" "identity-consent-chain" "identity-consent-node" \
+    pallet_identity_registry:identity-registry \
+    pallet_consent_manager:consent-manager \
+    pallet_authentication:authentication \
+    pallet_credentials:credentials \
+    pallet_organizations:organizations
+
+benchmark_parachain "HealthData Chain" "health-data-chain" "health-data-node" \
+    pallet_health_records:health-records \
+    pallet_ipfs_integration:ipfs-integration \
+    pallet_access_control:access-control \
+    pallet_encryption:encryption
+
+benchmark_parachain "Marketplace Chain" "marketplace-chain" "marketplace-node" \
+    pallet_data_listings:data-listings \
+    pallet_marketplace:marketplace \
+    pallet_reputation:reputation \
+    pallet_analytics:analytics
+
+echo ""
+echo "========================================="
+echo -e "${GREEN}Benchmarking Complete!${NC}"
+echo "========================================="
+echo "Review the regenerated weights.rs files and commit them."
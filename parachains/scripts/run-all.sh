@@ -259,6 +259,11 @@ if [ "$SKIP_TEST" = false ] && [ "$CHECK_ONLY" = false ]; then
         TEST_ERRORS=$((TEST_ERRORS + 1))
     fi
 
+    # Test cross-chain flows
+    if ! test_chain "XCM Simulator" "xcm-simulator"; then
+        TEST_ERRORS=$((TEST_ERRORS + 1))
+    fi
+
     echo ""
     echo "========================================="
     if [ $TEST_ERRORS -eq 0 ]; then
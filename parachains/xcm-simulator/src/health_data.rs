@@ -0,0 +1,221 @@
+//! HealthData parachain mock (Para ID 2001).
+//!
+//! Mirrors the XCM configuration of `health-data-chain-runtime` closely enough to exercise
+//! the consent verification flow of `pallet-access-control`, and the `XcmPassthrough` origin
+//! through which the IdentityConsent chain mirrors its registry.
+
+use frame_support::{
+    construct_runtime, derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64, EitherOfDiverse, Equals, Everything, Nothing},
+    weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
+};
+use frame_system::EnsureRoot;
+use polkadot_parachain_primitives::primitives::Sibling;
+use sp_core::H256;
+use sp_runtime::{traits::IdentityLookup, AccountId32};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+    AccountId32Aliases, AllowUnpaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
+    FrameTransactionalProcessor, FungibleAdapter, IsConcrete, ParentIsPreset,
+    SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
+    SovereignSignedViaLocation, XcmPassthrough,
+};
+use xcm_executor::XcmExecutor;
+use xcm_simulator::mock_message_queue;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+    type Block = Block;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Runtime {
+    type Balance = Balance;
+    type AccountStore = System;
+}
+
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<5>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const RelayLocation: Location = Location::parent();
+    pub RelayNetwork: NetworkId = ByGenesis([0; 32]);
+    pub UniversalLocation: InteriorLocation =
+        [GlobalConsensus(RelayNetwork::get()), Parachain(crate::HEALTH_DATA_PARA_ID)].into();
+    pub UnitWeightCost: Weight = Weight::from_parts(1_000, 1_000);
+    pub const MaxInstructions: u32 = 100;
+    pub const MaxAssetsIntoHolding: u32 = 64;
+    /// The runtime's `MessageQueueServiceWeight`: a quarter of the block's execution time and
+    /// of the relay chain's 5 MiB `MAX_POV_SIZE`
+    pub MessageQueueServiceWeight: Weight =
+        Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 4, 5 * 1024 * 1024 / 4);
+}
+
+pub type LocationToAccountId = (
+    ParentIsPreset<AccountId>,
+    SiblingParachainConvertsVia<Sibling, AccountId>,
+    AccountId32Aliases<RelayNetwork, AccountId>,
+);
+
+pub type XcmOriginToCallOrigin = (
+    SovereignSignedViaLocation<LocationToAccountId, RuntimeOrigin>,
+    SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+    XcmPassthrough<RuntimeOrigin>,
+);
+
+pub type LocalAssetTransactor =
+    FungibleAdapter<Balances, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>;
+
+pub type XcmRouter = crate::ParachainXcmRouter<MsgQueue>;
+pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+    type RuntimeCall = RuntimeCall;
+    type XcmSender = XcmRouter;
+    type AssetTransactor = LocalAssetTransactor;
+    type OriginConverter = XcmOriginToCallOrigin;
+    type IsReserve = ();
+    type IsTeleporter = ();
+    type UniversalLocation = UniversalLocation;
+    type Barrier = Barrier;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type Trader = ();
+    type ResponseHandler = PolkadotXcm;
+    type AssetTrap = PolkadotXcm;
+    type AssetLocker = ();
+    type AssetExchanger = ();
+    type AssetClaims = PolkadotXcm;
+    type SubscriptionService = PolkadotXcm;
+    type PalletInstancesInfo = AllPalletsWithSystem;
+    type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+    type FeeManager = ();
+    type MessageExporter = ();
+    type UniversalAliases = Nothing;
+    type CallDispatcher = RuntimeCall;
+    type SafeCallFilter = Everything;
+    type Aliasers = Nothing;
+    type TransactionalProcessor = FrameTransactionalProcessor;
+    type HrmpNewChannelOpenRequestHandler = ();
+    type HrmpChannelAcceptedHandler = ();
+    type HrmpChannelClosingHandler = ();
+    type XcmRecorder = PolkadotXcm;
+}
+
+impl mock_message_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmRouter = XcmRouter;
+    type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmExecuteFilter = Nothing;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type XcmTeleportFilter = Nothing;
+    type XcmReserveTransferFilter = Nothing;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type UniversalLocation = UniversalLocation;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+    type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+    type Currency = Balances;
+    type CurrencyMatcher = ();
+    type TrustedLockers = ();
+    type SovereignAccountOf = LocationToAccountId;
+    type MaxLockers = ConstU32<8>;
+    type MaxRemoteLockConsumers = ConstU32<0>;
+    type RemoteLockConsumerIdentifier = ();
+    type WeightInfo = pallet_xcm::TestWeightInfo;
+    type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+parameter_types! {
+    pub IdentityConsentLocation: Location =
+        Location::new(1, [Parachain(crate::IDENTITY_CONSENT_PARA_ID)]);
+    pub const ConsentManagerPalletIndex: u8 = 51;
+    pub ConsentQueryWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+    pub const ConsentQueryTimeout: u64 = 100;
+    pub const DefaultGrantDuration: u64 = 30 * 24 * 60 * 60 * 1000;
+    pub static AutoGrant: bool = true;
+    pub const MaxPendingRequests: u32 = 100;
+    pub const PendingRequestTimeout: u64 = 10;
+    pub const MaxBulkRequests: u32 = 10;
+    /// Owners of the health records known to the mock (record_id, patient)
+    pub static RecordOwners: Vec<(H256, AccountId)> = vec![];
+}
+
+/// Record lookup backed by the `RecordOwners` list, without categories or versions
+pub struct MockRecords;
+impl pallet_access_control::RecordInspect<AccountId, u8> for MockRecords {
+    fn record_owner(record_id: &H256) -> Option<AccountId> {
+        RecordOwners::get().into_iter().find(|(id, _)| id == record_id).map(|(_, owner)| owner)
+    }
+
+    fn record_category(_record_id: &H256) -> Option<u8> {
+        None
+    }
+
+    fn record_created_at(_record_id: &H256) -> Option<u64> {
+        None
+    }
+
+    fn record_version(_record_id: &H256) -> Option<u32> {
+        None
+    }
+}
+
+impl pallet_access_control::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type TimeProvider = Timestamp;
+    type XcmSender = XcmRouter;
+    type ConsentChainLocation = IdentityConsentLocation;
+    type ConsentManagerPalletIndex = ConsentManagerPalletIndex;
+    type ConsentQueryWeight = ConsentQueryWeight;
+    type ConsentQueryTimeout = ConsentQueryTimeout;
+    type ConsentResponseOrigin = pallet_xcm::EnsureResponse<Everything>;
+    type AutoGrantOnConsent = AutoGrant;
+    type DefaultGrantDuration = DefaultGrantDuration;
+    type RecordCategory = u8;
+    type Records = MockRecords;
+    type MaxPendingRequests = MaxPendingRequests;
+    type PendingRequestTimeout = PendingRequestTimeout;
+    type MaxBulkRequests = MaxBulkRequests;
+    type MaxExpiriesPerBlock = ConstU32<50>;
+    type RegistryOrigin = EitherOfDiverse<
+        EnsureRoot<AccountId>,
+        pallet_xcm::EnsureXcm<Equals<IdentityConsentLocation>>,
+    >;
+    type BreakGlassDuration = ConstU64<{ 4 * 60 * 60 * 1000 }>;
+    type WardCalls = Everything;
+    type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+    pub enum Runtime {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        MsgQueue: mock_message_queue,
+        PolkadotXcm: pallet_xcm,
+        AccessControl: pallet_access_control = 52,
+    }
+);
@@ -0,0 +1,279 @@
+//! IdentityConsent parachain mock (Para ID 2000).
+//!
+//! Mirrors the XCM configuration of `identity-consent-chain-runtime`, including the
+//! `XcmPassthrough` origin used to authorize consent queries from the HealthData chain, and the
+//! notifications through which identity recoveries and erasures reach the HealthData and
+//! Marketplace chains.
+
+use codec::Encode;
+use frame_support::{
+    construct_runtime, derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64, Equals, Everything, Nothing},
+    weights::Weight,
+};
+use frame_system::EnsureRoot;
+use polkadot_parachain_primitives::primitives::Sibling;
+use sp_runtime::{traits::IdentityLookup, AccountId32};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+    AccountId32Aliases, AllowUnpaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
+    FrameTransactionalProcessor, FungibleAdapter, IsConcrete, ParentIsPreset,
+    SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
+    SovereignSignedViaLocation, XcmPassthrough,
+};
+use xcm_executor::XcmExecutor;
+use xcm_simulator::mock_message_queue;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+    type Block = Block;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Runtime {
+    type Balance = Balance;
+    type AccountStore = System;
+}
+
+impl pallet_timestamp::Config for Runtime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<5>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const RelayLocation: Location = Location::parent();
+    pub RelayNetwork: NetworkId = ByGenesis([0; 32]);
+    pub UniversalLocation: InteriorLocation =
+        [GlobalConsensus(RelayNetwork::get()), Parachain(crate::IDENTITY_CONSENT_PARA_ID)].into();
+    pub UnitWeightCost: Weight = Weight::from_parts(1_000, 1_000);
+    pub const MaxInstructions: u32 = 100;
+    pub const MaxAssetsIntoHolding: u32 = 64;
+}
+
+pub type LocationToAccountId = (
+    ParentIsPreset<AccountId>,
+    SiblingParachainConvertsVia<Sibling, AccountId>,
+    AccountId32Aliases<RelayNetwork, AccountId>,
+);
+
+pub type XcmOriginToCallOrigin = (
+    SovereignSignedViaLocation<LocationToAccountId, RuntimeOrigin>,
+    SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+    XcmPassthrough<RuntimeOrigin>,
+);
+
+pub type LocalAssetTransactor =
+    FungibleAdapter<Balances, IsConcrete<RelayLocation>, LocationToAccountId, AccountId, ()>;
+
+pub type XcmRouter = crate::ParachainXcmRouter<MsgQueue>;
+pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+    type RuntimeCall = RuntimeCall;
+    type XcmSender = XcmRouter;
+    type AssetTransactor = LocalAssetTransactor;
+    type OriginConverter = XcmOriginToCallOrigin;
+    type IsReserve = ();
+    type IsTeleporter = ();
+    type UniversalLocation = UniversalLocation;
+    type Barrier = Barrier;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type Trader = ();
+    type ResponseHandler = PolkadotXcm;
+    type AssetTrap = PolkadotXcm;
+    type AssetLocker = ();
+    type AssetExchanger = ();
+    type AssetClaims = PolkadotXcm;
+    type SubscriptionService = PolkadotXcm;
+    type PalletInstancesInfo = AllPalletsWithSystem;
+    type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+    type FeeManager = ();
+    type MessageExporter = ();
+    type UniversalAliases = Nothing;
+    type CallDispatcher = RuntimeCall;
+    type SafeCallFilter = Everything;
+    type Aliasers = Nothing;
+    type TransactionalProcessor = FrameTransactionalProcessor;
+    type HrmpNewChannelOpenRequestHandler = ();
+    type HrmpChannelAcceptedHandler = ();
+    type HrmpChannelClosingHandler = ();
+    type XcmRecorder = PolkadotXcm;
+}
+
+impl mock_message_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmRouter = XcmRouter;
+    type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmExecuteFilter = Nothing;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type XcmTeleportFilter = Nothing;
+    type XcmReserveTransferFilter = Nothing;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type UniversalLocation = UniversalLocation;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+    type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+    type Currency = Balances;
+    type CurrencyMatcher = ();
+    type TrustedLockers = ();
+    type SovereignAccountOf = LocationToAccountId;
+    type MaxLockers = ConstU32<8>;
+    type MaxRemoteLockConsumers = ConstU32<0>;
+    type RemoteLockConsumerIdentifier = ();
+    type WeightInfo = pallet_xcm::TestWeightInfo;
+    type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+impl pallet_identity_registry::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxIdentitiesPerAccount = ConstU32<1>;
+    type VerifierOrigin = EnsureRoot<AccountId>;
+    type MaxVerifiers = ConstU32<16>;
+    type MaxDidControllers = ConstU32<4>;
+    type MaxVerificationMethods = ConstU32<8>;
+    type MaxServices = ConstU32<4>;
+    type MaxGuardians = ConstU32<8>;
+    type MaxGuardianships = ConstU32<4>;
+    type RecoveryHandler = CrossChainNotifier;
+    type GuardianshipOrigin = EnsureRoot<AccountId>;
+    type GuardianshipHandler = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub HealthDataLocation: Location = Location::new(1, [Parachain(crate::HEALTH_DATA_PARA_ID)]);
+    pub MarketplaceLocation: Location = Location::new(1, [Parachain(crate::MARKETPLACE_PARA_ID)]);
+}
+
+/// Weights of the calls sent to the HealthData and Marketplace chains, as configured there
+type AccessControlWeights =
+    <crate::health_data::Runtime as pallet_access_control::Config>::WeightInfo;
+type DataListingsWeights =
+    <crate::marketplace::Runtime as pallet_data_listings::Config>::WeightInfo;
+
+/// Mirrors the runtime's `CrossChainNotifier` for the pallets of the simulated chains
+pub struct CrossChainNotifier;
+
+impl CrossChainNotifier {
+    /// Message for unpaid execution of a call of the destination chain, bounded by its weight
+    /// there
+    fn message<C: Encode>(call: C, weight: Weight) -> Xcm<()> {
+        Xcm(vec![
+            UnpaidExecution { weight_limit: Unlimited, check_origin: None },
+            Transact {
+                origin_kind: OriginKind::Xcm,
+                require_weight_at_most: weight,
+                call: call.encode().into(),
+            },
+        ])
+    }
+
+    /// Messages sent to the other chains when `owner` exercises the right to erasure
+    pub fn erasure_messages(owner: &AccountId) -> Vec<(Location, Xcm<()>)> {
+        vec![(
+            MarketplaceLocation::get(),
+            Self::message(
+                crate::marketplace::RuntimeCall::DataListings(
+                    pallet_data_listings::Call::delist_provider { provider: owner.clone() },
+                ),
+                <DataListingsWeights as pallet_data_listings::WeightInfo>::delist_provider(),
+            ),
+        )]
+    }
+
+    /// Send `message` to `dest`
+    fn send(dest: Location, message: Xcm<()>) -> sp_runtime::DispatchResult {
+        send_xcm::<XcmRouter>(dest, message)
+            .map(|_| ())
+            .map_err(|_| sp_runtime::DispatchError::Other("cross-chain call not sent"))
+    }
+}
+
+impl pallet_identity_registry::OnIdentityRecovered<AccountId> for CrossChainNotifier {
+    fn on_identity_recovered(lost: &AccountId, new: &AccountId) -> sp_runtime::DispatchResult {
+        let (old, new) = (lost.clone(), new.clone());
+        ConsentManager::migrate_account(RuntimeOrigin::root(), old.clone(), new.clone())?;
+        Self::send(
+            HealthDataLocation::get(),
+            Self::message(
+                crate::health_data::RuntimeCall::AccessControl(
+                    pallet_access_control::Call::migrate_account {
+                        old: old.clone(),
+                        new: new.clone(),
+                    },
+                ),
+                <AccessControlWeights as pallet_access_control::WeightInfo>::migrate_account(),
+            ),
+        )?;
+        Self::send(
+            MarketplaceLocation::get(),
+            Self::message(
+                crate::marketplace::RuntimeCall::DataListings(
+                    pallet_data_listings::Call::migrate_provider { old, new },
+                ),
+                <DataListingsWeights as pallet_data_listings::WeightInfo>::migrate_provider(),
+            ),
+        )
+    }
+}
+
+impl pallet_consent_manager::OnDataErased<AccountId> for CrossChainNotifier {
+    fn on_data_erased(owner: &AccountId) -> sp_runtime::DispatchResult {
+        Self::erasure_messages(owner)
+            .into_iter()
+            .try_for_each(|(dest, message)| Self::send(dest, message))
+    }
+}
+
+impl pallet_consent_manager::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type MaxDataTypes = ConstU32<10>;
+    type AccessLogPageSize = ConstU32<100>;
+    type ExpiryBucketDuration = ConstU64<3_600_000>;
+    type MaxExpiriesPerBlock = ConstU32<50>;
+    type MaxPoliciesPerOwner = ConstU32<20>;
+    type MaxPolicyConsentsPerOwner = ConstU32<1000>;
+    type ConsentQueryOrigin = pallet_xcm::EnsureXcm<Equals<HealthDataLocation>>;
+    type TemplateApprovalOrigin = EnsureRoot<AccountId>;
+    type Credentials = ();
+    type Organizations = ();
+    type ErasureHandler = CrossChainNotifier;
+    type MigrationOrigin = EnsureRoot<AccountId>;
+    type MaxMigrationBatch = ConstU32<100>;
+    type WardCalls = Everything;
+    type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+    pub enum Runtime {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        MsgQueue: mock_message_queue,
+        PolkadotXcm: pallet_xcm,
+        IdentityRegistry: pallet_identity_registry = 50,
+        ConsentManager: pallet_consent_manager = 51,
+    }
+);
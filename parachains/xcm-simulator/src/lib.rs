@@ -0,0 +1,164 @@
+//! # Patient X XCM Simulator
+//!
+//! ## Overview
+//!
+//! An `xcm-simulator` network made of a relay chain and three parachains that mirror the
+//! XCM configuration of the Patient X runtimes:
+//! - IdentityConsent chain (Para ID 2000) with `pallet-identity-registry` and `pallet-consent-manager`
+//! - HealthData chain (Para ID 2001) with `pallet-access-control`
+//! - Marketplace chain (Para ID 2002) with `pallet-data-listings`
+//!
+//! It is used to test cross-chain flows end to end, such as the consent verification
+//! performed by `pallet_access_control::request_access` and the re-keying of a recovered
+//! identity's data.
+
+pub mod health_data;
+pub mod identity_consent;
+pub mod marketplace;
+pub mod relay_chain;
+
+#[cfg(test)]
+mod tests;
+
+use sp_runtime::{AccountId32, BuildStorage};
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
+
+pub const IDENTITY_CONSENT_PARA_ID: u32 = 2000;
+pub const HEALTH_DATA_PARA_ID: u32 = 2001;
+pub const MARKETPLACE_PARA_ID: u32 = 2002;
+
+pub const PATIENT: AccountId32 = AccountId32::new([1u8; 32]);
+pub const RESEARCHER: AccountId32 = AccountId32::new([2u8; 32]);
+pub const OTHER_PATIENT: AccountId32 = AccountId32::new([3u8; 32]);
+pub const GUARDIAN: AccountId32 = AccountId32::new([4u8; 32]);
+/// Account a lost identity is recovered to
+pub const NEW_ACCOUNT: AccountId32 = AccountId32::new([5u8; 32]);
+
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000;
+
+/// Health record owned by `patient` on the HealthData chain
+pub fn record_of(patient: &AccountId32) -> sp_core::H256 {
+    let index = if *patient == PATIENT { 42 } else { 43 };
+    sp_core::H256::from_low_u64_be(index)
+}
+
+decl_test_parachain! {
+    pub struct IdentityConsent {
+        Runtime = identity_consent::Runtime,
+        XcmpMessageHandler = identity_consent::MsgQueue,
+        DmpMessageHandler = identity_consent::MsgQueue,
+        new_ext = identity_consent_ext(),
+    }
+}
+
+decl_test_parachain! {
+    pub struct HealthData {
+        Runtime = health_data::Runtime,
+        XcmpMessageHandler = health_data::MsgQueue,
+        DmpMessageHandler = health_data::MsgQueue,
+        new_ext = health_data_ext(),
+    }
+}
+
+decl_test_parachain! {
+    pub struct Marketplace {
+        Runtime = marketplace::Runtime,
+        XcmpMessageHandler = marketplace::MsgQueue,
+        DmpMessageHandler = marketplace::MsgQueue,
+        new_ext = marketplace_ext(),
+    }
+}
+
+decl_test_relay_chain! {
+    pub struct Relay {
+        Runtime = relay_chain::Runtime,
+        RuntimeCall = relay_chain::RuntimeCall,
+        RuntimeEvent = relay_chain::RuntimeEvent,
+        XcmConfig = relay_chain::XcmConfig,
+        MessageQueue = relay_chain::MessageQueue,
+        System = relay_chain::System,
+        new_ext = relay_ext(),
+    }
+}
+
+decl_test_network! {
+    pub struct MockNet {
+        relay_chain = Relay,
+        parachains = vec![
+            (2000, IdentityConsent),
+            (2001, HealthData),
+            (2002, Marketplace),
+        ],
+    }
+}
+
+pub fn identity_consent_ext() -> sp_io::TestExternalities {
+    use identity_consent::{MsgQueue, Runtime, System};
+
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![(PATIENT, INITIAL_BALANCE), (RESEARCHER, INITIAL_BALANCE)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MsgQueue::set_para_id(IDENTITY_CONSENT_PARA_ID.into());
+    });
+    ext
+}
+
+pub fn health_data_ext() -> sp_io::TestExternalities {
+    use health_data::{MsgQueue, Runtime, System};
+
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![(PATIENT, INITIAL_BALANCE), (RESEARCHER, INITIAL_BALANCE)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MsgQueue::set_para_id(HEALTH_DATA_PARA_ID.into());
+        health_data::RecordOwners::set(vec![
+            (record_of(&PATIENT), PATIENT),
+            (record_of(&OTHER_PATIENT), OTHER_PATIENT),
+        ]);
+    });
+    ext
+}
+
+pub fn marketplace_ext() -> sp_io::TestExternalities {
+    use marketplace::{MsgQueue, Runtime, System};
+
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![(PATIENT, INITIAL_BALANCE), (RESEARCHER, INITIAL_BALANCE)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MsgQueue::set_para_id(MARKETPLACE_PARA_ID.into());
+    });
+    ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+    use relay_chain::{Runtime, System};
+
+    let t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,545 @@
+//! Cross-chain consent verification tests
+
+use crate::*;
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight, BoundedVec};
+use pallet_access_control::{AccessStatus, Error as AccessError, Event as AccessEvent};
+use pallet_consent_manager::{DataPurpose, DataType, Event as ConsentEvent};
+use pallet_identity_registry::UserRole;
+use sp_core::H256;
+use xcm_executor::XcmExecutor;
+
+fn register(account: AccountId32, role: UserRole, did: &str) {
+    assert_ok!(identity_consent::IdentityRegistry::register_identity(
+        identity_consent::RuntimeOrigin::signed(account),
+        BoundedVec::try_from(did.as_bytes().to_vec()).unwrap(),
+        role,
+        BoundedVec::try_from(b"Test User".to_vec()).unwrap(),
+        H256::from_low_u64_be(1),
+    ));
+}
+
+/// Registers the patient and researcher and creates a research consent between them
+fn setup_consent() -> H256 {
+    IdentityConsent::execute_with(|| {
+        register(PATIENT, UserRole::Patient, "did:patientx:patient");
+        register(RESEARCHER, UserRole::Researcher, "did:patientx:researcher");
+
+        let data_types = BoundedVec::try_from(vec![DataType::LabResults]).unwrap();
+        assert_ok!(identity_consent::ConsentManager::create_consent(
+            identity_consent::RuntimeOrigin::signed(PATIENT),
+            RESEARCHER,
+            DataPurpose::Research,
+            data_types,
+            u64::MAX,
+            H256::zero(),
+        ));
+
+        identity_consent::System::events()
+            .into_iter()
+            .rev()
+            .find_map(|record| match record.event {
+                identity_consent::RuntimeEvent::ConsentManager(ConsentEvent::ConsentCreated {
+                    consent_id,
+                    ..
+                }) => Some(consent_id),
+                _ => None,
+            })
+            .expect("Expected ConsentCreated event")
+    })
+}
+
+/// Sends an access request from the researcher and returns its ID
+fn request_access(patient: AccountId32, consent_id: H256) -> H256 {
+    HealthData::execute_with(|| {
+        assert_ok!(health_data::AccessControl::request_access(
+            health_data::RuntimeOrigin::signed(RESEARCHER),
+            record_of(&patient),
+            None,
+            patient,
+            consent_id,
+        ));
+
+        health_data::System::events()
+            .into_iter()
+            .rev()
+            .find_map(|record| match record.event {
+                health_data::RuntimeEvent::AccessControl(AccessEvent::AccessRequested {
+                    request_id,
+                    ..
+                }) => Some(request_id),
+                _ => None,
+            })
+            .expect("Expected AccessRequested event")
+    })
+}
+
+#[test]
+fn valid_consent_grants_access() {
+    MockNet::reset();
+
+    let consent_id = setup_consent();
+    let request_id = request_access(PATIENT, consent_id);
+
+    IdentityConsent::execute_with(|| {
+        assert!(identity_consent::System::events().iter().any(|record| matches!(
+            record.event,
+            identity_consent::RuntimeEvent::ConsentManager(ConsentEvent::ConsentVerified { .. })
+        )));
+    });
+
+    HealthData::execute_with(|| {
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Granted);
+        assert!(request.consent_verified);
+
+        let record_id = record_of(&PATIENT);
+        assert!(health_data::AccessControl::access_grants(record_id, RESEARCHER).is_some());
+        assert_eq!(health_data::AccessControl::consent_queries(0), None);
+    });
+}
+
+#[test]
+fn unknown_consent_denies_access() {
+    MockNet::reset();
+
+    setup_consent();
+    let request_id = request_access(PATIENT, H256::repeat_byte(9));
+
+    HealthData::execute_with(|| {
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Denied);
+        assert!(!request.consent_verified);
+        assert!(health_data::System::events().iter().any(|record| matches!(
+            record.event,
+            health_data::RuntimeEvent::AccessControl(AccessEvent::ConsentVerified {
+                valid: false,
+                ..
+            })
+        )));
+    });
+}
+
+#[test]
+fn consent_of_another_patient_denies_access() {
+    MockNet::reset();
+
+    let consent_id = setup_consent();
+    let request_id = request_access(OTHER_PATIENT, consent_id);
+
+    HealthData::execute_with(|| {
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Denied);
+
+        let record_id = record_of(&OTHER_PATIENT);
+        assert!(health_data::AccessControl::access_grants(record_id, RESEARCHER).is_none());
+    });
+}
+
+#[test]
+fn verified_consent_waits_for_patient_without_auto_grant() {
+    MockNet::reset();
+    HealthData::execute_with(|| health_data::AutoGrant::set(false));
+
+    let consent_id = setup_consent();
+    let request_id = request_access(PATIENT, consent_id);
+
+    HealthData::execute_with(|| {
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Pending);
+        assert!(request.consent_verified);
+
+        assert_ok!(health_data::AccessControl::grant_access(
+            health_data::RuntimeOrigin::signed(PATIENT),
+            request_id,
+            1_000_000,
+        ));
+
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Granted);
+    });
+}
+
+#[test]
+fn consent_response_requires_xcm_response_origin() {
+    MockNet::reset();
+
+    HealthData::execute_with(|| {
+        assert_noop!(
+            health_data::AccessControl::consent_response(
+                health_data::RuntimeOrigin::signed(PATIENT),
+                0,
+                xcm::latest::Response::Null,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn grant_access_fails_before_consent_is_verified() {
+    MockNet::reset();
+
+    HealthData::execute_with(|| {
+        // No consent chain response has been processed inside this block
+        assert_ok!(health_data::AccessControl::request_access(
+            health_data::RuntimeOrigin::signed(RESEARCHER),
+            record_of(&PATIENT),
+            None,
+            PATIENT,
+            H256::repeat_byte(1),
+        ));
+
+        let request_id = health_data::System::events()
+            .into_iter()
+            .find_map(|record| match record.event {
+                health_data::RuntimeEvent::AccessControl(AccessEvent::AccessRequested {
+                    request_id,
+                    ..
+                }) => Some(request_id),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_noop!(
+            health_data::AccessControl::grant_access(
+                health_data::RuntimeOrigin::signed(PATIENT),
+                request_id,
+                1_000_000,
+            ),
+            AccessError::<health_data::Runtime>::ConsentNotVerified
+        );
+    });
+}
+
+/// Creates a verified request that waits for the patient's decision
+fn pending_request() -> H256 {
+    HealthData::execute_with(|| health_data::AutoGrant::set(false));
+
+    let consent_id = setup_consent();
+    let request_id = request_access(PATIENT, consent_id);
+
+    HealthData::execute_with(|| {
+        let pending = health_data::AccessControl::pending_requests(PATIENT);
+        assert_eq!(pending.into_inner(), vec![request_id]);
+    });
+
+    request_id
+}
+
+#[test]
+fn delegate_grants_access_for_patient() {
+    MockNet::reset();
+
+    let request_id = pending_request();
+
+    HealthData::execute_with(|| {
+        assert_ok!(health_data::AccessControl::add_delegate(
+            health_data::RuntimeOrigin::signed(PATIENT),
+            GUARDIAN,
+            BoundedVec::new(),
+            None,
+        ));
+
+        assert_ok!(health_data::AccessControl::grant_access(
+            health_data::RuntimeOrigin::signed(GUARDIAN),
+            request_id,
+            1_000_000,
+        ));
+
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Granted);
+        assert!(health_data::AccessControl::pending_requests(PATIENT).is_empty());
+    });
+}
+
+#[test]
+fn only_patient_or_delegate_can_decide() {
+    MockNet::reset();
+
+    let request_id = pending_request();
+
+    HealthData::execute_with(|| {
+        assert_noop!(
+            health_data::AccessControl::deny_access(
+                health_data::RuntimeOrigin::signed(GUARDIAN),
+                request_id,
+            ),
+            AccessError::<health_data::Runtime>::NotAuthorized
+        );
+
+        // The mock has no record categories, so a category-scoped delegation covers nothing
+        assert_ok!(health_data::AccessControl::add_delegate(
+            health_data::RuntimeOrigin::signed(PATIENT),
+            GUARDIAN,
+            BoundedVec::try_from(vec![1u8]).unwrap(),
+            None,
+        ));
+        assert_noop!(
+            health_data::AccessControl::deny_access(
+                health_data::RuntimeOrigin::signed(GUARDIAN),
+                request_id,
+            ),
+            AccessError::<health_data::Runtime>::NotAuthorized
+        );
+
+        // Removed delegates lose their rights
+        assert_ok!(health_data::AccessControl::add_delegate(
+            health_data::RuntimeOrigin::signed(PATIENT),
+            GUARDIAN,
+            BoundedVec::new(),
+            None,
+        ));
+        assert_ok!(health_data::AccessControl::remove_delegate(
+            health_data::RuntimeOrigin::signed(PATIENT),
+            GUARDIAN,
+        ));
+        assert_noop!(
+            health_data::AccessControl::deny_access(
+                health_data::RuntimeOrigin::signed(GUARDIAN),
+                request_id,
+            ),
+            AccessError::<health_data::Runtime>::NotAuthorized
+        );
+    });
+}
+
+#[test]
+fn delegation_cannot_target_self_or_the_past() {
+    MockNet::reset();
+
+    HealthData::execute_with(|| {
+        assert_noop!(
+            health_data::AccessControl::add_delegate(
+                health_data::RuntimeOrigin::signed(PATIENT),
+                PATIENT,
+                BoundedVec::new(),
+                None,
+            ),
+            AccessError::<health_data::Runtime>::CannotDelegateToSelf
+        );
+
+        health_data::Timestamp::set_timestamp(1_000);
+        assert_noop!(
+            health_data::AccessControl::add_delegate(
+                health_data::RuntimeOrigin::signed(PATIENT),
+                GUARDIAN,
+                BoundedVec::new(),
+                Some(1_000),
+            ),
+            AccessError::<health_data::Runtime>::InvalidExpiry
+        );
+    });
+}
+
+#[test]
+fn bulk_deny_clears_pending_requests() {
+    MockNet::reset();
+
+    let first = pending_request();
+    let second = request_access(PATIENT, H256::repeat_byte(9));
+
+    HealthData::execute_with(|| {
+        // The second request was denied by the consent chain, so denying it again fails as a whole
+        assert_noop!(
+            health_data::AccessControl::bulk_deny_access(
+                health_data::RuntimeOrigin::signed(PATIENT),
+                BoundedVec::try_from(vec![first, second]).unwrap(),
+            ),
+            AccessError::<health_data::Runtime>::RequestNotPending
+        );
+
+        assert_ok!(health_data::AccessControl::bulk_deny_access(
+            health_data::RuntimeOrigin::signed(PATIENT),
+            BoundedVec::try_from(vec![first]).unwrap(),
+        ));
+
+        let request = health_data::AccessControl::access_requests(first).unwrap();
+        assert_eq!(request.status, AccessStatus::Denied);
+        assert!(health_data::AccessControl::pending_requests(PATIENT).is_empty());
+    });
+}
+
+#[test]
+fn undecided_request_expires() {
+    MockNet::reset();
+
+    let request_id = pending_request();
+
+    HealthData::execute_with(|| {
+        let expires_in = 1 + health_data::PendingRequestTimeout::get();
+        health_data::AccessControl::on_initialize(expires_in - 1);
+        assert_eq!(
+            health_data::AccessControl::access_requests(request_id).unwrap().status,
+            AccessStatus::Pending
+        );
+
+        health_data::AccessControl::on_initialize(expires_in);
+
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.status, AccessStatus::Expired);
+        assert!(health_data::AccessControl::pending_requests(PATIENT).is_empty());
+        assert_noop!(
+            health_data::AccessControl::grant_access(
+                health_data::RuntimeOrigin::signed(PATIENT),
+                request_id,
+                1_000_000,
+            ),
+            AccessError::<health_data::Runtime>::RequestNotPending
+        );
+    });
+}
+
+/// Creates a Marketplace listing provided by `provider` and returns its ID
+fn create_listing(provider: AccountId32) -> H256 {
+    Marketplace::execute_with(|| {
+        assert_ok!(marketplace::DataListings::create_listing(
+            marketplace::RuntimeOrigin::signed(provider.clone()),
+            BoundedVec::try_from(b"Lab panel".to_vec()).unwrap(),
+            BoundedVec::try_from(b"De-identified lab results".to_vec()).unwrap(),
+            pallet_data_listings::DataCategory::Laboratory,
+            pallet_data_listings::PricingModel::FixedPrice { amount: 1_000 },
+            1,
+            None,
+        ));
+        *marketplace::DataListings::provider_listings(provider).last().unwrap()
+    })
+}
+
+/// Recovers the patient's identity to `NEW_ACCOUNT` with a single guardian
+fn recover_patient() {
+    IdentityConsent::execute_with(|| {
+        assert_ok!(identity_consent::IdentityRegistry::set_recovery_config(
+            identity_consent::RuntimeOrigin::signed(PATIENT),
+            BoundedVec::try_from(vec![GUARDIAN]).unwrap(),
+            1,
+            0,
+        ));
+        assert_ok!(identity_consent::IdentityRegistry::initiate_recovery(
+            identity_consent::RuntimeOrigin::signed(GUARDIAN),
+            PATIENT,
+            NEW_ACCOUNT,
+        ));
+        assert_ok!(identity_consent::IdentityRegistry::complete_recovery(
+            identity_consent::RuntimeOrigin::signed(NEW_ACCOUNT),
+            PATIENT,
+        ));
+    });
+}
+
+#[test]
+fn recovered_identity_is_migrated_on_every_chain() {
+    MockNet::reset();
+
+    let request_id = pending_request();
+    let consent_id = IdentityConsent::execute_with(|| {
+        *identity_consent::ConsentManager::owner_consents(PATIENT).last().unwrap()
+    });
+    let listing_id = create_listing(PATIENT);
+
+    recover_patient();
+
+    IdentityConsent::execute_with(|| {
+        let consent = identity_consent::ConsentManager::consents(consent_id).unwrap();
+        assert_eq!(consent.data_owner, NEW_ACCOUNT);
+        assert_eq!(
+            identity_consent::ConsentManager::owner_consents(NEW_ACCOUNT).into_inner(),
+            vec![consent_id]
+        );
+        assert!(identity_consent::ConsentManager::owner_consents(PATIENT).is_empty());
+    });
+
+    HealthData::execute_with(|| {
+        let pending = health_data::AccessControl::pending_requests(NEW_ACCOUNT);
+        assert_eq!(pending.into_inner(), vec![request_id]);
+        let request = health_data::AccessControl::access_requests(request_id).unwrap();
+        assert_eq!(request.patient, NEW_ACCOUNT);
+        assert_eq!(health_data::AccessControl::account_migrations(PATIENT), Some(NEW_ACCOUNT));
+    });
+
+    Marketplace::execute_with(|| {
+        assert_eq!(
+            marketplace::DataListings::provider_listings(NEW_ACCOUNT).into_inner(),
+            vec![listing_id]
+        );
+        assert!(marketplace::DataListings::provider_listings(PATIENT).is_empty());
+        let listing = marketplace::DataListings::listings(listing_id).unwrap();
+        assert_eq!(listing.provider, NEW_ACCOUNT);
+    });
+}
+
+#[test]
+fn erasure_delists_provider_on_marketplace() {
+    MockNet::reset();
+
+    setup_consent();
+    let listing_id = create_listing(PATIENT);
+    let other_listing = create_listing(RESEARCHER);
+
+    IdentityConsent::execute_with(|| {
+        assert_ok!(identity_consent::ConsentManager::request_erasure(
+            identity_consent::RuntimeOrigin::signed(PATIENT),
+        ));
+    });
+
+    Marketplace::execute_with(|| {
+        assert!(marketplace::DataListings::listings(listing_id).is_none());
+        assert!(marketplace::DataListings::provider_listings(PATIENT).is_empty());
+        assert!(marketplace::System::events().iter().any(|record| matches!(
+            record.event,
+            marketplace::RuntimeEvent::DataListings(
+                pallet_data_listings::Event::ProviderDelisted { listings: 1, .. }
+            )
+        )));
+
+        // Other providers keep their listings
+        assert!(marketplace::DataListings::listings(other_listing).is_some());
+    });
+}
+
+#[test]
+fn erasure_message_executes_within_marketplace_service_weight() {
+    MockNet::reset();
+
+    let listing_id = create_listing(PATIENT);
+
+    Marketplace::execute_with(|| {
+        for (dest, message) in identity_consent::CrossChainNotifier::erasure_messages(&PATIENT) {
+            assert_eq!(dest, identity_consent::MarketplaceLocation::get());
+            let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
+            let outcome = XcmExecutor::<marketplace::XcmConfig>::prepare_and_execute(
+                marketplace::IdentityConsentLocation::get(),
+                message,
+                &mut hash,
+                marketplace::MessageQueueServiceWeight::get(),
+                Weight::zero(),
+            );
+            assert_ok!(outcome.ensure_complete());
+        }
+
+        assert!(marketplace::DataListings::listings(listing_id).is_none());
+    });
+}
+
+#[test]
+fn account_calls_fit_health_data_service_weight() {
+    type HealthRecords = pallet_health_records::weights::SubstrateWeight<health_data::Runtime>;
+    type Ipfs = pallet_ipfs_integration::weights::SubstrateWeight<health_data::Runtime>;
+    type Encryption = pallet_encryption::weights::SubstrateWeight<health_data::Runtime>;
+    type AccessControl = pallet_access_control::weights::SubstrateWeight<health_data::Runtime>;
+
+    // Each call is sent in its own message, as the erasure calls together exceed the proof
+    // budget
+    let limit = health_data::MessageQueueServiceWeight::get();
+    for weight in [
+        <HealthRecords as pallet_health_records::WeightInfo>::migrate_patient(),
+        <HealthRecords as pallet_health_records::WeightInfo>::erase_patient(),
+        <Ipfs as pallet_ipfs_integration::WeightInfo>::erase_owner_content(),
+        <Encryption as pallet_encryption::WeightInfo>::migrate_keys(),
+        <Encryption as pallet_encryption::WeightInfo>::revoke_account_keys(),
+        <AccessControl as pallet_access_control::WeightInfo>::migrate_account(),
+        <AccessControl as pallet_access_control::WeightInfo>::set_guardianship(),
+    ] {
+        assert!(weight.all_lte(limit));
+    }
+}